@@ -1,4 +1,5 @@
 use super::*;
+use serde_json::{Map, Value};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum KwArgRole {
@@ -32,7 +33,6 @@ impl KwArgRole {
 pub(super) struct KwArg {
     pub name: String,
     pub value: String,
-    #[allow(dead_code)]
     pub arg_type: String,
     pub role: KwArgRole,
 }
@@ -48,7 +48,7 @@ impl PluginExtractor {
             if let Some(end) = call_text.rfind(')') {
                 let args_str = &call_text[start + 1..end];
 
-                for arg in args_str.split(',') {
+                for arg in split_top_level(args_str) {
                     let arg = arg.trim();
                     if arg.is_empty() {
                         continue;
@@ -103,6 +103,10 @@ impl PluginExtractor {
             return "boolean".to_string();
         }
 
+        if self.parse_constructor_call(value_str).is_ok() {
+            return "constructor".to_string();
+        }
+
         if value_str.contains('.')
             && value_str
                 .chars()
@@ -122,6 +126,97 @@ impl PluginExtractor {
         "identifier".to_string()
     }
 
+    /// Classify a parameter's type annotation, e.g. `Optional[int]` as optional number
+    pub(super) fn infer_annotation_type(annotation: &str) -> TypeKind {
+        let annotation = annotation.trim();
+        let annotation = annotation.strip_prefix("typing.").unwrap_or(annotation);
+
+        let members: Vec<&str> = if let Some(inner) = generic_args(annotation, "Optional") {
+            vec![inner, "None"]
+        } else if let Some(inner) = generic_args(annotation, "Union") {
+            split_top_level(inner)
+        } else {
+            split_top_level_on(annotation, '|')
+        };
+        let members: Vec<&str> = members.into_iter().map(str::trim).collect();
+        if members.len() > 1 {
+            let non_none: Vec<&str> = members.iter().copied().filter(|m| *m != "None").collect();
+            return match non_none.as_slice() {
+                [inner] if non_none.len() < members.len() => {
+                    TypeKind::Optional(Box::new(Self::infer_annotation_type(inner)))
+                }
+                _ => TypeKind::Union,
+            };
+        }
+
+        if let Some(inner) = generic_args(annotation, "list").or(generic_args(annotation, "List")) {
+            return TypeKind::List(Box::new(Self::infer_annotation_type(inner)));
+        }
+
+        match annotation {
+            "str" => TypeKind::String,
+            "int" => TypeKind::Number,
+            "float" => TypeKind::Float,
+            "bool" => TypeKind::Boolean,
+            "None" => TypeKind::None,
+            _ if annotation.contains('[') => TypeKind::Complex,
+            _ if annotation.chars().next().is_some_and(|c| c.is_uppercase()) => {
+                TypeKind::ClassReference
+            }
+            _ => TypeKind::Identifier,
+        }
+    }
+
+    /// Parse a nested constructor call such as `ClassCallable(module="m", name="N")`
+    ///
+    /// Keyword arguments become a JSON object, recursing into nested constructors; the
+    /// qualified class name is stored under `__class__`. Errors when `value` is not a
+    /// `ClassName(...)` call.
+    pub(super) fn parse_constructor_call(&self, value: &str) -> Result<Value> {
+        let value = value.trim();
+        let open = value
+            .find('(')
+            .filter(|_| value.ends_with(')'))
+            .ok_or_else(|| anyhow!("Not a constructor call: {}", value))?;
+        let callee = value[..open].trim();
+        let class_name = callee.rsplit('.').next().unwrap_or(callee);
+        let is_class = class_name.chars().next().is_some_and(|c| c.is_uppercase())
+            && callee
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '.');
+        if !is_class || Self::bracket_delta(&value[open..]) != 0 {
+            return Err(anyhow!("Not a constructor call: {}", value));
+        }
+
+        let mut object = Map::new();
+        object.insert(
+            "__class__".to_string(),
+            Value::String(self.qualify_symbol(callee)),
+        );
+        for arg in split_top_level(&value[open + 1..value.len() - 1]) {
+            let Some((key, raw)) = arg.split_once('=') else {
+                continue;
+            };
+            object.insert(key.trim().to_string(), self.parse_kwarg_value(raw.trim()));
+        }
+        Ok(Value::Object(object))
+    }
+
+    fn parse_kwarg_value(&self, raw: &str) -> Value {
+        match self.infer_argument_type(raw).as_str() {
+            "string" => Value::String(
+                raw.trim_matches(|c: char| c == '"' || c == '\'')
+                    .to_string(),
+            ),
+            "number" | "float" => serde_json::from_str(raw).unwrap_or(Value::Null),
+            "boolean" => Value::Bool(raw == "True"),
+            _ if raw == "None" => Value::Null,
+            _ => self
+                .parse_constructor_call(raw)
+                .unwrap_or_else(|_| Value::String(raw.to_string())),
+        }
+    }
+
     pub(super) fn find_kwarg_value(&self, kwargs: &[KwArg], name: &str) -> Result<String> {
         kwargs
             .iter()
@@ -130,3 +225,80 @@ impl PluginExtractor {
             .ok_or_else(|| anyhow!("Argument '{}' not found", name))
     }
 }
+
+/// Parse a list literal such as `["SYSTEM", "Custom:database"]` into I/O slots
+///
+/// Unrecognized entries are skipped.
+pub(super) fn parse_io_slot_list(value: &str) -> Vec<IOSlot> {
+    parse_string_list(value)
+        .into_iter()
+        .filter_map(|item| {
+            let slot = IOSlot::from_declaration(&item);
+            if slot.is_none() {
+                debug!("Skipping unknown I/O slot '{}'", item);
+            }
+            slot
+        })
+        .collect()
+}
+
+/// Parse a list or tuple literal of strings such as `["energy", "reeds"]`
+pub(super) fn parse_string_list(value: &str) -> Vec<String> {
+    let inner = value
+        .trim()
+        .trim_start_matches(['[', '('])
+        .trim_end_matches([']', ')']);
+    split_top_level(inner)
+        .into_iter()
+        .map(|item| item.trim().trim_matches(|c: char| c == '"' || c == '\''))
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Arguments of a subscripted annotation such as `Optional[int]` for `name`
+fn generic_args<'a>(annotation: &'a str, name: &str) -> Option<&'a str> {
+    annotation
+        .strip_prefix(name)?
+        .strip_prefix('[')?
+        .strip_suffix(']')
+}
+
+/// Split call arguments on commas that are not nested in brackets or strings
+pub(super) fn split_top_level(args: &str) -> Vec<&str> {
+    split_top_level_on(args, ',')
+}
+
+/// Split on `separator` where it is not nested in brackets or strings
+fn split_top_level_on(args: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut in_str: Option<char> = None;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (idx, ch) in args.char_indices() {
+        if let Some(quote) = in_str {
+            if escaped {
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == quote {
+                in_str = None;
+            }
+            continue;
+        }
+        match ch {
+            '"' | '\'' => in_str = Some(ch),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            _ if ch == separator && depth == 0 => {
+                parts.push(&args[start..idx]);
+                start = idx + ch.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts
+}
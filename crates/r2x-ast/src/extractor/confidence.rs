@@ -0,0 +1,85 @@
+use super::*;
+
+/// Deduction when the entry symbol cannot be found in the package sources
+const UNRESOLVED_ENTRY: f32 = 0.3;
+/// Deduction for upgraders whose upgrade steps could not be extracted
+const MISSING_UPGRADE: f32 = 0.2;
+/// Deduction for each other missing optional field
+const MISSING_FIELD: f32 = 0.1;
+
+impl PluginExtractor {
+    /// Score how completely each plugin was extracted
+    ///
+    /// Callers compare the score with the configured threshold, so cached results are
+    /// reported the same way as fresh ones.
+    pub(super) fn with_confidence(&self, mut plugins: Vec<PluginSpec>) -> Vec<PluginSpec> {
+        for plugin in &mut plugins {
+            let (confidence, missing) = self.score_confidence(plugin);
+            if !missing.is_empty() {
+                debug!(
+                    "Plugin '{}' scored {:.2}: missing {}",
+                    plugin.name,
+                    confidence,
+                    missing.join(", ")
+                );
+            }
+            plugin.confidence = Some(confidence);
+        }
+        plugins
+    }
+
+    /// Start from 1.0 and deduct for every missing optional field and unresolved reference
+    ///
+    /// Returns the score and the names of what was missing.
+    pub(super) fn score_confidence(&self, plugin: &PluginSpec) -> (f32, Vec<&'static str>) {
+        let mut missing = Vec::new();
+        let mut confidence = 1.0_f32;
+
+        if !self.entry_resolves(&plugin.entry) {
+            confidence -= UNRESOLVED_ENTRY;
+            missing.push("entry");
+        }
+        if plugin.kind == PluginKind::Upgrader && plugin.upgrade.is_none() {
+            confidence -= MISSING_UPGRADE;
+            missing.push("upgrade_steps");
+        }
+        let has_config = plugin
+            .resources
+            .as_ref()
+            .is_some_and(|resources| resources.config.is_some());
+        if matches!(plugin.kind, PluginKind::Parser | PluginKind::Exporter) && !has_config {
+            confidence -= MISSING_FIELD;
+            missing.push("config");
+        }
+        if plugin.description.is_none() {
+            confidence -= MISSING_FIELD;
+            missing.push("description");
+        }
+
+        (confidence.clamp(0.0, 1.0), missing)
+    }
+
+    /// Whether the entry's class or function is defined in the package, following imports
+    fn entry_resolves(&self, entry: &str) -> bool {
+        Self::split_entry(entry)
+            .is_some_and(|(module, symbol)| self.symbol_defined(&module, &symbol, 0))
+    }
+
+    fn symbol_defined(&self, module: &str, symbol: &str, depth: usize) -> bool {
+        const MAX_IMPORT_DEPTH: usize = 5;
+        if depth > MAX_IMPORT_DEPTH {
+            return false;
+        }
+        let Some(source) = self.load_module_source(module) else {
+            return false;
+        };
+
+        Self::defines_symbol(&source, symbol)
+            || self
+                .find_import_source_module(&source, symbol, module)
+                .is_some_and(|source_module| {
+                    source_module != module
+                        && self.symbol_defined(&source_module, symbol, depth + 1)
+                })
+    }
+}
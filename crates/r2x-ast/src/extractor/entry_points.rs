@@ -0,0 +1,50 @@
+use super::*;
+use r2x_manifest::DistInfo;
+use std::path::Path;
+
+impl PluginExtractor {
+    /// Read the `[r2x_plugin]` entry points declared in the package dist-info
+    ///
+    /// `package_root` is the installed package directory (e.g. `site-packages/r2x_reeds`);
+    /// the dist-info is looked up next to it. Returns the raw `name = module:attr` strings.
+    pub fn extract_entry_points(package_root: &Path, package_name: &str) -> Result<Vec<String>> {
+        let site_packages = package_root
+            .parent()
+            .ok_or_else(|| anyhow!("Package root has no parent: {:?}", package_root))?;
+        let dist_info = DistInfo::find(site_packages, package_name)
+            .ok_or_else(|| anyhow!("No dist-info found for package '{}'", package_name))?;
+
+        let entry_points: Vec<String> = dist_info
+            .entry_points("r2x_plugin")
+            .into_iter()
+            .map(|(name, value)| format!("{} = {}", name, value))
+            .collect();
+
+        debug!(
+            "Found {} r2x_plugin entry points for {}",
+            entry_points.len(),
+            package_name
+        );
+        Ok(entry_points)
+    }
+
+    /// Replace heuristically derived plugin entries with declared entry points
+    ///
+    /// An entry point `reeds-parser = r2x_reeds.parser:ReEDSParser` sets the entry of the
+    /// plugin named `reeds-parser` to `r2x_reeds.parser.ReEDSParser`.
+    pub fn apply_entry_points(plugins: &mut [PluginSpec], entry_points: &[String]) {
+        for entry_point in entry_points {
+            let Some((name, value)) = entry_point.split_once('=') else {
+                continue;
+            };
+            let Some((module, attr)) = value.trim().split_once(':') else {
+                continue;
+            };
+
+            let name = name.trim();
+            if let Some(plugin) = plugins.iter_mut().find(|p| p.name == name) {
+                plugin.entry = format!("{}.{}", module.trim(), attr.trim());
+            }
+        }
+    }
+}
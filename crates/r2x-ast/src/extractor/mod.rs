@@ -1,16 +1,20 @@
 use anyhow::{anyhow, Result};
-use ast_grep_core::AstGrep;
+use ast_grep_core::source::StrDoc;
+use ast_grep_core::{AstGrep, Node};
 use ast_grep_language::Python;
 use r2x_manifest::{
-    ArgumentSpec, ConfigField, ConfigSpec, IOContract, IOSlot, ImplementationType, InvocationSpec,
-    PluginKind, PluginSpec, ResourceSpec, StoreMode, StoreSpec,
+    looks_like_abstract_class, ArgumentSpec, ConfigField, ConfigSpec, IOContract, IOSlot,
+    ImplementationType, InvocationSpec, PluginKind, PluginSpec, ResourceSpec, StoreMode, StoreSpec,
+    TypeKind,
 };
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 mod args;
+mod confidence;
+mod entry_points;
 #[allow(dead_code)]
 mod parameters;
 
@@ -22,7 +26,8 @@ pub struct PluginExtractor {
     pub(crate) package_root: PathBuf,
     pub(crate) package_prefix: String,
     pub(crate) content: String,
-    pub(crate) import_map: HashMap<String, String>,
+    /// Local name -> (module, imported name). `import module` entries have an empty name.
+    pub(crate) import_map: HashMap<String, (String, String)>,
     pub(crate) current_module: String,
 }
 
@@ -35,17 +40,32 @@ impl PluginExtractor {
         debug!("Initializing plugin extractor for: {:?}", python_file_path);
 
         let content = fs::read_to_string(&python_file_path)?;
+        Ok(Self::from_content(
+            python_file_path,
+            module_path,
+            package_root,
+            content,
+        ))
+    }
+
+    /// Build an extractor for module source that was already read
+    pub fn from_content(
+        python_file_path: PathBuf,
+        module_path: String,
+        package_root: PathBuf,
+        content: String,
+    ) -> Self {
         let package_prefix = module_path.split('.').next().unwrap_or("").to_string();
         let import_map = Self::build_import_map_static(&content);
 
-        Ok(PluginExtractor {
+        PluginExtractor {
             python_file_path,
             package_root,
             package_prefix,
             content,
             import_map,
             current_module: module_path,
-        })
+        }
     }
 
     pub fn extract_plugins(&self) -> Result<Vec<PluginSpec>> {
@@ -84,7 +104,7 @@ impl PluginExtractor {
                 "Extracted {} plugins from manifest.add() helpers",
                 plugins.len()
             );
-            return Ok(plugins);
+            return Ok(self.with_confidence(self.with_package_description(plugins)));
         }
 
         let constructor_plugins = self.extract_plugins_from_constructor_calls()?;
@@ -93,7 +113,7 @@ impl PluginExtractor {
                 "Extracted {} plugins from Package-based constructors",
                 constructor_plugins.len()
             );
-            return Ok(constructor_plugins);
+            return Ok(self.with_confidence(self.with_package_description(constructor_plugins)));
         }
 
         Err(anyhow!(
@@ -152,24 +172,32 @@ impl PluginExtractor {
                 (constructor, Vec::new())
             }
             ImplementationType::Function => {
+                self.warn_on_generator_entry(&entry);
                 let call = self.resolve_entry_parameters(&entry, &ImplementationType::Function);
                 (Vec::new(), call)
             }
         };
 
-        let description = self.find_optional_kwarg_by_role(&kwargs, args::KwArgRole::Description);
+        let description = self
+            .find_optional_kwarg_by_role(&kwargs, args::KwArgRole::Description)
+            .or_else(|| self.description_from_docstring(&entry));
 
         let method_param = self.find_optional_kwarg_by_role(&kwargs, args::KwArgRole::Method);
         let resolved_method = method_param.or_else(|| Self::default_method_for_kind(&kind));
 
+        let (timeout_secs, retry_count) = self.extract_runtime_policy(&kwargs);
         let invocation = InvocationSpec {
             implementation,
             method: resolved_method,
             constructor: constructor_args,
             call: call_args,
+            timeout_secs,
+            retry_count,
+            cacheable: self.extract_cacheable(&kwargs),
         };
 
-        let io = self.infer_io_contract(&kind);
+        let io = self.infer_io_contract(&kind, &kwargs);
+        let tags = self.extract_tags(&kwargs);
 
         let resources = self.extract_resources(&kwargs);
 
@@ -182,12 +210,221 @@ impl PluginExtractor {
             resources,
             upgrade: None,
             description,
-            tags: Vec::new(),
+            tags,
+            r2x_core_requirement: None,
+            confidence: None,
+        })
+    }
+
+    /// Docstring of a top-level class or function in `content`, cleaned up
+    ///
+    /// Decorated definitions are looked through. Returns `None` when the callable is
+    /// not defined in `content` or has no docstring.
+    pub fn extract_description(content: &str, callable_name: &str) -> Option<String> {
+        let sg = AstGrep::new(content, Python);
+        let definition = Self::top_level_definition(&sg.root(), callable_name)?;
+
+        // The docstring is a bare string expression as the first statement of the body
+        let first_statement = definition
+            .field("body")?
+            .children()
+            .find(|node| node.kind() != "comment")?;
+        if first_statement.kind() != "expression_statement" {
+            return None;
+        }
+        let literal = first_statement.children().next()?;
+        if literal.kind() != "string" {
+            return None;
+        }
+        Self::clean_docstring(&literal.text())
+    }
+
+    /// Whether `content` defines a class or function named `symbol` at module level
+    pub(crate) fn defines_symbol(content: &str, symbol: &str) -> bool {
+        let sg = AstGrep::new(content, Python);
+        Self::top_level_definition(&sg.root(), symbol).is_some()
+    }
+
+    /// Top-level class or function definition named `name`, looking through decorators
+    fn top_level_definition<'r>(
+        root: &Node<'r, StrDoc<Python>>,
+        name: &str,
+    ) -> Option<Node<'r, StrDoc<Python>>> {
+        root.children().find_map(|node| {
+            let node = if node.kind() == "decorated_definition" {
+                node.field("definition")?
+            } else {
+                node
+            };
+            let is_definition =
+                node.kind() == "class_definition" || node.kind() == "function_definition";
+            let name_matches = node.field("name").is_some_and(|field| field.text() == name);
+            (is_definition && name_matches).then_some(node)
         })
     }
 
+    /// Strip the quotes of a docstring literal and remove its common indentation
+    ///
+    /// Follows `inspect.cleandoc`: the first line is trimmed, the rest are dedented,
+    /// and surrounding blank lines are dropped.
+    fn clean_docstring(literal: &str) -> Option<String> {
+        let literal = literal.trim().trim_start_matches(['r', 'R', 'u', 'U']);
+        let body = ["\"\"\"", "'''", "\"", "'"]
+            .iter()
+            .find_map(|quote| literal.strip_prefix(quote)?.strip_suffix(quote))?;
+
+        let mut lines = body.lines();
+        let first = lines.next().unwrap_or_default().trim();
+        let rest: Vec<&str> = lines.collect();
+        let indent = rest
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+
+        let mut cleaned = vec![first];
+        cleaned.extend(
+            rest.iter()
+                .map(|line| line.get(indent..).unwrap_or_default().trim_end()),
+        );
+        while cleaned.first().is_some_and(|line| line.is_empty()) {
+            cleaned.remove(0);
+        }
+        while cleaned.last().is_some_and(|line| line.is_empty()) {
+            cleaned.pop();
+        }
+
+        let description = cleaned.join("\n");
+        (!description.is_empty()).then_some(description)
+    }
+
+    /// Use the `register_plugin()` docstring for the only plugin of a package when its
+    /// entry has no docstring of its own
+    fn with_package_description(&self, mut plugins: Vec<PluginSpec>) -> Vec<PluginSpec> {
+        if let [plugin] = plugins.as_mut_slice() {
+            if plugin.description.is_none() {
+                plugin.description = Self::extract_description(&self.content, "register_plugin");
+            }
+        }
+        plugins
+    }
+
+    /// Description taken from the docstring of the plugin entry
+    fn description_from_docstring(&self, entry: &str) -> Option<String> {
+        let (module, name) = Self::split_entry(entry)?;
+        if module == self.current_module {
+            Self::extract_description(&self.content, &name)
+        } else {
+            Self::extract_description(&self.load_module_source(&module)?, &name)
+        }
+    }
+
+    /// Source of the `register_plugin` function, dedented to module level
+    ///
+    /// Decorators directly above the definition are included, and the function may be
+    /// nested (e.g. a `@staticmethod` inside a class). Returns `None` when the file does
+    /// not define `register_plugin`.
+    pub fn extract_register_plugin_function(content: &str) -> Option<String> {
+        let lines: Vec<&str> = content.lines().collect();
+        let indent_of = |line: &str| line.len() - line.trim_start().len();
+
+        let def_idx = lines.iter().position(|line| {
+            let trimmed = line.trim_start();
+            trimmed.starts_with("def register_plugin(")
+                || trimmed.starts_with("async def register_plugin(")
+        })?;
+        let indent = indent_of(lines[def_idx]);
+
+        // Walk back over decorators, including ones whose arguments span several lines
+        let mut start = def_idx;
+        let mut open = 0;
+        for idx in (0..def_idx).rev() {
+            let line = lines[idx];
+            open -= Self::bracket_delta(line);
+            if open > 0 {
+                continue;
+            }
+            if indent_of(line) != indent || !line.trim_start().starts_with('@') {
+                break;
+            }
+            start = idx;
+        }
+
+        // The signature may span several lines; the body starts after its closing `:`
+        let mut depth = 0;
+        let mut body_start = def_idx;
+        for (idx, line) in lines.iter().enumerate().skip(def_idx) {
+            depth += Self::bracket_delta(line);
+            body_start = idx + 1;
+            let code = line.split('#').next().unwrap_or_default().trim_end();
+            if depth <= 0 && code.ends_with(':') {
+                break;
+            }
+        }
+
+        let end = lines
+            .iter()
+            .enumerate()
+            .skip(body_start)
+            .find(|(_, line)| {
+                let trimmed = line.trim_start();
+                !trimmed.is_empty() && !trimmed.starts_with('#') && indent_of(line) <= indent
+            })
+            .map_or(lines.len(), |(idx, _)| idx);
+
+        let source = lines[start..end]
+            .iter()
+            .map(|line| {
+                if indent_of(line) >= indent {
+                    &line[indent..]
+                } else {
+                    line.trim_start()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(source)
+    }
+
+    /// Module-level statements of `content`, without top-level functions and classes
+    ///
+    /// `def` and `class` blocks are dropped together with their decorators; statements
+    /// spanning several lines are kept whole.
+    pub fn extract_module_level_source(content: &str) -> String {
+        let mut kept = Vec::new();
+        let mut in_definition = false;
+        let mut open = 0;
+        for line in content.lines() {
+            let trimmed = line.trim_start();
+            let starts_statement = open <= 0
+                && trimmed.len() == line.len()
+                && !trimmed.is_empty()
+                && !trimmed.starts_with('#');
+            if starts_statement {
+                in_definition = ["def ", "async def ", "class ", "@"]
+                    .iter()
+                    .any(|prefix| trimmed.starts_with(prefix));
+            }
+            open += Self::bracket_delta(line);
+            if !in_definition {
+                kept.push(line);
+            }
+        }
+        kept.join("\n")
+    }
+
     fn extract_plugins_from_constructor_calls(&self) -> Result<Vec<PluginSpec>> {
-        let sg = AstGrep::new(&self.content, Python);
+        // Constructors in other functions and classes (e.g. helpers or tests) are not
+        // plugins, but plugins built at module level may be returned by `register_plugin`
+        let scoped = Self::extract_register_plugin_function(&self.content).map(|register| {
+            format!(
+                "{}\n{}",
+                Self::extract_module_level_source(&self.content),
+                register
+            )
+        });
+        let sg = AstGrep::new(scoped.as_deref().unwrap_or(&self.content), Python);
         let root = sg.root();
         let mut plugins = Vec::new();
 
@@ -233,7 +470,9 @@ impl PluginExtractor {
         let kwargs = self.extract_keyword_arguments_from_text(call_text)?;
         let name = self.find_kwarg_by_role(&kwargs, args::KwArgRole::Name)?;
         let entry = self.find_entry_reference(&kwargs)?;
-        let description = self.find_optional_kwarg_by_role(&kwargs, args::KwArgRole::Description);
+        let description = self
+            .find_optional_kwarg_by_role(&kwargs, args::KwArgRole::Description)
+            .or_else(|| self.description_from_docstring(&entry));
         let method_param = self.find_optional_kwarg_by_role(&kwargs, args::KwArgRole::Method);
 
         // Infer implementation type from the entry point
@@ -245,6 +484,7 @@ impl PluginExtractor {
                 (constructor, Vec::new())
             }
             ImplementationType::Function => {
+                self.warn_on_generator_entry(&entry);
                 let call = self.resolve_entry_parameters(&entry, &ImplementationType::Function);
                 (Vec::new(), call)
             }
@@ -253,14 +493,19 @@ impl PluginExtractor {
         let kind = self.infer_kind_from_constructor(constructor);
         let resolved_method = method_param.or_else(|| Self::default_method_for_kind(&kind));
 
+        let (timeout_secs, retry_count) = self.extract_runtime_policy(&kwargs);
         let invocation = InvocationSpec {
             implementation,
             method: resolved_method,
             constructor: constructor_args,
             call: call_args,
+            timeout_secs,
+            retry_count,
+            cacheable: self.extract_cacheable(&kwargs),
         };
 
-        let io = self.infer_io_contract(&kind);
+        let io = self.infer_io_contract(&kind, &kwargs);
+        let tags = self.extract_tags(&kwargs);
         let resources = self.extract_resources(&kwargs);
 
         Ok(PluginSpec {
@@ -272,11 +517,39 @@ impl PluginExtractor {
             resources,
             upgrade: None,
             description,
-            tags: Vec::new(),
+            tags,
+            r2x_core_requirement: None,
+            confidence: None,
         })
     }
 
-    fn infer_invocation_type(entry: &str) -> ImplementationType {
+    /// Read the `tags=[...]` keyword argument as a list of strings
+    fn extract_tags(&self, kwargs: &[args::KwArg]) -> Vec<String> {
+        self.find_kwarg_value(kwargs, "tags")
+            .map(|value| args::parse_string_list(&value))
+            .unwrap_or_default()
+    }
+
+    /// Read the `timeout` (seconds) and `retry` keyword arguments, when given as integers
+    fn extract_runtime_policy(&self, kwargs: &[args::KwArg]) -> (Option<u64>, Option<u32>) {
+        let timeout_secs = self
+            .find_kwarg_value(kwargs, "timeout")
+            .ok()
+            .and_then(|value| value.parse().ok());
+        let retry_count = self
+            .find_kwarg_value(kwargs, "retry")
+            .ok()
+            .and_then(|value| value.parse().ok());
+        (timeout_secs, retry_count)
+    }
+
+    /// Whether the plugin opts into result caching with `cacheable=True`
+    fn extract_cacheable(&self, kwargs: &[args::KwArg]) -> bool {
+        self.find_kwarg_value(kwargs, "cacheable")
+            .is_ok_and(|value| value == "True")
+    }
+
+    pub(crate) fn infer_invocation_type(entry: &str) -> ImplementationType {
         let ident = entry.rsplit('.').next().unwrap_or(entry);
         if ident
             .chars()
@@ -284,15 +557,75 @@ impl PluginExtractor {
             .map(|c| c.is_uppercase())
             .unwrap_or(false)
         {
+            // Abstract-looking classes are still invoked as classes for now
+            if looks_like_abstract_class(ident) {
+                warn!(
+                    "Plugin entry '{}' looks like an abstract or mixin class",
+                    entry
+                );
+            }
             ImplementationType::Class
         } else {
             ImplementationType::Function
         }
     }
 
+    /// Classify a function by its return annotation: `"generator"` for
+    /// `Iterator`/`Generator` (including `typing.` or `collections.abc.` prefixes and
+    /// subscripts), `"function"` otherwise
+    pub fn infer_callable_type_from_annotations(return_annotation: &str) -> &'static str {
+        let base = return_annotation
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .split('[')
+            .next()
+            .unwrap_or_default();
+        let name = base.rsplit('.').next().unwrap_or(base).trim();
+        match name {
+            "Iterator" | "Generator" => "generator",
+            _ => "function",
+        }
+    }
+
+    /// Warn when a function entry returns a generator, whose result cannot be
+    /// serialized the way plugin outputs are
+    fn warn_on_generator_entry(&self, entry: &str) {
+        let Some((module, name)) = Self::split_entry(entry) else {
+            return;
+        };
+        let source = if module == self.current_module {
+            Some(self.content.clone())
+        } else {
+            self.load_module_source(&module)
+        };
+        let return_type = source
+            .and_then(|content| self.extract_function_return_type_from_content(&content, &name));
+        if let Some(return_type) = return_type {
+            if Self::infer_callable_type_from_annotations(&return_type) == "generator" {
+                warn!(
+                    "Plugin entry '{}' returns {}; generator results are not serializable",
+                    entry, return_type
+                );
+            }
+        }
+    }
+
     fn find_entry_reference(&self, kwargs: &[args::KwArg]) -> Result<String> {
-        let symbol = self.find_kwarg_by_role(kwargs, args::KwArgRole::EntryReference)?;
-        Ok(self.qualify_symbol(&symbol))
+        let kwarg = kwargs
+            .iter()
+            .find(|kw| kw.role == args::KwArgRole::EntryReference)
+            .ok_or_else(|| anyhow!("Argument with role EntryReference not found"))?;
+
+        // `obj=ClassCallable(module="pkg.mod", name="Symbol")` names the entry explicitly
+        if kwarg.arg_type == "constructor" {
+            let object = self.parse_constructor_call(&kwarg.value)?;
+            if let (Some(module), Some(name)) = (object["module"].as_str(), object["name"].as_str())
+            {
+                return Ok(format!("{}.{}", module, name));
+            }
+        }
+
+        Ok(self.qualify_symbol(&kwarg.value))
     }
 
     fn find_kwarg_by_role(&self, kwargs: &[args::KwArg], role: args::KwArgRole) -> Result<String> {
@@ -338,6 +671,7 @@ impl PluginExtractor {
         entries
             .into_iter()
             .map(|param| ArgumentSpec {
+                type_kind: param.annotation.as_deref().map(Self::infer_annotation_type),
                 name: param.name,
                 annotation: param.annotation,
                 default: param.default,
@@ -364,6 +698,18 @@ impl PluginExtractor {
             return Vec::new();
         }
 
+        let module_file = self.resolve_module_file(module_path);
+        if let Some(cached) = module_file
+            .as_deref()
+            .and_then(|path| parameters::cached_parameters(path, symbol))
+        {
+            debug!(
+                "Using cached parameters for '{}' in '{}'",
+                symbol, module_path
+            );
+            return cached;
+        }
+
         let source = match self.load_module_source(module_path) {
             Some(src) => src,
             None => {
@@ -386,7 +732,12 @@ impl PluginExtractor {
         };
 
         match result {
-            Ok(entries) => entries,
+            Ok(entries) => {
+                if let Some(path) = module_file.as_deref() {
+                    parameters::cache_parameters(path, symbol, &entries);
+                }
+                entries
+            }
             Err(_) => {
                 // Symbol not found directly - check if it's imported from another module
                 debug!(
@@ -430,14 +781,12 @@ impl PluginExtractor {
         symbol: &str,
         current_module: &str,
     ) -> Option<String> {
-        for line in content.lines() {
-            let trimmed = line.trim();
-
-            // Match: from X import Y or from X import Y, Z, ...
-            if let Some(rest) = trimmed.strip_prefix("from ") {
+        for statement in Self::import_statements(content) {
+            // Match: from X import Y or from X import (Y, Z, ...)
+            if let Some(rest) = statement.strip_prefix("from ") {
                 if let Some(import_idx) = rest.find(" import ") {
                     let from_part = rest[..import_idx].trim();
-                    let import_part = rest[import_idx + 8..].trim();
+                    let import_part = Self::strip_import_parens(&rest[import_idx + 8..]);
 
                     // Check if our symbol is in the import list
                     let imports: Vec<&str> = import_part
@@ -524,7 +873,7 @@ impl PluginExtractor {
         }
     }
 
-    fn default_method_for_kind(kind: &PluginKind) -> Option<String> {
+    pub(crate) fn default_method_for_kind(kind: &PluginKind) -> Option<String> {
         match kind {
             PluginKind::Parser => Some("build_system".to_string()),
             PluginKind::Exporter => Some("export".to_string()),
@@ -541,7 +890,22 @@ impl PluginExtractor {
             .unwrap_or(false)
     }
 
-    fn infer_io_contract(&self, kind: &PluginKind) -> IOContract {
+    /// I/O contract for a plugin kind, overridden by `consumes=`/`produces=` lists
+    ///
+    /// Declared lists accept slot names (`"SYSTEM"`) and plugin-defined slots
+    /// (`"Custom:database"`).
+    fn infer_io_contract(&self, kind: &PluginKind, kwargs: &[args::KwArg]) -> IOContract {
+        let mut contract = Self::default_io_contract(kind);
+        if let Ok(consumes) = self.find_kwarg_value(kwargs, "consumes") {
+            contract.consumes = args::parse_io_slot_list(&consumes);
+        }
+        if let Ok(produces) = self.find_kwarg_value(kwargs, "produces") {
+            contract.produces = args::parse_io_slot_list(&produces);
+        }
+        contract
+    }
+
+    pub(crate) fn default_io_contract(kind: &PluginKind) -> IOContract {
         match kind {
             PluginKind::Parser => IOContract {
                 consumes: vec![IOSlot::StoreFolder, IOSlot::ConfigFile],
@@ -571,14 +935,18 @@ impl PluginExtractor {
                 let module = self
                     .import_map
                     .get(&config_class)
-                    .map(|m| self.normalize_module_path(m))
+                    .map(|(m, _)| self.normalize_module_path(m))
                     .unwrap_or_else(|| self.current_module.clone());
                 let fields = self.extract_config_fields(&module, &config_class);
+                let pydantic_version = self
+                    .load_module_source(&module)
+                    .and_then(|source| Self::detect_pydantic_version(&source, &config_class));
 
                 ConfigSpec {
                     module,
                     name: config_class,
                     fields,
+                    pydantic_version,
                 }
             });
 
@@ -607,42 +975,111 @@ impl PluginExtractor {
         }
     }
 
-    fn build_import_map_static(content: &str) -> HashMap<String, String> {
-        let mut map = HashMap::new();
+    /// Join import statements into one string each, comments removed
+    ///
+    /// Parenthesised imports and backslash continuations spanning several lines are
+    /// accumulated until the statement is complete.
+    fn import_statements(content: &str) -> Vec<String> {
+        let continues = |statement: &str, line: &str| {
+            line.ends_with('\\') || statement.matches('(').count() > statement.matches(')').count()
+        };
 
+        let mut statements = Vec::new();
+        let mut pending: Option<String> = None;
         for line in content.lines() {
-            let line = line.trim();
-            if line.starts_with('#') {
-                continue;
+            let code = line.split('#').next().unwrap_or_default().trim();
+            let statement = match pending.take() {
+                Some(mut statement) => {
+                    statement.push(' ');
+                    statement.push_str(code.trim_end_matches('\\').trim_end());
+                    statement
+                }
+                None if code.starts_with("from ") || code.starts_with("import ") => {
+                    code.trim_end_matches('\\').trim_end().to_string()
+                }
+                None => continue,
+            };
+            if continues(&statement, code) {
+                pending = Some(statement);
+            } else {
+                statements.push(statement);
             }
+        }
+        statements.extend(pending);
+        statements
+    }
+
+    /// Names part of a `from X import ...` statement without its parentheses
+    fn strip_import_parens(imports_part: &str) -> &str {
+        let imports_part = imports_part.trim();
+        imports_part
+            .strip_prefix('(')
+            .and_then(|inner| inner.strip_suffix(')'))
+            .unwrap_or(imports_part)
+    }
+
+    /// Map local names to `(module, imported name)`
+    ///
+    /// `import module` entries have an empty name. `from module import *` is skipped,
+    /// since the imported names are unknown.
+    fn build_import_map_static(content: &str) -> HashMap<String, (String, String)> {
+        let mut map = HashMap::new();
 
+        for line in Self::import_statements(content) {
+            let line = line.as_str();
             if line.starts_with("from ") && line.contains(" import ") {
                 if let Some(import_idx) = line.find(" import ") {
                     let module = line[5..import_idx].trim();
-                    let imports_part = line[import_idx + 8..].trim();
+                    let imports_part = Self::strip_import_parens(&line[import_idx + 8..]);
 
                     for import_item in imports_part.split(',') {
                         let import_item = import_item.trim();
-                        if import_item.ends_with('\\') || import_item.is_empty() {
+                        if import_item.is_empty() {
+                            continue;
+                        }
+                        if import_item == "*" {
+                            debug!("Skipped wildcard import of module {}", module);
                             continue;
                         }
 
-                        let class_name = if let Some(as_idx) = import_item.find(" as ") {
-                            import_item[as_idx + 4..].trim()
-                        } else {
-                            import_item
-                        };
+                        let (imported_name, local_name) =
+                            if let Some(as_idx) = import_item.find(" as ") {
+                                (&import_item[..as_idx], &import_item[as_idx + 4..])
+                            } else {
+                                (import_item, import_item)
+                            };
 
-                        let class_name = class_name
-                            .trim_matches(|c| c == '(' || c == ')' || c == ',')
-                            .trim();
+                        let imported_name = imported_name.trim().to_string();
+                        let local_name = local_name.trim().to_string();
 
-                        if !class_name.is_empty() && !class_name.starts_with('#') {
-                            map.insert(class_name.to_string(), module.to_string());
-                            debug!("Mapped class {} to module {}", class_name, module);
+                        if !local_name.is_empty() {
+                            debug!("Mapped class {} to module {}", local_name, module);
+                            map.insert(local_name, (module.to_string(), imported_name));
                         }
                     }
                 }
+            } else if let Some(imports_part) = line.strip_prefix("import ") {
+                for import_item in imports_part.split(',') {
+                    let import_item = import_item.trim();
+                    if import_item.is_empty() {
+                        continue;
+                    }
+
+                    let (module, local_name) = if let Some(as_idx) = import_item.find(" as ") {
+                        (
+                            import_item[..as_idx].trim(),
+                            import_item[as_idx + 4..].trim(),
+                        )
+                    } else {
+                        let last = import_item.rsplit('.').next().unwrap_or(import_item);
+                        (import_item, last)
+                    };
+
+                    if !local_name.is_empty() {
+                        debug!("Mapped name {} to module {}", local_name, module);
+                        map.insert(local_name.to_string(), (module.to_string(), String::new()));
+                    }
+                }
             }
         }
 
@@ -660,20 +1097,30 @@ impl PluginExtractor {
     }
 
     fn qualify_symbol(&self, symbol: &str) -> String {
-        if symbol.contains('.') || self.current_module.is_empty() {
+        if let Some((head, rest)) = symbol.split_once('.') {
+            // `module.Attr` where `module` came from an `import module [as alias]`
+            return match self.import_map.get(head) {
+                Some((module, name)) if name.is_empty() => {
+                    format!("{}.{}", self.normalize_module_path(module), rest)
+                }
+                _ => symbol.to_string(),
+            };
+        }
+
+        if self.current_module.is_empty() {
             return symbol.to_string();
         }
 
-        let module = self
-            .import_map
-            .get(symbol)
-            .map(|m| self.normalize_module_path(m))
-            .unwrap_or_else(|| self.current_module.clone());
+        let (module, name) = match self.import_map.get(symbol) {
+            Some((module, name)) if name.is_empty() => return module.clone(),
+            Some((module, name)) => (self.normalize_module_path(module), name.as_str()),
+            None => (self.current_module.clone(), symbol),
+        };
 
         if module.is_empty() {
-            symbol.to_string()
+            name.to_string()
         } else {
-            format!("{}.{}", module, symbol)
+            format!("{}.{}", module, name)
         }
     }
 
@@ -885,7 +1332,166 @@ impl PluginExtractor {
             }
         }
 
-        fields
+        Self::expand_model_fields(fields)
+    }
+
+    /// Replace a `model_fields: ClassVar[dict] = {...}` declaration by the fields it
+    /// lists
+    ///
+    /// Each entry maps a field name to `FieldInfo(annotation=..., default=...)`; entries
+    /// without a default or default factory are required.
+    fn expand_model_fields(fields: Vec<ConfigField>) -> Vec<ConfigField> {
+        let mut expanded = Vec::with_capacity(fields.len());
+        for field in fields {
+            let declared = (field.name == "model_fields")
+                .then_some(field.default.as_deref())
+                .flatten()
+                .and_then(|value| value.trim().strip_prefix('{')?.strip_suffix('}'));
+            let Some(entries) = declared else {
+                expanded.push(field);
+                continue;
+            };
+            for entry in args::split_top_level(entries) {
+                let Some((name, info)) = entry.split_once(':') else {
+                    continue;
+                };
+                let name = name.trim().trim_matches(['"', '\'']);
+                if name.is_empty() {
+                    continue;
+                }
+                let info = info.trim();
+                let info_args = info
+                    .split_once('(')
+                    .and_then(|(_, rest)| rest.strip_suffix(')'))
+                    .unwrap_or_default();
+                let mut annotation = None;
+                let mut default = None;
+                let mut has_factory = false;
+                for arg in args::split_top_level(info_args) {
+                    match arg.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+                        Some(("annotation", value)) => annotation = Some(value.to_string()),
+                        Some(("default", value))
+                            if value != "..." && value != "PydanticUndefined" =>
+                        {
+                            default = Some(value.to_string())
+                        }
+                        Some(("default_factory", _)) => has_factory = true,
+                        _ => {}
+                    }
+                }
+                expanded.push(ConfigField {
+                    name: name.to_string(),
+                    annotation,
+                    required: default.is_none() && !has_factory,
+                    default,
+                    constraints: Self::extract_field_constraints(info),
+                });
+            }
+        }
+        expanded
+    }
+
+    /// Major Pydantic version a model class is written for, from its bases and the APIs
+    /// it uses
+    ///
+    /// A base imported from `pydantic.v1` means version 1. Otherwise `model_config`,
+    /// `ConfigDict` and the v2 validator decorators mean version 2, and an inner
+    /// `class Config:`, `@validator` or `@root_validator` mean version 1. Returns `None`
+    /// for plain classes and models that use neither.
+    fn detect_pydantic_version(source: &str, class_name: &str) -> Option<u8> {
+        let lines: Vec<&str> = source.lines().collect();
+        let start = lines.iter().position(|line| {
+            line.trim_start()
+                .strip_prefix("class ")
+                .and_then(|rest| rest.strip_prefix(class_name))
+                .is_some_and(|rest| rest.starts_with('(') || rest.starts_with(':'))
+        })?;
+        let v1_names = Self::pydantic_v1_names(source);
+        let header_end = lines[start..]
+            .iter()
+            .position(|line| line.trim_end().ends_with(':'))
+            .map_or(start, |offset| start + offset);
+        let header = lines[start..=header_end].join(" ");
+        let bases = header
+            .split_once('(')
+            .and_then(|(_, rest)| rest.rsplit_once(')'))
+            .map_or("", |(bases, _)| bases);
+        let v1_base = bases
+            .split(',')
+            .map(str::trim)
+            .filter(|base| !base.contains('='))
+            .any(|base| {
+                base.starts_with("pydantic.v1.")
+                    || v1_names.iter().any(|name| {
+                        base == name
+                            || base
+                                .strip_prefix(name.as_str())
+                                .is_some_and(|rest| rest.starts_with('.'))
+                    })
+            });
+        if v1_base {
+            return Some(1);
+        }
+
+        let class_indent = lines[start].len() - lines[start].trim_start().len();
+        let body: Vec<&str> = lines[start + 1..]
+            .iter()
+            .take_while(|line| {
+                line.trim().is_empty() || line.len() - line.trim_start().len() > class_indent
+            })
+            .map(|line| line.trim())
+            .collect();
+
+        const V2_MARKERS: [&str; 5] = [
+            "model_config",
+            "ConfigDict(",
+            "@field_validator",
+            "@model_validator",
+            "@computed_field",
+        ];
+        const V1_MARKERS: [&str; 3] = ["class Config:", "@validator", "@root_validator"];
+        if body
+            .iter()
+            .any(|line| V2_MARKERS.iter().any(|marker| line.starts_with(marker)))
+        {
+            Some(2)
+        } else if body
+            .iter()
+            .any(|line| V1_MARKERS.iter().any(|marker| line.starts_with(marker)))
+        {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Names bound by single-line imports from `pydantic.v1`: imported classes, the `v1`
+    /// module itself and aliases of either
+    fn pydantic_v1_names(source: &str) -> Vec<String> {
+        let bound = |item: &str| {
+            let item = item.trim().trim_matches(|c| c == '(' || c == ')');
+            item.rsplit_once(" as ")
+                .map_or(item, |(_, alias)| alias)
+                .trim()
+                .to_string()
+        };
+        let mut names = Vec::new();
+        for line in source.lines().map(str::trim) {
+            if let Some(items) = line.strip_prefix("from pydantic.v1 import ") {
+                names.extend(items.split(',').map(bound));
+            } else if let Some(items) = line.strip_prefix("from pydantic import ") {
+                names.extend(
+                    items
+                        .split(',')
+                        .filter(|item| item.trim().trim_start_matches('(').starts_with("v1"))
+                        .map(bound),
+                );
+            } else if let Some(module) = line.strip_prefix("import pydantic.v1 as ") {
+                names.push(module.trim().to_string());
+            }
+        }
+        names.retain(|name| !name.is_empty());
+        names
     }
 
     fn line_contains_equals(line: &str) -> bool {
@@ -970,9 +1576,71 @@ impl PluginExtractor {
             annotation,
             default,
             required,
+            constraints: Self::extract_field_constraints(definition),
         })
     }
 
+    /// Validation bounds of the Pydantic `Field(...)` and `FieldInfo(...)` calls in a
+    /// field definition
+    ///
+    /// Both `x: int = Field(ge=0)` and `x: Annotated[int, Field(ge=0)]` are read.
+    /// Numbers are kept as JSON numbers and string literals are unquoted; other values
+    /// are stored as their source text. Returns `None` when no bound is declared.
+    fn extract_field_constraints(definition: &str) -> Option<serde_json::Value> {
+        const KEYS: [&str; 8] = [
+            "ge",
+            "le",
+            "gt",
+            "lt",
+            "min_length",
+            "max_length",
+            "regex",
+            "pattern",
+        ];
+        let sg = AstGrep::new(definition, Python);
+        let mut constraints = serde_json::Map::new();
+        for call in sg.root().find_all("$FUNC($$$ARGS)") {
+            let call = call.get_node();
+            let is_field_call = call.field("function").is_some_and(|func| {
+                matches!(func.text().rsplit('.').next(), Some("Field" | "FieldInfo"))
+            });
+            let Some(arguments) = call.field("arguments").filter(|_| is_field_call) else {
+                continue;
+            };
+            for argument in arguments
+                .children()
+                .filter(|node| node.kind() == "keyword_argument")
+            {
+                let (Some(key), Some(value)) = (argument.field("name"), argument.field("value"))
+                else {
+                    continue;
+                };
+                if KEYS.contains(&key.text().as_ref()) {
+                    constraints.insert(key.text().to_string(), Self::constraint_value(&value));
+                }
+            }
+        }
+        (!constraints.is_empty()).then_some(serde_json::Value::Object(constraints))
+    }
+
+    /// JSON value of a constraint argument: a number, the contents of a string
+    /// literal, or the source text
+    fn constraint_value(value: &Node<'_, StrDoc<Python>>) -> serde_json::Value {
+        let text = value.text();
+        if let Ok(number) = serde_json::from_str::<serde_json::Number>(&text) {
+            return serde_json::Value::Number(number);
+        }
+        if value.kind() == "string" {
+            let contents: String = value
+                .children()
+                .filter(|node| node.kind() == "string_content")
+                .map(|node| node.text().to_string())
+                .collect();
+            return serde_json::Value::String(contents);
+        }
+        serde_json::Value::String(text.to_string())
+    }
+
     fn split_annotation_and_default(text: &str) -> (Option<String>, Option<String>) {
         let mut depth = 0i32;
         let mut in_str: Option<char> = None;
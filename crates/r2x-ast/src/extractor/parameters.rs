@@ -2,7 +2,22 @@
 
 use super::*;
 use r2x_logger::debug;
+use serde_json::{Map, Value};
+use std::cell::RefCell;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Canonical file path, callable name and file mtime
+type ParameterCacheKey = (PathBuf, String, SystemTime);
+
+thread_local! {
+    /// Parameters already extracted per callable, so a module defining several
+    /// plugins is only parsed once. The mtime in the key invalidates stale entries.
+    static PARAMETER_CACHE: RefCell<HashMap<ParameterCacheKey, Vec<ParameterEntry>>> =
+        RefCell::new(HashMap::new());
+}
 
+#[derive(Debug, Clone)]
 pub(super) struct ParameterEntry {
     pub name: String,
     pub annotation: Option<String>,
@@ -10,6 +25,28 @@ pub(super) struct ParameterEntry {
     pub is_required: bool,
 }
 
+fn parameter_cache_key(file_path: &Path, callable_name: &str) -> Option<ParameterCacheKey> {
+    let canonical = fs::canonicalize(file_path).ok()?;
+    let mtime = fs::metadata(&canonical).ok()?.modified().ok()?;
+    Some((canonical, callable_name.to_string(), mtime))
+}
+
+pub(super) fn cached_parameters(
+    file_path: &Path,
+    callable_name: &str,
+) -> Option<Vec<ParameterEntry>> {
+    let key = parameter_cache_key(file_path, callable_name)?;
+    PARAMETER_CACHE.with(|cache| cache.borrow().get(&key).cloned())
+}
+
+pub(super) fn cache_parameters(file_path: &Path, callable_name: &str, entries: &[ParameterEntry]) {
+    if let Some(key) = parameter_cache_key(file_path, callable_name) {
+        PARAMETER_CACHE.with(|cache| {
+            cache.borrow_mut().insert(key, entries.to_vec());
+        });
+    }
+}
+
 impl PluginExtractor {
     pub(super) fn extract_class_parameters_from_content(
         &self,
@@ -66,6 +103,179 @@ impl PluginExtractor {
         )
     }
 
+    /// Extract the fields of a `@dataclass` class from its source
+    ///
+    /// Each field maps to an object with `annotation`, `default`, `default_factory`
+    /// and `required`. Defaults given through `field(default=...)` are unwrapped and
+    /// `field(default_factory=...)` is recorded as the factory source text.
+    pub fn extract_dataclass_fields(
+        file_content: &str,
+        class_name: &str,
+    ) -> Result<Map<String, Value>> {
+        let lines: Vec<&str> = file_content.lines().collect();
+        let mut decorators: Vec<&str> = Vec::new();
+        let mut class_start = None;
+
+        for (idx, line) in lines.iter().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with('@') {
+                decorators.push(trimmed);
+                continue;
+            }
+            if let Some(rest) = trimmed.strip_prefix("class ") {
+                if let Some(after_name) = rest.strip_prefix(class_name) {
+                    if after_name.starts_with('(') || after_name.starts_with(':') {
+                        class_start = Some(idx);
+                        break;
+                    }
+                }
+            }
+            if !trimmed.is_empty() && !trimmed.starts_with('#') {
+                decorators.clear();
+            }
+        }
+
+        let class_idx = class_start.ok_or_else(|| anyhow!("Class not found: {}", class_name))?;
+        let is_dataclass = decorators.iter().any(|decorator| {
+            let name = decorator.trim_start_matches('@');
+            let name = name.split('(').next().unwrap_or("").trim();
+            name == "dataclass" || name == "dataclasses.dataclass"
+        });
+        if !is_dataclass {
+            return Err(anyhow!("Class {} is not a dataclass", class_name));
+        }
+
+        let class_line = lines[class_idx];
+        let class_indent = class_line.len() - class_line.trim_start().len();
+        let mut body_indent = None;
+        let mut fields = Map::new();
+        let mut buffer = String::new();
+        let mut depth = 0i32;
+
+        for line in &lines[class_idx + 1..] {
+            let trimmed = line.trim_start();
+            let indent = line.len() - trimmed.len();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if indent <= class_indent && depth <= 0 {
+                break;
+            }
+
+            let code = trimmed.split('#').next().unwrap_or("").trim();
+            if !buffer.is_empty() {
+                buffer.push(' ');
+                buffer.push_str(code);
+                depth += Self::bracket_delta(code);
+                if depth <= 0 {
+                    Self::push_dataclass_field(&mut fields, &buffer);
+                    buffer.clear();
+                }
+                continue;
+            }
+
+            let body_indent = *body_indent.get_or_insert(indent);
+            let starts_identifier = code
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+            if indent != body_indent
+                || !starts_identifier
+                || code.starts_with("def ")
+                || code.starts_with("class ")
+                || !code.contains(':')
+            {
+                continue;
+            }
+
+            depth = Self::bracket_delta(code);
+            if depth > 0 {
+                buffer.push_str(code);
+            } else {
+                Self::push_dataclass_field(&mut fields, code);
+            }
+        }
+
+        debug(&format!(
+            "Extracted {} dataclass fields from {}",
+            fields.len(),
+            class_name
+        ));
+        Ok(fields)
+    }
+
+    fn push_dataclass_field(fields: &mut Map<String, Value>, definition: &str) {
+        let Some((name, rest)) = definition.split_once(':') else {
+            return;
+        };
+        let name = name.trim();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return;
+        }
+
+        let (annotation, raw_default) = Self::split_annotation_and_default(rest.trim());
+        let mut default = raw_default.filter(|value| !value.is_empty());
+        let mut default_factory = None;
+
+        if let Some(field_args) = default.as_deref().and_then(|value| {
+            value
+                .strip_prefix("dataclasses.field(")
+                .or_else(|| value.strip_prefix("field("))
+                .and_then(|args| args.strip_suffix(')'))
+        }) {
+            let mut field_default = None;
+            for arg in Self::split_top_level_commas(field_args) {
+                if let Some((key, value)) = arg.split_once('=') {
+                    match key.trim() {
+                        "default" => field_default = Some(value.trim().to_string()),
+                        "default_factory" => default_factory = Some(value.trim().to_string()),
+                        _ => {}
+                    }
+                }
+            }
+            default = field_default;
+        }
+
+        let required = default.is_none() && default_factory.is_none();
+        let mut entry = Map::new();
+        entry.insert(
+            "annotation".to_string(),
+            annotation.map(Value::String).unwrap_or(Value::Null),
+        );
+        entry.insert(
+            "default".to_string(),
+            default.map(Value::String).unwrap_or(Value::Null),
+        );
+        entry.insert(
+            "default_factory".to_string(),
+            default_factory.map(Value::String).unwrap_or(Value::Null),
+        );
+        entry.insert("required".to_string(), Value::Bool(required));
+        fields.insert(name.to_string(), Value::Object(entry));
+    }
+
+    fn split_top_level_commas(text: &str) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut current = String::new();
+        let mut depth = 0i32;
+        for ch in text.chars() {
+            match ch {
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(ch);
+        }
+        if !current.trim().is_empty() {
+            parts.push(current);
+        }
+        parts
+    }
+
     fn find_class_signature(&self, content: &str, class_name: &str) -> Option<String> {
         let sg = AstGrep::new(content, Python);
         let root = sg.root();
@@ -65,6 +65,59 @@ fn test_infer_argument_type_class() {
     assert_eq!(extractor.infer_argument_type("MyClass"), "class_reference");
 }
 
+#[test]
+fn test_infer_argument_type_ignores_annotation_syntax() {
+    let extractor = PluginExtractor {
+        python_file_path: PathBuf::from("test.py"),
+        package_root: PathBuf::from("."),
+        package_prefix: "test".to_string(),
+        content: "def register_plugin(): pass".to_string(),
+        import_map: HashMap::new(),
+        current_module: "test.module".to_string(),
+    };
+
+    // Values are classified as values, even when they look like annotations
+    assert_eq!(
+        extractor.infer_argument_type("Mode.FAST | Mode.SAFE"),
+        "class_reference"
+    );
+    assert_eq!(extractor.infer_argument_type("[1, 2]"), "complex");
+}
+
+#[test]
+fn test_infer_annotation_type() {
+    let optional = |kind| TypeKind::Optional(Box::new(kind));
+    let list = |kind| TypeKind::List(Box::new(kind));
+    let cases = [
+        ("int", TypeKind::Number),
+        ("Path", TypeKind::ClassReference),
+        ("Optional[int]", optional(TypeKind::Number)),
+        ("str | None", optional(TypeKind::String)),
+        ("Union[str, None]", optional(TypeKind::String)),
+        ("Union[str, int]", TypeKind::Union),
+        ("int | float", TypeKind::Union),
+        ("Path | str | None", TypeKind::Union),
+        ("list[str]", list(TypeKind::String)),
+        (
+            "typing.Optional[List[int]]",
+            optional(list(TypeKind::Number)),
+        ),
+        ("dict[str, int] | None", optional(TypeKind::Complex)),
+    ];
+    for (annotation, expected) in cases {
+        assert_eq!(
+            PluginExtractor::infer_annotation_type(annotation),
+            expected,
+            "{}",
+            annotation
+        );
+    }
+
+    let kind = optional(list(TypeKind::Number));
+    assert_eq!(kind.to_string(), "optional_list_number");
+    assert_eq!(TypeKind::from(kind.to_string()), kind);
+}
+
 #[test]
 fn test_extract_plugins_from_package_constructor_style() -> Result<()> {
     let content = r#"
@@ -135,6 +188,320 @@ def register_plugin() -> Package:
     Ok(())
 }
 
+#[test]
+fn test_nested_constructor_kwargs() -> Result<()> {
+    let extractor = PluginExtractor {
+        python_file_path: PathBuf::from("test.py"),
+        package_root: PathBuf::from("."),
+        package_prefix: "r2x_sienna".to_string(),
+        content: String::new(),
+        import_map: HashMap::new(),
+        current_module: "r2x_sienna.plugins".to_string(),
+    };
+
+    let call = r#"ExporterPlugin(name="sienna-exporter", obj=ClassCallable(module="r2x_sienna.exporter", name="SiennaExporter", options=Options(strict=True, retries=3, extra=None)), description="Export, then save")"#;
+    let kwargs = extractor.extract_keyword_arguments_from_text(call)?;
+
+    assert_eq!(kwargs.len(), 3);
+    assert_eq!(kwargs[0].value, "sienna-exporter");
+    assert_eq!(kwargs[1].arg_type, "constructor");
+    assert_eq!(kwargs[2].value, "Export, then save");
+    assert_eq!(
+        extractor.find_entry_reference(&kwargs)?,
+        "r2x_sienna.exporter.SiennaExporter"
+    );
+
+    let nested = extractor.parse_constructor_call(&kwargs[1].value)?;
+    assert_eq!(nested["name"], "SiennaExporter");
+    assert_eq!(nested["options"]["strict"], true);
+    assert_eq!(nested["options"]["retries"], 3);
+    assert!(nested["options"]["extra"].is_null());
+    assert!(extractor.parse_constructor_call("add_defaults").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_infer_io_contract_with_custom_slots() -> Result<()> {
+    let extractor = PluginExtractor {
+        python_file_path: PathBuf::from("test.py"),
+        package_root: PathBuf::from("."),
+        package_prefix: "demo".to_string(),
+        content: String::new(),
+        import_map: HashMap::new(),
+        current_module: "demo.plugins".to_string(),
+    };
+
+    let kwargs = extractor.extract_keyword_arguments_from_text(
+        r#"ParserPlugin(name="demo", obj=DemoParser, consumes=["Custom:database", "CONFIG_FILE", "bogus"])"#,
+    )?;
+    let io = extractor.infer_io_contract(&PluginKind::Parser, &kwargs);
+    assert_eq!(
+        io.consumes,
+        vec![IOSlot::Custom("database".to_string()), IOSlot::ConfigFile]
+    );
+    assert_eq!(io.produces, vec![IOSlot::System]);
+    Ok(())
+}
+
+#[test]
+fn test_extract_tags() -> Result<()> {
+    let extractor = PluginExtractor {
+        python_file_path: PathBuf::from("test.py"),
+        package_root: PathBuf::from("."),
+        package_prefix: "demo".to_string(),
+        content: String::new(),
+        import_map: HashMap::new(),
+        current_module: "demo.plugins".to_string(),
+    };
+
+    let kwargs = extractor.extract_keyword_arguments_from_text(
+        r#"PluginSpec.parser(name="reeds-parser", entry=ReEDSParser, tags=["energy", "reeds"])"#,
+    )?;
+    assert_eq!(extractor.extract_tags(&kwargs), vec!["energy", "reeds"]);
+
+    let kwargs = extractor.extract_keyword_arguments_from_text(
+        r#"PluginSpec.parser(name="reeds-parser", entry=ReEDSParser)"#,
+    )?;
+    assert!(extractor.extract_tags(&kwargs).is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_extract_description_from_docstrings() {
+    let content = r#"
+class SingleLine:
+    """Parse ReEDS outputs into a System."""
+
+    def __init__(self, config):
+        self.config = config
+
+@dataclass
+class MultiLine:
+    """
+    Export a System to PLEXOS.
+
+    Writes the XML model and time series.
+    """
+
+class NoDocstring:
+    name = "plain"
+
+def convert(system, year: int):
+    '''Convert the system in place.'''
+    return system
+"#;
+
+    assert_eq!(
+        PluginExtractor::extract_description(content, "SingleLine").as_deref(),
+        Some("Parse ReEDS outputs into a System.")
+    );
+    assert_eq!(
+        PluginExtractor::extract_description(content, "MultiLine").as_deref(),
+        Some("Export a System to PLEXOS.\n\nWrites the XML model and time series.")
+    );
+    assert_eq!(
+        PluginExtractor::extract_description(content, "convert").as_deref(),
+        Some("Convert the system in place.")
+    );
+    assert_eq!(
+        PluginExtractor::extract_description(content, "NoDocstring"),
+        None
+    );
+    assert_eq!(
+        PluginExtractor::extract_description(content, "Missing"),
+        None
+    );
+}
+
+#[test]
+fn test_register_plugin_docstring_describes_single_plugin() -> Result<()> {
+    let content = r#"
+from r2x_core import Package, ParserPlugin
+
+class SiennaParser:
+    def __init__(self, config, path: str):
+        pass
+
+def register_plugin() -> Package:
+    """Parse Sienna systems."""
+    return Package(
+        name="r2x-sienna",
+        plugins=[ParserPlugin(name="sienna-parser", obj=SiennaParser)],
+    )
+"#;
+
+    let temp_dir = TempDir::new()?;
+    let pkg_root = temp_dir.path().join("r2x_sienna");
+    fs::create_dir_all(&pkg_root)?;
+    let plugin_file = pkg_root.join("plugins.py");
+    fs::write(&plugin_file, content)?;
+
+    let extractor = PluginExtractor::new(plugin_file, "r2x_sienna.plugins".to_string(), pkg_root)?;
+    let plugins = extractor.extract_plugins()?;
+    assert_eq!(plugins.len(), 1);
+    assert_eq!(
+        plugins[0].description.as_deref(),
+        Some("Parse Sienna systems.")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_infer_callable_type_from_annotations() {
+    for annotation in [
+        "Iterator[System]",
+        "typing.Generator[System, None, None]",
+        "collections.abc.Iterator",
+        "\"Generator\"",
+    ] {
+        assert_eq!(
+            PluginExtractor::infer_callable_type_from_annotations(annotation),
+            "generator",
+            "{}",
+            annotation
+        );
+    }
+    assert_eq!(
+        PluginExtractor::infer_callable_type_from_annotations("System"),
+        "function"
+    );
+    assert_eq!(
+        PluginExtractor::infer_callable_type_from_annotations("list[Iterator]"),
+        "function"
+    );
+}
+
+#[test]
+fn test_clean_docstring() {
+    assert_eq!(
+        PluginExtractor::clean_docstring(r#""""  One line.  """"#).as_deref(),
+        Some("One line.")
+    );
+    assert_eq!(
+        PluginExtractor::clean_docstring("r'''\n    Summary.\n\n      Indented detail.\n    '''")
+            .as_deref(),
+        Some("Summary.\n\n  Indented detail.")
+    );
+    assert_eq!(PluginExtractor::clean_docstring(r#""""   """"#), None);
+    assert_eq!(PluginExtractor::clean_docstring("not a string"), None);
+}
+
+#[test]
+fn test_extract_runtime_policy() -> Result<()> {
+    let extractor = PluginExtractor {
+        python_file_path: PathBuf::from("test.py"),
+        package_root: PathBuf::from("."),
+        package_prefix: "demo".to_string(),
+        content: String::new(),
+        import_map: HashMap::new(),
+        current_module: "demo.plugins".to_string(),
+    };
+
+    let kwargs = extractor.extract_keyword_arguments_from_text(
+        r#"PluginSpec.parser(name="demo.parser", entry=DemoParser, timeout=120, retry=2)"#,
+    )?;
+    assert_eq!(
+        extractor.extract_runtime_policy(&kwargs),
+        (Some(120), Some(2))
+    );
+    assert!(!extractor.extract_cacheable(&kwargs));
+
+    let kwargs = extractor.extract_keyword_arguments_from_text(
+        r#"PluginSpec.parser(name="demo.parser", entry=DemoParser, cacheable=True)"#,
+    )?;
+    assert!(extractor.extract_cacheable(&kwargs));
+
+    let kwargs = extractor.extract_keyword_arguments_from_text(
+        r#"PluginSpec.parser(name="demo.parser", entry=DemoParser, timeout=TIMEOUT)"#,
+    )?;
+    assert_eq!(extractor.extract_runtime_policy(&kwargs), (None, None));
+    Ok(())
+}
+
+#[test]
+fn test_extract_register_plugin_function_with_decorator() {
+    let content = r#"
+import functools
+
+@functools.cache
+@register_hook(
+    "r2x",
+)
+def register_plugin(
+    extra: bool = False,
+) -> Package:
+    return Package(
+        name="r2x-reeds",
+        plugins=[ParserPlugin(name="reeds-parser", obj=ReEDSParser)],
+    )
+
+def helper():
+    return ParserPlugin(name="not-a-plugin", obj=Helper)
+"#;
+
+    let source = PluginExtractor::extract_register_plugin_function(content).unwrap();
+    assert!(source.starts_with("@functools.cache\n@register_hook("));
+    assert!(source.contains("reeds-parser"));
+    assert!(!source.contains("not-a-plugin"));
+}
+
+#[test]
+fn test_extract_register_plugin_function_staticmethod() {
+    let content = r#"
+class Plugins:
+    @staticmethod
+    def register_plugin() -> Package:
+        return Package(
+            name="demo",
+            plugins=[ParserPlugin(name="demo-parser", obj=DemoParser)],
+        )
+
+    @staticmethod
+    def other():
+        pass
+"#;
+
+    let source = PluginExtractor::extract_register_plugin_function(content).unwrap();
+    assert_eq!(
+        source.lines().take(2).collect::<Vec<_>>(),
+        vec!["@staticmethod", "def register_plugin() -> Package:"]
+    );
+    assert!(source.contains("demo-parser"));
+    assert!(!source.contains("def other"));
+    assert!(PluginExtractor::extract_register_plugin_function("x = 1\n").is_none());
+}
+
+#[test]
+fn test_extract_module_level_source() {
+    let content = r#"from r2x_core import Package, ParserPlugin
+
+REEDS_PARSER = ParserPlugin(
+    name="reeds-parser",
+    obj=ReEDSParser,
+)
+
+@dataclass
+class Helper:
+    plugin = ParserPlugin(name="not-a-plugin", obj=Helper)
+
+def make_test_plugin():
+    return ParserPlugin(
+        name="test-only",
+        obj=TestParser,
+    )
+
+def register_plugin() -> Package:
+    return Package(name="r2x-reeds", plugins=[REEDS_PARSER])
+"#;
+
+    let source = PluginExtractor::extract_module_level_source(content);
+    assert!(source.contains("name=\"reeds-parser\""));
+    assert!(source.contains("obj=ReEDSParser,\n)"));
+    assert!(!source.contains("not-a-plugin"));
+    assert!(!source.contains("test-only"));
+    assert!(!source.contains("register_plugin"));
+}
+
 #[test]
 fn test_extract_plugins_from_manifest_add_style() -> Result<()> {
     let content = r#"
@@ -257,6 +624,139 @@ class TestConfig:
     Ok(())
 }
 
+#[test]
+fn test_extract_config_field_constraints() -> Result<()> {
+    let content = r#"
+class ReEDSConfig(BaseModel):
+    weather_year: int = Field(2012, ge=2007, le=2013)
+    solve_year: Annotated[int, Field(gt=2020, description="Year to solve")]
+    scenario: str = Field(min_length=1, max_length=64, pattern=r"^[a-z_]+$")
+    folder: str = "inputs"
+"#;
+
+    let temp_dir = TempDir::new()?;
+    let pkg_root = temp_dir.path().join("test_pkg");
+    fs::create_dir_all(&pkg_root)?;
+    let test_file = pkg_root.join("test.py");
+    fs::write(&test_file, content)?;
+
+    let extractor = PluginExtractor::new(test_file, "test_pkg.test".to_string(), pkg_root.clone())?;
+    let fields = extractor.extract_config_fields("test_pkg.test", "ReEDSConfig");
+    assert_eq!(fields.len(), 4);
+
+    assert_eq!(
+        fields[0].constraints,
+        Some(serde_json::json!({"ge": 2007, "le": 2013}))
+    );
+    assert_eq!(fields[1].constraints, Some(serde_json::json!({"gt": 2020})));
+    assert_eq!(
+        fields[2].constraints,
+        Some(serde_json::json!({"min_length": 1, "max_length": 64, "pattern": "^[a-z_]+$"}))
+    );
+    assert_eq!(fields[3].constraints, None);
+    Ok(())
+}
+
+#[test]
+fn test_extract_pydantic_model_fields() -> Result<()> {
+    let content = r#"
+from typing import ClassVar
+
+from pydantic import BaseModel, ConfigDict, Field, validator
+from pydantic.fields import FieldInfo
+
+
+class ReEDSV2Config(BaseModel):
+    model_config = ConfigDict(extra="forbid")
+    model_fields: ClassVar[dict[str, FieldInfo]] = {
+        "weather_year": FieldInfo(annotation=int, default=2012, ge=2007),
+        "solve_year": FieldInfo(annotation=int),
+        "scenarios": FieldInfo(annotation=list[str], default_factory=list),
+    }
+
+
+class ReEDSV1Config(BaseModel):
+    weather_year: int = Field(2012, ge=2007)
+
+    class Config:
+        extra = "forbid"
+
+    @validator("weather_year")
+    def check_year(cls, value):
+        return value
+
+
+class PlainConfig:
+    weather_year: int = 2012
+"#;
+
+    let temp_dir = TempDir::new()?;
+    let pkg_root = temp_dir.path().join("test_pkg");
+    fs::create_dir_all(&pkg_root)?;
+    let test_file = pkg_root.join("test.py");
+    fs::write(&test_file, content)?;
+
+    let extractor = PluginExtractor::new(test_file, "test_pkg.test".to_string(), pkg_root.clone())?;
+    let fields = extractor.extract_config_fields("test_pkg.test", "ReEDSV2Config");
+    let names: Vec<&str> = fields.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names, ["weather_year", "solve_year", "scenarios"]);
+    assert_eq!(fields[0].annotation.as_deref(), Some("int"));
+    assert_eq!(fields[0].default.as_deref(), Some("2012"));
+    assert_eq!(fields[0].constraints, Some(serde_json::json!({"ge": 2007})));
+    assert!(fields[1].required);
+    assert!(!fields[2].required);
+
+    let fields = extractor.extract_config_fields("test_pkg.test", "ReEDSV1Config");
+    assert_eq!(fields[0].name, "weather_year");
+    assert_eq!(fields[0].constraints, Some(serde_json::json!({"ge": 2007})));
+
+    assert_eq!(
+        PluginExtractor::detect_pydantic_version(content, "ReEDSV2Config"),
+        Some(2)
+    );
+    assert_eq!(
+        PluginExtractor::detect_pydantic_version(content, "ReEDSV1Config"),
+        Some(1)
+    );
+    assert_eq!(
+        PluginExtractor::detect_pydantic_version(content, "PlainConfig"),
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_detect_pydantic_version_from_bases() {
+    let content = r#"
+from pydantic import BaseModel, ConfigDict
+from pydantic.v1 import BaseModel as LegacyModel
+import pydantic.v1 as pv1
+
+
+class CurrentConfig(BaseModel):
+    model_config = ConfigDict(extra="forbid")
+
+
+class LegacyConfig(LegacyModel):
+    weather_year: int = 2012
+
+
+class AliasedConfig(
+    pv1.BaseModel,
+):
+    weather_year: int = 2012
+
+
+class UnmarkedConfig(BaseModel):
+    weather_year: int = 2012
+"#;
+    let version = |class| PluginExtractor::detect_pydantic_version(content, class);
+    assert_eq!(version("CurrentConfig"), Some(2));
+    assert_eq!(version("LegacyConfig"), Some(1));
+    assert_eq!(version("AliasedConfig"), Some(1));
+    assert_eq!(version("UnmarkedConfig"), None);
+}
+
 #[test]
 fn test_extract_multiple_config_fields_separately() -> Result<()> {
     let content = r#"
@@ -327,3 +827,290 @@ class PLEXOSConfig:
 
     Ok(())
 }
+
+#[test]
+fn test_extract_entry_points_from_dist_info() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let site_packages = temp_dir.path();
+    let pkg_root = site_packages.join("r2x_reeds");
+    fs::create_dir_all(&pkg_root)?;
+    let dist_info = site_packages.join("r2x_reeds-0.3.0.dist-info");
+    fs::create_dir_all(&dist_info)?;
+    // Similarly named package must not be picked up
+    fs::create_dir_all(site_packages.join("r2x_reeds_to_sienna-0.1.0.dist-info"))?;
+    fs::write(
+        dist_info.join("entry_points.txt"),
+        r#"[console_scripts]
+reeds = r2x_reeds.cli:main
+
+[r2x_plugin]
+r2x-reeds = r2x_reeds.plugins:register_plugin
+reeds-parser = r2x_reeds.parser:ReEDSParser
+"#,
+    )?;
+
+    let entry_points = PluginExtractor::extract_entry_points(&pkg_root, "r2x-reeds")?;
+    assert_eq!(
+        entry_points,
+        vec![
+            "r2x-reeds = r2x_reeds.plugins:register_plugin",
+            "reeds-parser = r2x_reeds.parser:ReEDSParser",
+        ]
+    );
+
+    let mut plugins = vec![PluginSpec {
+        name: "reeds-parser".to_string(),
+        kind: PluginKind::Parser,
+        entry: "r2x_reeds.plugins.ReEDSParser".to_string(),
+        invocation: InvocationSpec {
+            implementation: ImplementationType::Class,
+            method: Some("build_system".to_string()),
+            constructor: Vec::new(),
+            call: Vec::new(),
+            timeout_secs: None,
+            retry_count: None,
+            cacheable: false,
+        },
+        io: IOContract {
+            consumes: Vec::new(),
+            produces: Vec::new(),
+        },
+        resources: None,
+        upgrade: None,
+        description: None,
+        tags: Vec::new(),
+        r2x_core_requirement: None,
+        confidence: None,
+    }];
+    PluginExtractor::apply_entry_points(&mut plugins, &entry_points);
+    assert_eq!(plugins[0].entry, "r2x_reeds.parser.ReEDSParser");
+
+    Ok(())
+}
+
+#[test]
+fn test_build_import_map_import_styles() {
+    let content = r#"
+from r2x_core import System, DataStore as Store
+import r2x_reeds.parser
+import r2x_core as core
+"#;
+    let map = PluginExtractor::build_import_map_static(content);
+
+    assert_eq!(
+        map.get("System"),
+        Some(&("r2x_core".to_string(), "System".to_string()))
+    );
+    assert_eq!(
+        map.get("Store"),
+        Some(&("r2x_core".to_string(), "DataStore".to_string()))
+    );
+    assert_eq!(
+        map.get("parser"),
+        Some(&("r2x_reeds.parser".to_string(), String::new()))
+    );
+    assert_eq!(
+        map.get("core"),
+        Some(&("r2x_core".to_string(), String::new()))
+    );
+}
+
+#[test]
+fn test_build_import_map_multiline_and_wildcard() {
+    let content = r#"
+from r2x_core import (
+    ParserPlugin,
+    ExporterPlugin as Exporter,  # renamed
+    UpgraderPlugin,
+)
+from .parser import \
+    ReEDSParser, ReEDSConfig
+from r2x_core.units import *
+import numpy as np, pandas
+"#;
+    let map = PluginExtractor::build_import_map_static(content);
+
+    assert_eq!(
+        map.get("ParserPlugin"),
+        Some(&("r2x_core".to_string(), "ParserPlugin".to_string()))
+    );
+    assert_eq!(
+        map.get("Exporter"),
+        Some(&("r2x_core".to_string(), "ExporterPlugin".to_string()))
+    );
+    assert_eq!(
+        map.get("UpgraderPlugin"),
+        Some(&("r2x_core".to_string(), "UpgraderPlugin".to_string()))
+    );
+    assert_eq!(
+        map.get("ReEDSConfig"),
+        Some(&(".parser".to_string(), "ReEDSConfig".to_string()))
+    );
+    assert!(map.values().all(|(module, _)| module != "r2x_core.units"));
+    assert_eq!(map.get("np"), Some(&("numpy".to_string(), String::new())));
+    assert_eq!(
+        map.get("pandas"),
+        Some(&("pandas".to_string(), String::new()))
+    );
+    assert_eq!(map.len(), 7);
+}
+
+#[test]
+fn test_qualify_symbol_through_module_import() {
+    let content = "import r2x_core\nimport r2x_reeds.parser as reeds\nfrom r2x_core import DataStore as Store\n";
+    let extractor = PluginExtractor {
+        python_file_path: PathBuf::from("test.py"),
+        package_root: PathBuf::from("."),
+        package_prefix: "r2x_reeds".to_string(),
+        content: content.to_string(),
+        import_map: PluginExtractor::build_import_map_static(content),
+        current_module: "r2x_reeds.plugins".to_string(),
+    };
+
+    assert_eq!(
+        extractor.qualify_symbol("r2x_core.System"),
+        "r2x_core.System"
+    );
+    assert_eq!(
+        extractor.qualify_symbol("reeds.ReEDSParser"),
+        "r2x_reeds.parser.ReEDSParser"
+    );
+    assert_eq!(extractor.qualify_symbol("Store"), "r2x_core.DataStore");
+    assert_eq!(
+        extractor.qualify_symbol("LocalClass"),
+        "r2x_reeds.plugins.LocalClass"
+    );
+}
+
+#[test]
+fn test_extract_dataclass_fields() {
+    let content = r#"
+from dataclasses import dataclass, field
+
+
+@dataclass(frozen=True)
+class ReEDSConfig:
+    """ReEDS parser configuration."""
+
+    weather_year: int
+    solve_year: int = 2030
+    scenario: str = field(default="base")
+    tags: list[str] = field(
+        default_factory=list,
+    )
+
+    def describe(self) -> str:
+        label: str = "unused"
+        return label
+
+
+class PlainConfig:
+    name: str
+"#;
+
+    let fields = PluginExtractor::extract_dataclass_fields(content, "ReEDSConfig").unwrap();
+    assert_eq!(fields.len(), 4);
+
+    assert_eq!(fields["weather_year"]["annotation"], "int");
+    assert_eq!(fields["weather_year"]["required"], true);
+    assert_eq!(fields["solve_year"]["default"], "2030");
+    assert_eq!(fields["scenario"]["default"], "\"base\"");
+    assert_eq!(fields["tags"]["default_factory"], "list");
+    assert!(fields["tags"]["default"].is_null());
+    assert_eq!(fields["tags"]["required"], false);
+
+    assert!(PluginExtractor::extract_dataclass_fields(content, "PlainConfig").is_err());
+    assert!(PluginExtractor::extract_dataclass_fields(content, "Missing").is_err());
+}
+
+#[test]
+fn test_parameter_cache_keyed_by_file_and_callable() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let module_file = temp_dir.path().join("parser.py");
+    fs::write(&module_file, "class ReEDSParser:\n    pass\n")?;
+
+    assert!(parameters::cached_parameters(&module_file, "ReEDSParser").is_none());
+
+    let entries = vec![parameters::ParameterEntry {
+        name: "solve_year".to_string(),
+        annotation: Some("int".to_string()),
+        default: None,
+        is_required: true,
+    }];
+    parameters::cache_parameters(&module_file, "ReEDSParser", &entries);
+
+    let cached = parameters::cached_parameters(&module_file, "ReEDSParser").unwrap();
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].name, "solve_year");
+    assert!(parameters::cached_parameters(&module_file, "OtherParser").is_none());
+
+    fs::remove_file(&module_file)?;
+    assert!(parameters::cached_parameters(&module_file, "ReEDSParser").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_score_confidence() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let pkg_root = temp_dir.path().join("r2x_reeds");
+    fs::create_dir_all(&pkg_root)?;
+    fs::write(
+        pkg_root.join("plugins.py"),
+        "from r2x_reeds.upgrader import ReEDSUpgrader\n",
+    )?;
+    fs::write(
+        pkg_root.join("upgrader.py"),
+        "class ReEDSUpgrader(BaseUpgrader):\n    pass\n",
+    )?;
+    let extractor = PluginExtractor {
+        python_file_path: pkg_root.join("plugins.py"),
+        package_root: pkg_root,
+        package_prefix: "r2x_reeds".to_string(),
+        content: String::new(),
+        import_map: HashMap::new(),
+        current_module: "r2x_reeds.plugins".to_string(),
+    };
+
+    let mut plugin = PluginSpec {
+        name: "reeds-upgrader".to_string(),
+        kind: PluginKind::Upgrader,
+        entry: "r2x_reeds.plugins.ReEDSUpgrader".to_string(),
+        invocation: InvocationSpec {
+            implementation: ImplementationType::Class,
+            method: None,
+            constructor: Vec::new(),
+            call: Vec::new(),
+            timeout_secs: None,
+            retry_count: None,
+            cacheable: false,
+        },
+        io: IOContract {
+            consumes: Vec::new(),
+            produces: Vec::new(),
+        },
+        resources: None,
+        upgrade: None,
+        description: Some("Upgrade ReEDS inputs".to_string()),
+        tags: Vec::new(),
+        r2x_core_requirement: None,
+        confidence: None,
+    };
+
+    // Entry resolved through the re-export; only the upgrade steps are missing
+    let (confidence, missing) = extractor.score_confidence(&plugin);
+    assert!((confidence - 0.8).abs() < 1e-6);
+    assert_eq!(missing, vec!["upgrade_steps"]);
+
+    plugin.entry = "r2x_reeds.plugins.Missing".to_string();
+    plugin.description = None;
+    let (confidence, missing) = extractor.score_confidence(&plugin);
+    assert!((confidence - 0.4).abs() < 1e-6);
+    assert_eq!(missing, vec!["entry", "upgrade_steps", "description"]);
+
+    let plugins = extractor.with_confidence(vec![plugin]);
+    assert!(plugins[0]
+        .confidence
+        .is_some_and(|c| (c - 0.4).abs() < 1e-6));
+
+    Ok(())
+}
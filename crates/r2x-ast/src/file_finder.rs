@@ -0,0 +1,128 @@
+//! Locating the Python file that defines `register_plugin()`
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Which file of a package defines `register_plugin()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginFileSource {
+    /// `<package>/plugins.py`
+    Plugins,
+    /// `<package>/plugin.py`
+    Plugin,
+    /// `<package>/__init__.py`, for packages registering plugins at the top level
+    Init,
+}
+
+impl PluginFileSource {
+    /// File name of the source inside the package directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            PluginFileSource::Plugins => "plugins.py",
+            PluginFileSource::Plugin => "plugin.py",
+            PluginFileSource::Init => "__init__.py",
+        }
+    }
+
+    /// Import path of the plugin module in package `package_module`
+    ///
+    /// `r2x_reeds` gives `r2x_reeds.plugins`, `r2x_reeds.plugin` or `r2x_reeds` itself.
+    pub fn module_path(&self, package_module: &str) -> String {
+        match self {
+            PluginFileSource::Plugins => format!("{}.plugins", package_module),
+            PluginFileSource::Plugin => format!("{}.plugin", package_module),
+            PluginFileSource::Init => package_module.to_string(),
+        }
+    }
+}
+
+/// Find the plugin file of the package directory `package_dir`
+///
+/// `plugins.py` wins over `plugin.py`. `__init__.py` is only used when it defines
+/// `register_plugin()` itself, since every package has one.
+pub fn find_plugins_py(package_dir: &Path) -> Option<(PathBuf, PluginFileSource)> {
+    for source in [PluginFileSource::Plugins, PluginFileSource::Plugin] {
+        let path = package_dir.join(source.file_name());
+        if path.is_file() {
+            return Some((path, source));
+        }
+    }
+
+    let init = package_dir.join(PluginFileSource::Init.file_name());
+    let content = fs::read_to_string(&init).ok()?;
+    defines_register_plugin(&content).then_some((init, PluginFileSource::Init))
+}
+
+/// Whether module source defines a top-level `register_plugin` function
+fn defines_register_plugin(content: &str) -> bool {
+    content.lines().any(|line| {
+        line.strip_prefix("def register_plugin")
+            .or_else(|| line.strip_prefix("async def register_plugin"))
+            .is_some_and(|rest| rest.trim_start().starts_with('('))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_find_plugins_py_fallbacks() {
+        let dir = TempDir::new().unwrap();
+        let package = dir.path().join("r2x_reeds");
+        fs::create_dir_all(&package).unwrap();
+        fs::write(
+            package.join("__init__.py"),
+            "from .parser import ReEDSParser\n",
+        )
+        .unwrap();
+        assert_eq!(find_plugins_py(&package), None);
+
+        fs::write(
+            package.join("__init__.py"),
+            "def register_plugin() -> Package:\n    return Package(name=\"r2x-reeds\")\n",
+        )
+        .unwrap();
+        assert_eq!(
+            find_plugins_py(&package),
+            Some((package.join("__init__.py"), PluginFileSource::Init))
+        );
+
+        fs::write(package.join("plugin.py"), "").unwrap();
+        assert_eq!(
+            find_plugins_py(&package).map(|(_, source)| source),
+            Some(PluginFileSource::Plugin)
+        );
+
+        fs::write(package.join("plugins.py"), "").unwrap();
+        assert_eq!(
+            find_plugins_py(&package).map(|(_, source)| source),
+            Some(PluginFileSource::Plugins)
+        );
+    }
+
+    #[test]
+    fn test_defines_register_plugin() {
+        assert!(defines_register_plugin("def register_plugin():\n    pass"));
+        assert!(defines_register_plugin(
+            "def register_plugin ( ) -> Package: ..."
+        ));
+        assert!(!defines_register_plugin(
+            "    def register_plugin(self): ..."
+        ));
+        assert!(!defines_register_plugin("def register_plugins(): ..."));
+        assert!(!defines_register_plugin(
+            "from .plugins import register_plugin"
+        ));
+    }
+
+    #[test]
+    fn test_module_path() {
+        assert_eq!(
+            PluginFileSource::Plugins.module_path("r2x_reeds"),
+            "r2x_reeds.plugins"
+        );
+        assert_eq!(PluginFileSource::Init.module_path("r2x_reeds"), "r2x_reeds");
+    }
+}
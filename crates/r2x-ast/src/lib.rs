@@ -10,11 +10,18 @@
 //! no Python interpreter startup.
 pub mod decorator_scanner;
 pub mod extractor;
+pub mod file_finder;
+pub mod metrics;
+pub mod runtime;
 use anyhow::{anyhow, Result};
 use ast_grep_language::Python;
+use metrics::as_millis;
+pub use metrics::{BatchDiscoveryMetrics, DiscoveryMetrics};
 use r2x_logger as logger;
-use r2x_manifest::{DecoratorRegistration, FunctionSignature, PluginSpec};
+use r2x_manifest::{dist_info, DecoratorRegistration, DistInfo, FunctionSignature, PluginSpec};
+pub use runtime::DiscoveryMode;
 use std::path::Path;
+use std::time::Instant;
 
 /// AST-based plugin discovery orchestrator
 pub struct AstDiscovery;
@@ -27,16 +34,78 @@ impl AstDiscovery {
     /// * `package_name_full` - Full package name (e.g., "r2x-reeds")
     /// * `venv_path` - Optional path to virtual environment for entry_points.txt lookup
     /// * `package_version` - Optional package version string
+    /// * `mode` - Whether to load the package in Python instead of, or after, AST parsing
     ///
     /// # Returns
-    /// Tuple of (plugins with resolved references, decorator registrations)
+    /// Tuple of (plugins with resolved references, decorator registrations, timings)
     pub fn discover_plugins(
+        package_path: &Path,
+        package_name_full: &str,
+        venv_path: Option<&str>,
+        package_version: Option<&str>,
+        mode: DiscoveryMode,
+    ) -> Result<(
+        Vec<PluginSpec>,
+        Vec<DecoratorRegistration>,
+        DiscoveryMetrics,
+    )> {
+        logger::debug(&format!(
+            "Discovering plugins for {} ({})",
+            package_name_full,
+            mode.as_str()
+        ));
+        let ast_result =
+            Self::discover_plugins_ast(package_path, package_name_full, venv_path, package_version);
+        if mode == DiscoveryMode::AstOnly {
+            return ast_result;
+        }
+        match ast_result {
+            Ok(found) if !found.0.is_empty() => return Ok(found),
+            Ok(_) => logger::info(&format!(
+                "AST discovery found no plugins in {}, loading it in Python",
+                package_name_full
+            )),
+            Err(e) => logger::info(&format!(
+                "AST discovery failed for {} ({}), loading it in Python",
+                package_name_full, e
+            )),
+        }
+        Self::discover_plugins_runtime(package_name_full)
+    }
+
+    fn discover_plugins_runtime(
+        package_name_full: &str,
+    ) -> Result<(
+        Vec<PluginSpec>,
+        Vec<DecoratorRegistration>,
+        DiscoveryMetrics,
+    )> {
+        let start_time = Instant::now();
+        let plugins = runtime::discover_plugins_at_runtime(package_name_full)?;
+        let metrics = DiscoveryMetrics {
+            package: package_name_full.to_string(),
+            total_ms: as_millis(start_time.elapsed()),
+            plugin_count: plugins.len(),
+            ..Default::default()
+        };
+        Ok((plugins, Vec::new(), metrics))
+    }
+
+    fn discover_plugins_ast(
         package_path: &Path,
         package_name_full: &str,
         venv_path: Option<&str>,
         _package_version: Option<&str>,
-    ) -> Result<(Vec<PluginSpec>, Vec<DecoratorRegistration>)> {
-        let start_time = std::time::Instant::now();
+    ) -> Result<(
+        Vec<PluginSpec>,
+        Vec<DecoratorRegistration>,
+        DiscoveryMetrics,
+    )> {
+        let start_time = Instant::now();
+        let mut metrics = DiscoveryMetrics {
+            package: package_name_full.to_string(),
+            ..Default::default()
+        };
         logger::debug(&format!("AST discovery started for: {}", package_name_full));
 
         // Find the plugins.py file using entry_points.txt
@@ -53,9 +122,11 @@ impl AstDiscovery {
                     "No r2x_plugin entry point found for '{}': {}",
                     package_name_full, e
                 ));
-                return Ok((Vec::new(), Vec::new()));
+                metrics.total_ms = as_millis(start_time.elapsed());
+                return Ok((Vec::new(), Vec::new(), metrics));
             }
         };
+        metrics.file_path = plugins_py.clone();
         logger::debug(&format!("Found plugins.py at: {:?}", plugins_py));
 
         // Phase 1: Extract plugins with constructor_args
@@ -63,13 +134,21 @@ impl AstDiscovery {
             .parent()
             .map(|p| p.to_path_buf())
             .unwrap_or_else(|| package_path.to_path_buf());
-        let extractor = extractor::PluginExtractor::new(
+        let phase_start = Instant::now();
+        let content = std::fs::read_to_string(&plugins_py)
+            .map_err(|e| anyhow!("Failed to create extractor: {}", e))?;
+        metrics.file_read_ms = as_millis(phase_start.elapsed());
+
+        let phase_start = Instant::now();
+        let extractor = extractor::PluginExtractor::from_content(
             plugins_py.clone(),
             plugin_module.clone(),
             package_root.clone(),
-        )
-        .map_err(|e| anyhow!("Failed to create extractor: {}", e))?;
+            content,
+        );
+        metrics.import_parse_ms = as_millis(phase_start.elapsed());
 
+        let phase_start = Instant::now();
         let mut plugins = extractor
             .extract_plugins()
             .map_err(|e| anyhow!("Failed to extract plugins: {}", e))?;
@@ -79,6 +158,17 @@ impl AstDiscovery {
             plugins.len()
         ));
 
+        // Prefer entry points declared in the dist-info over derived module+class entries
+        match extractor::PluginExtractor::extract_entry_points(package_path, package_name_full) {
+            Ok(entry_points) => {
+                extractor::PluginExtractor::apply_entry_points(&mut plugins, &entry_points)
+            }
+            Err(e) => logger::debug(&format!(
+                "Could not read entry points for {}: {}",
+                package_name_full, e
+            )),
+        }
+
         // Phase 2: Resolve all class/function references
         for plugin in &mut plugins {
             extractor
@@ -87,6 +177,8 @@ impl AstDiscovery {
         }
 
         logger::debug("Resolved class/function references for extracted plugins");
+        metrics.plugin_extract_ms = as_millis(phase_start.elapsed());
+        metrics.plugin_count = plugins.len();
 
         // Phase 3: Scan for decorator registrations and associate with plugins
         let decorator_registrations = Self::scan_package_for_decorators(&plugins_py)?;
@@ -98,13 +190,24 @@ impl AstDiscovery {
         // TODO: Associate decorators with plugins based on class references
 
         let elapsed = start_time.elapsed();
+        metrics.total_ms = as_millis(elapsed);
         logger::info(&format!(
             "AST discovery completed in {:.2}ms for {}",
             elapsed.as_secs_f64() * 1000.0,
             package_name_full
         ));
+        logger::debug(&format!("AST discovery metrics: {:?}", metrics));
 
-        Ok((plugins, decorator_registrations))
+        Ok((plugins, decorator_registrations, metrics))
+    }
+    /// Path of the module holding `register_plugin()`, as declared by the package entry point
+    pub fn plugin_module_path(
+        package_path: &Path,
+        package_name_full: &str,
+        venv_path: Option<&str>,
+    ) -> Result<std::path::PathBuf> {
+        Self::find_plugins_py_via_entry_points(package_path, package_name_full, venv_path)
+            .map(|(path, _)| path)
     }
     /// Find plugins.py file using entry_points.txt
     fn find_plugins_py_via_entry_points(
@@ -122,19 +225,35 @@ impl AstDiscovery {
         // Look for [r2x_plugin] section and extract module:function
         let (module_path, _function) = Self::parse_entry_point(&entry_points_content)?;
         // Convert module path to file path
-        // e.g., "r2x_reeds.plugins" -> "r2x_reeds/plugins.py"
-        let relative_path = module_path.replace('.', "/") + ".py";
-        // For editable installs, package_path points to src/ or similar
-        // Try to locate the actual file
-        let plugins_path = package_path.join(&relative_path);
-        if plugins_path.exists() {
-            return Ok((plugins_path, module_path));
+        // e.g., "r2x_reeds.plugins" -> "r2x_reeds/plugins.py", or "r2x_reeds/__init__.py"
+        // when the entry point names the package itself
+        let module_file = module_path.replace('.', "/");
+        let relative_path = module_file.clone() + ".py";
+        // For editable installs, package_path points to src/ or similar.
+        // Also try one level up (in case package_path is the package root)
+        let bases: Vec<&Path> = std::iter::once(package_path)
+            .chain(package_path.parent())
+            .collect();
+        for base in &bases {
+            for candidate in [
+                base.join(&relative_path),
+                base.join(&module_file).join("__init__.py"),
+            ] {
+                if candidate.exists() {
+                    return Ok((candidate, module_path));
+                }
+            }
         }
-        // Try one level up (in case package_path is the package root)
-        if let Some(parent) = package_path.parent() {
-            let plugins_path = parent.join(&relative_path);
-            if plugins_path.exists() {
-                return Ok((plugins_path, module_path));
+        // The entry point module is missing: fall back to the conventional plugin
+        // files of its top-level package
+        let package_module = module_path.split('.').next().unwrap_or(&module_path);
+        for base in &bases {
+            if let Some((path, source)) = file_finder::find_plugins_py(&base.join(package_module)) {
+                logger::debug(&format!(
+                    "Entry point module {} not found, using {:?}",
+                    module_path, path
+                ));
+                return Ok((path, source.module_path(package_module)));
             }
         }
         Err(anyhow!(
@@ -149,11 +268,9 @@ impl AstDiscovery {
         package_name_full: &str,
         venv_path: Option<&str>,
     ) -> Result<std::path::PathBuf> {
-        use std::fs;
-        let normalized_name = package_name_full.replace('-', "_");
         logger::debug(&format!(
-            "Looking for entry_points.txt for package: {} (normalized: {})",
-            package_name_full, normalized_name
+            "Looking for entry_points.txt for package: {}",
+            package_name_full
         ));
 
         // Try venv site-packages first if provided
@@ -170,31 +287,10 @@ impl AstDiscovery {
                     "Searching site-packages at: {}",
                     site_packages.display()
                 ));
-                if let Ok(sp_entries) = fs::read_dir(&site_packages) {
-                    for sp_entry in sp_entries.flatten() {
-                        let name = sp_entry.file_name().to_string_lossy().to_string();
-                        // Match exact package name followed by version (e.g., "r2x_reeds-0.3.0.dist-info")
-                        // Use format!("{}-", normalized_name) to avoid false matches like
-                        // "r2x_reeds" matching "r2x_reeds_to_sienna-0.0.0.dist-info"
-                        if name.starts_with(&format!("{}-", normalized_name))
-                            && name.ends_with(".dist-info")
-                        {
-                            logger::debug(&format!("Found dist-info directory: {}", name));
-                            let entry_points = sp_entry.path().join("entry_points.txt");
-                            if entry_points.exists() {
-                                logger::debug(&format!(
-                                    "Found entry_points.txt at: {}",
-                                    entry_points.display()
-                                ));
-                                return Ok(entry_points);
-                            }
-                        }
-                    }
-                } else {
-                    logger::debug(&format!(
-                        "Failed to read site-packages directory: {}",
-                        site_packages.display()
-                    ));
+                if let Some(entry_points) =
+                    Self::find_entry_points_in(&site_packages, package_name_full)
+                {
+                    return Ok(entry_points);
                 }
             } else {
                 logger::debug(&format!(
@@ -213,24 +309,8 @@ impl AstDiscovery {
                 "Searching in parent directory: {}",
                 parent.display()
             ));
-            if let Ok(entries) = fs::read_dir(parent) {
-                for entry in entries.flatten() {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    // Match exact package name followed by version
-                    if name.starts_with(&format!("{}-", normalized_name))
-                        && name.ends_with(".dist-info")
-                    {
-                        logger::debug(&format!("Found dist-info directory near package: {}", name));
-                        let entry_points = entry.path().join("entry_points.txt");
-                        if entry_points.exists() {
-                            logger::debug(&format!(
-                                "Found entry_points.txt at: {}",
-                                entry_points.display()
-                            ));
-                            return Ok(entry_points);
-                        }
-                    }
-                }
+            if let Some(entry_points) = Self::find_entry_points_in(parent, package_name_full) {
+                return Ok(entry_points);
             }
         }
         logger::debug(&format!(
@@ -242,36 +322,34 @@ impl AstDiscovery {
             package_name_full
         ))
     }
+    /// Find entry_points.txt in a package's `.dist-info`, falling back to `.egg-info`
+    fn find_entry_points_in(dir: &Path, package_name_full: &str) -> Option<std::path::PathBuf> {
+        let entry_points = DistInfo::find(dir, package_name_full)?
+            .path
+            .join("entry_points.txt");
+        if !entry_points.exists() {
+            return None;
+        }
+        logger::debug(&format!(
+            "Found entry_points.txt at: {}",
+            entry_points.display()
+        ));
+        Some(entry_points)
+    }
     /// Parse entry_points.txt to extract r2x_plugin entry point
     fn parse_entry_point(content: &str) -> Result<(String, String)> {
-        let mut in_r2x_section = false;
-        for line in content.lines() {
-            let line = line.trim();
-            if line == "[r2x_plugin]" {
-                in_r2x_section = true;
-                continue;
-            }
-            if in_r2x_section {
-                if line.starts_with('[') {
-                    break;
-                }
-                if line.is_empty() || line.starts_with('#') {
-                    continue;
-                }
-                // Parse "key = module:function" format
-                if let Some(eq_idx) = line.find('=') {
-                    let value = line[eq_idx + 1..].trim();
-                    if let Some(colon_idx) = value.find(':') {
-                        let module = value[..colon_idx].trim().to_string();
-                        let function = value[colon_idx + 1..].trim().to_string();
-                        return Ok((module, function));
-                    }
-                }
-            }
-        }
-        Err(anyhow!(
-            "Package has entry_points.txt but no [r2x_plugin] section (not an r2x plugin package)"
-        ))
+        // Parse "key = module:function" format
+        dist_info::parse_entry_points(content, "r2x_plugin")
+            .into_iter()
+            .find_map(|(_, value)| {
+                let (module, function) = value.split_once(':')?;
+                Some((module.trim().to_string(), function.trim().to_string()))
+            })
+            .ok_or_else(|| {
+                anyhow!(
+                    "Package has entry_points.txt but no [r2x_plugin] section (not an r2x plugin package)"
+                )
+            })
     }
     /// Scan entire package directory for decorator registrations
     fn scan_package_for_decorators(
@@ -450,6 +528,36 @@ reeds = r2x_reeds.plugins:register_plugin
         assert_eq!(function, "register_plugin");
     }
     #[test]
+    fn test_find_entry_points_egg_info_fallback() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let site_packages = temp_dir.path();
+        let package_path = site_packages.join("r2x_reeds");
+        fs::create_dir_all(&package_path).unwrap();
+
+        // A similarly named package must not match
+        let other = site_packages.join("r2x_reeds_to_sienna.egg-info");
+        fs::create_dir_all(&other).unwrap();
+        fs::write(other.join("entry_points.txt"), "[r2x_plugin]\n").unwrap();
+        assert!(AstDiscovery::find_entry_points_txt(&package_path, "r2x-reeds", None).is_err());
+
+        let egg_info = site_packages.join("r2x_reeds.egg-info");
+        fs::create_dir_all(&egg_info).unwrap();
+        fs::write(
+            egg_info.join("entry_points.txt"),
+            "[r2x_plugin]\nreeds = r2x_reeds.plugins:register_plugin\n",
+        )
+        .unwrap();
+        let found = AstDiscovery::find_entry_points_txt(&package_path, "r2x-reeds", None).unwrap();
+        assert_eq!(found, egg_info.join("entry_points.txt"));
+
+        // A dist-info takes precedence when both exist
+        let dist_info = site_packages.join("r2x_reeds-0.3.0.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(dist_info.join("entry_points.txt"), "[r2x_plugin]\n").unwrap();
+        let found = AstDiscovery::find_entry_points_txt(&package_path, "r2x-reeds", None).unwrap();
+        assert_eq!(found, dist_info.join("entry_points.txt"));
+    }
+    #[test]
     fn test_plugin_extraction() {
         use r2x_manifest::{IOContract, IOSlot, ImplementationType, InvocationSpec, PluginKind};
 
@@ -462,6 +570,9 @@ reeds = r2x_reeds.plugins:register_plugin
                 method: Some("build_system".to_string()),
                 constructor: vec![],
                 call: vec![],
+                timeout_secs: None,
+                retry_count: None,
+                cacheable: false,
             },
             io: IOContract {
                 consumes: vec![IOSlot::StoreFolder, IOSlot::ConfigFile],
@@ -471,6 +582,8 @@ reeds = r2x_reeds.plugins:register_plugin
             upgrade: None,
             description: None,
             tags: vec![],
+            r2x_core_requirement: None,
+            confidence: None,
         };
 
         assert_eq!(plugin.name, "test-parser");
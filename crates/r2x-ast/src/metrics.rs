@@ -0,0 +1,78 @@
+//! Timing metrics for AST discovery
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Per-phase timings for discovering the plugins of one package
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiscoveryMetrics {
+    /// Package name, e.g. `r2x-reeds`
+    pub package: String,
+    pub total_ms: u64,
+    /// Reading the plugin module from disk
+    pub file_read_ms: u64,
+    /// Building the import map of the plugin module
+    pub import_parse_ms: u64,
+    /// Extracting plugins and resolving their references
+    pub plugin_extract_ms: u64,
+    pub plugin_count: usize,
+    /// Plugin module that was analyzed; empty when the package declares no entry point
+    pub file_path: PathBuf,
+}
+
+/// Discovery metrics for several packages, e.g. a package and its dependencies
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatchDiscoveryMetrics {
+    pub per_package: Vec<DiscoveryMetrics>,
+    pub total_ms: u64,
+}
+
+impl BatchDiscoveryMetrics {
+    pub fn push(&mut self, metrics: DiscoveryMetrics) {
+        self.total_ms += metrics.total_ms;
+        self.per_package.push(metrics);
+    }
+
+    pub fn extend(&mut self, other: BatchDiscoveryMetrics) {
+        for metrics in other.per_package {
+            self.push(metrics);
+        }
+    }
+
+    pub fn plugin_count(&self) -> usize {
+        self.per_package.iter().map(|m| m.plugin_count).sum()
+    }
+}
+
+pub(crate) fn as_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_metrics_aggregate() {
+        let mut batch = BatchDiscoveryMetrics::default();
+        batch.push(DiscoveryMetrics {
+            package: "r2x-reeds".to_string(),
+            total_ms: 12,
+            plugin_count: 3,
+            ..Default::default()
+        });
+
+        let mut deps = BatchDiscoveryMetrics::default();
+        deps.push(DiscoveryMetrics {
+            package: "r2x-plexos".to_string(),
+            total_ms: 8,
+            plugin_count: 2,
+            ..Default::default()
+        });
+        batch.extend(deps);
+
+        assert_eq!(batch.total_ms, 20);
+        assert_eq!(batch.plugin_count(), 5);
+        assert_eq!(batch.per_package[1].package, "r2x-plexos");
+    }
+}
@@ -0,0 +1,159 @@
+//! Runtime discovery used when static analysis cannot understand a package
+//!
+//! Imports the package through the Python bridge, calls its `register_plugin()` entry
+//! point and converts the serialized r2x-core `Package` into plugin specs.
+
+use crate::extractor::PluginExtractor;
+use anyhow::{anyhow, Result};
+use r2x_logger as logger;
+use r2x_manifest::{InvocationSpec, PluginKind, PluginSpec};
+use serde::Deserialize;
+
+/// How plugins of a package are discovered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiscoveryMode {
+    /// Static analysis only; Python is never started
+    #[default]
+    AstOnly,
+    /// Static analysis, loading the package in Python when it finds no plugins or fails
+    AstWithRuntimeFallback,
+}
+
+impl DiscoveryMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscoveryMode::AstOnly => "ast-only",
+            DiscoveryMode::AstWithRuntimeFallback => "ast-with-runtime-fallback",
+        }
+    }
+}
+
+/// `model_dump_json()` output of an r2x-core `Package`
+#[derive(Deserialize)]
+struct RuntimePackage {
+    plugins: Vec<RuntimePlugin>,
+}
+
+/// Serialized r2x-core `PluginSpec`; `entry` is the dotted import path of the
+/// plugin class or function
+#[derive(Deserialize)]
+struct RuntimePlugin {
+    name: String,
+    kind: String,
+    entry: String,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    cacheable: bool,
+}
+
+/// Load `package_name_full` through the Python bridge and return its plugins
+pub fn discover_plugins_at_runtime(package_name_full: &str) -> Result<Vec<PluginSpec>> {
+    // The bridge adds the `r2x_` prefix itself
+    let short_name = package_name_full
+        .strip_prefix("r2x-")
+        .or_else(|| package_name_full.strip_prefix("r2x_"))
+        .unwrap_or(package_name_full);
+    let json = r2x_python::Bridge::get()
+        .and_then(|bridge| bridge.load_plugin_package(short_name))
+        .map_err(|e| anyhow!("Failed to load '{}' in Python: {}", package_name_full, e))?;
+    let plugins = plugins_from_package_json(&json)?;
+    logger::debug(&format!(
+        "Runtime discovery found {} plugin(s) in {}",
+        plugins.len(),
+        package_name_full
+    ));
+    Ok(plugins)
+}
+
+/// Convert the `model_dump_json()` output of an r2x-core `Package` into plugin specs
+///
+/// Plugins of an unknown kind are skipped.
+pub fn plugins_from_package_json(json: &str) -> Result<Vec<PluginSpec>> {
+    let package: RuntimePackage =
+        serde_json::from_str(json).map_err(|e| anyhow!("Invalid package JSON: {}", e))?;
+    Ok(package
+        .plugins
+        .into_iter()
+        .filter_map(|plugin| match plugin.kind.parse::<PluginKind>() {
+            Ok(kind) => Some(plugin_spec(plugin, kind)),
+            Err(e) => {
+                logger::debug(&format!("Skipping plugin '{}': {}", plugin.name, e));
+                None
+            }
+        })
+        .collect())
+}
+
+fn plugin_spec(plugin: RuntimePlugin, kind: PluginKind) -> PluginSpec {
+    PluginSpec {
+        name: plugin.name,
+        invocation: InvocationSpec {
+            implementation: PluginExtractor::infer_invocation_type(&plugin.entry),
+            method: plugin
+                .method
+                .or_else(|| PluginExtractor::default_method_for_kind(&kind)),
+            constructor: Vec::new(),
+            call: Vec::new(),
+            timeout_secs: None,
+            retry_count: None,
+            cacheable: plugin.cacheable,
+        },
+        io: PluginExtractor::default_io_contract(&kind),
+        kind,
+        entry: plugin.entry,
+        resources: None,
+        upgrade: None,
+        description: plugin.description.filter(|text| !text.is_empty()),
+        tags: plugin.tags,
+        r2x_core_requirement: None,
+        confidence: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use r2x_manifest::ImplementationType;
+
+    #[test]
+    fn test_plugins_from_package_json() {
+        let json = r#"{
+            "name": "r2x-reeds",
+            "plugins": [
+                {"name": "reeds-parser", "kind": "parser",
+                 "entry": "r2x_reeds.parser.ReEDSParser", "description": "ReEDS parser"},
+                {"name": "add-pcm-defaults", "kind": "MODIFIER",
+                 "entry": "r2x_reeds.sysmod.add_pcm_defaults", "tags": ["pcm"]},
+                {"name": "mystery", "kind": "importer", "entry": "r2x_reeds.Importer"}
+            ]
+        }"#;
+        let plugins = plugins_from_package_json(json).unwrap();
+        assert_eq!(plugins.len(), 2);
+
+        assert_eq!(plugins[0].kind, PluginKind::Parser);
+        assert_eq!(plugins[0].entry, "r2x_reeds.parser.ReEDSParser");
+        assert_eq!(
+            plugins[0].invocation.method.as_deref(),
+            Some("build_system")
+        );
+        assert_eq!(plugins[0].description.as_deref(), Some("ReEDS parser"));
+
+        assert_eq!(plugins[1].kind, PluginKind::Modifier);
+        assert_eq!(
+            plugins[1].invocation.implementation,
+            ImplementationType::Function
+        );
+        assert_eq!(plugins[1].tags, vec!["pcm".to_string()]);
+
+        assert!(plugins_from_package_json("{\"name\": \"x\"}").is_err());
+        assert!(plugins_from_package_json(
+            r#"{"plugins": [{"name": "broken", "kind": "parser"}]}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! Inspection and selective cleanup of the r2x cache directory
+//!
+//! Backs `r2x cache inspect` and `r2x cache clear`.
+
+use crate::config_manager::Config;
+use crate::logger;
+use crate::plugins::ast_cache::PluginAstCache;
+use crate::plugins::result_cache::PluginResultCache;
+use crate::r2x_manifest::Manifest;
+use crate::GlobalOpts;
+use clap::{Subcommand, ValueEnum};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use walkdir::WalkDir;
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheAction {
+    /// Show the manifest, AST results and plugin results in the cache, with sizes
+    Inspect,
+    /// Remove one kind of cached data, keeping the rest of the cache folder
+    Clear {
+        #[arg(value_enum, default_value = "all")]
+        target: CacheTarget,
+        /// Only remove data older than this many days
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u64>,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum CacheTarget {
+    /// Stored plugin results reused by `r2x run`
+    Results,
+    /// AST discovery results reused by `r2x sync`
+    Ast,
+    /// Both plugin and AST discovery results
+    All,
+}
+
+pub fn handle_cache(action: CacheAction, _opts: GlobalOpts) -> Result<(), String> {
+    match action {
+        CacheAction::Inspect => inspect_cache(),
+        CacheAction::Clear { target, older_than } => clear_cache(target, older_than),
+    }
+}
+
+/// Print what the cache directory holds, with sizes and ages
+pub fn inspect_cache() -> Result<(), String> {
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    let cache_dir = config.get_cache_path();
+    println!("{} {}", "Cache:".bold(), cache_dir);
+
+    let manifest_path = Manifest::path();
+    match fs::metadata(&manifest_path) {
+        Ok(meta) => println!(
+            "  {:<10} {}  {}  {}",
+            "manifest".cyan(),
+            manifest_path.display(),
+            format_size(meta.len()),
+            age_label(meta.modified().ok()).dimmed()
+        ),
+        Err(_) => println!("  {:<10} {}", "manifest".cyan(), "not found".dimmed()),
+    }
+
+    let ast_path = PluginAstCache::default_path(&config);
+    match fs::metadata(&ast_path) {
+        Ok(meta) => {
+            let packages = PluginAstCache::load(ast_path.clone()).packages();
+            println!(
+                "  {:<10} {}  {}  {}  ({} packages)",
+                "ast".cyan(),
+                ast_path.display(),
+                format_size(meta.len()),
+                age_label(meta.modified().ok()).dimmed(),
+                packages.len()
+            );
+            for package in packages {
+                println!("      {}", package);
+            }
+        }
+        Err(_) => println!("  {:<10} {}", "ast".cyan(), "empty".dimmed()),
+    }
+
+    let mut results = PluginResultCache::new(PluginResultCache::default_dir(&config))
+        .entries()
+        .map_err(|e| format!("Failed to list plugin results: {}", e))?;
+    results.sort_by_key(|entry| std::cmp::Reverse(entry.modified));
+    let results_size: u64 = results.iter().map(|entry| entry.size).sum();
+    println!(
+        "  {:<10} {} entries, {}",
+        "results".cyan(),
+        results.len(),
+        format_size(results_size)
+    );
+    for entry in &results {
+        println!(
+            "      {:<32} {:>10}  {}",
+            entry.plugin.as_deref().unwrap_or("(unknown plugin)"),
+            format_size(entry.size),
+            age_label(entry.modified).dimmed()
+        );
+    }
+
+    println!(
+        "  {:<10} {}",
+        "total".cyan().bold(),
+        format_size(directory_size(Path::new(&cache_dir)))
+    );
+    Ok(())
+}
+
+/// Remove cached data of `target`, only entries older than `older_than_days` when set
+pub fn clear_cache(target: CacheTarget, older_than_days: Option<u64>) -> Result<(), String> {
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    let cutoff = match older_than_days {
+        None => None,
+        Some(days) => match cutoff_before(SystemTime::now(), days) {
+            Some(cutoff) => Some(cutoff),
+            None => {
+                logger::info(&format!("Nothing in the cache is {} days old", days));
+                return Ok(());
+            }
+        },
+    };
+
+    if matches!(target, CacheTarget::Results | CacheTarget::All) {
+        let cache = PluginResultCache::new(PluginResultCache::default_dir(&config));
+        let removed = cache
+            .clear_older_than(cutoff)
+            .map_err(|e| format!("Failed to clear plugin results: {}", e))?;
+        logger::success(&format!("Removed {} cached plugin result(s)", removed));
+    }
+
+    if matches!(target, CacheTarget::Ast | CacheTarget::All) {
+        let path = PluginAstCache::default_path(&config);
+        let modified = fs::metadata(&path).and_then(|meta| meta.modified());
+        match modified {
+            Err(_) => logger::debug("AST cache already clear"),
+            Ok(modified) if cutoff.is_some_and(|cutoff| modified >= cutoff) => {
+                logger::info("AST cache was updated recently; kept")
+            }
+            Ok(_) => {
+                fs::remove_file(&path).map_err(|e| format!("Failed to clear AST cache: {}", e))?;
+                logger::success("AST cache cleared");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The time `days` days before `now`; `None` when that predates what the clock can
+/// represent, so nothing can be older
+fn cutoff_before(now: SystemTime, days: u64) -> Option<SystemTime> {
+    let age = Duration::from_secs(days.checked_mul(SECS_PER_DAY)?);
+    now.checked_sub(age)
+}
+
+/// Total size of the files under `dir`
+fn directory_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+/// Size in binary units, e.g. `512 B`, `1.5 KB`, `12.0 MB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next;
+    }
+    format!("{:.1} {}", size, unit)
+}
+
+fn age_label(modified: Option<SystemTime>) -> String {
+    modified
+        .and_then(|time| SystemTime::now().duration_since(time).ok())
+        .map(format_age)
+        .unwrap_or_else(|| "age unknown".to_string())
+}
+
+/// Coarse age such as `3d ago`, `5h ago` or `just now`
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        s if s >= SECS_PER_DAY => format!("{}d ago", s / SECS_PER_DAY),
+        s if s >= 3600 => format!("{}h ago", s / 3600),
+        s if s >= 60 => format!("{}m ago", s / 60),
+        _ => "just now".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KB");
+        assert_eq!(format_size(12 * 1024 * 1024), "12.0 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn test_cutoff_before() {
+        let now = SystemTime::now();
+        assert_eq!(
+            cutoff_before(now, 2),
+            Some(now - Duration::from_secs(2 * SECS_PER_DAY))
+        );
+        assert_eq!(cutoff_before(now, u64::MAX), None);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::from_secs(5)), "just now");
+        assert_eq!(format_age(Duration::from_secs(120)), "2m ago");
+        assert_eq!(format_age(Duration::from_secs(7200)), "2h ago");
+        assert_eq!(format_age(Duration::from_secs(3 * SECS_PER_DAY)), "3d ago");
+    }
+}
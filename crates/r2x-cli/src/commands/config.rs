@@ -1,6 +1,7 @@
-use crate::config_manager::Config;
+use crate::config_manager::{self, redact_credentials, Config, ConfigDiff, ConfigKey};
 use crate::logger;
 use crate::plugins::get_package_info;
+use crate::plugins::install::Installer;
 use crate::python_bridge::configure_python_venv;
 use crate::GlobalOpts;
 use clap::Subcommand;
@@ -16,6 +17,15 @@ pub enum ConfigAction {
     Show,
     /// Update a configuration key (e.g. `r2x config set default-python-version 3.13`).
     Set { key: String, value: String },
+    /// Show how the running configuration differs from the saved config file.
+    Diff,
+    /// Save a timestamped copy of the config file next to it.
+    Backup,
+    /// Restore the config file from a backup (the latest one by default).
+    Restore {
+        /// Backup file to restore
+        path: Option<PathBuf>,
+    },
     /// Show the config path or set it when `new_path` is provided.
     Path {
         /// Optional new config path to set
@@ -149,13 +159,19 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
                 if let Some(ref core_ver) = config.r2x_core_version {
                     println!("  {}: {}", "r2x-core-version".cyan(), core_ver);
                 }
+                if !config.registries.is_empty() {
+                    println!("  {}:", "registries".cyan());
+                    for (name, url) in &config.registries {
+                        println!("    {} = {}", name, redact_credentials(url));
+                    }
+                }
 
                 // Show installed r2x-core version
                 let python_path = config.get_venv_python_path();
-                if PathBuf::from(&python_path).exists() {
-                    // Try to get uv_path from config, or use "uv" from PATH
-                    let uv_path = config.uv_path.as_deref().unwrap_or("uv");
-                    match get_package_info(uv_path, &python_path, "r2x-core") {
+                let installer = Installer::find(&config);
+                if let Some(installer) = installer.filter(|_| PathBuf::from(&python_path).exists())
+                {
+                    match get_package_info(&installer, &python_path, "r2x-core") {
                         Ok((Some(version), _)) => {
                             println!("  {}: {}", "r2x-core-version".cyan(), version);
                         }
@@ -167,7 +183,22 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
                         }
                     }
                 } else {
-                    logger::debug("Venv does not exist, skipping r2x-core version check");
+                    logger::debug("No venv or installer, skipping r2x-core version check");
+                }
+
+                if opts.verbosity_level() > 0 {
+                    println!("\n{}", "Effective values:".bold().green());
+                    config.show_effective();
+                }
+
+                // Invalid values are reported but not fatal, so `r2x config set` can fix them
+                match config.validate() {
+                    Ok(warnings) => {
+                        for warning in warnings {
+                            logger::debug(&format!("Config: {}", warning));
+                        }
+                    }
+                    Err(e) => logger::warn(&e.to_string()),
                 }
             }
             Err(e) => {
@@ -176,17 +207,11 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
         },
         ConfigAction::Set { key, value } => match Config::load() {
             Ok(mut config) => {
-                if config.get(&key).is_some()
-                    || matches!(
-                        key.as_str(),
-                        "cache-path"
-                            | "verbosity"
-                            | "python-version"
-                            | "venv-path"
-                            | "r2x-core-version"
-                    )
-                {
-                    config.set(&key, value.clone());
+                if ConfigKey::NAMES.contains(&key.as_str()) {
+                    if let Err(e) = config.set_str(&key, value.clone()) {
+                        logger::error(&e);
+                        return;
+                    }
                     match config.save() {
                         Ok(_) => {
                             logger::success(&format!("Set {} = {}", key, value));
@@ -201,8 +226,9 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
                     );
                 } else {
                     logger::error(&format!(
-                        "Unknown config key: {}. Currently supported keys: cache-path, verbosity, python-version, venv-path, r2x-core-version",
-                        key
+                        "Unknown config key: {}. Currently supported keys: {}",
+                        key,
+                        ConfigKey::NAMES.join(", ")
                     ));
                 }
             }
@@ -210,6 +236,29 @@ pub fn handle_config(action: Option<ConfigAction>, opts: GlobalOpts) {
                 logger::error(&format!("Failed to load config: {}", e));
             }
         },
+        ConfigAction::Diff => match (Config::load(), Config::load_file()) {
+            (Ok(running), Ok(saved)) => print_config_diff(&config_manager::diff(&running, &saved)),
+            (Err(e), _) | (_, Err(e)) => {
+                logger::error(&format!("Failed to load config: {}", e));
+            }
+        },
+        ConfigAction::Backup => match Config::backup() {
+            Ok(backup) => logger::success(&format!("Config backed up to {}", backup.display())),
+            Err(e) => logger::error(&format!("Failed to back up config: {}", e)),
+        },
+        ConfigAction::Restore { path } => {
+            let Some(backup) = path.or_else(Config::latest_backup) else {
+                logger::error(&format!(
+                    "No config backups found next to {}",
+                    Config::path().display()
+                ));
+                return;
+            };
+            match Config::restore(&backup) {
+                Ok(()) => logger::success(&format!("Config restored from {}", backup.display())),
+                Err(e) => logger::error(&format!("Failed to restore config: {}", e)),
+            }
+        }
         ConfigAction::Path { new_path } => {
             // Show or set the configuration file path.
             // When `new_path` is provided, write it to a pointer file next to the default config dir.
@@ -551,6 +600,26 @@ fn handle_venv_path(new_path: Option<String>, _opts: GlobalOpts) {
     }
 }
 
+/// Print config differences as `-` (saved file) and `+` (running config) lines
+fn print_config_diff(diffs: &[ConfigDiff]) {
+    let path = Config::path();
+    if diffs.is_empty() {
+        println!("Running config matches {}", path.display());
+        return;
+    }
+
+    println!("{}", format!("--- {} (saved)", path.display()).bold());
+    println!("{}", "+++ running config".bold());
+    for diff in diffs {
+        if let Some(ref old) = diff.old {
+            println!("{}", format!("- {} = {}", diff.key, old).red());
+        }
+        if let Some(ref new) = diff.new {
+            println!("{}", format!("+ {} = {}", diff.key, new).green());
+        }
+    }
+}
+
 // Simple check to avoid people setting virtual environments to not executable folders.
 fn is_valid_venv(path: &Path) -> bool {
     logger::debug(&format!("Validating venv at: {}", path.display()));
@@ -638,6 +707,13 @@ fn handle_python_show(_opts: GlobalOpts) {
             println!("  version: {}", version);
             println!("  venv path: {}", venv_path);
             println!("  venv exists: {}", if venv_exists { "yes" } else { "no" });
+            if venv_exists {
+                let functional = config.is_venv_functional();
+                println!(
+                    "  venv functional: {}",
+                    if functional { "yes" } else { "no" }
+                );
+            }
             if !actual_version_str.is_empty() {
                 println!("  Actual venv version: {}", actual_version_str.trim());
             }
@@ -713,6 +789,9 @@ mod tests {
             verbose: 0,
             log_python: false,
             no_stdout: false,
+            ignore_python_version: false,
+            fallback_to_runtime: false,
+            log_format: None,
         }
     }
 
@@ -722,6 +801,9 @@ mod tests {
             verbose: 1,
             log_python: false,
             no_stdout: false,
+            ignore_python_version: false,
+            fallback_to_runtime: false,
+            log_format: None,
         }
     }
 
@@ -731,6 +813,9 @@ mod tests {
             verbose: 0,
             log_python: false,
             no_stdout: false,
+            ignore_python_version: false,
+            fallback_to_runtime: false,
+            log_format: None,
         }
     }
 
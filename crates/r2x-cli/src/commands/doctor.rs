@@ -0,0 +1,324 @@
+//! `r2x doctor`: checks of the environment plugins run in
+//!
+//! Each check reports a [`CheckResult`]; the exit code is 0 when every check passes,
+//! 1 when any warns and 2 when any fails.
+
+use crate::config_manager::{uv_version_matches, Config, UV_MINIMUM_VERSION};
+use crate::python_bridge::resolve_site_package_path;
+use crate::r2x_manifest::Manifest;
+use crate::GlobalOpts;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Free space in the cache directory below which the check warns
+const DISK_SPACE_WARN_BYTES: u64 = 1024 * 1024 * 1024;
+/// Free space in the cache directory below which the check fails
+const DISK_SPACE_FAIL_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Outcome of a single check, ordered by severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Ok, detail)
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Warn, detail)
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>) -> Self {
+        Self::new(name, CheckStatus::Fail, detail)
+    }
+
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Run every check, print the checklist and return the exit code
+pub fn handle_doctor(_opts: GlobalOpts) -> i32 {
+    let results = run_checks();
+    for result in &results {
+        let mark = match result.status {
+            CheckStatus::Ok => "✔".green().bold(),
+            CheckStatus::Warn => "!".yellow().bold(),
+            CheckStatus::Fail => "✗".red().bold(),
+        };
+        println!("{} {:<16} {}", mark, result.name, result.detail);
+    }
+    exit_code(&results)
+}
+
+fn exit_code(results: &[CheckResult]) -> i32 {
+    match results.iter().map(|result| result.status).max() {
+        Some(CheckStatus::Fail) => 2,
+        Some(CheckStatus::Warn) => 1,
+        _ => 0,
+    }
+}
+
+fn run_checks() -> Vec<CheckResult> {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            return vec![CheckResult::fail(
+                "config",
+                format!("Failed to load config: {}", e),
+            )]
+        }
+    };
+
+    let venv = check_venv(&config);
+    let python = (venv.status == CheckStatus::Ok).then(|| config.get_venv_python_path());
+    let (manifest_check, manifest) = check_manifest();
+
+    vec![
+        check_config(&config),
+        check_uv(&config),
+        venv,
+        check_site_packages(&config),
+        check_r2x_core(python.as_deref()),
+        manifest_check,
+        check_plugin_modules(manifest.as_ref(), python.as_deref()),
+        check_disk_space(Path::new(&config.get_cache_path())),
+    ]
+}
+
+fn check_config(config: &Config) -> CheckResult {
+    match config.validate() {
+        Ok(_) => CheckResult::ok("config", Config::path().display().to_string()),
+        Err(e) => CheckResult::fail("config", e.to_string()),
+    }
+}
+
+fn check_uv(config: &Config) -> CheckResult {
+    let uv_path = config.uv_path.clone().or_else(|| {
+        which::which("uv")
+            .ok()
+            .map(|path| path.to_string_lossy().into_owned())
+    });
+    let Some(uv_path) = uv_path else {
+        return CheckResult::fail(
+            "uv",
+            "Not found. Install it from https://docs.astral.sh/uv/ \
+             or run `r2x config set uv-path <path>`",
+        );
+    };
+    let output = match Command::new(&uv_path).arg("--version").output() {
+        Ok(output) if output.status.success() => output,
+        _ => {
+            return CheckResult::fail(
+                "uv",
+                format!(
+                "{} does not run. Point r2x at a working uv with `r2x config set uv-path <path>`",
+                uv_path
+            ),
+            )
+        }
+    };
+    let reported = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if uv_version_matches(&reported) {
+        CheckResult::ok("uv", reported)
+    } else {
+        CheckResult::warn(
+            "uv",
+            format!(
+                "{} is older than {}. Run `uv self update`",
+                reported, UV_MINIMUM_VERSION
+            ),
+        )
+    }
+}
+
+fn check_venv(config: &Config) -> CheckResult {
+    match config.verify_venv_python() {
+        Ok(path) if config.is_venv_functional() => CheckResult::ok("venv", path),
+        Ok(path) => CheckResult::fail(
+            "venv",
+            format!(
+                "Python at {} does not start. Run `r2x config venv create --yes`",
+                path
+            ),
+        ),
+        Err(e) => CheckResult::fail("venv", e),
+    }
+}
+
+fn check_site_packages(config: &Config) -> CheckResult {
+    match resolve_site_package_path(&PathBuf::from(config.get_venv_path())) {
+        Ok(path) => CheckResult::ok("site-packages", path.display().to_string()),
+        Err(e) => CheckResult::fail("site-packages", e.to_string()),
+    }
+}
+
+fn check_r2x_core(python: Option<&str>) -> CheckResult {
+    let Some(python) = python else {
+        return CheckResult::warn("r2x-core", "Not checked: the venv Python does not work");
+    };
+    let output = Command::new(python)
+        .args([
+            "-c",
+            "import r2x_core, importlib.metadata as m; print(m.version('r2x-core'))",
+        ])
+        .stdin(Stdio::null())
+        .output();
+    match output {
+        Ok(output) if output.status.success() => CheckResult::ok(
+            "r2x-core",
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        _ => CheckResult::fail(
+            "r2x-core",
+            "Cannot be imported from the venv. Run `r2x install <package>` for a plugin \
+             package, which installs r2x-core with it",
+        ),
+    }
+}
+
+fn check_manifest() -> (CheckResult, Option<Manifest>) {
+    match Manifest::load() {
+        Ok(manifest) => {
+            let detail = format!(
+                "{} ({} plugins)",
+                Manifest::path().display(),
+                manifest.total_plugin_count()
+            );
+            (CheckResult::ok("manifest", detail), Some(manifest))
+        }
+        Err(e) => (
+            CheckResult::fail("manifest", format!("{}. Run `r2x sync` to rebuild it", e)),
+            None,
+        ),
+    }
+}
+
+fn check_plugin_modules(manifest: Option<&Manifest>, python: Option<&str>) -> CheckResult {
+    let (Some(manifest), Some(python)) = (manifest, python) else {
+        return CheckResult::warn(
+            "plugin modules",
+            "Not checked: needs a readable manifest and a working venv",
+        );
+    };
+    match manifest.unresolved_plugins(python) {
+        Ok(missing) if missing.is_empty() => CheckResult::ok(
+            "plugin modules",
+            format!("{} plugins resolve", manifest.total_plugin_count()),
+        ),
+        Ok(missing) => CheckResult::fail(
+            "plugin modules",
+            format!(
+                "Not found: {}. Run `r2x manifest repair` or reinstall the packages",
+                missing.join(", ")
+            ),
+        ),
+        Err(e) => CheckResult::fail("plugin modules", format!("Lookup failed: {}", e)),
+    }
+}
+
+fn check_disk_space(cache_dir: &Path) -> CheckResult {
+    if cfg!(not(unix)) {
+        return CheckResult::ok("disk space", "Not checked on this platform");
+    }
+    // The cache directory is created on first use, so measure where it will live
+    let existing = cache_dir
+        .ancestors()
+        .find(|dir| dir.exists())
+        .unwrap_or(cache_dir);
+    let Some(available) = available_bytes(existing) else {
+        return CheckResult::warn(
+            "disk space",
+            format!("Could not determine free space at {}", cache_dir.display()),
+        );
+    };
+    let detail = format!(
+        "{} MiB free at {}",
+        available / (1024 * 1024),
+        cache_dir.display()
+    );
+    if available < DISK_SPACE_FAIL_BYTES {
+        CheckResult::fail("disk space", detail)
+    } else if available < DISK_SPACE_WARN_BYTES {
+        CheckResult::warn("disk space", detail)
+    } else {
+        CheckResult::ok("disk space", detail)
+    }
+}
+
+/// Free bytes on the filesystem holding `path`, read from `df`
+#[cfg(unix)]
+fn available_bytes(path: &Path) -> Option<u64> {
+    let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_df_available(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(unix))]
+fn available_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// Available space from POSIX `df -Pk` output, in bytes
+#[cfg_attr(not(unix), allow(dead_code))]
+fn parse_df_available(output: &str) -> Option<u64> {
+    let kib: u64 = output
+        .lines()
+        .nth(1)?
+        .split_whitespace()
+        .nth(3)?
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        let ok = CheckResult::ok("uv", "uv 0.9.24");
+        let warn = CheckResult::warn("disk space", "512 MiB free");
+        let fail = CheckResult::fail("venv", "Python not found");
+
+        assert_eq!(exit_code(&[]), 0);
+        assert_eq!(exit_code(std::slice::from_ref(&ok)), 0);
+        assert_eq!(exit_code(&[ok.clone(), warn.clone()]), 1);
+        assert_eq!(exit_code(&[fail, ok, warn]), 2);
+    }
+
+    #[test]
+    fn test_parse_df_available() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n\
+                      /dev/nvme0n1p2   490617784 301234560 164383224      65% /\n";
+        assert_eq!(parse_df_available(output), Some(164_383_224 * 1024));
+        assert_eq!(parse_df_available("Filesystem\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_disk_space_of_missing_cache_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = check_disk_space(&dir.path().join("not-created-yet/cache"));
+        assert!(result.detail.contains("MiB free"), "{}", result.detail);
+    }
+}
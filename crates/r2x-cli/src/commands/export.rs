@@ -0,0 +1,81 @@
+use crate::logger;
+use crate::plugins::utils::load_manifest;
+use crate::r2x_manifest::ShellKind;
+use crate::GlobalOpts;
+use clap::ValueEnum;
+use std::path::Path;
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Bash script of `r2x install` commands
+    BashScript,
+    /// PowerShell script of `r2x install` commands
+    PowershellScript,
+    /// Fish script of `r2x install` commands
+    FishScript,
+    /// One package spec per line, for `r2x install --requirements`
+    Requirements,
+}
+
+impl ExportFormat {
+    /// Format matching an output file extension, e.g. `.txt` for requirements
+    fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "txt" => Some(ExportFormat::Requirements),
+            "ps1" => Some(ExportFormat::PowershellScript),
+            "fish" => Some(ExportFormat::FishScript),
+            "sh" | "bash" => Some(ExportFormat::BashScript),
+            _ => None,
+        }
+    }
+}
+
+pub fn handle_export(
+    output: Option<&Path>,
+    format: Option<ExportFormat>,
+    _opts: GlobalOpts,
+) -> Result<(), String> {
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+
+    if manifest.is_empty() {
+        logger::warn("No plugins installed. The exported script will be empty.");
+    }
+
+    let format = format
+        .or_else(|| output.and_then(ExportFormat::from_extension))
+        .unwrap_or(ExportFormat::BashScript);
+    let content = match format {
+        ExportFormat::BashScript => manifest.generate_install_script(ShellKind::Bash),
+        ExportFormat::PowershellScript => manifest.generate_install_script(ShellKind::Powershell),
+        ExportFormat::FishScript => manifest.generate_install_script(ShellKind::Fish),
+        ExportFormat::Requirements => manifest.generate_requirements(),
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            logger::success(&format!("Exported plugins to {}", path.display()));
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            ExportFormat::from_extension(Path::new("plugins.txt")),
+            Some(ExportFormat::Requirements)
+        );
+        assert_eq!(
+            ExportFormat::from_extension(Path::new("install.ps1")),
+            Some(ExportFormat::PowershellScript)
+        );
+        assert_eq!(ExportFormat::from_extension(Path::new("install")), None);
+    }
+}
@@ -0,0 +1,363 @@
+use super::plugins::lock_installation;
+use crate::config_manager::Config;
+use crate::logger;
+use crate::package_verification;
+use crate::plugins::utils::load_manifest;
+use crate::r2x_manifest::{integrity, read_from_path, Manifest, ManifestError, MergeStrategy};
+use crate::GlobalOpts;
+use clap::{Subcommand, ValueEnum};
+use std::path::{Path, PathBuf};
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ManifestAction {
+    /// Check the manifest file against its stored checksum without loading it
+    Verify,
+    /// Recompute the manifest checksum after a deliberate manual edit
+    Rehash,
+    /// Remove plugins whose Python module can no longer be imported
+    Repair,
+    /// Show plugins added, removed or changed between two manifest files
+    Diff {
+        /// Manifest snapshot to compare from
+        before: PathBuf,
+        /// Manifest snapshot to compare to
+        after: PathBuf,
+    },
+    /// Add the plugins of another manifest file to the installed manifest
+    ///
+    /// Packages that are not installed in the venv at the recorded version are skipped.
+    Merge {
+        /// Manifest to merge in, e.g. a team-wide list of approved plugins
+        other: PathBuf,
+        /// How to resolve plugins defined differently in both manifests
+        #[arg(long, value_enum, default_value = "error-on-conflict")]
+        strategy: MergeStrategyArg,
+    },
+    /// Write the installed manifest as JSON, YAML or TOML
+    Export {
+        /// Output format; inferred from the output extension, JSON otherwise
+        #[arg(long, value_enum)]
+        format: Option<ManifestFormat>,
+        /// File to write; prints to stdout when omitted
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Validate an exported manifest and merge it into the installed manifest
+    Import {
+        /// Manifest written by `r2x manifest export`
+        file: PathBuf,
+        /// Input format; inferred from the file extension, else JSON when the file
+        /// starts with `{` and TOML otherwise
+        #[arg(long, value_enum)]
+        format: Option<ManifestFormat>,
+        /// How to resolve plugins defined differently in both manifests
+        #[arg(long, value_enum, default_value = "error-on-conflict")]
+        strategy: MergeStrategyArg,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ManifestFormat {
+    /// Format matching a file extension, e.g. `.yml` for YAML
+    fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(ManifestFormat::Json),
+            "yaml" | "yml" => Some(ManifestFormat::Yaml),
+            "toml" => Some(ManifestFormat::Toml),
+            _ => None,
+        }
+    }
+
+    /// Format of manifest text without a known extension: JSON when it starts with
+    /// `{` (as `export` writes by default), TOML otherwise
+    fn detect(content: &str) -> Self {
+        if content.trim_start().starts_with('{') {
+            ManifestFormat::Json
+        } else {
+            ManifestFormat::Toml
+        }
+    }
+
+    fn serialize(self, manifest: &Manifest) -> Result<String, String> {
+        match self {
+            ManifestFormat::Json => serde_json::to_string_pretty(manifest)
+                .map(|json| json + "\n")
+                .map_err(|e| e.to_string()),
+            ManifestFormat::Yaml => serde_yaml::to_string(manifest).map_err(|e| e.to_string()),
+            ManifestFormat::Toml => toml::to_string_pretty(manifest).map_err(|e| e.to_string()),
+        }
+    }
+
+    fn parse(self, content: &str) -> Result<Manifest, String> {
+        match self {
+            ManifestFormat::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            ManifestFormat::Yaml => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+            ManifestFormat::Toml => toml::from_str(content).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum MergeStrategyArg {
+    /// Replace conflicting plugins with those from the other manifest
+    OtherWins,
+    /// Keep the installed plugins
+    SelfWins,
+    /// Abort the merge when any plugin conflicts
+    ErrorOnConflict,
+}
+
+pub fn handle_manifest(action: ManifestAction, _opts: GlobalOpts) -> Result<(), String> {
+    match action {
+        ManifestAction::Verify => verify_manifest(),
+        ManifestAction::Rehash => rehash_manifest(),
+        ManifestAction::Repair => {
+            let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+            repair_manifest(&config)
+        }
+        ManifestAction::Merge { other, strategy } => {
+            let other = read_from_path(&other)
+                .map_err(|e| format!("Failed to read {}: {}", other.display(), e))?;
+            merge_manifest(other, strategy)
+        }
+        ManifestAction::Export { format, output } => export_manifest(format, output.as_deref()),
+        ManifestAction::Import {
+            file,
+            format,
+            strategy,
+        } => import_manifest(&file, format, strategy),
+        ManifestAction::Diff { before, after } => diff_manifests(&before, &after),
+    }
+}
+
+fn diff_manifests(before: &Path, after: &Path) -> Result<(), String> {
+    let read = |path: &Path| {
+        read_from_path(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    };
+    let diff = Manifest::diff(&read(before)?, &read(after)?);
+    print!("{}", diff);
+    Ok(())
+}
+
+fn verify_manifest() -> Result<(), String> {
+    let path = Manifest::path();
+    logger::debug(&format!("Verifying manifest at: {}", path.display()));
+
+    if !path.exists() {
+        logger::info("No manifest found, nothing to verify");
+        return Ok(());
+    }
+
+    match Manifest::verify_integrity() {
+        Ok(true) => {
+            logger::success(&format!("Manifest {} is intact", path.display()));
+            Ok(())
+        }
+        Ok(false) => {
+            logger::warn(&format!(
+                "No checksum found at {}. It will be created on the next manifest update.",
+                integrity::checksum_path(&path).display()
+            ));
+            Ok(())
+        }
+        Err(e) => Err(format!(
+            "{}. Run `r2x sync` to rebuild the manifest, or `r2x manifest rehash` if the edit was intended.",
+            e
+        )),
+    }
+}
+
+/// Drop manifest plugins whose entry module the configured venv cannot find
+pub(crate) fn repair_manifest(config: &Config) -> Result<(), String> {
+    let _lock = lock_installation()?;
+    let mut manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+
+    let report = manifest
+        .auto_repair(&config.get_venv_python_path())
+        .map_err(|e| format!("Failed to look up plugin modules: {}", e))?;
+    if report.removed.is_empty() {
+        logger::debug(&format!("All {} plugin modules resolve", report.kept));
+        return Ok(());
+    }
+
+    manifest
+        .save()
+        .map_err(|e| format!("Failed to save manifest: {}", e))?;
+    for plugin in &report.removed {
+        logger::warn(&format!("Removed {}: module not found", plugin));
+    }
+    for package in &report.removed_packages {
+        logger::warn(&format!("Removed package {}: no plugins left", package));
+    }
+    logger::success(&format!(
+        "Removed {} plugins, kept {}",
+        report.removed.len(),
+        report.kept
+    ));
+    Ok(())
+}
+
+fn export_manifest(format: Option<ManifestFormat>, output: Option<&Path>) -> Result<(), String> {
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let format = format
+        .or_else(|| output.and_then(ManifestFormat::from_extension))
+        .unwrap_or(ManifestFormat::Json);
+    let content = format
+        .serialize(&manifest)
+        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            logger::success(&format!("Exported manifest to {}", path.display()));
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+fn import_manifest(
+    file: &Path,
+    format: Option<ManifestFormat>,
+    strategy: MergeStrategyArg,
+) -> Result<(), String> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let format = format
+        .or_else(|| ManifestFormat::from_extension(file))
+        .unwrap_or_else(|| ManifestFormat::detect(&content));
+    let other = format
+        .parse(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", file.display(), e))?;
+    other
+        .validate()
+        .map_err(|e| format!("{} is not a valid manifest: {}", file.display(), e))?;
+    merge_manifest(other, strategy)
+}
+
+fn merge_manifest(mut other: Manifest, strategy: MergeStrategyArg) -> Result<(), String> {
+    let _lock = lock_installation()?;
+    let mut manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let before = manifest.total_plugin_count();
+
+    // The other manifest's install metadata only holds for packages this venv has
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    let venv_path = PathBuf::from(config.get_venv_path());
+    let stale = package_verification::stale_packages(&other, &venv_path)
+        .map_err(|e| format!("Failed to check installed packages: {}", e))?;
+    other.packages.retain(|pkg| !stale.contains(&pkg.name));
+    for package in &stale {
+        logger::warn(&format!(
+            "Skipped {}: not installed in the venv at the version in the other manifest. Install it with `r2x install {}`",
+            package, package
+        ));
+    }
+
+    let strategy = match strategy {
+        MergeStrategyArg::OtherWins => MergeStrategy::OtherWins,
+        MergeStrategyArg::SelfWins => MergeStrategy::SelfWins,
+        MergeStrategyArg::ErrorOnConflict => MergeStrategy::ErrorOnConflict,
+    };
+    let conflicts = match manifest.merge(&other, strategy) {
+        Ok(conflicts) => conflicts,
+        Err(ManifestError::MergeConflicts(conflicts)) => {
+            for conflict in &conflicts {
+                logger::error(&format!(
+                    "{}: {} ({}) here, {} ({}) in the other manifest",
+                    conflict.plugin,
+                    conflict.ours.entry,
+                    conflict.ours.package,
+                    conflict.theirs.entry,
+                    conflict.theirs.package
+                ));
+            }
+            return Err(format!(
+                "{} conflicting plugins; rerun with --strategy other-wins or self-wins",
+                conflicts.len()
+            ));
+        }
+        Err(e) => return Err(format!("Failed to merge manifests: {}", e)),
+    };
+
+    manifest
+        .save()
+        .map_err(|e| format!("Failed to save manifest: {}", e))?;
+    for conflict in &conflicts {
+        let used = match strategy {
+            MergeStrategy::OtherWins => &conflict.theirs,
+            _ => &conflict.ours,
+        };
+        logger::warn(&format!(
+            "Conflicting plugin {}: using {} ({})",
+            conflict.plugin, used.entry, used.package
+        ));
+    }
+    logger::success(&format!(
+        "Added {} plugins, resolved {} conflicts",
+        manifest.total_plugin_count() - before,
+        conflicts.len()
+    ));
+    Ok(())
+}
+
+fn rehash_manifest() -> Result<(), String> {
+    let _lock = lock_installation()?;
+    let path = Manifest::path();
+    match Manifest::rehash() {
+        Ok(true) => {
+            logger::success(&format!(
+                "Updated checksum at {}",
+                integrity::checksum_path(&path).display()
+            ));
+            Ok(())
+        }
+        Ok(false) => {
+            logger::info("No manifest found, nothing to rehash");
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to rehash {}: {}", path.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_format_round_trip() {
+        let mut manifest = Manifest::default();
+        let pkg = manifest.get_or_create_package("r2x-reeds");
+        pkg.version = Some("1.2.0".to_string());
+
+        for format in [
+            ManifestFormat::Json,
+            ManifestFormat::Yaml,
+            ManifestFormat::Toml,
+        ] {
+            let content = format.serialize(&manifest).unwrap();
+            let parsed = format.parse(&content).unwrap();
+            assert_eq!(parsed.packages.len(), 1);
+            assert_eq!(parsed.packages[0].version.as_deref(), Some("1.2.0"));
+        }
+
+        assert_eq!(
+            ManifestFormat::from_extension(Path::new("manifest.YML")),
+            Some(ManifestFormat::Yaml)
+        );
+        assert_eq!(ManifestFormat::from_extension(Path::new("manifest")), None);
+        let exported = ManifestFormat::Json.serialize(&manifest).unwrap();
+        assert_eq!(ManifestFormat::detect(&exported), ManifestFormat::Json);
+        assert_eq!(
+            ManifestFormat::detect("[[packages]]\nname = \"r2x-reeds\"\n"),
+            ManifestFormat::Toml
+        );
+        assert!(ManifestFormat::Json.parse("{\"packages\": 1}").is_err());
+    }
+}
@@ -1,5 +1,11 @@
+pub mod cache;
 pub mod config;
+pub mod doctor;
+pub mod export;
 pub mod init;
+pub mod manifest;
+pub mod new;
 pub mod plugins;
 pub mod read;
 pub mod run;
+pub mod status;
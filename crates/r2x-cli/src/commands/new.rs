@@ -0,0 +1,63 @@
+use crate::logger;
+use crate::plugins::scaffold::{module_name, scaffold_plugin};
+use crate::r2x_manifest::PluginKind;
+use crate::GlobalOpts;
+use clap::{Subcommand, ValueEnum};
+use colored::*;
+use std::path::PathBuf;
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum NewAction {
+    /// Create the skeleton of a new r2x plugin package
+    Plugin {
+        /// Package name, e.g. r2x-my-model
+        name: String,
+        /// Kind of the starter plugin
+        #[arg(long, value_enum, default_value = "parser")]
+        kind: ScaffoldKindArg,
+        /// Directory to create the package in (default: current directory)
+        #[arg(long)]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ScaffoldKindArg {
+    Parser,
+    Exporter,
+    Modifier,
+    Upgrader,
+}
+
+impl From<ScaffoldKindArg> for PluginKind {
+    fn from(kind: ScaffoldKindArg) -> Self {
+        match kind {
+            ScaffoldKindArg::Parser => PluginKind::Parser,
+            ScaffoldKindArg::Exporter => PluginKind::Exporter,
+            ScaffoldKindArg::Modifier => PluginKind::Modifier,
+            ScaffoldKindArg::Upgrader => PluginKind::Upgrader,
+        }
+    }
+}
+
+pub fn handle_new(action: NewAction, _opts: GlobalOpts) -> Result<(), String> {
+    match action {
+        NewAction::Plugin { name, kind, path } => {
+            let parent = path.unwrap_or_else(|| PathBuf::from("."));
+            let root = scaffold_plugin(&parent, &name, &kind.into())?;
+            logger::success(&format!("Created plugin package: {}", root.display()));
+
+            println!();
+            println!("Next steps:");
+            println!("  1. cd {}", root.display());
+            println!(
+                "  2. Implement your plugin in src/{}/plugins.py",
+                module_name(&name)
+            );
+            println!("  3. Install it: {}", "r2x install ./".bold());
+            println!("     (use {} while developing)", "r2x install -e ./".bold());
+            println!("  4. Check it was registered: r2x list {}", name);
+            Ok(())
+        }
+    }
+}
@@ -1,10 +1,12 @@
+use super::lock_installation;
 use crate::logger;
-use crate::r2x_manifest::Manifest;
+use crate::plugins::utils::load_manifest;
 use crate::GlobalOpts;
 use colored::Colorize;
 
 pub fn clean_manifest(yes: bool, _opts: &GlobalOpts) -> Result<(), String> {
-    let mut manifest = Manifest::load().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let _lock = lock_installation()?;
+    let mut manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
 
     if manifest.is_empty() {
         logger::warn("Manifest is empty.");
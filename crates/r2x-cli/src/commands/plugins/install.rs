@@ -1,11 +1,17 @@
-use super::setup_config;
+use super::{lock_installation, read_install_config, setup_install_config};
+use crate::config_manager::{redact_credentials, Config, IndexArgs};
 use crate::logger;
+use crate::plugins::utils::{load_manifest, looks_like_r2x_plugin};
 use crate::plugins::{
+    ast_cache,
     discovery::{discover_and_register_entry_points_with_deps, DiscoveryOptions},
-    install::get_package_info,
-    package_spec::{build_package_spec, extract_package_name},
+    find_package_path,
+    install::{get_package_info, Installer},
+    package_resolver::same_package_name,
+    package_spec::InstallSource,
+    AstDiscovery,
 };
-use crate::r2x_manifest::Manifest;
+use crate::r2x_ast::DiscoveryMode;
 use crate::GlobalOpts;
 use colored::Colorize;
 use std::fs;
@@ -13,6 +19,7 @@ use std::path::Path;
 use std::process::{Command, Stdio};
 
 /// Options for git-based package installation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct GitOptions {
     pub host: Option<String>,
     pub branch: Option<String>,
@@ -26,42 +33,62 @@ pub fn install_plugin(
     editable: bool,
     no_cache: bool,
     git_opts: GitOptions,
-    _opts: &GlobalOpts,
+    opts: &GlobalOpts,
+) -> Result<(), String> {
+    let _lock = lock_installation()?;
+    install_package(package, editable, no_cache, git_opts, opts.discovery_mode())
+}
+
+fn install_package(
+    package: &str,
+    editable: bool,
+    no_cache: bool,
+    git_opts: GitOptions,
+    mode: DiscoveryMode,
 ) -> Result<(), String> {
     logger::debug("Loading configuration for plugin installation");
 
-    let (uv_path, venv_path, python_path) = setup_config()?;
+    let (installer, venv_path, python_path) = setup_install_config()?;
     logger::debug(&format!("Using venv: {}", venv_path));
 
     let total_start = std::time::Instant::now();
-    let package_spec = build_package_spec(
+    let source = InstallSource::resolve(
         package,
         git_opts.host.clone(),
         git_opts.branch.clone(),
         git_opts.tag.clone(),
         git_opts.commit.clone(),
     )?;
+    let index_args = index_args_for(&source, &installer)?;
+    let package_spec = source.spec();
 
     // Check if this is a workspace installation
-    if is_workspace_package(&package_spec)? {
+    if is_workspace_package(&source)? {
         logger::info("Detected workspace repository, installing all members...");
         // Just install the workspace - uv will handle all members
-        run_pip_install(&uv_path, &python_path, &package_spec, editable, no_cache)?;
+        run_pip_install(
+            &installer,
+            &python_path,
+            &package_spec,
+            editable,
+            no_cache,
+            &index_args,
+        )?;
 
         // Now discover all packages with entry points (like sync command)
         logger::info("Discovering plugins from installed packages...");
-        return discover_all_installed_packages(&uv_path, &python_path, no_cache, total_start);
+        return discover_all_installed_packages(&installer, &python_path, no_cache, total_start);
     }
 
-    let package_name_for_query = extract_package_name(package)?;
+    let package_name_for_query = source.package_name()?;
 
     let check_start = std::time::Instant::now();
     let is_already_installed = if no_cache {
         None
     } else {
-        match get_package_info(&uv_path, &python_path, &package_name_for_query) {
+        match get_package_info(&installer, &python_path, &package_name_for_query) {
             Ok((version, _deps)) => {
-                let manifest = Manifest::load().unwrap_or_default();
+                let manifest = load_manifest().unwrap_or_default();
                 let has_plugins = manifest
                     .packages
                     .iter()
@@ -96,9 +123,17 @@ pub fn install_plugin(
     // Print status without spinner since we need interactive terminal for SSH prompts
     logger::info(&format!("Installing: {}", package));
     let start = std::time::Instant::now();
-    match run_pip_install(&uv_path, &python_path, &package_spec, editable, no_cache) {
+    match run_pip_install(
+        &installer,
+        &python_path,
+        &package_spec,
+        editable,
+        no_cache,
+        &index_args,
+    ) {
         Ok(_) => {
             logger::debug(&format!("pip install took: {:?}", start.elapsed()));
+            ast_cache::invalidate_package(&package_name_for_query);
         }
         Err(e) => {
             logger::error(&format!("Failed to install: {}", package));
@@ -107,44 +142,52 @@ pub fn install_plugin(
     }
 
     let start = std::time::Instant::now();
-    let (package_version, dependencies) =
-        match get_package_info(&uv_path, &python_path, &package_name_for_query) {
-            Ok((version, deps)) => (version, deps),
-            Err(e) => {
-                logger::debug(&format!("Failed to get package info: {}", e));
-                (None, Vec::new())
+    let mut discovery = match Config::load()
+        .map_err(|e| e.to_string())
+        .and_then(|config| {
+            DiscoveryOptions::from_installed_package(&package_name_for_query, &config)
+                .map_err(|e| e.to_string())
+        }) {
+        Ok(discovery) => discovery,
+        Err(e) => {
+            logger::debug(&format!("Failed to read installed package metadata: {}", e));
+            DiscoveryOptions {
+                package_name_full: package_name_for_query.to_string(),
+                ..Default::default()
             }
-        };
-    logger::debug(&format!("get_package_info took: {:?}", start.elapsed()));
-
-    // Resolve source path for editable installs
-    let source_path = if editable {
-        // If it's a local path, canonicalize it
-        if Path::new(package).exists() {
-            fs::canonicalize(package)
-                .ok()
-                .and_then(|p| p.to_str().map(|s| s.to_string()))
-        } else {
-            None
         }
-    } else {
-        None
+    };
+    logger::debug(&format!(
+        "Reading installed package metadata took: {:?}",
+        start.elapsed()
+    ));
+
+    // Record where non-index installs came from so exports can reproduce them
+    let install_source = match &source {
+        InstallSource::LocalPath(path) => fs::canonicalize(path)
+            .ok()
+            .and_then(|p| p.to_str().map(|s| s.to_string())),
+        InstallSource::Git(url) => Some(url.clone()),
+        _ => None,
     };
 
     let start = std::time::Instant::now();
-    let entry_count = discover_and_register_entry_points_with_deps(
-        &uv_path,
-        &python_path,
-        DiscoveryOptions {
-            package: package.to_string(),
-            package_name_full: package_name_for_query.to_string(),
-            dependencies,
-            package_version: package_version.clone(),
-            no_cache,
-            editable,
-            source_path,
-        },
-    )?;
+    let package_version = discovery.package_version.clone();
+    if let InstallSource::Registry { registry, .. } = &source {
+        discovery.registry = Some(registry.clone());
+    }
+    discovery.package = match source {
+        InstallSource::LocalPath(_) => package_name_for_query.clone(),
+        InstallSource::Registry { package, .. } => package,
+        _ => package.to_string(),
+    };
+    discovery.no_cache = no_cache;
+    discovery.editable = editable;
+    discovery.source_path = install_source.clone().filter(|_| editable);
+    discovery.install_source = install_source;
+    discovery.mode = mode;
+    let entry_count =
+        discover_and_register_entry_points_with_deps(&installer, &python_path, discovery)?;
     logger::debug(&format!(
         "discover_and_register_entry_points took: {:?}",
         start.elapsed()
@@ -160,6 +203,314 @@ pub fn install_plugin(
     Ok(())
 }
 
+/// A package the installer would add or upgrade
+#[derive(Debug, PartialEq, Eq)]
+struct PlannedInstall {
+    name: String,
+    version: String,
+    /// Installed version this replaces, when the installer reports it
+    replaces: Option<String>,
+}
+
+/// Show what installing a package would change, without touching the venv or manifest
+///
+/// Dependencies are resolved with the installer's `--dry-run`. Plugins are listed for
+/// planned r2x packages that are registered at the planned version or found on disk;
+/// upgrades and packages new to the venv are only discovered once they are actually
+/// installed, since the files on disk belong to the old version.
+pub fn dry_run_install(source: &str, opts: &GitOptions) -> Result<(), String> {
+    let (installer, venv_path, python_path) = read_install_config()?;
+    logger::debug(&format!("Using venv: {}", venv_path));
+
+    let install_source = InstallSource::resolve(
+        source,
+        opts.host.clone(),
+        opts.branch.clone(),
+        opts.tag.clone(),
+        opts.commit.clone(),
+    )?;
+    let index_args = index_args_for(&install_source, &installer)?;
+
+    let mut command = installer.install_command(&python_path);
+    command
+        .arg("--dry-run")
+        .args(&index_args.args)
+        .arg(install_source.spec());
+
+    logger::debug(&format!("Running: {}", command_line(&command)));
+    let output = command
+        .envs(index_args.env.iter().map(|(key, value)| (key, value)))
+        .output()
+        .map_err(|e| format!("Failed to run dry-run install: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(format!("Failed to resolve '{}': {}", source, stderr.trim()));
+    }
+
+    let planned = parse_dry_run_output(&format!("{}\n{}", stdout, stderr));
+    if planned.is_empty() {
+        println!("{}", "Nothing to install".bold().dimmed());
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        format!("Would install {} package(s)", planned.len())
+            .bold()
+            .dimmed()
+    );
+    let manifest = load_manifest().unwrap_or_default();
+    let venv = Config::load().ok().map(|config| config.get_venv_path());
+    for install in &planned {
+        match &install.replaces {
+            Some(old) => println!(
+                " {} {}=={} {}",
+                "+".bold().green(),
+                install.name.bold(),
+                install.version,
+                format!("(replaces {})", old).dimmed()
+            ),
+            None => println!(
+                " {} {}=={}",
+                "+".bold().green(),
+                install.name.bold(),
+                install.version
+            ),
+        }
+        if !looks_like_r2x_plugin(&install.name) {
+            continue;
+        }
+
+        let registered = manifest
+            .packages
+            .iter()
+            .find(|pkg| pkg.name == install.name && !pkg.plugins.is_empty());
+        let is_upgrade = install.replaces.is_some()
+            || registered.is_some_and(|pkg| pkg.version.as_deref() != Some(&install.version));
+        if is_upgrade {
+            println!("     {}", "plugins are discovered after install".dimmed());
+            continue;
+        }
+        let plugins = match registered {
+            Some(pkg) => pkg.plugins.clone(),
+            None => match find_package_path(&install.name) {
+                Ok(path) => {
+                    match AstDiscovery::discover_plugins(
+                        &path,
+                        &install.name,
+                        venv.as_deref(),
+                        Some(&install.version),
+                        DiscoveryMode::AstOnly,
+                    ) {
+                        Ok((plugins, _, _)) => plugins,
+                        Err(e) => {
+                            logger::debug(&format!(
+                                "Dry-run discovery failed for '{}': {}",
+                                install.name, e
+                            ));
+                            Vec::new()
+                        }
+                    }
+                }
+                Err(_) => {
+                    println!("     {}", "plugins are discovered after install".dimmed());
+                    continue;
+                }
+            },
+        };
+        for plugin in &plugins {
+            println!(
+                "     {} {}",
+                plugin.name,
+                format!("({})", plugin.kind.as_str()).dimmed()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse the packages an installer `--dry-run` would add
+///
+/// Understands uv (` + name==version`, with ` - name==version` for the versions it
+/// replaces) and pip (`Would install name-version ...`). uv's ` (from git+...)` source
+/// suffix is dropped from the version.
+fn parse_dry_run_output(output: &str) -> Vec<PlannedInstall> {
+    // `name==version (from ...)` -> (name, version)
+    fn split_uv_spec(spec: &str) -> Option<(String, String)> {
+        let (name, rest) = spec.trim().split_once("==")?;
+        let version = rest.split_whitespace().next()?;
+        Some((name.trim().to_string(), version.to_string()))
+    }
+
+    let mut planned = Vec::new();
+    let mut removed = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if let Some(spec) = line.strip_prefix("+ ") {
+            if let Some((name, version)) = split_uv_spec(spec) {
+                planned.push(PlannedInstall {
+                    name,
+                    version,
+                    replaces: None,
+                });
+            }
+        } else if let Some(spec) = line.strip_prefix("- ") {
+            removed.extend(split_uv_spec(spec));
+        } else if let Some(specs) = line.strip_prefix("Would install ") {
+            planned.extend(specs.split_whitespace().filter_map(|spec| {
+                let (name, version) = spec.rsplit_once('-')?;
+                Some(PlannedInstall {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    replaces: None,
+                })
+            }));
+        }
+    }
+    for install in &mut planned {
+        install.replaces = removed
+            .iter()
+            .find(|(name, _)| same_package_name(name, &install.name))
+            .map(|(_, version)| version.clone());
+    }
+    planned
+}
+
+/// One line of a requirements file: a package spec and its install flags
+#[derive(Debug, PartialEq, Eq)]
+struct Requirement {
+    spec: String,
+    editable: bool,
+    no_cache: bool,
+    git: GitOptions,
+}
+
+/// Strip a comment the way pip does: `#` at the start of a line or after whitespace
+fn strip_comment(line: &str) -> &str {
+    let mut after_whitespace = true;
+    for (idx, c) in line.char_indices() {
+        if c == '#' && after_whitespace {
+            return &line[..idx];
+        }
+        after_whitespace = c.is_whitespace();
+    }
+    line
+}
+
+/// Parse a requirements line such as `NREL/r2x-reeds --branch develop`
+///
+/// Accepts the same flags as `r2x install`; flags on the line override those in
+/// `defaults`, whose spec is ignored. Returns `Ok(None)` for blank and comment lines.
+fn parse_requirement(line: &str, defaults: &Requirement) -> Result<Option<Requirement>, String> {
+    let line = strip_comment(line).trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let mut requirement = Requirement {
+        spec: String::new(),
+        editable: defaults.editable,
+        no_cache: defaults.no_cache,
+        git: defaults.git.clone(),
+    };
+    let mut tokens = line.split_whitespace();
+    while let Some(token) = tokens.next() {
+        let mut value = |flag: &str| {
+            tokens
+                .next()
+                .map(str::to_string)
+                .ok_or_else(|| format!("{} needs a value", flag))
+        };
+        match token {
+            "-e" | "--editable" => requirement.editable = true,
+            "--no-cache" => requirement.no_cache = true,
+            "--host" => requirement.git.host = Some(value(token)?),
+            "--branch" => requirement.git.branch = Some(value(token)?),
+            "--tag" => requirement.git.tag = Some(value(token)?),
+            "--commit" => requirement.git.commit = Some(value(token)?),
+            flag if flag.starts_with('-') => return Err(format!("Unknown option '{}'", flag)),
+            spec if requirement.spec.is_empty() => requirement.spec = spec.to_string(),
+            extra => return Err(format!("Unexpected argument '{}'", extra)),
+        }
+    }
+
+    if requirement.spec.is_empty() {
+        return Err("Missing package spec".to_string());
+    }
+    Ok(Some(requirement))
+}
+
+/// Install every package listed in a requirements file
+///
+/// Each line is installed like [`install_plugin`], under a single install lock. Failures are collected and reported
+/// together once all lines have been tried. `editable`, `no_cache` and `git_opts` come from the command line
+/// and apply to every line.
+pub fn install_from_requirements(
+    path: &Path,
+    editable: bool,
+    no_cache: bool,
+    git_opts: GitOptions,
+    opts: &GlobalOpts,
+) -> Result<(), String> {
+    let _lock = lock_installation()?;
+    let mode = opts.discovery_mode();
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let defaults = Requirement {
+        spec: String::new(),
+        editable,
+        no_cache,
+        git: git_opts,
+    };
+    let mut attempted = 0;
+    let mut installed = 0;
+    let mut failures = Vec::new();
+    for (idx, line) in content.lines().enumerate() {
+        let requirement = match parse_requirement(line, &defaults) {
+            Ok(Some(requirement)) => requirement,
+            Ok(None) => continue,
+            Err(e) => {
+                failures.push(format!("line {}: {}", idx + 1, e));
+                continue;
+            }
+        };
+        attempted += 1;
+        match install_package(
+            &requirement.spec,
+            requirement.editable,
+            requirement.no_cache,
+            requirement.git,
+            mode,
+        ) {
+            Ok(()) => installed += 1,
+            Err(e) => failures.push(format!("line {} ({}): {}", idx + 1, requirement.spec, e)),
+        }
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Installed {} of {} package(s) from {}",
+            installed,
+            attempted,
+            path.display()
+        )
+        .bold()
+    );
+    if failures.is_empty() {
+        return Ok(());
+    }
+    for failure in &failures {
+        logger::error(failure);
+    }
+    Err(format!(
+        "{} requirement(s) failed to install",
+        failures.len()
+    ))
+}
+
 pub fn show_install_help() -> Result<(), String> {
     println!();
     println!("{}", "Install a plugin package".bold());
@@ -177,6 +528,7 @@ pub fn show_install_help() -> Result<(), String> {
     println!("  --branch <BRANCH>  Install from a git branch");
     println!("  --tag <TAG>        Install from a git tag");
     println!("  --commit <COMMIT>  Install from a git commit hash");
+    println!("  -r, --requirements <FILE>  Install every package listed in FILE");
     println!();
     println!("{}", "Examples:".bold());
     println!("  Install from PyPI:\n    r2x install r2x-reeds");
@@ -189,6 +541,7 @@ pub fn show_install_help() -> Result<(), String> {
     );
     println!("\n  Install workspace (all packages in monorepo):\n    r2x install https://github.com/NREL/R2X --branch v2.0.0");
     println!("\n  Install local workspace:\n    r2x install ./R2X");
+    println!("\n  Install from a requirements file:\n    r2x install --requirements plugins.txt");
     println!();
     println!("{}", "Workspace Installation:".bold());
     println!("  When installing from a repository with [tool.uv.workspace] in its");
@@ -198,50 +551,30 @@ pub fn show_install_help() -> Result<(), String> {
     Ok(())
 }
 
-fn run_pip_install(
-    uv_path: &str,
+pub(super) fn run_pip_install(
+    installer: &Installer,
     python_path: &str,
     package: &str,
     editable: bool,
     no_cache: bool,
+    index: &IndexArgs,
 ) -> Result<(), String> {
-    let mut install_args: Vec<String> = vec![
-        "pip".to_string(),
-        "install".to_string(),
-        "--python".to_string(),
-        python_path.to_string(),
-        "--prerelease=allow".to_string(),
-        "--no-progress".to_string(),
-    ];
-
+    let mut command = installer.install_command(python_path);
+    command.args(installer.no_progress_flags());
     if no_cache {
-        install_args.push("--no-cache".to_string());
+        command.arg(installer.no_cache_flag());
     }
-
+    command.args(&index.args);
     if editable {
-        install_args.push("-e".to_string());
+        command.arg("-e");
     }
+    command.arg(package);
 
-    install_args.push(package.to_string());
-
-    let debug_flags = if editable && no_cache {
-        "-e --no-cache"
-    } else if editable {
-        "-e"
-    } else if no_cache {
-        "--no-cache"
-    } else {
-        ""
-    };
-
-    logger::debug(&format!(
-        "Running: {} pip install {} --python {} {}",
-        uv_path, debug_flags, python_path, package
-    ));
+    logger::debug(&format!("Running: {}", command_line(&command)));
 
     // Use inherited stdio to allow interactive prompts (e.g., SSH key passphrases)
-    let status = Command::new(uv_path)
-        .args(&install_args)
+    let status = command
+        .envs(index.env.iter().map(|(key, value)| (key, value)))
         .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
@@ -253,6 +586,12 @@ fn run_pip_install(
 
     if !status.success() {
         logger::error(&format!("pip install failed for package '{}'", package));
+        if index.is_custom() {
+            logger::warn(
+                "A custom package index is configured. If it returned 401/403 or a TLS error, \
+                 check its credentials or run `r2x config set trusted-host <host>`",
+            );
+        }
         return Err(format!(
             "pip install failed for package '{}': exit code {}",
             package,
@@ -277,43 +616,69 @@ fn print_install_summary(pkg: &str, version: &str, count: usize, elapsed: std::t
     println!(" {} {}", "+".bold().green(), disp);
 }
 
-/// Check if a package is a workspace (by detecting [tool.uv.workspace] in pyproject.toml)
-fn is_workspace_package(package_spec: &str) -> Result<bool, String> {
-    // Only check for local paths or git URLs
-    let is_local_path = package_spec.starts_with("./")
-        || package_spec.starts_with("../")
-        || package_spec.starts_with('/');
-
-    let is_git_url = package_spec.starts_with("git+") || package_spec.starts_with("git@");
+/// Command line for logging, with registry credentials masked in each argument
+fn command_line(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| redact_credentials(&arg.to_string_lossy()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
 
-    if !is_local_path && !is_git_url {
-        return Ok(false);
+/// Index arguments for installing `source`: its registry for `registry:package`
+/// sources, the configured package index otherwise
+fn index_args_for(source: &InstallSource, installer: &Installer) -> Result<IndexArgs, String> {
+    match source {
+        InstallSource::Registry { registry, .. } => {
+            let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+            package_index_args(&config, Some(registry), installer)
+        }
+        _ => Ok(Config::load()
+            .map(|config| config.get_index_args())
+            .unwrap_or_default()
+            .into()),
     }
+}
 
-    // For local paths, check directly
-    if is_local_path {
-        let pyproject_path = Path::new(package_spec).join("pyproject.toml");
-        if !pyproject_path.exists() {
-            return Ok(false);
-        }
+/// Index arguments for a package installed from `registry`, or from the configured
+/// package index when it came from no registry
+///
+/// A registry package is only ever looked up in its registry, so a same-named package
+/// on the public index cannot replace it.
+pub(super) fn package_index_args(
+    config: &Config,
+    registry: Option<&str>,
+    installer: &Installer,
+) -> Result<IndexArgs, String> {
+    match registry {
+        Some(registry) => config.get_registry_index_args(registry, installer.is_pip()),
+        None => Ok(config.get_index_args().into()),
+    }
+}
 
-        let content = fs::read_to_string(&pyproject_path)
-            .map_err(|e| format!("Failed to read pyproject.toml: {}", e))?;
+/// Check if a package is a workspace (by detecting [tool.uv.workspace] in pyproject.toml)
+fn is_workspace_package(source: &InstallSource) -> Result<bool, String> {
+    match source {
+        InstallSource::LocalPath(path) => {
+            let pyproject_path = path.join("pyproject.toml");
+            if !pyproject_path.exists() {
+                return Ok(false);
+            }
 
-        return Ok(content.contains("[tool.uv.workspace]"));
-    }
+            let content = fs::read_to_string(&pyproject_path)
+                .map_err(|e| format!("Failed to read pyproject.toml: {}", e))?;
 
-    // For git URLs, use heuristic: if it's a git URL pointing to NREL/R2X, assume it's a workspace
-    if is_git_url && (package_spec.contains("NREL/R2X") || package_spec.contains("NREL/r2x")) {
-        return Ok(true);
+            Ok(content.contains("[tool.uv.workspace]"))
+        }
+        // For git URLs, use heuristic: if it's a git URL pointing to NREL/R2X, assume it's a workspace
+        InstallSource::Git(url) => Ok(url.contains("NREL/R2X") || url.contains("NREL/r2x")),
+        InstallSource::PyPi(_) | InstallSource::Registry { .. } => Ok(false),
     }
-
-    Ok(false)
 }
 
 /// Discover all installed packages with r2x_plugin entry points
 fn discover_all_installed_packages(
-    uv_path: &str,
+    installer: &Installer,
     python_path: &str,
     no_cache: bool,
     total_start: std::time::Instant,
@@ -380,14 +745,14 @@ except Exception as e:
 
         // Get package info
         let (package_version, dependencies) =
-            match get_package_info(uv_path, python_path, &package_name) {
+            match get_package_info(installer, python_path, &package_name) {
                 Ok((version, deps)) => (version, deps),
                 Err(_) => continue,
             };
 
         // Try to discover entry points
         match discover_and_register_entry_points_with_deps(
-            uv_path,
+            installer,
             python_path,
             DiscoveryOptions {
                 package: package_name.clone(),
@@ -397,6 +762,7 @@ except Exception as e:
                 no_cache,
                 editable: false,
                 source_path: None,
+                ..Default::default()
             },
         ) {
             Ok(entry_count) => {
@@ -431,3 +797,102 @@ except Exception as e:
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dry_run_output() {
+        let uv = "Resolved 4 packages in 12ms\n\
+                  Would download 2 packages\n\
+                  Would install 3 packages\n \
+                  - r2x-reeds==0.9.0\n \
+                  + r2x-reeds==1.0.0\n \
+                  + r2x-plexos==0.4.0 (from git+https://github.com/NREL/r2x-plexos@abc123)\n \
+                  + pandas==2.2.1\n";
+        assert_eq!(
+            parse_dry_run_output(uv),
+            vec![
+                PlannedInstall {
+                    name: "r2x-reeds".to_string(),
+                    version: "1.0.0".to_string(),
+                    replaces: Some("0.9.0".to_string()),
+                },
+                PlannedInstall {
+                    name: "r2x-plexos".to_string(),
+                    version: "0.4.0".to_string(),
+                    replaces: None,
+                },
+                PlannedInstall {
+                    name: "pandas".to_string(),
+                    version: "2.2.1".to_string(),
+                    replaces: None,
+                },
+            ]
+        );
+
+        let pip = "Collecting r2x-plexos\nWould install r2x-plexos-0.3.0 plexosdb-1.1.2\n";
+        let planned = parse_dry_run_output(pip);
+        assert_eq!(planned.len(), 2);
+        assert_eq!(planned[0].name, "r2x-plexos");
+        assert_eq!(planned[1].version, "1.1.2");
+
+        assert!(parse_dry_run_output("Would make no changes\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_requirement() {
+        let defaults = Requirement {
+            spec: String::new(),
+            editable: false,
+            no_cache: true,
+            git: GitOptions {
+                host: Some("gitlab.com".to_string()),
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(parse_requirement("  # comment", &defaults), Ok(None));
+        assert_eq!(parse_requirement("\t# comment", &defaults), Ok(None));
+        assert_eq!(parse_requirement("", &defaults), Ok(None));
+
+        let req = parse_requirement("r2x-reeds==0.2.0  # pinned", &defaults)
+            .unwrap()
+            .unwrap();
+        assert_eq!(req.spec, "r2x-reeds==0.2.0");
+        assert_eq!(req.git, defaults.git);
+        assert!(req.no_cache);
+
+        let req = parse_requirement("r2x-reeds==0.2.0\t# pinned", &defaults)
+            .unwrap()
+            .unwrap();
+        assert_eq!(req.spec, "r2x-reeds==0.2.0");
+
+        // A '#' inside a token, such as a URL fragment, is not a comment
+        let req = parse_requirement("git+https://example.com/r2x-reeds#egg=r2x-reeds", &defaults)
+            .unwrap()
+            .unwrap();
+        assert_eq!(req.spec, "git+https://example.com/r2x-reeds#egg=r2x-reeds");
+
+        let req = parse_requirement(
+            "NREL/r2x-plexos --branch develop --host github.com",
+            &defaults,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(req.spec, "NREL/r2x-plexos");
+        assert_eq!(req.git.branch.as_deref(), Some("develop"));
+        assert_eq!(req.git.host.as_deref(), Some("github.com"));
+
+        let req = parse_requirement("-e ./packages/r2x-reeds", &defaults)
+            .unwrap()
+            .unwrap();
+        assert!(req.editable);
+        assert_eq!(req.spec, "./packages/r2x-reeds");
+
+        assert!(parse_requirement("r2x-reeds --branch", &defaults).is_err());
+        assert!(parse_requirement("r2x-reeds --upgrade", &defaults).is_err());
+        assert!(parse_requirement("r2x-reeds r2x-plexos", &defaults).is_err());
+    }
+}
@@ -1,16 +1,42 @@
-use crate::config_manager::Config;
+use super::install::package_index_args;
+use super::read_install_config;
+use crate::config_manager::{Config, DEFAULT_CONFIDENCE_THRESHOLD};
+use crate::logger;
 use crate::plugins::get_package_info;
-use crate::r2x_manifest::{ImplementationType, Manifest};
+use crate::plugins::graph::build_dependency_graph;
+use crate::plugins::install::Installer;
+use crate::plugins::package_resolver::{list_outdated_r2x_packages, same_package_name};
+use crate::plugins::utils::load_manifest;
+use crate::r2x_manifest::{ImplementationType, Manifest, PluginKind, PluginSpec};
 use crate::GlobalOpts;
+use clap::ValueEnum;
 use colored::Colorize;
-use std::collections::BTreeMap;
+use std::collections::{BTreeSet, HashMap};
+
+/// Output format of `r2x list --graph`
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphFormat {
+    /// Indented tree below each explicitly installed package
+    Tree,
+    /// Graphviz DOT, e.g. `r2x list --graph --format dot > deps.dot`
+    Dot,
+}
 
 pub fn list_plugins(
     opts: &GlobalOpts,
     plugin_filter: Option<String>,
     module_filter: Option<String>,
+    kind_filter: Option<String>,
+    tag_filter: Option<String>,
+    search: Option<String>,
 ) -> Result<(), String> {
-    let manifest = Manifest::load().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let kind_filter = kind_filter.as_deref().map(parse_plugin_kind).transpose()?;
+    let config = Config::load().ok();
+    let threshold = config.as_ref().map_or(
+        DEFAULT_CONFIDENCE_THRESHOLD,
+        Config::get_confidence_threshold,
+    );
 
     let has_plugins = !manifest.is_empty();
     let has_decorators = manifest
@@ -27,6 +53,16 @@ pub fn list_plugins(
         return Ok(());
     }
 
+    if let Some(query) = search {
+        return search_plugins(
+            &manifest,
+            &query,
+            kind_filter.as_ref(),
+            tag_filter.as_deref(),
+            threshold,
+        );
+    }
+
     // If a plugin filter is provided, show detailed information
     if let Some(ref plugin_name) = plugin_filter {
         return show_plugin_details(
@@ -34,66 +70,44 @@ pub fn list_plugins(
             plugin_name,
             module_filter.as_deref(),
             opts.verbose,
+            config.as_ref(),
         );
     }
 
     // Otherwise, show the standard list view
-    let mut packages: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    for pkg in &manifest.packages {
-        let mut names: Vec<String> = pkg.plugins.iter().map(|p| p.name.clone()).collect();
-        names.sort();
-        packages.insert(pkg.name.clone(), names);
-    }
+    let packages: Vec<(&str, Vec<&PluginSpec>)> = manifest
+        .list_plugins_by_package()
+        .into_iter()
+        .map(|(name, plugins)| {
+            let plugins = filter_plugins(plugins, kind_filter.as_ref(), tag_filter.as_deref());
+            (name, plugins)
+        })
+        .filter(|(_, plugins)| {
+            (kind_filter.is_none() && tag_filter.is_none()) || !plugins.is_empty()
+        })
+        .collect();
 
     if has_plugins {
-        println!("{}", "Plugins:".bold().green());
-
         // Get package version info
-        let config = Config::load().ok();
         let python_path = config.as_ref().map(|c| c.get_venv_python_path());
-        let uv_path = config
-            .as_ref()
-            .and_then(|c| c.uv_path.as_deref())
-            .unwrap_or("uv");
-
-        for (package_name, plugin_names) in &packages {
-            // Get package metadata
-            let pkg = manifest.packages.iter().find(|p| p.name == *package_name);
-            let is_editable = pkg.map(|p| p.editable_install).unwrap_or(false);
-
-            // Get version info
-            let version_info = if let Some(ref py_path) = python_path {
-                get_package_info(uv_path, py_path, package_name)
-                    .ok()
-                    .and_then(|(v, _)| v)
-            } else {
-                None
-            };
-
-            // Build package header with version and editable status
-            let mut package_header = format!(" {}:", package_name.bold().blue());
-            if let Some(version) = version_info {
-                package_header.push_str(&format!(" {}", format!("v{}", version).dimmed()));
-            }
-            if is_editable {
-                if let Some(source_path) = pkg.and_then(|p| p.resolved_source_path.as_ref()) {
-                    package_header.push_str(&format!(
-                        " {}",
-                        format!("(file://{})", source_path).dimmed()
-                    ));
-                } else {
-                    package_header.push_str(&format!(" {}", "[editable]".yellow()));
-                }
-            }
-            println!("{}", package_header);
-
-            for plugin_name in plugin_names {
-                println!("    - {}", plugin_name);
-            }
-            println!();
-        }
+        let installer = config.as_ref().and_then(Installer::find);
+        let versions: HashMap<&str, String> = match (&installer, &python_path) {
+            (Some(installer), Some(py_path)) => packages
+                .iter()
+                .filter_map(|(package_name, _)| {
+                    get_package_info(installer, py_path, package_name)
+                        .ok()
+                        .and_then(|(v, _)| v)
+                        .map(|version| (*package_name, version))
+                })
+                .collect(),
+            _ => HashMap::new(),
+        };
 
-        println!("{}: {}", "Total plugin packages".bold(), packages.len());
+        print!(
+            "{}",
+            render_plugin_list(&manifest, &packages, &versions, opts.verbose > 0, threshold)
+        );
     }
 
     if has_decorators {
@@ -133,11 +147,314 @@ pub fn list_plugins(
     Ok(())
 }
 
+/// Show installed plugin packages that have a newer release
+///
+/// Only packages with plugins matching the filters are checked. The manifest is
+/// left untouched.
+pub fn list_outdated_plugins(
+    plugin_filter: Option<String>,
+    module_filter: Option<String>,
+    kind_filter: Option<String>,
+    tag_filter: Option<String>,
+) -> Result<(), String> {
+    let kind_filter = kind_filter.as_deref().map(parse_plugin_kind).transpose()?;
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    let (installer, _venv_path, python_path) = read_install_config()?;
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+
+    let packages: Vec<_> = manifest
+        .packages
+        .iter()
+        .filter(|pkg| {
+            plugin_filter
+                .as_deref()
+                .map_or(true, |name| same_package_name(name, &pkg.name))
+        })
+        .filter(|pkg| {
+            let plugins = pkg
+                .plugins
+                .iter()
+                .filter(|plugin| {
+                    module_filter
+                        .as_deref()
+                        .map_or(true, |module| plugin_in_module(plugin, module))
+                })
+                .collect();
+            let unfiltered =
+                module_filter.is_none() && kind_filter.is_none() && tag_filter.is_none();
+            unfiltered
+                || !filter_plugins(plugins, kind_filter.as_ref(), tag_filter.as_deref()).is_empty()
+        })
+        .collect();
+    if packages.is_empty() {
+        println!("No plugin packages match the filter criteria.");
+        return Ok(());
+    }
+
+    // Packages from a private registry are only compared against that registry
+    let registries: BTreeSet<Option<String>> = std::iter::once(None)
+        .chain(packages.iter().map(|pkg| pkg.registry.clone()))
+        .collect();
+    let mut outdated = HashMap::new();
+    for registry in registries {
+        let index_args = match package_index_args(&config, registry.as_deref(), &installer) {
+            Ok(index_args) => index_args,
+            Err(e) => {
+                logger::warn(&e);
+                continue;
+            }
+        };
+        let packages = list_outdated_r2x_packages(&installer, &python_path, &index_args)
+            .map_err(|e| format!("Failed to check for updates: {}", e))?;
+        outdated.insert(registry, packages);
+    }
+
+    let mut rows = Vec::new();
+    for pkg in packages {
+        let update = outdated
+            .get(&pkg.registry)
+            .into_iter()
+            .flatten()
+            .find(|candidate| same_package_name(&candidate.name, &pkg.name));
+        if let Some(candidate) = update {
+            rows.push((
+                pkg.name.clone(),
+                candidate.version.clone(),
+                candidate.latest_version.clone(),
+            ));
+        }
+    }
+
+    if rows.is_empty() {
+        println!("All plugin packages are up to date.");
+        return Ok(());
+    }
+    println!("{}", "Outdated plugin packages:".bold().green());
+    for (name, installed, latest) in &rows {
+        println!(
+            "  {:<30}{} -> {}",
+            name.bold().blue(),
+            installed.dimmed(),
+            latest.green()
+        );
+    }
+    println!(
+        "\nUpdate a package with:\n  {} update <package>\nor all of them with:\n  {} update --all",
+        "r2x".bold().cyan(),
+        "r2x".bold().cyan()
+    );
+    Ok(())
+}
+
+/// Print which packages were installed as dependencies of which
+pub fn show_dependency_graph(format: GraphFormat) -> Result<(), String> {
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    if manifest.packages.is_empty() {
+        println!("There are no current plugins installed.");
+        return Ok(());
+    }
+
+    let graph = build_dependency_graph(&manifest);
+    match format {
+        GraphFormat::Tree => {
+            println!("{}", "Plugin packages:".bold().green());
+            print!("{}", graph.render_tree());
+        }
+        GraphFormat::Dot => print!("{}", graph.to_dot()),
+    }
+    Ok(())
+}
+
+/// Print plugins matching a search query, grouped by package
+fn search_plugins(
+    manifest: &Manifest,
+    query: &str,
+    kind: Option<&PluginKind>,
+    tag: Option<&str>,
+    threshold: f32,
+) -> Result<(), String> {
+    let mut matches: Vec<(&str, &PluginSpec)> = manifest
+        .search(query)
+        .into_iter()
+        .filter(|(_, plugin)| !filter_plugins(vec![plugin], kind, tag).is_empty())
+        .collect();
+    if matches.is_empty() {
+        println!("No plugins match '{}'", query);
+        return Ok(());
+    }
+    matches.sort_by_key(|(package, _)| *package);
+
+    println!("{}", "Plugins:".bold().green());
+    let mut current = None;
+    for (package, plugin) in &matches {
+        if current != Some(*package) {
+            if current.is_some() {
+                println!();
+            }
+            println!(" {}:", package.bold().blue());
+            current = Some(*package);
+        }
+        println!("{}", format_plugin_row(plugin, threshold));
+    }
+    println!();
+    println!("{}: {}", "Matching plugins".bold(), matches.len());
+    Ok(())
+}
+
+/// Render the `r2x list` view of `packages` with versions and a totals footer
+///
+/// Plugins are grouped by type, or shown as aligned rows with callable details when `verbose`.
+fn render_plugin_list(
+    manifest: &Manifest,
+    packages: &[(&str, Vec<&PluginSpec>)],
+    versions: &HashMap<&str, String>,
+    verbose: bool,
+    threshold: f32,
+) -> String {
+    let mut out = format!("{}\n", "Plugins:".bold().green());
+
+    for (package_name, plugins) in packages {
+        // Get package metadata
+        let pkg = manifest.packages.iter().find(|p| p.name == *package_name);
+        let is_editable = pkg.map(|p| p.editable_install).unwrap_or(false);
+
+        // Build package header with version and editable status
+        let mut package_header = format!(" {}:", package_name.bold().blue());
+        if let Some(version) = versions.get(package_name) {
+            package_header.push_str(&format!(" {}", format!("v{}", version).dimmed()));
+        }
+        if is_editable {
+            if let Some(source_path) = pkg.and_then(|p| p.resolved_source_path.as_ref()) {
+                package_header.push_str(&format!(
+                    " {}",
+                    format!("(file://{})", source_path).dimmed()
+                ));
+            } else {
+                package_header.push_str(&format!(" {}", "[editable]".yellow()));
+            }
+        }
+        out.push_str(&format!("{}\n", package_header));
+
+        if verbose {
+            for plugin in plugins {
+                out.push_str(&format!("{}\n", format_plugin_row(plugin, threshold)));
+            }
+        } else {
+            for plugin_type in manifest.plugin_types() {
+                let of_type: Vec<&&PluginSpec> = plugins
+                    .iter()
+                    .filter(|plugin| plugin.kind.as_str() == plugin_type)
+                    .collect();
+                if of_type.is_empty() {
+                    continue;
+                }
+                out.push_str(&format!("   {}\n", format!("{}:", plugin_type).dimmed()));
+                for plugin in of_type {
+                    out.push_str(&format!("    - {}\n", plugin.name));
+                }
+            }
+        }
+        out.push('\n');
+    }
+
+    let total_plugins: usize = manifest.count_by_package().values().sum();
+    out.push_str(&format!(
+        "{}: {} ({} plugins)\n",
+        "Total plugin packages".bold(),
+        manifest.total_package_count(),
+        total_plugins
+    ));
+    out
+}
+
+/// Render a plugin as a 30/12 column row followed by an indented callable line
+fn format_plugin_row(plugin: &PluginSpec, threshold: f32) -> String {
+    let bindings = r2x_manifest::build_runtime_bindings(plugin);
+    let row = format!(
+        "    {:<30}{:<12}{}",
+        plugin.name,
+        format!("{:?}", plugin.kind),
+        plugin.description.as_deref().unwrap_or("")
+    );
+
+    let mut details = format!(
+        "callable: {}:{}",
+        bindings.entry_module, bindings.entry_name
+    );
+    if let Some(config) = &bindings.config {
+        details.push_str(&format!("  config: {}", config.name));
+    }
+    let required: Vec<String> = bindings
+        .entry_parameters
+        .iter()
+        .filter(|param| param.required)
+        .map(|param| {
+            format!(
+                "{}: {}",
+                param.name,
+                param.annotation.as_deref().unwrap_or("Any")
+            )
+        })
+        .collect();
+    if !required.is_empty() {
+        details.push_str(&format!("  required: {}", required.join(", ")));
+    }
+    if let Some(confidence) = plugin.confidence {
+        details.push_str(&format!(
+            "  confidence: {}",
+            format_confidence(confidence, threshold)
+        ));
+    }
+
+    format!("{}\n      {}", row.trim_end(), details)
+}
+
+/// Render a discovery confidence, highlighting partially understood plugins
+fn format_confidence(confidence: f32, threshold: f32) -> String {
+    let text = format!("{:.2}", confidence);
+    if confidence < threshold {
+        format!("{} (partially understood)", text)
+            .yellow()
+            .to_string()
+    } else {
+        text
+    }
+}
+
+fn filter_plugins<'a>(
+    plugins: Vec<&'a PluginSpec>,
+    kind: Option<&PluginKind>,
+    tag: Option<&str>,
+) -> Vec<&'a PluginSpec> {
+    plugins
+        .into_iter()
+        .filter(|plugin| kind.map_or(true, |kind| plugin.kind == *kind))
+        .filter(|plugin| tag.map_or(true, |tag| plugin.has_tag(tag)))
+        .collect()
+}
+
+/// Match if the plugin name ends with the module filter,
+/// e.g. "r2x_reeds.break_gens" matches module "break_gens"
+fn plugin_in_module(plugin: &PluginSpec, module_name: &str) -> bool {
+    plugin.name.rsplit('.').next() == Some(module_name)
+}
+
+/// Parse `--kind`, accepting plurals such as `parsers` or `utilities`
+fn parse_plugin_kind(value: &str) -> Result<PluginKind, String> {
+    let lowered = value.to_ascii_lowercase();
+    let singular = match lowered.as_str() {
+        "utilities" => "utility",
+        lowered => lowered.trim_end_matches('s'),
+    };
+    value.parse().or_else(|e| singular.parse().map_err(|_| e))
+}
+
 fn show_plugin_details(
     manifest: &Manifest,
     plugin_filter: &str,
     module_filter: Option<&str>,
     verbose_level: u8,
+    config: Option<&Config>,
 ) -> Result<(), String> {
     // Find the package containing this plugin
     let package = manifest
@@ -147,19 +464,18 @@ fn show_plugin_details(
         .ok_or_else(|| format!("Plugin package '{}' not found", plugin_filter))?;
 
     // Build package header with version and editable info
-    let config = Config::load().ok();
-    let python_path = config.as_ref().map(|c| c.get_venv_python_path());
-    let uv_path = config
-        .as_ref()
-        .and_then(|c| c.uv_path.as_deref())
-        .unwrap_or("uv");
-
-    let version_info = if let Some(ref py_path) = python_path {
-        get_package_info(uv_path, py_path, &package.name)
+    let python_path = config.map(|c| c.get_venv_python_path());
+    let installer = config.and_then(Installer::find);
+    let threshold = config.map_or(
+        DEFAULT_CONFIDENCE_THRESHOLD,
+        Config::get_confidence_threshold,
+    );
+
+    let version_info = match (&installer, &python_path) {
+        (Some(installer), Some(py_path)) => get_package_info(installer, py_path, &package.name)
             .ok()
-            .and_then(|(v, _)| v)
-    } else {
-        None
+            .and_then(|(v, _)| v),
+        _ => None,
     };
 
     print!(
@@ -178,6 +494,12 @@ fn show_plugin_details(
         }
     }
     println!();
+    if let Some(homepage) = package.homepage_url() {
+        println!("  {}: {}", "Homepage".dimmed(), homepage);
+    }
+    if let Some(license) = &package.license {
+        println!("  {}: {}", "License".dimmed(), license);
+    }
     println!();
 
     // Filter plugins by module name if provided
@@ -185,15 +507,7 @@ fn show_plugin_details(
         package
             .plugins
             .iter()
-            .filter(|p| {
-                // Match if the plugin name ends with the module filter
-                // e.g., "r2x_reeds.break_gens" matches module "break_gens"
-                let parts: Vec<&str> = p.name.split('.').collect();
-                parts
-                    .last()
-                    .map(|&last| last == module_name)
-                    .unwrap_or(false)
-            })
+            .filter(|p| plugin_in_module(p, module_name))
             .collect()
     } else {
         package.plugins.iter().collect()
@@ -208,7 +522,7 @@ fn show_plugin_details(
 
     for plugin in plugins_to_show {
         if verbose_level > 0 {
-            show_plugin_verbose(plugin);
+            show_plugin_verbose(plugin, threshold);
         } else {
             show_plugin_compact(plugin);
         }
@@ -252,7 +566,7 @@ fn show_plugin_compact(plugin: &crate::r2x_manifest::PluginSpec) {
     }
 }
 
-fn show_plugin_verbose(plugin: &crate::r2x_manifest::PluginSpec) {
+fn show_plugin_verbose(plugin: &crate::r2x_manifest::PluginSpec, threshold: f32) {
     println!("{}", plugin.name.bold().cyan());
 
     if let Some(desc) = &plugin.description {
@@ -261,6 +575,13 @@ fn show_plugin_verbose(plugin: &crate::r2x_manifest::PluginSpec) {
 
     println!("  {}: {:?}", "Kind".dimmed(), plugin.kind);
     println!("  {}: {}", "Entry".dimmed(), plugin.entry);
+    if let Some(confidence) = plugin.confidence {
+        println!(
+            "  {}: {}",
+            "Confidence".dimmed(),
+            format_confidence(confidence, threshold)
+        );
+    }
 
     // Show implementation type
     println!(
@@ -377,3 +698,114 @@ fn show_plugin_verbose(plugin: &crate::r2x_manifest::PluginSpec) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r2x_manifest::{ArgumentSpec, IOContract, InvocationSpec, TypeKind};
+
+    fn parser() -> PluginSpec {
+        PluginSpec {
+            name: "reeds-parser".to_string(),
+            kind: PluginKind::Parser,
+            entry: "r2x_reeds.parser.ReEDSParser".to_string(),
+            invocation: InvocationSpec {
+                implementation: ImplementationType::Class,
+                method: Some("build_system".to_string()),
+                constructor: vec![
+                    ArgumentSpec {
+                        name: "path".to_string(),
+                        annotation: Some("str".to_string()),
+                        default: None,
+                        required: true,
+                        type_kind: Some(TypeKind::String),
+                    },
+                    ArgumentSpec {
+                        name: "year".to_string(),
+                        annotation: Some("int".to_string()),
+                        default: Some("2030".to_string()),
+                        required: false,
+                        type_kind: Some(TypeKind::Number),
+                    },
+                ],
+                call: Vec::new(),
+                timeout_secs: None,
+                retry_count: None,
+                cacheable: false,
+            },
+            io: IOContract {
+                consumes: Vec::new(),
+                produces: Vec::new(),
+            },
+            resources: None,
+            upgrade: None,
+            description: Some("Parse ReEDS outputs".to_string()),
+            tags: Vec::new(),
+            r2x_core_requirement: None,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_format_plugin_row_alignment() {
+        let row = format_plugin_row(&parser(), 0.7);
+        let lines: Vec<&str> = row.lines().collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(&lines[0][4..34], format!("{:<30}", "reeds-parser"));
+        assert_eq!(&lines[0][34..46], format!("{:<12}", "Parser"));
+        assert_eq!(&lines[0][46..], "Parse ReEDS outputs");
+        assert_eq!(
+            lines[1],
+            "      callable: r2x_reeds.parser:ReEDSParser  required: path: str"
+        );
+    }
+
+    #[test]
+    fn test_render_plugin_list() {
+        colored::control::set_override(false);
+        let mut manifest = Manifest::default();
+        manifest
+            .get_or_create_package("r2x-reeds")
+            .plugins
+            .push(parser());
+        let packages = manifest.list_plugins_by_package();
+        let versions = HashMap::from([("r2x-reeds", "0.3.0".to_string())]);
+
+        let compact = render_plugin_list(&manifest, &packages, &versions, false, 0.7);
+        assert_eq!(
+            compact,
+            "Plugins:\n r2x-reeds: v0.3.0\n   parser:\n    - reeds-parser\n\n\
+             Total plugin packages: 1 (1 plugins)\n"
+        );
+
+        let verbose = render_plugin_list(&manifest, &packages, &versions, true, 0.7);
+        let lines: Vec<&str> = verbose.lines().collect();
+        assert_eq!(lines[1], " r2x-reeds: v0.3.0");
+        assert_eq!(lines[2..4].join("\n"), format_plugin_row(&parser(), 0.7));
+        assert_eq!(lines.last(), Some(&"Total plugin packages: 1 (1 plugins)"));
+    }
+
+    #[test]
+    fn test_parse_plugin_kind() {
+        assert_eq!(parse_plugin_kind("parser"), Ok(PluginKind::Parser));
+        assert_eq!(parse_plugin_kind("Exporters"), Ok(PluginKind::Exporter));
+        assert_eq!(parse_plugin_kind("utilities"), Ok(PluginKind::Utility));
+        assert!(parse_plugin_kind("reader").is_err());
+
+        let mut spec = parser();
+        assert_eq!(
+            filter_plugins(vec![&spec], Some(&PluginKind::Exporter), None).len(),
+            0
+        );
+        assert_eq!(filter_plugins(vec![&spec], None, None).len(), 1);
+
+        spec.tags = vec!["energy".to_string()];
+        assert_eq!(filter_plugins(vec![&spec], None, Some("energy")).len(), 1);
+        assert_eq!(filter_plugins(vec![&spec], None, Some("reeds")).len(), 0);
+
+        spec.name = "r2x_reeds.break_gens".to_string();
+        assert!(plugin_in_module(&spec, "break_gens"));
+        assert!(!plugin_in_module(&spec, "r2x_reeds"));
+    }
+}
@@ -1,19 +1,41 @@
 use crate::config_manager::Config;
 use crate::logger;
+use crate::plugins::install::Installer;
+use crate::r2x_manifest::errors::ManifestError;
+use crate::r2x_manifest::lock::{lock_timeout, ManifestLock};
+use std::path::Path;
 
 pub mod clean;
 pub mod install;
 pub mod list;
 pub mod remove;
 pub mod sync;
+pub mod update;
 
 pub use clean::clean_manifest;
-pub use install::{install_plugin, show_install_help, GitOptions};
-pub use list::list_plugins;
+pub use install::{
+    dry_run_install, install_from_requirements, install_plugin, show_install_help, GitOptions,
+};
+pub use list::{list_outdated_plugins, list_plugins, show_dependency_graph};
 pub use remove::remove_plugin;
-pub use sync::sync_manifest;
+pub use sync::{sync_manifest, SyncReport};
+pub use update::{update_all_plugins, update_plugin};
 
-pub(super) fn setup_config() -> Result<(String, String, String), String> {
+/// Take the install lock so concurrent r2x processes do not interleave manifest updates
+///
+/// Every command that writes the manifest holds it for its whole read-modify-write.
+pub(crate) fn lock_installation() -> Result<ManifestLock, String> {
+    ManifestLock::try_acquire_within(lock_timeout()).map_err(|e| match e {
+        ManifestError::LockTimeout(path) => format!(
+            "Another r2x process is updating plugins (lock {}). \
+             Set R2X_MANIFEST_LOCK_TIMEOUT_SECS to wait longer",
+            path.display()
+        ),
+        e => format!("Failed to take install lock: {}", e),
+    })
+}
+
+pub(super) fn setup_config() -> Result<(Installer, String, String), String> {
     let mut config = Config::load().map_err(|e| {
         logger::error(&format!("Failed to load config: {}", e));
         format!("Failed to load config: {}", e)
@@ -27,7 +49,7 @@ pub(super) fn setup_config() -> Result<(String, String, String), String> {
         logger::error(&format!("Failed to setup cache: {}", e));
         format!("Failed to setup cache: {}", e)
     })?;
-    config.ensure_venv_path().map_err(|e| {
+    config.ensure_venv_path(true).map_err(|e| {
         logger::error(&format!("Failed to setup venv: {}", e));
         format!("Failed to setup venv: {}", e)
     })?;
@@ -40,5 +62,54 @@ pub(super) fn setup_config() -> Result<(String, String, String), String> {
     let venv_path = config.get_venv_path();
     let python_path = config.get_venv_python_path();
 
-    Ok((uv_path, venv_path, python_path))
+    Ok((Installer::Uv(uv_path), venv_path, python_path))
+}
+
+/// Like [`setup_config`], but falls back to the venv pip when uv cannot be set up
+pub(super) fn setup_install_config() -> Result<(Installer, String, String), String> {
+    let mut config = Config::load().map_err(|e| {
+        logger::error(&format!("Failed to load config: {}", e));
+        format!("Failed to load config: {}", e)
+    })?;
+
+    if let Err(uv_err) = config.ensure_uv_path() {
+        let pip_path = config.ensure_pip_path().map_err(|_| {
+            logger::error(&format!("Failed to setup uv: {}", uv_err));
+            format!("Failed to setup uv: {}", uv_err)
+        })?;
+        logger::warn(&format!(
+            "uv is unavailable ({}). Falling back to pip at {}",
+            uv_err, pip_path
+        ));
+        return Ok((
+            Installer::Pip(pip_path),
+            config.get_venv_path(),
+            config.get_venv_python_path(),
+        ));
+    }
+
+    setup_config()
+}
+
+/// Installer, venv and venv Python paths from the config, without setting anything up
+///
+/// Unlike [`setup_install_config`] this never downloads uv, writes the config or
+/// touches the venv, so it suits read-only operations such as `install --dry-run`.
+pub(super) fn read_install_config() -> Result<(Installer, String, String), String> {
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    let venv_path = config.get_venv_path();
+    let python_path = config.get_venv_python_path();
+    if !Path::new(&python_path).exists() {
+        return Err(format!(
+            "No Python environment at {}. Run 'r2x config venv create --yes' first",
+            venv_path
+        ));
+    }
+
+    let installer = Installer::find(&config).ok_or_else(|| {
+        "Neither uv nor pip is available. Install uv from https://docs.astral.sh/uv/ \
+             or run 'r2x config set uv-path <path>'"
+            .to_string()
+    })?;
+    Ok((installer, venv_path, python_path))
 }
@@ -1,15 +1,24 @@
-use super::setup_config;
+use super::{lock_installation, setup_install_config};
 use crate::logger;
+use crate::plugins::install::Installer;
+use crate::plugins::removal::{self, RemovalHooks, RemovalReport};
+use crate::plugins::utils::load_manifest;
+use crate::python_bridge::resolve_site_package_path;
 use crate::r2x_manifest::Manifest;
 use crate::GlobalOpts;
 use colored::Colorize;
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub fn remove_plugin(package: &str, _opts: &GlobalOpts) -> Result<(), String> {
+    let _lock = lock_installation()?;
+    RemovalHooks::run_pre(package);
+
     let mut removed_count = 0usize;
     let mut orphaned_dependencies = Vec::new();
 
-    match Manifest::load() {
+    match load_manifest() {
         Ok(mut manifest) => {
             orphaned_dependencies = find_orphaned_dependencies(&manifest, package);
             removed_count = manifest.remove_plugins_by_package(package);
@@ -47,33 +56,51 @@ pub fn remove_plugin(package: &str, _opts: &GlobalOpts) -> Result<(), String> {
         }
     }
 
-    let (uv_path, venv_path, _python_path) = setup_config()?;
+    let (installer, venv_path, python_path) = setup_install_config()?;
     logger::info(&format!("Using venv: {}", venv_path));
 
-    let check_output = Command::new(&uv_path)
-        .args(["pip", "show", "--python", &venv_path, package])
+    let mut report = RemovalReport {
+        removed_plugins: removed_count,
+        orphaned_dependencies: orphaned_dependencies.clone(),
+        uninstalled: false,
+    };
+
+    let check_output = installer
+        .pip_command("show", &python_path)
+        .arg(package)
         .output()
         .map_err(|e| format!("Failed to check package status: {}", e))?;
 
     if !check_output.status.success() {
         logger::warn(&format!("Package '{}' is not installed", package));
+        RemovalHooks::run_post(package, &report);
         return Ok(());
     }
 
+    if let Some(target) = resolve_site_package_path(&PathBuf::from(&venv_path))
+        .ok()
+        .and_then(|site_packages| removal::find_uninstall_entry_point(&site_packages, package))
+    {
+        removal::run_uninstall_entry_point(&python_path, package, &target);
+    }
+
     logger::debug(&format!(
-        "Running: {} pip uninstall --python {} {}",
-        uv_path, venv_path, package
+        "Running: {} uninstall {}",
+        installer.name(),
+        package
     ));
 
-    let output = Command::new(&uv_path)
-        .args(["pip", "uninstall", "--python", &venv_path, package])
+    let output = uninstall_command(&installer, &python_path, package)
         .output()
         .map_err(|e| {
             logger::error(&format!("Failed to run pip uninstall: {}", e));
             format!("Failed to run pip uninstall: {}", e)
         })?;
 
-    logger::capture_output(&format!("uv pip uninstall {}", package), &output);
+    logger::capture_output(
+        &format!("{} uninstall {}", installer.name(), package),
+        &output,
+    );
 
     if !output.status.success() {
         logger::error(&format!("pip uninstall failed for package '{}'", package));
@@ -81,10 +108,12 @@ pub fn remove_plugin(package: &str, _opts: &GlobalOpts) -> Result<(), String> {
     }
 
     logger::info(&format!("Package '{}' uninstalled successfully", package));
+    report.uninstalled = true;
 
     for orphan_pkg in &orphaned_dependencies {
-        let check_orphan = Command::new(&uv_path)
-            .args(["pip", "show", "--python", &venv_path, orphan_pkg])
+        let check_orphan = installer
+            .pip_command("show", &python_path)
+            .arg(orphan_pkg)
             .output()
             .map_err(|e| {
                 format!(
@@ -95,12 +124,12 @@ pub fn remove_plugin(package: &str, _opts: &GlobalOpts) -> Result<(), String> {
 
         if check_orphan.status.success() {
             logger::debug(&format!(
-                "Running: {} pip uninstall --python {} {}",
-                uv_path, venv_path, orphan_pkg
+                "Running: {} uninstall {}",
+                installer.name(),
+                orphan_pkg
             ));
 
-            let orphan_output = Command::new(&uv_path)
-                .args(["pip", "uninstall", "--python", &venv_path, orphan_pkg])
+            let orphan_output = uninstall_command(&installer, &python_path, orphan_pkg)
                 .output()
                 .map_err(|e| {
                     logger::error(&format!(
@@ -114,7 +143,11 @@ pub fn remove_plugin(package: &str, _opts: &GlobalOpts) -> Result<(), String> {
                 })?;
 
             logger::capture_output(
-                &format!("uv pip uninstall {} (orphaned dependency)", orphan_pkg),
+                &format!(
+                    "{} uninstall {} (orphaned dependency)",
+                    installer.name(),
+                    orphan_pkg
+                ),
                 &orphan_output,
             );
 
@@ -147,9 +180,122 @@ pub fn remove_plugin(package: &str, _opts: &GlobalOpts) -> Result<(), String> {
         );
     }
 
+    RemovalHooks::run_post(package, &report);
     Ok(())
 }
 
-fn find_orphaned_dependencies(_manifest: &Manifest, _package: &str) -> Vec<String> {
-    Vec::new()
+/// `pip uninstall` for one package; pip needs `-y` to skip its confirmation prompt
+fn uninstall_command(installer: &Installer, python_path: &str, package: &str) -> Command {
+    let mut command = installer.pip_command("uninstall", python_path);
+    if installer.is_pip() {
+        command.arg("-y");
+    }
+    command.arg(package);
+    command
+}
+
+/// Dependencies of `package` that nothing else needs once it is removed
+///
+/// A dependency is orphaned when it was not installed explicitly and no other
+/// package in the manifest depends on it.
+fn find_orphaned_dependencies(manifest: &Manifest, package: &str) -> Vec<String> {
+    let Some(target) = manifest.packages.iter().find(|pkg| pkg.name == package) else {
+        return Vec::new();
+    };
+
+    let still_required: HashSet<&str> = manifest
+        .packages
+        .iter()
+        .filter(|pkg| pkg.name != package)
+        .flat_map(|pkg| pkg.dependencies.iter().map(String::as_str))
+        .collect();
+
+    target
+        .dependencies
+        .iter()
+        .filter(|dep| !still_required.contains(dep.as_str()))
+        .filter(|dep| {
+            manifest
+                .packages
+                .iter()
+                .find(|pkg| pkg.name == **dep)
+                .is_some_and(|pkg| pkg.install_type.as_deref() != Some("explicit"))
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_with_dependency(main: &str, dep: &str) -> Manifest {
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package(main);
+        manifest.mark_explicit(main);
+        manifest.get_or_create_package(dep);
+        manifest.mark_dependency(dep, main);
+        manifest.add_dependency(main, dep);
+        manifest
+    }
+
+    #[test]
+    fn test_single_orphaned_dependency() {
+        let manifest = manifest_with_dependency("r2x-main", "r2x-dep");
+        assert_eq!(
+            find_orphaned_dependencies(&manifest, "r2x-main"),
+            vec!["r2x-dep".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_shared_dependency_not_orphaned() {
+        let mut manifest = manifest_with_dependency("r2x-main1", "r2x-shared");
+        manifest.get_or_create_package("r2x-main2");
+        manifest.mark_explicit("r2x-main2");
+        manifest.mark_dependency("r2x-shared", "r2x-main2");
+        manifest.add_dependency("r2x-main2", "r2x-shared");
+
+        assert!(find_orphaned_dependencies(&manifest, "r2x-main1").is_empty());
+        assert!(find_orphaned_dependencies(&manifest, "r2x-main2").is_empty());
+    }
+
+    #[test]
+    fn test_explicit_dependency_not_orphaned() {
+        let mut manifest = manifest_with_dependency("r2x-main", "r2x-dep");
+        manifest.mark_explicit("r2x-dep");
+
+        assert!(find_orphaned_dependencies(&manifest, "r2x-main").is_empty());
+    }
+
+    #[test]
+    fn test_empty_manifest() {
+        let manifest = Manifest::default();
+        assert!(find_orphaned_dependencies(&manifest, "r2x-main").is_empty());
+    }
+
+    #[test]
+    fn test_package_without_dependencies() {
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-main");
+        manifest.mark_explicit("r2x-main");
+
+        assert!(find_orphaned_dependencies(&manifest, "r2x-main").is_empty());
+    }
+
+    #[test]
+    fn test_multiple_dependencies_partially_shared() {
+        let mut manifest = manifest_with_dependency("r2x-main", "r2x-only");
+        manifest.get_or_create_package("r2x-shared");
+        manifest.mark_dependency("r2x-shared", "r2x-main");
+        manifest.add_dependency("r2x-main", "r2x-shared");
+        manifest.get_or_create_package("r2x-other");
+        manifest.mark_explicit("r2x-other");
+        manifest.add_dependency("r2x-other", "r2x-shared");
+
+        assert_eq!(
+            find_orphaned_dependencies(&manifest, "r2x-main"),
+            vec!["r2x-only".to_string()]
+        );
+    }
 }
@@ -1,80 +1,182 @@
-use super::setup_config;
+use super::{lock_installation, setup_install_config};
+use crate::config_manager::Config;
 use crate::logger;
-use crate::plugins::{
-    discovery::{discover_and_register_entry_points_with_deps, DiscoveryOptions},
-    install::get_package_info,
+use crate::plugins::ast_cache::PluginAstCache;
+use crate::plugins::discovery::{
+    discover_and_register_with_metrics, discover_package_cached, DiscoveredPlugins,
+    DiscoveryOptions,
 };
-use crate::r2x_manifest::Manifest;
+use crate::plugins::package_resolver::list_installed_r2x_packages;
+use crate::plugins::utils::load_manifest;
+use crate::r2x_ast::BatchDiscoveryMetrics;
 use crate::GlobalOpts;
 use colored::Colorize;
+use rayon::prelude::*;
+use std::collections::HashMap;
 
-pub fn sync_manifest(_opts: &GlobalOpts) -> Result<(), String> {
+/// Summary of a manifest sync
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    /// Number of packages re-discovered
+    pub synced: usize,
+    /// Packages whose installed version differs from the manifest:
+    /// `(package, manifest_version, installed_version)`
+    pub version_mismatches: Vec<(String, String, String)>,
+    /// AST discovery timings of every analyzed package
+    pub metrics: BatchDiscoveryMetrics,
+}
+
+/// Re-run discovery for every package in the manifest
+///
+/// AST discovery runs in parallel on `jobs` threads (all cores when `None` or 0); the
+/// results are then registered in the manifest one package at a time. Packages whose
+/// plugin module is unchanged reuse the AST cache, unless `no_cache` is set, which
+/// drops their cached results first. With `show_metrics`, a table of per-package
+/// discovery times is printed at the end.
+pub fn sync_manifest(
+    opts: &GlobalOpts,
+    show_metrics: bool,
+    jobs: Option<usize>,
+    no_cache: bool,
+) -> Result<SyncReport, String> {
+    let _lock = lock_installation()?;
     logger::debug("Loading manifest for syncing");
 
-    let manifest = Manifest::load().map_err(|e| {
+    let manifest = load_manifest().map_err(|e| {
         logger::error(&format!("Failed to load manifest: {}", e));
         format!("Failed to load manifest: {}", e)
     })?;
 
     if manifest.is_empty() {
         logger::warn("No plugins installed. Nothing to sync.");
-        return Ok(());
+        return Ok(SyncReport::default());
     }
 
-    let (uv_path, _venv_path, python_path) = setup_config()?;
+    let (installer, venv_path, python_path) = setup_install_config()?;
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
     let total_start = std::time::Instant::now();
 
-    let packages_to_sync: Vec<String> = manifest
+    let packages_to_sync: Vec<(String, Option<String>)> = manifest
         .packages
         .iter()
-        .map(|pkg| pkg.name.clone())
+        .map(|pkg| (pkg.name.clone(), pkg.version.clone()))
         .collect();
 
     if packages_to_sync.is_empty() {
         logger::warn("No packages found in manifest to sync.");
-        return Ok(());
+        return Ok(SyncReport::default());
     }
 
+    // One `pip list` call gives the installed version of every plugin package
+    let installed = list_installed_r2x_packages(&installer, &python_path).unwrap_or_else(|e| {
+        logger::debug(&format!("Could not list installed packages: {}", e));
+        Vec::new()
+    });
+
     let num_packages = packages_to_sync.len();
     logger::step(&format!("Syncing {} package(s)...", num_packages));
 
-    for package_name in packages_to_sync {
-        logger::spinner_start(&format!("Syncing: {}", package_name));
-
-        let (package_version, dependencies) =
-            match get_package_info(&uv_path, &python_path, &package_name) {
-                Ok((version, deps)) => (version, deps),
-                Err(e) => {
-                    logger::spinner_error(&format!(
-                        "Failed to get package info for {}: {}",
-                        package_name, e
-                    ));
-                    logger::debug(&format!("Skipping package: {}", package_name));
-                    (None, Vec::new())
+    let mut report = SyncReport::default();
+    let mut to_register = Vec::with_capacity(num_packages);
+    for (package_name, manifest_version) in packages_to_sync {
+        let mut discovery = match DiscoveryOptions::from_installed_package(&package_name, &config) {
+            Ok(discovery) => discovery,
+            Err(e) => {
+                logger::warn(&format!(
+                    "Failed to get package info for {}: {}",
+                    package_name, e
+                ));
+                logger::debug(&format!("Skipping package: {}", package_name));
+                DiscoveryOptions {
+                    package: package_name.clone(),
+                    package_name_full: package_name.clone(),
+                    ..Default::default()
                 }
-            };
-
-        match discover_and_register_entry_points_with_deps(
-            &uv_path,
-            &python_path,
-            DiscoveryOptions {
-                package: package_name.to_string(),
-                package_name_full: package_name.to_string(),
-                dependencies,
-                package_version,
-                // Always re-scan when syncing so manifest reflects the latest plugin code
-                no_cache: true,
-                // During sync, preserve existing editable/source_path from manifest
-                editable: false,
-                source_path: None,
-            },
+            }
+        };
+
+        let installed_version = installed
+            .iter()
+            .find(|pkg| pkg.name == package_name)
+            .map(|pkg| pkg.version.as_str())
+            .or(discovery.package_version.as_deref());
+        if let Some(mismatch) = version_mismatch(
+            &package_name,
+            manifest_version.as_deref(),
+            installed_version,
         ) {
-            Ok(_) => {
-                logger::spinner_stop();
+            logger::warn(&format!(
+                "{} was changed outside r2x: manifest has {}, installed is {}",
+                mismatch.0, mismatch.1, mismatch.2
+            ));
+            report.version_mismatches.push(mismatch);
+        }
+
+        // Always re-scan when syncing so manifest reflects the latest plugin code,
+        // which also covers packages flagged by the version check above.
+        // Editable/source_path stay unset so the existing manifest values are preserved.
+        discovery.no_cache = true;
+        to_register.push(discovery);
+    }
+
+    let mode = opts.discovery_mode();
+    let ast_cache = PluginAstCache::load_configured(&config);
+    if no_cache {
+        for discovery in &to_register {
+            ast_cache.invalidate(&discovery.package_name_full);
+        }
+    }
+
+    logger::spinner_start(&format!(
+        "Discovering plugins in {} package(s)",
+        num_packages
+    ));
+    let discovered = run_parallel(&to_register, jobs, |discovery| {
+        (
+            discovery.package_name_full.clone(),
+            discover_package_cached(
+                &discovery.package_name_full,
+                discovery.package_version.as_deref(),
+                Some(&venv_path),
+                mode,
+                &ast_cache,
+            ),
+        )
+    })?;
+    logger::spinner_stop();
+    if let Err(e) = ast_cache.save() {
+        logger::warn(&format!("Failed to save AST cache: {}", e));
+    }
+
+    let mut found: HashMap<String, DiscoveredPlugins> = HashMap::new();
+    for (package_name, result) in discovered {
+        match result {
+            Ok((plugins, metrics)) => {
+                report.metrics.push(metrics);
+                found.insert(package_name, plugins);
+            }
+            Err(e) => {
+                logger::error(&format!("Failed to sync {}: {}", package_name, e));
+                return Err(format!("Failed to sync package '{}': {}", package_name, e));
+            }
+        }
+    }
+
+    // Registering rewrites the manifest, so it stays serial
+    for mut discovery in to_register {
+        let package_name = discovery.package_name_full.clone();
+        discovery.prediscovered = std::iter::once(&package_name)
+            .chain(&discovery.dependencies)
+            .filter_map(|name| Some((name.clone(), found.get(name)?.clone())))
+            .collect();
+        match discover_and_register_with_metrics(&installer, &python_path, discovery) {
+            Ok((_, metrics)) => {
+                report.synced += 1;
+                report.metrics.extend(metrics);
                 logger::info(&format!("Successfully synced: {}", package_name));
             }
             Err(e) => {
-                logger::spinner_error(&format!("Failed to sync {}: {}", package_name, e));
+                logger::error(&format!("Failed to sync {}: {}", package_name, e));
                 return Err(format!("Failed to sync package '{}': {}", package_name, e));
             }
         }
@@ -85,6 +187,176 @@ pub fn sync_manifest(_opts: &GlobalOpts) -> Result<(), String> {
         "{}",
         format!("Synced {} package(s) in {}ms", num_packages, elapsed_ms).dimmed()
     );
+    if show_metrics {
+        print!("{}", format_metrics_table(&report.metrics));
+    }
 
-    Ok(())
+    Ok(report)
+}
+
+/// Map `f` over `items` on a thread pool of `jobs` threads, keeping the input order
+///
+/// `None` or 0 uses one thread per core.
+fn run_parallel<T, R, F>(items: &[T], jobs: Option<usize>, f: F) -> Result<Vec<R>, String>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .map_err(|e| format!("Failed to start discovery threads: {}", e))?;
+    Ok(pool.install(|| items.par_iter().map(f).collect()))
+}
+
+/// Render per-package discovery timings as a plain-text table
+fn format_metrics_table(metrics: &BatchDiscoveryMetrics) -> String {
+    let mut table = format!(
+        "{:<30} {:>8} {:>8} {:>8} {:>8} {:>8}\n",
+        "Package", "Plugins", "Read", "Imports", "Extract", "Total"
+    );
+    for m in &metrics.per_package {
+        table.push_str(&format!(
+            "{:<30} {:>8} {:>6}ms {:>6}ms {:>6}ms {:>6}ms\n",
+            m.package,
+            m.plugin_count,
+            m.file_read_ms,
+            m.import_parse_ms,
+            m.plugin_extract_ms,
+            m.total_ms
+        ));
+    }
+    table.push_str(&format!(
+        "{:<30} {:>8} {:>35}ms\n",
+        "Total",
+        metrics.plugin_count(),
+        metrics.total_ms
+    ));
+    table
+}
+
+/// Compare the manifest version of a package with the installed one
+///
+/// Packages without a recorded version (manifests written before versions were
+/// tracked) or whose installed version is unknown are not reported.
+fn version_mismatch(
+    package_name: &str,
+    manifest_version: Option<&str>,
+    installed_version: Option<&str>,
+) -> Option<(String, String, String)> {
+    match (manifest_version, installed_version) {
+        (Some(recorded), Some(installed)) if recorded != installed => Some((
+            package_name.to_string(),
+            recorded.to_string(),
+            installed.to_string(),
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::AstDiscovery;
+    use crate::r2x_ast::DiscoveryMode;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// Write a package with one parser plugin and its dist-info entry point
+    fn write_synthetic_package(site_packages: &Path, index: usize) -> PathBuf {
+        let module = format!("r2x_synthetic{}", index);
+        let package_path = site_packages.join(&module);
+        fs::create_dir_all(&package_path).unwrap();
+        let mut plugins = String::from("from r2x_core import Package, ParserPlugin\n\n");
+        for class in 0..20 {
+            plugins.push_str(&format!(
+                "class Parser{class}:\n    def __init__(self, config, path: str):\n        pass\n\n"
+            ));
+        }
+        plugins.push_str(&format!(
+            "def register_plugin() -> Package:\n    return Package(\n        \
+             name=\"r2x-synthetic{index}\",\n        plugins=[\n            \
+             ParserPlugin(name=\"synthetic{index}-parser\", obj=Parser0),\n        ],\n    )\n"
+        ));
+        fs::write(package_path.join("plugins.py"), plugins).unwrap();
+
+        let dist_info = site_packages.join(format!("{}-0.1.0.dist-info", module));
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(
+            dist_info.join("entry_points.txt"),
+            format!(
+                "[r2x_plugin]\nsynthetic{} = {}.plugins:register_plugin\n",
+                index, module
+            ),
+        )
+        .unwrap();
+        package_path
+    }
+
+    #[test]
+    fn test_parallel_discovery_matches_serial() {
+        let site_packages = tempfile::TempDir::new().unwrap();
+        let packages: Vec<(String, PathBuf)> = (0..10)
+            .map(|i| {
+                (
+                    format!("r2x-synthetic{}", i),
+                    write_synthetic_package(site_packages.path(), i),
+                )
+            })
+            .collect();
+        let discover = |(name, path): &(String, PathBuf)| {
+            AstDiscovery::discover_plugins(path, name, None, None, DiscoveryMode::AstOnly)
+                .map(|(plugins, _, _)| plugins.into_iter().map(|p| p.name).collect::<Vec<_>>())
+                .map_err(|e| e.to_string())
+        };
+
+        let serial: Vec<_> = packages.iter().map(discover).collect();
+        let parallel = run_parallel(&packages, Some(4), discover).unwrap();
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_format_metrics_table() {
+        let mut metrics = BatchDiscoveryMetrics::default();
+        metrics.push(crate::r2x_ast::DiscoveryMetrics {
+            package: "r2x-reeds".to_string(),
+            total_ms: 42,
+            file_read_ms: 1,
+            import_parse_ms: 2,
+            plugin_extract_ms: 30,
+            plugin_count: 3,
+            ..Default::default()
+        });
+
+        let table = format_metrics_table(&metrics);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Package"));
+        assert!(lines[1].starts_with("r2x-reeds"));
+        assert!(lines[1].ends_with("42ms"));
+        assert!(lines[2].starts_with("Total"));
+    }
+
+    #[test]
+    fn test_version_mismatch_detected() {
+        assert_eq!(
+            version_mismatch("r2x-reeds", Some("0.1.0"), Some("0.2.0")),
+            Some((
+                "r2x-reeds".to_string(),
+                "0.1.0".to_string(),
+                "0.2.0".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_version_mismatch_ignores_matching_or_unknown() {
+        assert_eq!(
+            version_mismatch("r2x-reeds", Some("0.1.0"), Some("0.1.0")),
+            None
+        );
+        assert_eq!(version_mismatch("r2x-reeds", None, Some("0.1.0")), None);
+        assert_eq!(version_mismatch("r2x-reeds", Some("0.1.0"), None), None);
+    }
 }
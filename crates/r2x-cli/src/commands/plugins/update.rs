@@ -0,0 +1,197 @@
+use super::install::{package_index_args, run_pip_install};
+use super::{lock_installation, setup_install_config};
+use crate::config_manager::Config;
+use crate::logger;
+use crate::plugins::ast_cache;
+use crate::plugins::discovery::{discover_and_register_entry_points_with_deps, DiscoveryOptions};
+use crate::plugins::install::Installer;
+use crate::plugins::package_resolver::same_package_name;
+use crate::plugins::utils::load_manifest;
+use crate::r2x_ast::DiscoveryMode;
+use crate::r2x_manifest::{Manifest, Package};
+use crate::GlobalOpts;
+use colored::Colorize;
+
+/// Upgrade an installed plugin package and refresh its manifest entries
+pub fn update_plugin(package: &str, opts: &GlobalOpts) -> Result<(), String> {
+    let _lock = lock_installation()?;
+    let (installer, _venv_path, python_path) = setup_install_config()?;
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    update_package(
+        package,
+        &installer,
+        &python_path,
+        &config,
+        opts.discovery_mode(),
+    )
+}
+
+/// Upgrade every explicitly installed plugin package
+///
+/// Editable installs track a local checkout and are skipped.
+pub fn update_all_plugins(opts: &GlobalOpts) -> Result<(), String> {
+    let _lock = lock_installation()?;
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let packages: Vec<String> = manifest
+        .packages
+        .iter()
+        .filter(|pkg| pkg.install_type.as_deref() == Some("explicit"))
+        .filter(|pkg| {
+            if pkg.editable_install {
+                logger::info(&format!("Skipping editable install: {}", pkg.name));
+            }
+            !pkg.editable_install
+        })
+        .map(|pkg| pkg.name.clone())
+        .collect();
+    if packages.is_empty() {
+        logger::warn("No explicitly installed packages to update.");
+        return Ok(());
+    }
+
+    let (installer, _venv_path, python_path) = setup_install_config()?;
+    let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+    let mode = opts.discovery_mode();
+    let mut failed = Vec::new();
+    for package in &packages {
+        if let Err(e) = update_package(package, &installer, &python_path, &config, mode) {
+            logger::error(&e);
+            failed.push(package.as_str());
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to update: {}", failed.join(", ")))
+    }
+}
+
+fn update_package(
+    package: &str,
+    installer: &Installer,
+    python_path: &str,
+    config: &Config,
+    mode: DiscoveryMode,
+) -> Result<(), String> {
+    let before = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let previous = updatable_package(&before, package)?;
+    let package = previous.name.as_str();
+
+    logger::info(&format!("Updating: {}", package));
+    let mut index = package_index_args(config, previous.registry.as_deref(), installer)?;
+    index.args.push("--upgrade".to_string());
+    run_pip_install(installer, python_path, package, false, false, &index)?;
+    ast_cache::invalidate_package(package);
+
+    let mut discovery = DiscoveryOptions::from_installed_package(package, config)
+        .map_err(|e| format!("Failed to read installed package metadata: {}", e))?;
+    discovery.no_cache = true;
+    discovery.mode = mode;
+    discover_and_register_entry_points_with_deps(installer, python_path, discovery)?;
+
+    // Discovery marks the package as explicitly installed; keep how it was installed
+    let mut after = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    if let Some(pkg) = after.packages.iter_mut().find(|pkg| pkg.name == package) {
+        pkg.install_type = previous.install_type.clone();
+        pkg.installed_by = previous.installed_by.clone();
+    }
+    after
+        .save()
+        .map_err(|e| format!("Failed to save manifest: {}", e))?;
+
+    let version = |manifest: &Manifest| {
+        manifest
+            .packages
+            .iter()
+            .find(|pkg| pkg.name == package)
+            .and_then(|pkg| pkg.version.clone())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+    let (old, new) = (version(&before), version(&after));
+    if old == new {
+        logger::success(&format!("{} is up to date ({})", package, new));
+    } else {
+        logger::success(&format!("Updated {}: {} -> {}", package, old, new.green()));
+    }
+    print!("{}", Manifest::diff(&before, &after));
+    Ok(())
+}
+
+/// The manifest entry of `package` when it can be upgraded from its index
+///
+/// Names match after PEP 503 normalization. Editable installs track a local
+/// checkout, which an upgrade would replace with the released package.
+fn updatable_package<'a>(manifest: &'a Manifest, package: &str) -> Result<&'a Package, String> {
+    let Some(found) = manifest
+        .packages
+        .iter()
+        .find(|pkg| same_package_name(&pkg.name, package))
+    else {
+        return Err(format!(
+            "Package '{}' is not installed. Install it with `r2x install {}`",
+            package, package
+        ));
+    };
+    if found.editable_install {
+        let source = found
+            .resolved_source_path
+            .as_deref()
+            .unwrap_or("its checkout");
+        return Err(format!(
+            "'{}' is an editable install of {}; update the checkout and run `r2x sync` instead",
+            found.name, source
+        ));
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, editable: bool) -> Package {
+        Package {
+            name: name.to_string(),
+            version: Some("0.1.0".to_string()),
+            homepage: None,
+            license: None,
+            entry_points_dist_info: String::new(),
+            editable_install: editable,
+            pth_file: None,
+            resolved_source_path: editable.then(|| "/home/dev/r2x-plexos".to_string()),
+            install_source: None,
+            install_type: Some("explicit".to_string()),
+            installed_by: Vec::new(),
+            registry: None,
+            dependencies: Vec::new(),
+            plugins: Vec::new(),
+            decorator_registrations: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_updatable_package_normalizes_names() {
+        let manifest = Manifest {
+            packages: vec![package("r2x-reeds", false)],
+            ..Default::default()
+        };
+        assert_eq!(
+            updatable_package(&manifest, "R2X_Reeds").unwrap().name,
+            "r2x-reeds"
+        );
+        assert!(updatable_package(&manifest, "r2x-plexos")
+            .unwrap_err()
+            .contains("is not installed"));
+    }
+
+    #[test]
+    fn test_updatable_package_refuses_editable_installs() {
+        let manifest = Manifest {
+            packages: vec![package("r2x-plexos", true)],
+            ..Default::default()
+        };
+        let err = updatable_package(&manifest, "r2x-plexos").unwrap_err();
+        assert!(err.contains("editable install of /home/dev/r2x-plexos"));
+    }
+}
@@ -20,7 +20,7 @@ pub fn handle_read(cmd: ReadCommand, _opts: GlobalOpts) -> Result<(), Box<dyn st
 
     // Load configuration
     let mut config = Config::load()?;
-    let venv_path = config.ensure_venv_path()?;
+    let venv_path = config.ensure_venv_path(true)?;
     logger::debug(&format!("Using virtual environment at {}", venv_path));
 
     // Get Python executable path (ensured via ensure_venv_path)
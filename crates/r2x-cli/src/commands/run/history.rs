@@ -0,0 +1,423 @@
+//! History of pipeline runs
+//!
+//! Every executed pipeline appends a [`RunRecord`] to `run_history.json` in the r2x
+//! cache directory. A record keeps the path of the pipeline file, the directory it was
+//! run from and a checksum of the file, but not the configuration itself, which may
+//! hold credentials. `r2x run --replay <id>` runs the file again from that directory
+//! and refuses when it changed since. Only the last [`MAX_RUNS`] runs are kept.
+//!
+//! Runs are appended while holding `run_history.lock` and the file is replaced
+//! atomically. An unreadable file is moved aside rather than silently overwritten.
+
+use super::RunError;
+use crate::config_manager::Config;
+use crate::errors::PipelineError;
+use crate::logger;
+use crate::pipeline_config::PipelineConfig;
+use crate::profiler::Profile;
+use crate::r2x_manifest::atomic::write_atomic;
+use crate::r2x_manifest::integrity;
+use crate::r2x_manifest::lock::{lock_timeout, ManifestLock};
+use chrono::{DateTime, Local, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// File name of the history inside the r2x cache directory
+pub const RUN_HISTORY_FILE: &str = "run_history.json";
+
+/// Number of runs kept; older ones are dropped when a run is recorded
+pub const MAX_RUNS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Succeeded,
+    Failed,
+    NotRun,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepRecord {
+    pub plugin: String,
+    pub status: StepStatus,
+}
+
+/// Options a pipeline ran with, besides its configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunOptions {
+    #[serde(default)]
+    pub parallel: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_results: Option<bool>,
+    #[serde(default)]
+    pub profile: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub id: String,
+    pub pipeline_file: String,
+    pub pipeline_name: String,
+    pub timestamp: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub status: RunStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub steps: Vec<StepRecord>,
+    #[serde(default)]
+    pub options: RunOptions,
+    /// Step timings, for runs made with `--profile`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<Profile>,
+    /// Directory the run was started from, which relative paths are resolved against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<PathBuf>,
+    /// SHA256 of the pipeline file as read for the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config_sha256: Option<String>,
+}
+
+impl RunRecord {
+    /// Record of running `pipeline_name` from `config`, which ended with `result`
+    pub fn new(
+        pipeline_file: &str,
+        pipeline_name: &str,
+        config: &PipelineConfig,
+        options: RunOptions,
+        duration: Duration,
+        result: &Result<(), RunError>,
+    ) -> Self {
+        let timestamp = Utc::now();
+        let pipeline = config
+            .get_pipeline(pipeline_name)
+            .cloned()
+            .unwrap_or_default();
+        let failed_position = match result {
+            Err(RunError::Pipeline(PipelineError::StepFailed { position, .. })) => Some(*position),
+            Err(_) => Some(0),
+            Ok(()) => None,
+        };
+        let steps = pipeline
+            .into_iter()
+            .enumerate()
+            .map(|(index, plugin)| {
+                let status = match failed_position {
+                    None => StepStatus::Succeeded,
+                    Some(position) if index + 1 < position => StepStatus::Succeeded,
+                    Some(position) if index + 1 == position => StepStatus::Failed,
+                    Some(_) => StepStatus::NotRun,
+                };
+                StepRecord { plugin, status }
+            })
+            .collect();
+
+        Self {
+            id: format!(
+                "{}-{:04x}",
+                timestamp.format("%Y%m%d-%H%M%S"),
+                timestamp.timestamp_subsec_micros() & 0xffff
+            ),
+            pipeline_file: pipeline_file.to_string(),
+            pipeline_name: pipeline_name.to_string(),
+            timestamp,
+            duration_ms: duration.as_millis() as u64,
+            status: if result.is_ok() {
+                RunStatus::Succeeded
+            } else {
+                RunStatus::Failed
+            },
+            error: result.as_ref().err().map(|e| e.to_string()),
+            steps,
+            options,
+            profile: None,
+            cwd: std::env::current_dir().ok(),
+            config_sha256: PipelineConfig::read_source(pipeline_file)
+                .ok()
+                .map(|content| integrity::compute_checksum(&content)),
+        }
+    }
+
+    /// Pipeline configuration to replay this run with, read again from its file
+    ///
+    /// Fails when the file changed since the run, or when the run was recorded from
+    /// another directory and the caller did not switch to [`RunRecord::cwd`] first.
+    pub fn load_config(&self) -> Result<PipelineConfig, RunError> {
+        let content = PipelineConfig::read_source(&self.pipeline_file)?;
+        match &self.config_sha256 {
+            Some(sha256) if *sha256 != integrity::compute_checksum(&content) => {
+                return Err(RunError::Config(format!(
+                    "{} changed since run {}; run the pipeline again with `r2x run {} {}`",
+                    self.pipeline_file, self.id, self.pipeline_file, self.pipeline_name
+                )));
+            }
+            Some(_) => {}
+            None => logger::warn(&format!(
+                "Run {} has no checksum of {}; replaying the file as it is now",
+                self.id, self.pipeline_file
+            )),
+        }
+        Ok(serde_yaml::from_str(&content).map_err(PipelineError::from)?)
+    }
+}
+
+/// Recorded runs, oldest first
+#[derive(Debug, Default)]
+pub struct RunHistory {
+    path: PathBuf,
+    pub runs: Vec<RunRecord>,
+}
+
+impl RunHistory {
+    /// Location of the history in the configured r2x cache directory
+    pub fn default_path(config: &Config) -> PathBuf {
+        Path::new(&config.get_cache_path()).join(RUN_HISTORY_FILE)
+    }
+
+    /// Read the history at `path`; a missing file gives an empty history
+    ///
+    /// An unreadable file is moved to `<file>.corrupt`, so the runs it held are not
+    /// overwritten by the next recorded run.
+    pub fn load(path: PathBuf) -> Self {
+        let runs = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| match serde_json::from_str(&content) {
+                Ok(runs) => Some(runs),
+                Err(e) => {
+                    let aside = path.with_extension("json.corrupt");
+                    match fs::rename(&path, &aside) {
+                        Ok(()) => logger::warn(&format!(
+                            "Run history {} is unreadable ({}); moved it to {}",
+                            path.display(),
+                            e,
+                            aside.display()
+                        )),
+                        Err(rename_err) => logger::warn(&format!(
+                            "Run history {} is unreadable ({}) and could not be moved aside: {}",
+                            path.display(),
+                            e,
+                            rename_err
+                        )),
+                    }
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self { path, runs }
+    }
+
+    /// Append `record`, dropping the oldest runs beyond [`MAX_RUNS`]
+    pub fn push(&mut self, record: RunRecord) {
+        self.runs.push(record);
+        if self.runs.len() > MAX_RUNS {
+            let excess = self.runs.len() - MAX_RUNS;
+            self.runs.drain(..excess);
+        }
+    }
+
+    pub fn find(&self, id: &str) -> Option<&RunRecord> {
+        self.runs.iter().find(|run| run.id == id)
+    }
+
+    /// Append `record` to the history at `path` while holding its lock
+    pub fn append(path: PathBuf, record: RunRecord) -> Result<(), String> {
+        let lock_path = path.with_extension("lock");
+        let _lock = ManifestLock::exclusive_file(&lock_path, lock_timeout())
+            .map_err(|e| format!("Failed to lock {}: {}", lock_path.display(), e))?;
+        let mut history = Self::load(path);
+        history.push(record);
+        history.save()
+    }
+
+    /// Write the history back to its file
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(&self.runs)
+            .map_err(|e| format!("Failed to serialize run history: {}", e))?;
+        write_atomic(&self.path, json)
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+}
+
+/// Append a finished run to the history
+///
+/// Best-effort: failures are only logged.
+pub(super) fn record_run(record: RunRecord) {
+    let Ok(config) = Config::load() else {
+        return;
+    };
+    if let Err(e) = RunHistory::append(RunHistory::default_path(&config), record) {
+        logger::warn(&format!("Failed to record run history: {}", e));
+    }
+}
+
+/// Load the recorded run `id`
+pub(super) fn find_run(id: &str) -> Result<RunRecord, RunError> {
+    let config = Config::load().map_err(|e| RunError::Config(e.to_string()))?;
+    RunHistory::load(RunHistory::default_path(&config))
+        .find(id)
+        .cloned()
+        .ok_or_else(|| {
+            RunError::InvalidArgs(format!(
+                "No recorded run '{}'. List runs with `r2x run --history`",
+                id
+            ))
+        })
+}
+
+/// Print the recorded runs, most recent first
+pub(super) fn print_history() -> Result<(), RunError> {
+    let config = Config::load().map_err(|e| RunError::Config(e.to_string()))?;
+    let history = RunHistory::load(RunHistory::default_path(&config));
+    if history.runs.is_empty() {
+        logger::warn("No pipeline runs recorded yet");
+        return Ok(());
+    }
+
+    for run in history.runs.iter().rev() {
+        let status = match run.status {
+            RunStatus::Succeeded => "✔".green(),
+            RunStatus::Failed => "✗".red(),
+        };
+        let time = DateTime::<Local>::from(run.timestamp).format("%Y-%m-%d %H:%M:%S");
+        println!(
+            "{} {}  {}  {} ({})  {}",
+            status,
+            run.id.bold(),
+            time,
+            run.pipeline_name,
+            run.pipeline_file,
+            super::format_duration(Duration::from_millis(run.duration_ms)).dimmed()
+        );
+        if let Some(step) = run.steps.iter().find(|s| s.status == StepStatus::Failed) {
+            println!("    failed at {}", step.plugin.red());
+        }
+    }
+    println!("\nReplay a run with: r2x run --replay <id>");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::python_bridge::BridgeError;
+
+    fn pipeline_config() -> PipelineConfig {
+        serde_yaml::from_str("pipelines:\n  main: [parser, modifier, exporter]\n").unwrap()
+    }
+
+    #[test]
+    fn test_record_step_status() {
+        let config = pipeline_config();
+        let pipeline = config.get_pipeline("main").unwrap().clone();
+        let failed: Result<(), RunError> =
+            Err(
+                PipelineError::step_failed(&pipeline, 1, BridgeError::Python("boom".to_string()))
+                    .into(),
+            );
+        let record = RunRecord::new(
+            "pipeline.yaml",
+            "main",
+            &config,
+            RunOptions::default(),
+            Duration::from_millis(1500),
+            &failed,
+        );
+
+        assert_eq!(record.status, RunStatus::Failed);
+        assert_eq!(record.duration_ms, 1500);
+        let statuses: Vec<StepStatus> = record.steps.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                StepStatus::Succeeded,
+                StepStatus::Failed,
+                StepStatus::NotRun
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_rotation_and_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RUN_HISTORY_FILE);
+        let config = pipeline_config();
+        let mut history = RunHistory::load(path.clone());
+        for index in 0..MAX_RUNS + 5 {
+            let mut record = RunRecord::new(
+                "pipeline.yaml",
+                "main",
+                &config,
+                RunOptions::default(),
+                Duration::ZERO,
+                &Ok(()),
+            );
+            record.id = format!("run-{}", index);
+            history.push(record);
+        }
+        history.save().unwrap();
+
+        let loaded = RunHistory::load(path);
+        assert_eq!(loaded.runs.len(), MAX_RUNS);
+        assert_eq!(loaded.runs[0].id, "run-5");
+        let last = loaded.find(&format!("run-{}", MAX_RUNS + 4)).unwrap();
+        assert_eq!(last.steps.len(), 3);
+        assert!(loaded.find("run-0").is_none());
+    }
+
+    #[test]
+    fn test_record_keeps_checksum_not_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let yaml = dir.path().join("pipeline.yaml");
+        fs::write(
+            &yaml,
+            "pipelines:\n  main: [parser]\nconfig:\n  parser:\n    token: secret\n",
+        )
+        .unwrap();
+        let yaml_path = yaml.to_string_lossy().to_string();
+        let config = PipelineConfig::load(&yaml).unwrap();
+        let record = RunRecord::new(
+            &yaml_path,
+            "main",
+            &config,
+            RunOptions::default(),
+            Duration::ZERO,
+            &Ok(()),
+        );
+
+        assert!(!serde_json::to_string(&record).unwrap().contains("secret"));
+        assert!(record.load_config().is_ok());
+
+        fs::write(&yaml, "pipelines:\n  main: [parser, exporter]\n").unwrap();
+        let err = record.load_config().unwrap_err().to_string();
+        assert!(err.contains("changed since run"));
+    }
+
+    #[test]
+    fn test_corrupt_history_is_moved_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(RUN_HISTORY_FILE);
+        fs::write(&path, "not json").unwrap();
+
+        assert!(RunHistory::load(path.clone()).runs.is_empty());
+        assert!(!path.exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("run_history.json.corrupt")).unwrap(),
+            "not json"
+        );
+    }
+}
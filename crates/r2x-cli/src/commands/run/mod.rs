@@ -1,14 +1,19 @@
 use crate::errors::{BridgeError, ManifestError, PipelineError};
 use crate::logger;
+use crate::plugins::result_cache::PluginResultCache;
+use crate::python_bridge::Bridge;
 use crate::r2x_manifest;
 use crate::GlobalOpts;
 use clap::Parser;
 use pipeline::handle_pipeline_mode;
 use plugin::handle_plugin_command;
-use r2x_manifest::{runtime::RuntimeBindings, PluginKind};
-use r2x_python::plugin_invoker::PluginInvocationTimings;
+use r2x_manifest::{runtime::RuntimeBindings, PluginKind, PluginSpec};
+use r2x_python::plugin_invoker::{
+    InvocationConfig, PluginInvocationResult, PluginInvocationTimings,
+};
 use std::time::Duration;
 
+mod history;
 mod pipeline;
 mod plugin;
 
@@ -43,6 +48,35 @@ impl std::fmt::Display for RunError {
 
 impl std::error::Error for RunError {}
 
+impl RunError {
+    /// Message for end users; bridge errors carry a suggested fix
+    pub fn user_friendly_message(&self) -> String {
+        match self {
+            RunError::Bridge(e) => e.user_friendly_message(),
+            RunError::Pipeline(PipelineError::StepFailed {
+                step_name,
+                position,
+                source,
+            }) => format!(
+                "Step {} ('{}') failed: {}",
+                position,
+                step_name,
+                source.user_friendly_message()
+            ),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Bridge error behind this error, including the one that failed a pipeline step
+    pub fn bridge_error(&self) -> Option<&BridgeError> {
+        match self {
+            RunError::Bridge(e) => Some(e),
+            RunError::Pipeline(PipelineError::StepFailed { source, .. }) => Some(source),
+            _ => None,
+        }
+    }
+}
+
 impl From<ManifestError> for RunError {
     fn from(e: ManifestError) -> Self {
         RunError::Manifest(e)
@@ -77,6 +111,43 @@ pub struct RunCommand {
     pub dry_run: bool,
     #[arg(short = 'o', long, value_name = "FILE")]
     pub output: Option<String>,
+    /// Run steps that read the same upstream system concurrently
+    #[arg(long)]
+    pub parallel: bool,
+    /// Check the pipeline file, then the config and data store of every step, before
+    /// running the pipeline; without a pipeline name only the file is checked
+    #[arg(long)]
+    pub validate: bool,
+    /// Reuse and store results of every parser, modifier and translation, not only
+    /// those marked cacheable; exporters and other plugins that write files still run
+    #[arg(long, conflicts_with = "no_cache_results")]
+    pub cache_results: bool,
+    /// Always run plugins, neither reading nor storing cached results
+    #[arg(long)]
+    pub no_cache_results: bool,
+    /// Time every plugin invocation and print a table of the slowest steps
+    #[arg(long, global = true)]
+    pub profile: bool,
+    /// List recent pipeline runs with their status
+    #[arg(long, conflicts_with = "replay")]
+    pub history: bool,
+    /// Run a recorded pipeline again with the same configuration and options
+    #[arg(long, value_name = "RUN_ID")]
+    pub replay: Option<String>,
+}
+
+impl RunCommand {
+    /// `Some(true)` to cache every plugin without side effects, `Some(false)` to cache
+    /// none, `None` to follow each plugin's `cacheable` flag
+    fn cache_results(&self) -> Option<bool> {
+        if self.cache_results {
+            Some(true)
+        } else if self.no_cache_results {
+            Some(false)
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -89,28 +160,118 @@ pub struct PluginCommand {
     pub plugin_name: Option<String>,
     #[arg(long)]
     pub show_help: bool,
+    /// Check that the plugin resolves and accepts the arguments without running it
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Check the plugin's config and data store without running it
+    #[arg(long)]
+    pub validate: bool,
+    /// Stop the plugin after this many seconds, overriding its declared timeout
+    #[arg(long, value_name = "SECS")]
+    pub timeout: Option<u64>,
+    /// Fail on missing required parameters instead of prompting for them
+    #[arg(long)]
+    pub non_interactive: bool,
     #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
     pub args: Vec<String>,
 }
 
 pub fn handle_run(cmd: RunCommand, opts: GlobalOpts) -> Result<(), RunError> {
+    if let Ok(config) = crate::config_manager::Config::load() {
+        if config.auto_repair.unwrap_or(false) {
+            if let Err(e) = super::manifest::repair_manifest(&config) {
+                logger::warn(&e);
+            }
+        }
+    }
+
+    let cache_results = cmd.cache_results();
     match cmd.command {
-        Some(RunSubcommand::Plugin(plugin_cmd)) => handle_plugin_command(plugin_cmd, &opts),
-        None => {
-            let yaml_path = cmd.yaml_path.unwrap_or_else(|| "pipeline.yaml".to_string());
-            handle_pipeline_mode(
-                yaml_path,
-                cmd.pipeline_name,
-                cmd.list,
-                cmd.print,
-                cmd.dry_run,
-                cmd.output,
-                &opts,
-            )
+        Some(RunSubcommand::Plugin(plugin_cmd)) => {
+            handle_plugin_command(plugin_cmd, cache_results, cmd.profile, &opts)
+        }
+        None => handle_pipeline_mode(cmd, &opts),
+    }
+}
+
+/// Invoke a plugin, going through the result cache when `use_cache` is set
+///
+/// A cached output is returned without starting Python; a fresh non-empty output is
+/// stored for later runs. Cache failures are logged and never fail the run.
+/// Whether results of `plugin` go through the result cache
+///
+/// `--cache-results` only extends to kinds whose whole effect is their output. A cache
+/// hit skips the plugin, so an exporter or upgrader would never write its files.
+pub(super) fn use_result_cache(cache_results: Option<bool>, plugin: &PluginSpec) -> bool {
+    match cache_results {
+        Some(false) => false,
+        Some(true) => {
+            plugin.invocation.cacheable
+                || matches!(
+                    plugin.kind,
+                    PluginKind::Parser | PluginKind::Modifier | PluginKind::Translation
+                )
         }
+        None => plugin.invocation.cacheable,
     }
 }
 
+pub(super) fn invoke_with_result_cache(
+    invocation: &InvocationConfig,
+    plugin_name: &str,
+    package_version: Option<&str>,
+    use_cache: bool,
+) -> Result<PluginInvocationResult, BridgeError> {
+    let cache = use_cache
+        .then(crate::config_manager::Config::load)
+        .and_then(Result::ok)
+        .map(|config| PluginResultCache::new(PluginResultCache::default_dir(&config)));
+    let Some(cache) = cache else {
+        return warm_bridge()?.invoke_plugin(invocation);
+    };
+
+    let key = PluginResultCache::key(
+        plugin_name,
+        package_version,
+        invocation.config_json,
+        invocation.stdin_json,
+    );
+    if let Some(output) = cache.get(&key) {
+        logger::info(&format!("Using cached result for {}", plugin_name));
+        return Ok(PluginInvocationResult {
+            output,
+            timings: None,
+        });
+    }
+
+    let result = warm_bridge()?.invoke_plugin(invocation)?;
+    if !result.output.is_empty() && result.output != "null" {
+        if let Err(e) = cache.insert(&key, plugin_name, &result.output) {
+            logger::warn(&format!("Failed to cache result of {}: {}", plugin_name, e));
+        }
+    }
+    Ok(result)
+}
+
+/// The bridge with its common modules imported
+///
+/// A failed warm-up only costs speed, so it is logged and the plugin imports what it
+/// needs itself.
+pub(super) fn warm_bridge() -> Result<&'static Bridge, BridgeError> {
+    let bridge = Bridge::get()?;
+    if let Err(e) = bridge.warm_up() {
+        logger::warn(&format!("Bridge warm-up failed: {}", e));
+    }
+    Ok(bridge)
+}
+
+/// Timeout from the `plugin-timeout-secs` setting, for plugins that declare none
+pub(super) fn default_timeout_secs() -> Option<u64> {
+    crate::config_manager::Config::load()
+        .ok()
+        .and_then(|config| config.plugin_timeout_secs)
+}
+
 pub(super) fn build_call_target(bindings: &RuntimeBindings) -> Result<String, RunError> {
     let target = match bindings.implementation_type {
         r2x_manifest::ImplementationType::Class => {
@@ -152,3 +313,56 @@ pub(super) fn print_plugin_timing_breakdown(timings: &PluginInvocationTimings) {
         format_duration(timings.serialization)
     ));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::r2x_manifest::{IOContract, ImplementationType, InvocationSpec};
+
+    fn plugin(kind: PluginKind, cacheable: bool) -> PluginSpec {
+        PluginSpec {
+            name: "plugin".to_string(),
+            kind,
+            entry: "package.Plugin".to_string(),
+            invocation: InvocationSpec {
+                implementation: ImplementationType::Class,
+                method: None,
+                constructor: Vec::new(),
+                call: Vec::new(),
+                timeout_secs: None,
+                retry_count: None,
+                cacheable,
+            },
+            io: IOContract {
+                consumes: Vec::new(),
+                produces: Vec::new(),
+            },
+            resources: None,
+            upgrade: None,
+            description: None,
+            tags: Vec::new(),
+            r2x_core_requirement: None,
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_cache_results_skips_side_effecting_kinds() {
+        let parser = plugin(PluginKind::Parser, false);
+        let exporter = plugin(PluginKind::Exporter, false);
+        let upgrader = plugin(PluginKind::Upgrader, false);
+
+        assert!(!use_result_cache(None, &parser));
+        assert!(use_result_cache(Some(true), &parser));
+        assert!(!use_result_cache(Some(true), &exporter));
+        assert!(!use_result_cache(Some(true), &upgrader));
+        assert!(use_result_cache(
+            Some(true),
+            &plugin(PluginKind::Exporter, true)
+        ));
+        assert!(!use_result_cache(
+            Some(false),
+            &plugin(PluginKind::Parser, true)
+        ));
+    }
+}
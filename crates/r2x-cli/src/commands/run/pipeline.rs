@@ -1,46 +1,65 @@
-use super::RunError;
-use crate::errors::PipelineError;
+use super::history::{self, RunOptions, RunRecord};
+use super::{RunCommand, RunError};
+use crate::errors::{BridgeError, PipelineError};
 use crate::logger;
 use crate::package_verification;
 use crate::pipeline_config::PipelineConfig;
+use crate::pipeline_validator::{validate_pipeline_yaml, Severity};
+use crate::plugins::utils::load_manifest;
+use crate::profiler::Profiler;
+use crate::python_bridge::plugin_invoker::{InvocationConfig, PipelineStep};
 use crate::python_bridge::Bridge;
-use crate::r2x_manifest::{self, Manifest};
+use crate::r2x_manifest::runtime::RuntimeBindings;
+use crate::r2x_manifest::{self, IOSlot, Manifest, Package, PluginSpec};
 use crate::GlobalOpts;
 use colored::Colorize;
 use r2x_config::Config;
 use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
-pub(super) fn handle_pipeline_mode(
-    yaml_path: String,
-    pipeline_name: Option<String>,
-    list: bool,
-    print: bool,
-    dry_run: bool,
-    output: Option<String>,
-    opts: &GlobalOpts,
-) -> Result<(), RunError> {
+pub(super) fn handle_pipeline_mode(cmd: RunCommand, opts: &GlobalOpts) -> Result<(), RunError> {
+    if cmd.history {
+        return history::print_history();
+    }
+    if let Some(id) = &cmd.replay {
+        return replay_run(id, opts);
+    }
+
+    let cache_results = cmd.cache_results();
+    let yaml_path = cmd.yaml_path.unwrap_or_else(|| "pipeline.yaml".to_string());
+    if cmd.validate {
+        check_pipeline_file(&yaml_path)?;
+    }
     let config = PipelineConfig::load(&yaml_path)?;
 
-    if list {
+    if cmd.list {
         list_pipelines(&config);
-    } else if print {
-        if let Some(name) = pipeline_name {
+    } else if cmd.print {
+        if let Some(name) = cmd.pipeline_name {
             print_pipeline_config(&config, &name)?;
         } else {
             return Err(RunError::InvalidArgs(
                 "Pipeline name required with --print".to_string(),
             ));
         }
-    } else if let Some(name) = pipeline_name {
-        if dry_run {
-            show_pipeline_flow(&config, &name)?;
+    } else if let Some(name) = cmd.pipeline_name {
+        if cmd.validate && !cmd.dry_run {
+            validate_pipeline(&config, &name)?;
+        }
+        if cmd.dry_run {
+            dry_run_pipeline(&config, &name, cmd.parallel)?;
         } else {
-            run_pipeline(&config, &name, output.as_deref(), opts)?;
+            let options = RunOptions {
+                parallel: cmd.parallel,
+                output: cmd.output,
+                cache_results,
+                profile: cmd.profile,
+            };
+            execute_and_record(&yaml_path, &config, &name, options, opts)?;
         }
-    } else {
+    } else if !cmd.validate {
         return Err(RunError::InvalidArgs(
             "Pipeline name required for execution".to_string(),
         ));
@@ -49,6 +68,105 @@ pub(super) fn handle_pipeline_mode(
     Ok(())
 }
 
+/// Run a pipeline and append the outcome to the run history
+fn execute_and_record(
+    yaml_path: &str,
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    options: RunOptions,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
+    let start = Instant::now();
+    let mut profiler = Profiler::new(options.profile && !options.parallel);
+    let result = if options.parallel {
+        if options.cache_results.is_some() {
+            logger::warn("Result caching is not used with --parallel");
+        }
+        if options.profile {
+            logger::warn("Steps are not profiled with --parallel");
+        }
+        run_pipeline_parallel(config, pipeline_name, options.output.as_deref(), opts)
+    } else {
+        run_pipeline(
+            config,
+            pipeline_name,
+            options.output.as_deref(),
+            options.cache_results,
+            &mut profiler,
+            opts,
+        )
+    };
+    let mut record = RunRecord::new(
+        yaml_path,
+        pipeline_name,
+        config,
+        options,
+        start.elapsed(),
+        &result,
+    );
+    record.profile = profiler.finish();
+    if let Some(profile) = &record.profile {
+        profile.print_table();
+    }
+    history::record_run(record);
+    result
+}
+
+/// Run a recorded pipeline again from the directory it was run from
+///
+/// Relative paths in the command and the pipeline file resolve as they did for the
+/// recorded run. Fails when the pipeline file changed since.
+fn replay_run(id: &str, opts: &GlobalOpts) -> Result<(), RunError> {
+    let record = history::find_run(id)?;
+    if let Some(cwd) = &record.cwd {
+        std::env::set_current_dir(cwd).map_err(|e| {
+            RunError::Config(format!(
+                "Cannot enter {}, where run {} was started: {}",
+                cwd.display(),
+                record.id,
+                e
+            ))
+        })?;
+    }
+    let config = record.load_config()?;
+    logger::step(&format!(
+        "Replaying run {} of '{}' from {}",
+        record.id, record.pipeline_name, record.pipeline_file
+    ));
+    execute_and_record(
+        &record.pipeline_file,
+        &config,
+        &record.pipeline_name,
+        record.options.clone(),
+        opts,
+    )
+}
+
+/// Report structural problems of a pipeline file, failing when any is an error
+fn check_pipeline_file(yaml_path: &str) -> Result<(), RunError> {
+    let issues = validate_pipeline_yaml(Path::new(yaml_path));
+    for issue in &issues {
+        match issue.severity {
+            Severity::Error => logger::error(&issue.to_string()),
+            Severity::Warning => logger::warn(&issue.to_string()),
+        }
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    if errors > 0 {
+        return Err(RunError::Config(format!(
+            "{} has {} error{}",
+            yaml_path,
+            errors,
+            if errors == 1 { "" } else { "s" }
+        )));
+    }
+    logger::success(&format!("{} is well formed", yaml_path));
+    Ok(())
+}
+
 fn list_pipelines(config: &PipelineConfig) {
     let pipelines = config.list_pipelines();
 
@@ -74,62 +192,155 @@ fn print_pipeline_config(config: &PipelineConfig, pipeline_name: &str) -> Result
     Ok(())
 }
 
-fn show_pipeline_flow(config: &PipelineConfig, pipeline_name: &str) -> Result<(), RunError> {
+/// Resolve and check every step of a pipeline, printing the plan instead of running it
+///
+/// The plan is resolved like a real run, with each step reading from the step that would
+/// feed it in the serial or, with `parallel`, the parallel runner. Each step is checked
+/// like `--validate` would, but missing packages are reported rather than installed.
+/// Fails when any step does not pass.
+fn dry_run_pipeline(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    parallel: bool,
+) -> Result<(), RunError> {
     let pipeline = config
         .get_pipeline(pipeline_name)
         .ok_or_else(|| PipelineError::PipelineNotFound(pipeline_name.to_string()))?;
 
-    let manifest = Manifest::load()?;
+    let manifest = load_manifest()?;
+    let plan = PipelinePlan::resolve(config, pipeline, &manifest)?;
+    let steps = plan.pipeline_steps()?;
+    let mut bridge = None;
+    let mut failed = Vec::new();
 
     logger::success(&format!("Pipeline: {}", pipeline_name));
-    println!("\nPipeline flow (--dry-run):");
+    println!("\nPipeline plan (--dry-run):");
+
+    for (index, (plugin_name, step)) in pipeline.iter().zip(&steps).enumerate() {
+        println!("\n  {}. {}", index + 1, plugin_name.bold());
+        println!("     {} {}", "target:".dimmed(), step.target);
+        if let Some(plugin) = &step.plugin {
+            if !plugin.io.consumes.is_empty() {
+                println!(
+                    "     {} {}",
+                    "consumes:".dimmed(),
+                    slot_labels(&plugin.io.consumes)
+                );
+            }
+            if !plugin.io.produces.is_empty() {
+                println!(
+                    "     {} {}",
+                    "produces:".dimmed(),
+                    slot_labels(&plugin.io.produces)
+                );
+            }
+        }
+        let input = plan.input(index, parallel);
+        let input_label = input
+            .map(|input| format!("stdin from step {} ({})", input + 1, pipeline[input]))
+            .unwrap_or_else(|| "none".to_string());
+        println!("     {} {}", "input:".dimmed(), input_label);
+        if let Some(field) = input.and(plan.upstream_field(index)) {
+            println!(
+                "     {} a system read from upstream is passed as `{}`",
+                "overrides:".dimmed(),
+                field
+            );
+        }
+        let config_json = serde_json::from_str::<serde_json::Value>(&step.config_json)
+            .and_then(|value| serde_json::to_string_pretty(&value))
+            .unwrap_or_else(|_| step.config_json.clone());
+        println!("     {}", "config:".dimmed());
+        for line in config_json.lines() {
+            println!("       {}", line);
+        }
 
-    for (index, plugin_name) in pipeline.iter().enumerate() {
-        let (_pkg, plugin) = manifest
-            .packages
-            .iter()
-            .find_map(|pkg| {
-                pkg.plugins
-                    .iter()
-                    .find(|p| p.name == *plugin_name)
-                    .map(|p| (pkg, p))
-            })
-            .ok_or_else(|| RunError::PluginNotFound(plugin_name.to_string()))?;
+        match check_step(&manifest, plugin_name, step, &mut bridge) {
+            Ok(()) => println!("     {} valid", "✔".green()),
+            Err(e) => {
+                println!("     {} {}", "✗".red(), e);
+                failed.push(plugin_name.as_str());
+            }
+        }
+    }
 
-        let bindings = r2x_manifest::build_runtime_bindings(plugin);
-        let has_obj = bindings.implementation_type == r2x_manifest::ImplementationType::Class;
-        let input_marker = if index > 0 { "← stdin" } else { "" };
-        let output_marker = if has_obj { "→ stdout" } else { "" };
+    if failed.is_empty() {
+        println!(
+            "\n{}  No actual execution. Use without --dry-run to run the pipeline.",
+            "✔".green()
+        );
+        Ok(())
+    } else {
+        Err(RunError::Config(format!(
+            "Pipeline '{}' failed validation: {}",
+            pipeline_name,
+            failed.join(", ")
+        )))
+    }
+}
 
-        print!("  {}", plugin_name);
-        if !input_marker.is_empty() {
-            print!("  {}", input_marker.dimmed());
-        }
-        if !output_marker.is_empty() {
-            print!("  {}", output_marker.dimmed());
+/// Validate one dry-run step, starting the bridge on first use
+fn check_step(
+    manifest: &Manifest,
+    plugin_name: &str,
+    step: &PipelineStep,
+    bridge: &mut Option<&'static Bridge>,
+) -> Result<(), RunError> {
+    match package_verification::verify_plugin_packages(manifest, plugin_name) {
+        Ok(package_verification::VerificationResult::Valid) => {}
+        Ok(package_verification::VerificationResult::Missing(missing)) => {
+            return Err(RunError::Verification(format!(
+                "Missing packages: {}",
+                missing.join(", ")
+            )));
         }
-        println!();
+        Err(e) => return Err(RunError::Verification(e.to_string())),
     }
 
-    println!(
-        "\n{}  No actual execution. Use without --dry-run to run the pipeline.",
-        "✔".green()
-    );
+    let bridge = match bridge {
+        Some(bridge) => *bridge,
+        None => *bridge.insert(Bridge::get()?),
+    };
+    let invocation = InvocationConfig::builder()
+        .target(&step.target)
+        .config_json(&step.config_json)
+        .plugin_metadata(step.plugin.as_ref())
+        .build();
+    let report = bridge.validate_plugin(&invocation)?;
+    if report.is_ok() {
+        Ok(())
+    } else {
+        super::plugin::check_validation(plugin_name, &report)
+    }
+}
 
-    Ok(())
+fn slot_labels(slots: &[IOSlot]) -> String {
+    slots
+        .iter()
+        .map(|slot| match slot {
+            IOSlot::Custom(name) => format!("Custom:{}", name),
+            other => serde_json::to_value(other)
+                .ok()
+                .and_then(|value| value.as_str().map(str::to_string))
+                .unwrap_or_else(|| format!("{:?}", other)),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
 }
 
 fn run_pipeline(
     config: &PipelineConfig,
     pipeline_name: &str,
     output_file: Option<&str>,
+    cache_results: Option<bool>,
+    profiler: &mut Profiler,
     opts: &GlobalOpts,
 ) -> Result<(), RunError> {
     let pipeline = config
         .get_pipeline(pipeline_name)
         .ok_or_else(|| PipelineError::PipelineNotFound(pipeline_name.to_string()))?;
 
-    let manifest = Manifest::load()?;
+    let manifest = load_manifest()?;
     let total_steps = pipeline.len();
 
     logger::debug("Verifying packages for pipeline...");
@@ -139,77 +350,23 @@ fn run_pipeline(
     }
     logger::debug("All pipeline packages verified");
 
+    let plan = PipelinePlan::resolve(config, pipeline, &manifest)?;
     let pipeline_start = Instant::now();
-    eprintln!("{}", format!("Running: {}", pipeline_name).cyan().bold());
-
-    // Show log file location to user
-    if let Some(log_path) = logger::get_log_path() {
-        eprintln!("{}", format!("  Log file: {}", log_path.display()).dimmed());
-    }
+    announce_start(pipeline_name);
 
     let mut current_stdin: Option<String> = None;
+    let default_timeout_secs = super::default_timeout_secs();
 
-    let resolved_output_folder = if let Some(folder) = &config.output_folder {
-        Some(
-            config
-                .substitute_string(folder)
-                .map_err(RunError::Pipeline)?,
-        )
-    } else {
-        None
-    };
-
-    let mut current_store_path: Option<String> = None;
-
-    for (idx, plugin_name) in pipeline.iter().enumerate() {
+    for (idx, step) in plan.steps.iter().enumerate() {
+        let plugin_name = step.plugin_name;
         let step_num = idx + 1;
         logger::spinner_start(&format!("  {} [{}/{}]", plugin_name, step_num, total_steps));
         let step_start = Instant::now();
 
-        let (pkg, plugin) = manifest
-            .packages
-            .iter()
-            .find_map(|pkg| {
-                pkg.plugins
-                    .iter()
-                    .find(|p| p.name == *plugin_name)
-                    .map(|p| (pkg, p))
-            })
-            .ok_or_else(|| RunError::PluginNotFound(plugin_name.to_string()))?;
-
-        let bindings = r2x_manifest::build_runtime_bindings(plugin);
-
-        let yaml_config = if config.config.contains_key(plugin_name) {
-            config.get_plugin_config_json(plugin_name)?
-        } else {
-            "{}".to_string()
-        };
-
-        if let Ok(serde_json::Value::Object(map)) =
-            serde_json::from_str::<serde_json::Value>(&yaml_config)
-        {
-            if let Some(store_path) = map.get("store_path").and_then(|value| value.as_str()) {
-                current_store_path = Some(store_path.to_string());
-            }
-        }
-
-        let pipeline_input = current_stdin.as_deref();
-        let stdin_json = pipeline_input;
-
-        let pipeline_overrides =
-            prepare_pipeline_overrides(pipeline_input, &bindings, plugin_name)?;
-
-        let final_config_json = build_plugin_config(
-            &bindings,
-            &pkg.name,
-            &yaml_config,
-            resolved_output_folder.as_deref(),
-            current_store_path.as_deref(),
-            pipeline_overrides.as_deref(),
-        )?;
-
-        let target = super::build_call_target(&bindings)?;
-        let bridge = Bridge::get()?;
+        let (pkg, plugin) = (step.package, step.plugin);
+        let stdin_json = current_stdin.as_deref();
+        let final_config_json = plan.config_json(idx, stdin_json)?;
+        let target = &step.target;
         logger::debug(&format!("Invoking: {}", target));
         logger::debug(&format!("Config: {}", final_config_json));
 
@@ -224,38 +381,53 @@ fn run_pipeline(
             ));
         }
 
-        let invocation_result =
-            match bridge.invoke_plugin(&target, &final_config_json, stdin_json, Some(plugin)) {
-                Ok(inv_result) => {
-                    let elapsed = step_start.elapsed();
-                    logger::spinner_success(&format!(
-                        "{} [{}/{}] ({})",
-                        plugin_name,
-                        step_num,
-                        total_steps,
-                        super::format_duration(elapsed)
-                    ));
-                    if logger::get_verbosity() > 0 {
-                        if let Some(timings) = &inv_result.timings {
-                            super::print_plugin_timing_breakdown(timings);
-                        }
+        let invocation = InvocationConfig::builder()
+            .target(target)
+            .config_json(&final_config_json)
+            .stdin_json(stdin_json)
+            .plugin_metadata(Some(plugin))
+            .default_timeout_secs(default_timeout_secs)
+            .build();
+
+        let use_cache = super::use_result_cache(cache_results, plugin);
+        let invocation_result = match profiler.time(step_num, plugin_name, || {
+            super::invoke_with_result_cache(
+                &invocation,
+                plugin_name,
+                pkg.version.as_deref(),
+                use_cache,
+            )
+        }) {
+            Ok(inv_result) => {
+                let elapsed = step_start.elapsed();
+                logger::spinner_success(&format!(
+                    "{} [{}/{}] ({})",
+                    plugin_name,
+                    step_num,
+                    total_steps,
+                    super::format_duration(elapsed)
+                ));
+                if logger::get_verbosity() > 0 {
+                    if let Some(timings) = &inv_result.timings {
+                        super::print_plugin_timing_breakdown(timings);
                     }
-                    inv_result
                 }
-                Err(e) => {
-                    let elapsed = step_start.elapsed();
-                    logger::spinner_error(&format!(
-                        "{} [{}/{}] ({})",
-                        plugin_name,
-                        step_num,
-                        total_steps,
-                        super::format_duration(elapsed)
-                    ));
-                    // Clear plugin context before returning error
-                    logger::set_current_plugin(None);
-                    return Err(RunError::Bridge(e));
-                }
-            };
+                inv_result
+            }
+            Err(e) => {
+                let elapsed = step_start.elapsed();
+                logger::spinner_error(&format!(
+                    "{} [{}/{}] ({})",
+                    plugin_name,
+                    step_num,
+                    total_steps,
+                    super::format_duration(elapsed)
+                ));
+                // Clear plugin context before returning error
+                logger::set_current_plugin(None);
+                return Err(PipelineError::step_failed(pipeline, idx, e).into());
+            }
+        };
 
         // Clear plugin context after execution
         logger::set_current_plugin(None);
@@ -274,29 +446,311 @@ fn run_pipeline(
         }
     }
 
-    eprintln!(
-        "{}",
-        format!(
-            "Finished in: {}",
-            super::format_duration(pipeline_start.elapsed())
-        )
-        .green()
-        .bold()
-    );
+    announce_finish(pipeline_start);
 
     if let Some(final_output) = current_stdin {
-        if let Some(output_path) = output_file {
-            logger::step(&format!("Writing output to: {}", output_path));
-            std::fs::write(output_path, final_output.as_bytes())
-                .map_err(|e| RunError::Pipeline(PipelineError::Io(e)))?;
-            logger::success(&format!("Output saved to: {}", output_path));
-        } else if opts.suppress_stdout() || opts.no_stdout {
-            logger::debug("Pipeline output suppressed");
+        write_pipeline_output(&final_output, output_file, opts)?;
+    }
+
+    Ok(())
+}
+
+/// Run a pipeline with `Bridge::invoke_pipeline_parallel`
+///
+/// A step that consumes a system reads from the latest earlier step producing one, so
+/// several exporters after the same parser or modifier run concurrently. Any other step
+/// waits for the step before it, which keeps upgraders ahead of parsers. Each step is
+/// configured from its upstream output like in [`run_pipeline`].
+fn run_pipeline_parallel(
+    config: &PipelineConfig,
+    pipeline_name: &str,
+    output_file: Option<&str>,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
+    let pipeline = config
+        .get_pipeline(pipeline_name)
+        .ok_or_else(|| PipelineError::PipelineNotFound(pipeline_name.to_string()))?;
+
+    let manifest = load_manifest()?;
+    for plugin_name in pipeline.iter() {
+        package_verification::verify_and_ensure_plugin(&manifest, plugin_name)
+            .map_err(|e| RunError::Verification(e.to_string()))?;
+    }
+
+    let plan = PipelinePlan::resolve(config, pipeline, &manifest)?;
+    let steps = plan.pipeline_steps()?;
+    let pipeline_start = Instant::now();
+    announce_start(&format!("{} (parallel)", pipeline_name));
+
+    let results = super::warm_bridge()?.invoke_pipeline_parallel(steps, |idx, stdin| {
+        plan.config_json(idx, stdin).map_err(step_error)
+    });
+    let total_steps = pipeline.len();
+    let mut final_output = None;
+    let mut first_error = None;
+    for (idx, (plugin_name, result)) in pipeline.iter().zip(results).enumerate() {
+        match result {
+            Ok(output) => {
+                logger::success(&format!("{} [{}/{}]", plugin_name, idx + 1, total_steps));
+                if !output.is_empty() && output != "null" {
+                    final_output = Some(output);
+                }
+            }
+            Err(e) => {
+                logger::error(&format!(
+                    "{} [{}/{}]: {}",
+                    plugin_name,
+                    idx + 1,
+                    total_steps,
+                    e
+                ));
+                first_error.get_or_insert((idx, e));
+            }
+        }
+    }
+    if let Some((idx, e)) = first_error {
+        return Err(PipelineError::step_failed(pipeline, idx, e).into());
+    }
+
+    announce_finish(pipeline_start);
+    if let Some(final_output) = final_output {
+        write_pipeline_output(&final_output, output_file, opts)?;
+    }
+    Ok(())
+}
+
+/// Check the config and data store of every pipeline step before anything runs
+///
+/// Steps are checked with their pipeline configuration only; values a step would take
+/// from upstream output are not available yet.
+fn validate_pipeline(config: &PipelineConfig, pipeline_name: &str) -> Result<(), RunError> {
+    let pipeline = config
+        .get_pipeline(pipeline_name)
+        .ok_or_else(|| PipelineError::PipelineNotFound(pipeline_name.to_string()))?;
+
+    let manifest = load_manifest()?;
+    for plugin_name in pipeline.iter() {
+        package_verification::verify_and_ensure_plugin(&manifest, plugin_name)
+            .map_err(|e| RunError::Verification(e.to_string()))?;
+    }
+
+    let steps = build_pipeline_steps(config, pipeline, &manifest)?;
+    let bridge = Bridge::get()?;
+    let mut failed = Vec::new();
+    for (plugin_name, step) in pipeline.iter().zip(&steps) {
+        let invocation = InvocationConfig::builder()
+            .target(&step.target)
+            .config_json(&step.config_json)
+            .plugin_metadata(step.plugin.as_ref())
+            .build();
+        let report = bridge.validate_plugin(&invocation)?;
+        if let Err(e) = super::plugin::check_validation(plugin_name, &report) {
+            logger::error(&e.to_string());
+            failed.push(plugin_name.as_str());
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(RunError::Config(format!(
+            "Pipeline '{}' failed validation: {}",
+            pipeline_name,
+            failed.join(", ")
+        )))
+    }
+}
+
+/// Log that `pipeline_name` starts and where its log file is
+fn announce_start(pipeline_name: &str) {
+    logger::info(&format!("Running: {}", pipeline_name));
+    if let Some(log_path) = logger::get_log_path() {
+        logger::info(&format!("Log file: {}", log_path.display()));
+    }
+}
+
+fn announce_finish(pipeline_start: Instant) {
+    logger::success(&format!(
+        "Finished in: {}",
+        super::format_duration(pipeline_start.elapsed())
+    ));
+}
+
+/// Error of a step that could not be configured, as reported by the parallel runner
+fn step_error(error: RunError) -> BridgeError {
+    match error {
+        RunError::Bridge(e) => e,
+        RunError::Pipeline(PipelineError::Io(e)) => BridgeError::Io(e),
+        other => BridgeError::Serialization(other.to_string()),
+    }
+}
+
+/// One pipeline step with everything but its upstream input resolved
+struct StepWiring<'a> {
+    plugin_name: &'a str,
+    package: &'a Package,
+    plugin: &'a PluginSpec,
+    bindings: RuntimeBindings,
+    /// Configuration of the step in the pipeline file, as JSON
+    yaml_config: String,
+    /// `store_path` of this step or of the closest earlier step that sets one
+    store_path: Option<String>,
+    target: String,
+    /// Earlier step whose output this step reads with `--parallel`
+    ///
+    /// A step that consumes a system reads from the latest earlier step producing one;
+    /// any other step reads from the step before it.
+    input: Option<usize>,
+}
+
+/// Steps of a pipeline resolved against the manifest, shared by every way of running
+/// or checking a pipeline
+struct PipelinePlan<'a> {
+    output_folder: Option<String>,
+    steps: Vec<StepWiring<'a>>,
+}
+
+impl<'a> PipelinePlan<'a> {
+    fn resolve(
+        config: &PipelineConfig,
+        pipeline: &'a [String],
+        manifest: &'a Manifest,
+    ) -> Result<Self, RunError> {
+        let output_folder = config
+            .output_folder
+            .as_ref()
+            .map(|folder| config.substitute_string(folder))
+            .transpose()
+            .map_err(RunError::Pipeline)?;
+
+        let mut steps = Vec::with_capacity(pipeline.len());
+        let mut store_path: Option<String> = None;
+        let mut last_system_producer: Option<usize> = None;
+        for (idx, plugin_name) in pipeline.iter().enumerate() {
+            let (package, plugin) = manifest
+                .packages
+                .iter()
+                .find_map(|pkg| {
+                    pkg.plugins
+                        .iter()
+                        .find(|p| p.name == *plugin_name)
+                        .map(|p| (pkg, p))
+                })
+                .ok_or_else(|| RunError::PluginNotFound(plugin_name.to_string()))?;
+            let bindings = r2x_manifest::build_runtime_bindings(plugin);
+
+            let yaml_config = if config.config.contains_key(plugin_name) {
+                config.get_plugin_config_json(plugin_name)?
+            } else {
+                "{}".to_string()
+            };
+            if let Ok(serde_json::Value::Object(map)) =
+                serde_json::from_str::<serde_json::Value>(&yaml_config)
+            {
+                if let Some(path) = map.get("store_path").and_then(|value| value.as_str()) {
+                    store_path = Some(path.to_string());
+                }
+            }
+
+            let input = if plugin.io.consumes.contains(&IOSlot::System) {
+                last_system_producer
+            } else {
+                idx.checked_sub(1)
+            };
+            if plugin.io.produces.contains(&IOSlot::System) {
+                last_system_producer = Some(idx);
+            }
+
+            steps.push(StepWiring {
+                plugin_name,
+                package,
+                plugin,
+                target: super::build_call_target(&bindings)?,
+                bindings,
+                yaml_config,
+                store_path: store_path.clone(),
+                input,
+            });
+        }
+        Ok(Self {
+            output_folder,
+            steps,
+        })
+    }
+
+    /// Configuration of step `idx` when it reads `stdin` from upstream
+    fn config_json(&self, idx: usize, stdin: Option<&str>) -> Result<String, RunError> {
+        let step = &self.steps[idx];
+        let overrides = prepare_pipeline_overrides(stdin, &step.bindings, step.plugin_name)?;
+        build_plugin_config(
+            &step.bindings,
+            &step.package.name,
+            &step.yaml_config,
+            self.output_folder.as_deref(),
+            step.store_path.as_deref(),
+            overrides.as_deref(),
+        )
+    }
+
+    /// Step whose output step `idx` reads: the one before it, or with `parallel` the
+    /// one the parallel runner makes it wait for
+    fn input(&self, idx: usize, parallel: bool) -> Option<usize> {
+        if parallel {
+            self.steps[idx].input
         } else {
-            println!("{}", final_output);
+            idx.checked_sub(1)
         }
     }
 
+    /// Configuration field that receives a system read from upstream by step `idx`
+    fn upstream_field(&self, idx: usize) -> Option<&'static str> {
+        let step = &self.steps[idx];
+        determine_json_path_field(&step.bindings, step.plugin_name)
+    }
+
+    /// Steps for the parallel runner and validation, configured without upstream input
+    fn pipeline_steps(&self) -> Result<Vec<PipelineStep>, RunError> {
+        let default_timeout_secs = super::default_timeout_secs();
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| {
+                Ok(PipelineStep {
+                    target: step.target.clone(),
+                    config_json: self.config_json(idx, None)?,
+                    plugin: Some(step.plugin.clone()),
+                    input: step.input,
+                    default_timeout_secs,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Resolve the configuration, call target and input step of every pipeline step
+fn build_pipeline_steps(
+    config: &PipelineConfig,
+    pipeline: &[String],
+    manifest: &Manifest,
+) -> Result<Vec<PipelineStep>, RunError> {
+    PipelinePlan::resolve(config, pipeline, manifest)?.pipeline_steps()
+}
+
+fn write_pipeline_output(
+    final_output: &str,
+    output_file: Option<&str>,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
+    if let Some(output_path) = output_file {
+        logger::step(&format!("Writing output to: {}", output_path));
+        std::fs::write(output_path, final_output.as_bytes())
+            .map_err(|e| RunError::Pipeline(PipelineError::Io(e)))?;
+        logger::success(&format!("Output saved to: {}", output_path));
+    } else if opts.suppress_stdout() || opts.no_stdout {
+        logger::debug("Pipeline output suppressed");
+    } else {
+        println!("{}", final_output);
+    }
     Ok(())
 }
 
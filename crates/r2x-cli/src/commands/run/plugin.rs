@@ -2,22 +2,38 @@ use super::{PluginCommand, RunError};
 use crate::help::show_plugin_help;
 use crate::logger;
 use crate::package_verification;
+use crate::plugins::utils::load_manifest;
+use crate::profiler::Profiler;
 use crate::python_bridge::Bridge;
-use crate::r2x_manifest::Manifest;
+use crate::r2x_manifest::runtime::RuntimeBindings;
+use crate::r2x_manifest::PluginSpec;
 use crate::GlobalOpts;
 use colored::Colorize;
-use r2x_python::plugin_invoker::PluginInvocationResult;
-use std::collections::BTreeMap;
+use r2x_python::plugin_invoker::{
+    config_param_name, is_store_param, param_env_var, parse_param_value, store_config_keys,
+    store_env_params, DryRunResult, InvocationConfig, PluginInvocationResult, ValidationReport,
+    PARAM_ENV_PREFIX,
+};
+use std::io::{self, IsTerminal, Write};
 use std::time::Instant;
 
-pub(super) fn handle_plugin_command(cmd: PluginCommand, opts: &GlobalOpts) -> Result<(), RunError> {
-    match cmd.plugin_name {
+pub(super) fn handle_plugin_command(
+    cmd: PluginCommand,
+    cache_results: Option<bool>,
+    profile: bool,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
+    match &cmd.plugin_name {
         Some(plugin_name) => {
             if cmd.show_help {
-                show_plugin_help(&plugin_name)
+                show_plugin_help(plugin_name)
                     .map_err(|e| RunError::Config(format!("Help error: {}", e)))?;
+            } else if cmd.dry_run {
+                dry_run_plugin(plugin_name, &cmd.args)?;
+            } else if cmd.validate {
+                validate_plugin(plugin_name, &cmd.args)?;
             } else {
-                run_plugin(&plugin_name, &cmd.args, opts)?;
+                run_plugin(plugin_name, &cmd, cache_results, profile, opts)?;
             }
         }
         None => {
@@ -28,7 +44,7 @@ pub(super) fn handle_plugin_command(cmd: PluginCommand, opts: &GlobalOpts) -> Re
 }
 
 fn list_available_plugins() -> Result<(), RunError> {
-    let manifest = Manifest::load()?;
+    let manifest = load_manifest()?;
 
     if manifest.is_empty() {
         println!("No plugins installed.\n");
@@ -37,20 +53,17 @@ fn list_available_plugins() -> Result<(), RunError> {
     }
 
     println!("Available plugins:\n");
-    let mut packages: BTreeMap<String, Vec<String>> = BTreeMap::new();
-    for pkg in &manifest.packages {
-        let mut names: Vec<String> = pkg.plugins.iter().map(|p| p.name.clone()).collect();
-        names.sort();
-        packages.insert(pkg.name.clone(), names);
-    }
-
-    for (idx, (package_name, plugin_names)) in packages.iter().enumerate() {
+    for (idx, plugin_type) in manifest.plugin_types().into_iter().enumerate() {
         if idx > 0 {
             println!();
         }
-        println!("{}:", package_name.bold());
-        for plugin_name in plugin_names {
-            println!("  - {}", plugin_name);
+        println!("{}:", plugin_type.bold());
+        for (package_name, plugin) in manifest.get_plugins_by_type(plugin_type) {
+            println!(
+                "  - {} {}",
+                plugin.name,
+                format!("({})", package_name).dimmed()
+            );
         }
     }
 
@@ -60,12 +73,18 @@ fn list_available_plugins() -> Result<(), RunError> {
     Ok(())
 }
 
-fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(), RunError> {
+fn run_plugin(
+    plugin_name: &str,
+    cmd: &PluginCommand,
+    cache_results: Option<bool>,
+    profile: bool,
+    opts: &GlobalOpts,
+) -> Result<(), RunError> {
     logger::step(&format!("Running plugin: {}", plugin_name));
-    logger::debug(&format!("Received args: {:?}", args));
+    logger::debug(&format!("Received args: {:?}", cmd.args));
 
-    let manifest = Manifest::load()?;
-    let (_pkg, plugin) = manifest
+    let manifest = load_manifest()?;
+    let (pkg, plugin) = manifest
         .packages
         .iter()
         .find_map(|pkg| {
@@ -81,13 +100,25 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
     package_verification::verify_and_ensure_plugin(&manifest, plugin_name)
         .map_err(|e| RunError::Verification(e.to_string()))?;
 
-    let config_map = parse_plugin_args(args)?;
+    let mut config_map = parse_plugin_args(&cmd.args)?;
+    if cmd.non_interactive {
+        let missing = missing_required_params(&bindings, &config_map);
+        if !missing.is_empty() {
+            let names: Vec<String> = missing.into_iter().map(|(name, _)| name).collect();
+            return Err(RunError::InvalidArgs(format!(
+                "Missing required parameters: {}. Pass them as key=value or set {}<NAME>",
+                names.join(", "),
+                PARAM_ENV_PREFIX
+            )));
+        }
+    } else if io::stdin().is_terminal() {
+        prompt_missing_params(&bindings, &mut config_map)?;
+    }
     let config_json = serde_json::to_string(&config_map)
         .map_err(|e| RunError::Config(format!("Failed to serialize config: {}", e)))?;
 
     let target = super::build_call_target(&bindings)?;
 
-    let bridge = Bridge::get()?;
     logger::debug(&format!("Invoking plugin with target: {}", target));
     logger::debug(&format!("Config: {}", config_json));
 
@@ -103,7 +134,18 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
     }
 
     let start = Instant::now();
-    let invocation_result = bridge.invoke_plugin(&target, &config_json, None, Some(plugin))?;
+    let invocation = InvocationConfig::builder()
+        .target(&target)
+        .config_json(&config_json)
+        .plugin_metadata(Some(plugin))
+        .timeout_secs(cmd.timeout)
+        .default_timeout_secs(super::default_timeout_secs())
+        .build();
+    let use_cache = super::use_result_cache(cache_results, plugin);
+    let mut profiler = Profiler::new(profile);
+    let invocation_result = profiler.time(1, plugin_name, || {
+        super::invoke_with_result_cache(&invocation, plugin_name, pkg.version.as_deref(), use_cache)
+    })?;
     let PluginInvocationResult {
         output: result,
         timings,
@@ -133,9 +175,118 @@ fn run_plugin(plugin_name: &str, args: &[String], opts: &GlobalOpts) -> Result<(
         }
     }
 
+    if let Some(profile) = profiler.finish() {
+        profile.print_table();
+    }
+
     Ok(())
 }
 
+/// Validate a plugin invocation without running it
+///
+/// Prints the [`DryRunResult`] as JSON and fails when problems were found.
+fn dry_run_plugin(plugin_name: &str, args: &[String]) -> Result<(), RunError> {
+    let (plugin, target, config_json) = prepare_check(plugin_name, args)?;
+
+    let bridge = Bridge::get()?;
+    let invocation = InvocationConfig::builder()
+        .target(&target)
+        .config_json(&config_json)
+        .plugin_metadata(Some(&plugin))
+        .dry_run(true)
+        .build();
+    let output = bridge.invoke_plugin(&invocation)?.output;
+    println!("{}", output);
+
+    let result = DryRunResult::from_json(&output)
+        .ok_or_else(|| RunError::Config("Invalid dry-run result".to_string()))?;
+    if result.is_ok() {
+        logger::success(&format!("{} dry run passed", plugin_name));
+        Ok(())
+    } else {
+        Err(RunError::InvalidArgs(format!(
+            "Dry run of '{}' failed: {}",
+            plugin_name,
+            result.parameter_errors.join("; ")
+        )))
+    }
+}
+
+/// Check the config and data store of a plugin without running it
+///
+/// Prints the [`ValidationReport`] as JSON and fails when problems were found.
+fn validate_plugin(plugin_name: &str, args: &[String]) -> Result<(), RunError> {
+    let (plugin, target, config_json) = prepare_check(plugin_name, args)?;
+
+    let invocation = InvocationConfig::builder()
+        .target(&target)
+        .config_json(&config_json)
+        .plugin_metadata(Some(&plugin))
+        .build();
+    let report = Bridge::get()?.validate_plugin(&invocation)?;
+    println!("{}", report.to_json());
+    check_validation(plugin_name, &report)
+}
+
+/// Turn a failed [`ValidationReport`] into an error naming its problems
+pub(super) fn check_validation(
+    plugin_name: &str,
+    report: &ValidationReport,
+) -> Result<(), RunError> {
+    if report.is_ok() {
+        logger::success(&format!("{} validation passed", plugin_name));
+        return Ok(());
+    }
+    let mut problems = report.errors.clone();
+    if !report.missing_files.is_empty() {
+        problems.push(format!(
+            "missing files: {}",
+            report.missing_files.join(", ")
+        ));
+    }
+    Err(RunError::Config(format!(
+        "Validation of '{}' failed: {}",
+        plugin_name,
+        problems.join("; ")
+    )))
+}
+
+/// Plugin, call target and config JSON for checking an invocation without running it
+///
+/// Missing packages are reported instead of reinstalled, so checks have no side effects.
+fn prepare_check(
+    plugin_name: &str,
+    args: &[String],
+) -> Result<(PluginSpec, String, String), RunError> {
+    let manifest = load_manifest()?;
+    let plugin = manifest
+        .packages
+        .iter()
+        .find_map(|pkg| pkg.plugins.iter().find(|p| p.name == plugin_name))
+        .ok_or_else(|| RunError::PluginNotFound(plugin_name.to_string()))?;
+
+    match package_verification::verify_plugin_packages(&manifest, plugin_name) {
+        Ok(package_verification::VerificationResult::Missing(missing)) => {
+            return Err(RunError::Verification(format!(
+                "Missing packages: {}",
+                missing.join(", ")
+            )));
+        }
+        Ok(package_verification::VerificationResult::Valid) => {}
+        Err(e) => return Err(RunError::Verification(e.to_string())),
+    }
+
+    let config_map = parse_plugin_args(args)?;
+    let config_json = serde_json::to_string(&config_map)
+        .map_err(|e| RunError::Config(format!("Failed to serialize config: {}", e)))?;
+    let target = super::build_call_target(&r2x_manifest::build_runtime_bindings(plugin))?;
+    Ok((plugin.clone(), target, config_json))
+}
+
+/// Parse `key=value` plugin arguments into a config object
+///
+/// A value of `@path` is read from the JSON file at `path`, and a bare `@path`
+/// argument merges the JSON object in that file into the config.
 fn parse_plugin_args(args: &[String]) -> Result<serde_json::Value, RunError> {
     let mut config = serde_json::json!({});
 
@@ -144,11 +295,21 @@ fn parse_plugin_args(args: &[String]) -> Result<serde_json::Value, RunError> {
             let key = &arg[..eq_pos];
             let value_str = &arg[eq_pos + 1..];
             let python_key = key.replace('-', "_");
-            let value = parse_json_value(value_str)?;
-            config[python_key] = value;
+            config[python_key] = parse_arg_value(value_str)?;
+        } else if let Some(path) = arg.strip_prefix('@') {
+            let serde_json::Value::Object(values) = read_json_file(path)? else {
+                return Err(RunError::InvalidArgs(format!(
+                    "'{}' must contain a JSON object to be merged into the config. \
+Use key=@{} to pass it as a single parameter",
+                    path, path
+                )));
+            };
+            for (key, value) in values {
+                config[key] = value;
+            }
         } else {
             return Err(RunError::InvalidArgs(format!(
-                "Invalid argument format: '{}'. Expected key=value",
+                "Invalid argument format: '{}'. Expected key=value or @file.json",
                 arg
             )));
         }
@@ -157,24 +318,203 @@ fn parse_plugin_args(args: &[String]) -> Result<serde_json::Value, RunError> {
     Ok(config)
 }
 
-fn parse_json_value(value_str: &str) -> Result<serde_json::Value, RunError> {
-    if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(value_str) {
-        return Ok(json_val);
+/// Value of a `key=value` argument, read from a JSON file when it starts with `@`
+fn parse_arg_value(value_str: &str) -> Result<serde_json::Value, RunError> {
+    match value_str.strip_prefix('@') {
+        Some(path) => read_json_file(path),
+        None => Ok(parse_param_value(value_str)),
     }
+}
 
-    match value_str.to_lowercase().as_str() {
-        "true" => return Ok(serde_json::json!(true)),
-        "false" => return Ok(serde_json::json!(false)),
-        _ => {}
+/// Ask on stderr for each required parameter missing from `config`, so the prompts
+/// stay out of piped plugin output
+///
+/// An empty answer leaves the parameter unset, so the plugin reports it as usual.
+fn prompt_missing_params(
+    bindings: &RuntimeBindings,
+    config: &mut serde_json::Value,
+) -> Result<(), RunError> {
+    for (name, annotation) in missing_required_params(bindings, config) {
+        let annotation = annotation
+            .map(|annotation| format!(" ({})", annotation))
+            .unwrap_or_default();
+        eprint!(
+            "{} {}{} {} ",
+            "?".bold().cyan(),
+            name,
+            annotation.dimmed(),
+            "›".dimmed()
+        );
+        io::stderr()
+            .flush()
+            .map_err(|e| RunError::Config(format!("Failed to flush stderr: {}", e)))?;
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| RunError::InvalidArgs(format!("Failed to read '{}': {}", name, e)))?;
+        let input = input.trim();
+        if !input.is_empty() {
+            config[name] = parse_arg_value(input)?;
+        }
     }
+    Ok(())
+}
+
+/// Required call and config-class parameters absent from `config`, with their
+/// annotations
+///
+/// Parameters are resolved as the bridge resolves them: the config object and stdin
+/// are skipped, the data store is asked for as `store_path`, and anything set through
+/// `R2X_PLUGIN_<NAME>` counts as present.
+fn missing_required_params(
+    bindings: &RuntimeBindings,
+    config: &serde_json::Value,
+) -> Vec<(String, Option<String>)> {
+    let is_set =
+        |name: &str| config.get(name).is_some() || std::env::var_os(param_env_var(name)).is_some();
+    let config_param = config_param_name(bindings);
+    let mut missing: Vec<(String, Option<String>)> = Vec::new();
 
-    if let Ok(num) = value_str.parse::<i64>() {
-        return Ok(serde_json::json!(num));
+    for param in bindings.entry_parameters.iter().filter(|p| p.required) {
+        if config_param == Some(param.name.as_str())
+            || param.name == "stdin"
+            || param.name == "system"
+        {
+            continue;
+        }
+        if is_store_param(param) {
+            let from_config = store_config_keys(&param.name)
+                .iter()
+                .any(|key| config.get(key).is_some());
+            let from_env = store_env_params(&param.name)
+                .iter()
+                .any(|name| std::env::var_os(param_env_var(name)).is_some());
+            if !from_config && !from_env {
+                missing.push(("store_path".to_string(), Some("path".to_string())));
+            }
+            continue;
+        }
+        if !is_set(&param.name) {
+            missing.push((param.name.clone(), param.annotation.clone()));
+        }
     }
 
-    if let Ok(num) = value_str.parse::<f64>() {
-        return Ok(serde_json::json!(num));
+    let fields = bindings.config.iter().flat_map(|spec| &spec.fields);
+    for field in fields.filter(|field| field.required) {
+        if !is_set(&field.name) && !missing.iter().any(|(name, _)| name == &field.name) {
+            missing.push((field.name.clone(), field.annotation.clone()));
+        }
     }
+    missing
+}
 
-    Ok(serde_json::json!(value_str))
+fn read_json_file(path: &str) -> Result<serde_json::Value, RunError> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| RunError::InvalidArgs(format!("Failed to read '{}': {}", path, e)))?;
+    serde_json::from_str(&content).map_err(|e| {
+        RunError::InvalidArgs(format!(
+            "'{}' is not valid JSON (line {}, column {}): {}",
+            path,
+            e.line(),
+            e.column(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_plugin_args_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let object = dir.path().join("config.json");
+        let array = dir.path().join("scenarios.json");
+        let broken = dir.path().join("broken.json");
+        std::fs::write(&object, r#"{"solve_year": 2030, "case_name": "base"}"#).unwrap();
+        std::fs::write(&array, r#"["low", "high"]"#).unwrap();
+        std::fs::write(&broken, "{\"solve_year\": ").unwrap();
+
+        let config = parse_plugin_args(&args(&[
+            &format!("@{}", object.display()),
+            "case-name=override",
+            &format!("scenarios=@{}", array.display()),
+        ]))
+        .unwrap();
+        assert_eq!(
+            config,
+            json!({"solve_year": 2030, "case_name": "override", "scenarios": ["low", "high"]})
+        );
+
+        let err = parse_plugin_args(&args(&[&format!("@{}", array.display())])).unwrap_err();
+        assert!(err.to_string().contains("must contain a JSON object"));
+        let err =
+            parse_plugin_args(&args(&[&format!("config=@{}", broken.display())])).unwrap_err();
+        assert!(err.to_string().contains("is not valid JSON"));
+        let err = parse_plugin_args(&args(&["x=@/nonexistent/config.json"])).unwrap_err();
+        assert!(err.to_string().contains("Failed to read"));
+    }
+
+    #[test]
+    fn test_missing_required_params() {
+        use crate::r2x_manifest::{
+            ArgumentSpec, ConfigField, ConfigSpec, ImplementationType, PluginKind,
+        };
+        let arg = |name: &str, annotation: &str, required: bool| ArgumentSpec {
+            name: name.to_string(),
+            annotation: Some(annotation.to_string()),
+            default: None,
+            required,
+            type_kind: None,
+        };
+        let field = |name: &str, required: bool| ConfigField {
+            name: name.to_string(),
+            annotation: Some("int".to_string()),
+            default: None,
+            required,
+            constraints: None,
+        };
+        let bindings = RuntimeBindings {
+            entry_module: "r2x_test.parser".to_string(),
+            entry_name: "TestParser".to_string(),
+            implementation_type: ImplementationType::Class,
+            plugin_kind: PluginKind::Parser,
+            config: Some(ConfigSpec {
+                module: "r2x_test.config".to_string(),
+                name: "TestConfig".to_string(),
+                fields: vec![field("solve_year", true), field("weather_year", false)],
+                pydantic_version: Some(2),
+            }),
+            call_method: Some("build_system".to_string()),
+            requires_store: true,
+            entry_parameters: vec![
+                arg("config", "TestConfig", true),
+                arg("data_store", "DataStore", true),
+                arg("case_name", "str", true),
+                arg("name", "str", false),
+            ],
+            custom_inputs: Vec::new(),
+        };
+
+        let names = |config: serde_json::Value| -> Vec<String> {
+            missing_required_params(&bindings, &config)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        };
+        assert_eq!(
+            names(json!({})),
+            vec!["store_path", "case_name", "solve_year"]
+        );
+        assert_eq!(
+            names(json!({"store_path": "/data", "case_name": "base", "solve_year": 2030})),
+            Vec::<String>::new()
+        );
+    }
 }
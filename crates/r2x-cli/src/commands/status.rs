@@ -0,0 +1,174 @@
+//! `r2x status`: summary of the r2x environment
+//!
+//! Gathers what is usually asked for first when debugging a user's setup: versions,
+//! paths, configuration and what the plugin manifest holds.
+
+use crate::config_manager::Config;
+use crate::package_verification::stale_packages;
+use crate::plugins::utils::load_manifest;
+use crate::r2x_manifest::Manifest;
+use crate::GlobalOpts;
+use chrono::{DateTime, Local, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Serialize)]
+pub struct StatusReport {
+    pub version: &'static str,
+    pub config_path: PathBuf,
+    /// Values set in the config file, by key
+    pub config: BTreeMap<String, String>,
+    pub venv_path: String,
+    /// `python --version` of the venv, when it runs
+    pub python_version: Option<String>,
+    /// `uv --version`, when uv is configured and runs
+    pub uv_version: Option<String>,
+    pub manifest_path: PathBuf,
+    pub packages: usize,
+    pub plugins: usize,
+    pub plugins_by_type: BTreeMap<String, usize>,
+    /// Modification time of the manifest
+    pub last_sync: Option<DateTime<Utc>>,
+    /// Packages not installed at the version recorded in the manifest; `None` when
+    /// the venv could not be inspected
+    pub stale_packages: Option<Vec<String>>,
+}
+
+impl StatusReport {
+    pub fn collect() -> Result<Self, String> {
+        let config = Config::load().map_err(|e| format!("Failed to load config: {}", e))?;
+        let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
+        let manifest_path = Manifest::path();
+        let venv_path = config.get_venv_path();
+
+        let plugins_by_type = manifest
+            .plugin_types()
+            .into_iter()
+            .map(|kind| (kind.to_string(), manifest.get_plugins_by_type(kind).len()))
+            .collect();
+        let last_sync = std::fs::metadata(&manifest_path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .map(DateTime::<Utc>::from);
+
+        Ok(Self {
+            version: env!("CARGO_PKG_VERSION"),
+            config_path: Config::path(),
+            config: config
+                .values_iter()
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+            python_version: command_version(Path::new(&config.get_venv_python_path())),
+            uv_version: config
+                .uv_path
+                .as_deref()
+                .and_then(|uv| command_version(Path::new(uv))),
+            stale_packages: stale_packages(&manifest, &PathBuf::from(&venv_path)).ok(),
+            venv_path,
+            manifest_path,
+            packages: manifest.packages.len(),
+            plugins: manifest.total_plugin_count(),
+            plugins_by_type,
+            last_sync,
+        })
+    }
+
+    fn print(&self) {
+        let unknown = || "unknown".dimmed().to_string();
+        println!("{}", "r2x status".bold().green());
+        println!("  {}: {}", "version".cyan(), self.version);
+        println!("  {}: {}", "config file".cyan(), self.config_path.display());
+        for (key, value) in &self.config {
+            println!("    {} = {}", key, value);
+        }
+        println!("  {}: {}", "venv".cyan(), self.venv_path);
+        println!(
+            "  {}: {}",
+            "python".cyan(),
+            self.python_version.clone().unwrap_or_else(unknown)
+        );
+        println!(
+            "  {}: {}",
+            "uv".cyan(),
+            self.uv_version.clone().unwrap_or_else(unknown)
+        );
+        println!("  {}: {}", "manifest".cyan(), self.manifest_path.display());
+        println!(
+            "  {}: {} packages, {} plugins",
+            "installed".cyan(),
+            self.packages,
+            self.plugins
+        );
+        for (kind, count) in &self.plugins_by_type {
+            println!("    {}: {}", kind, count);
+        }
+        println!(
+            "  {}: {}",
+            "last sync".cyan(),
+            self.last_sync
+                .map(|time| {
+                    DateTime::<Local>::from(time)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "never".dimmed().to_string())
+        );
+        let stale = match &self.stale_packages {
+            Some(stale) if stale.is_empty() => "none".to_string(),
+            Some(stale) => format!("{} (run `r2x sync`)", stale.join(", "))
+                .yellow()
+                .to_string(),
+            None => unknown(),
+        };
+        println!("  {}: {}", "stale packages".cyan(), stale);
+    }
+}
+
+pub fn handle_status(json: bool, _opts: GlobalOpts) -> Result<(), String> {
+    let report = StatusReport::collect()?;
+    if json {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize status: {}", e))?;
+        println!("{}", json);
+    } else {
+        report.print();
+    }
+    Ok(())
+}
+
+/// First line printed by `<program> --version`, from stdout or stderr
+fn command_version(program: &Path) -> Option<String> {
+    let output = Command::new(program)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let raw = if output.stdout.is_empty() {
+        output.stderr
+    } else {
+        output.stdout
+    };
+    String::from_utf8_lossy(&raw)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_version() {
+        assert_eq!(command_version(Path::new("/nonexistent/uv")), None);
+        if let Some(version) = command_version(Path::new("python3")) {
+            assert!(version.starts_with("Python 3."));
+        }
+    }
+}
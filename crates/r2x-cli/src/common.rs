@@ -30,6 +30,28 @@ pub struct GlobalOpts {
         help = "Disable logging stdout to file (useful with --log-python to avoid large system objects in logs)"
     )]
     pub no_stdout: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Skip checking that the venv Python matches the configured python-version"
+    )]
+    pub ignore_python_version: bool,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Load plugin packages in Python when AST discovery finds no plugins"
+    )]
+    pub fallback_to_runtime: bool,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        help = "Console log format: human or json (default: $R2X_LOG_FORMAT, then human)"
+    )]
+    pub log_format: Option<crate::logger::LogFormat>,
 }
 
 impl GlobalOpts {
@@ -45,6 +67,23 @@ impl GlobalOpts {
         }
     }
 
+    /// Console log format from `--log-format`, then `R2X_LOG_FORMAT`
+    pub fn log_format(&self) -> crate::logger::LogFormat {
+        self.log_format
+            .or_else(crate::logger::LogFormat::from_env)
+            .unwrap_or_default()
+    }
+
+    /// Discovery mode from `--fallback-to-runtime`; `main` also sets the flag from
+    /// the `fallback-to-runtime` config key
+    pub fn discovery_mode(&self) -> crate::r2x_ast::DiscoveryMode {
+        if self.fallback_to_runtime {
+            crate::r2x_ast::DiscoveryMode::AstWithRuntimeFallback
+        } else {
+            crate::r2x_ast::DiscoveryMode::AstOnly
+        }
+    }
+
     /// Returns true when output (plugin stdout) should be fully suppressed
     pub fn suppress_stdout(&self) -> bool {
         self.quiet >= 2
@@ -26,6 +26,28 @@ pub enum PipelineError {
 
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
+
+    #[error("Circular dependency: {}", cycle.join(" -> "))]
+    CircularDependency { cycle: Vec<String> },
+
+    #[error("Step {position} ('{step_name}') failed: {source}")]
+    StepFailed {
+        step_name: String,
+        /// 1-based position of the step in the pipeline
+        position: usize,
+        source: Box<BridgeError>,
+    },
+}
+
+impl PipelineError {
+    /// Wrap the error of the step at `index` (0-based) of `pipeline`
+    pub fn step_failed(pipeline: &[String], index: usize, source: BridgeError) -> Self {
+        PipelineError::StepFailed {
+            step_name: pipeline.get(index).cloned().unwrap_or_default(),
+            position: index + 1,
+            source: Box::new(source),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -40,4 +62,28 @@ mod tests {
             "Pipeline 'test-pipeline' not found in YAML"
         );
     }
+
+    #[test]
+    fn test_step_failed() {
+        let yaml = r#"
+pipelines:
+  reeds-to-plexos:
+    - reeds-parser
+    - plexos-exporter
+"#;
+        let config: crate::pipeline_config::PipelineConfig = serde_yaml::from_str(yaml).unwrap();
+        let pipeline = config.get_pipeline("reeds-to-plexos").unwrap();
+
+        let err = PipelineError::step_failed(
+            pipeline,
+            1,
+            BridgeError::Python("KeyError: 'generators'".to_string()),
+        );
+        assert_eq!(
+            err.to_string(),
+            "Step 2 ('plexos-exporter') failed: Python error: KeyError: 'generators'"
+        );
+        let source = std::error::Error::source(&err).unwrap();
+        assert_eq!(source.to_string(), "Python error: KeyError: 'generators'");
+    }
 }
@@ -1,10 +1,10 @@
 use crate::logger;
-use crate::r2x_manifest::Manifest;
+use crate::plugins::utils::load_manifest;
 use colored::Colorize;
 
 /// Show help for the run command when invoked with no arguments
 pub fn show_run_help() -> Result<(), String> {
-    let manifest = Manifest::load().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
 
     println!();
     println!("{}", "No pipeline or plugin specified.".bold());
@@ -13,14 +13,13 @@ pub fn show_run_help() -> Result<(), String> {
     // Show installed plugins
     if !manifest.is_empty() {
         println!("{}", "Installed plugins:".bold());
-        for pkg in &manifest.packages {
-            for plugin in &pkg.plugins {
-                let plugin_type = format!("{:?}", plugin.kind);
+        for plugin_type in manifest.plugin_types() {
+            println!("  {}:", plugin_type.bold());
+            for (package, plugin) in manifest.get_plugins_by_type(plugin_type) {
                 println!(
-                    "  {} {} - from package {}",
+                    "    {} - from package {}",
                     plugin.name.cyan(),
-                    format!("({})", plugin_type).dimmed(),
-                    pkg.name.dimmed()
+                    package.dimmed()
                 );
             }
         }
@@ -55,9 +54,9 @@ pub fn show_run_help() -> Result<(), String> {
 
 /// Show detailed help for a specific plugin
 pub fn show_plugin_help(plugin_name: &str) -> Result<(), String> {
-    let manifest = Manifest::load().map_err(|e| format!("Failed to load manifest: {}", e))?;
+    let manifest = load_manifest().map_err(|e| format!("Failed to load manifest: {}", e))?;
 
-    let (_pkg, plugin) = manifest
+    let (pkg, plugin) = manifest
         .packages
         .iter()
         .find_map(|pkg| {
@@ -72,7 +71,13 @@ pub fn show_plugin_help(plugin_name: &str) -> Result<(), String> {
 
     logger::step(&format!("Plugin: {}", plugin_name));
 
+    if let Some(description) = &plugin.description {
+        println!("\n{}", description);
+    }
     println!("\nType: {:?}", plugin.kind);
+    if let Some(homepage) = pkg.homepage_url() {
+        println!("Homepage: {}", homepage);
+    }
 
     let needs_store = bindings.requires_store;
 
@@ -111,6 +116,21 @@ pub fn show_plugin_help(plugin_name: &str) -> Result<(), String> {
             );
         }
     }
+    let has_required_fields = bindings
+        .config
+        .iter()
+        .flat_map(|config| &config.fields)
+        .any(|field| field.required);
+    if has_required_fields || bindings.entry_parameters.iter().any(|param| param.required) {
+        println!(
+            "\n  Required parameters missing from the command line are read from {}<NAME>,",
+            crate::python_bridge::plugin_invoker::PARAM_ENV_PREFIX
+        );
+        println!(
+            "  e.g. {}",
+            crate::python_bridge::plugin_invoker::param_env_var("store_path")
+        );
+    }
 
     // Show config parameters
     if let Some(config) = &bindings.config {
@@ -129,9 +149,16 @@ pub fn show_plugin_help(plugin_name: &str) -> Result<(), String> {
                     .as_deref()
                     .map(|d| format!(" (default: {})", d))
                     .unwrap_or_default();
+                let constraints = field
+                    .constraints
+                    .as_ref()
+                    .map(format_constraints)
+                    .filter(|text| !text.is_empty())
+                    .map(|text| format!(" [{}]", text))
+                    .unwrap_or_default();
                 println!(
-                    "  --{:<20} {:<15} {}{}",
-                    field.name, annotation, required, default
+                    "  --{:<20} {:<15} {}{}{}",
+                    field.name, annotation, required, default, constraints
                 );
             }
         }
@@ -155,8 +182,38 @@ pub fn show_plugin_help(plugin_name: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Describe Pydantic field bounds, e.g. `>= 0, <= 100`
+fn format_constraints(constraints: &serde_json::Value) -> String {
+    let Some(map) = constraints.as_object() else {
+        return String::new();
+    };
+    [
+        ("ge", ">="),
+        ("gt", ">"),
+        ("le", "<="),
+        ("lt", "<"),
+        ("min_length", "min length"),
+        ("max_length", "max length"),
+        ("regex", "matches"),
+        ("pattern", "matches"),
+    ]
+    .iter()
+    .filter_map(|(key, label)| {
+        let value = map.get(*key)?;
+        let value = value
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| value.to_string());
+        Some(format!("{} {}", label, value))
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
 #[cfg(test)]
 mod tests {
+    use super::format_constraints;
+
     #[test]
     fn test_show_run_help() {
         // Test run help display
@@ -166,4 +223,14 @@ mod tests {
     fn test_show_plugin_help() {
         // Test plugin help display
     }
+
+    #[test]
+    fn test_format_constraints() {
+        let constraints = serde_json::json!({"le": 100, "ge": 0, "pattern": "^[a-z]+$"});
+        assert_eq!(
+            format_constraints(&constraints),
+            ">= 0, <= 100, matches ^[a-z]+$"
+        );
+        assert_eq!(format_constraints(&serde_json::json!(null)), "");
+    }
 }
@@ -8,8 +8,10 @@ pub mod errors;
 pub mod help;
 pub mod package_verification;
 pub mod pipeline_config;
+pub mod pipeline_validator;
 pub mod plugin_manifest;
 pub mod plugins;
+pub mod profiler;
 
 // Re-export dedicated crates so internal modules can continue using the previous paths.
 pub use r2x_ast;
@@ -1,11 +1,19 @@
 use clap::{Parser, Subcommand};
+use miette::Diagnostic;
 use r2x::{
     commands::{
+        cache::{self, CacheAction},
         config::{self, ConfigAction},
-        init, plugins, read, run,
+        doctor,
+        export::{self, ExportFormat},
+        init,
+        manifest::{self, ManifestAction},
+        new::{self, NewAction},
+        plugins, read, run, status,
     },
     config_manager, logger, GlobalOpts,
 };
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "r2x")]
@@ -36,16 +44,41 @@ enum Commands {
         plugin: Option<String>,
         /// Optional module/function name to filter by (e.g., break_gens)
         module: Option<String>,
+        /// Only show plugins of this type (parser, exporter, modifier, upgrader, ...)
+        #[arg(long = "type", value_name = "TYPE")]
+        kind: Option<String>,
+        /// Only show plugins declaring this tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Search plugin metadata; use `field:value` to match one field
+        /// (name, description, type, package, module)
+        #[arg(long, value_name = "QUERY")]
+        search: Option<String>,
+        /// Only show plugin packages with a newer release on the package index
+        #[arg(long, conflicts_with = "search")]
+        outdated: bool,
+        /// Show the install-time dependency graph of plugin packages
+        #[arg(long, conflicts_with_all = ["search", "outdated"])]
+        graph: bool,
+        /// Output format of --graph
+        #[arg(long, value_enum, default_value = "tree", requires = "graph")]
+        format: plugins::list::GraphFormat,
     },
     /// Install a plugin
     Install {
         plugin: Option<String>,
+        /// Install every package listed in a requirements file, one spec per line
+        #[arg(short, long, value_name = "FILE", conflicts_with = "plugin")]
+        requirements: Option<PathBuf>,
         /// Install in editable mode (-e)
         #[arg(short, long)]
         editable: bool,
         /// Skip metadata cache and force rebuild
         #[arg(long)]
         no_cache: bool,
+        /// Show the packages and plugins that would be installed, without installing
+        #[arg(long, conflicts_with = "requirements", requires = "plugin")]
+        dry_run: bool,
         /// Git host (default: github.com). Use with org/repo format or full URLs.
         #[arg(long)]
         host: Option<String>,
@@ -61,15 +94,57 @@ enum Commands {
     },
     /// Remove a plugin
     Remove { plugin: String },
+    /// Upgrade installed plugin packages and refresh their plugins
+    Update {
+        /// Package to upgrade (e.g., r2x-reeds)
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        package: Option<String>,
+        /// Upgrade every explicitly installed package
+        #[arg(long)]
+        all: bool,
+    },
     /// Sync plugin manifest (re-run plugin discovery for all installed packages)
     /// Useful when developing plugins locally with -e to refresh the plugin registry
-    Sync,
+    Sync {
+        /// Print per-package discovery times
+        #[arg(long)]
+        metrics: bool,
+        /// Number of packages to discover in parallel (default: one per CPU core)
+        #[arg(short, long, value_name = "N")]
+        jobs: Option<usize>,
+        /// Re-parse every package instead of reusing cached discovery results
+        #[arg(long)]
+        no_cache: bool,
+    },
     /// Clean the plugin manifest (removes all installed plugins)
     Clean {
         /// Skip confirmation prompt
         #[arg(short = 'y', long)]
         yes: bool,
     },
+    /// Inspect and clear cached discovery and plugin results
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Inspect and maintain the plugin manifest
+    Manifest {
+        #[command(subcommand)]
+        action: ManifestAction,
+    },
+    /// Export the installed plugin setup
+    Export {
+        /// File to write; prints to stdout when omitted
+        output: Option<PathBuf>,
+        /// Output format; inferred from the output extension, bash script otherwise
+        #[arg(long, value_enum)]
+        format: Option<ExportFormat>,
+    },
+    /// Create new r2x projects, e.g. a plugin package skeleton
+    New {
+        #[command(subcommand)]
+        action: NewAction,
+    },
     /// Initialize a new pipeline file
     Init {
         /// Optional filename for the pipeline (default: pipeline.yaml)
@@ -83,6 +158,14 @@ enum Commands {
         /// Path to JSON file to read. If not provided, reads from stdin
         file: Option<std::path::PathBuf>,
     },
+    /// Check uv, the Python environment, r2x-core and the plugin manifest
+    Doctor,
+    /// Show versions, paths, configuration and installed plugins at a glance
+    Status {
+        /// Print the status as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -122,7 +205,8 @@ enum PluginsAction {
 }
 
 fn main() {
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    logger::set_log_format(cli.global.log_format());
 
     // Initialize logger with verbosity level, log_python flag, and no_stdout flag
     if let Err(e) = logger::init_with_verbosity(
@@ -133,9 +217,14 @@ fn main() {
         eprintln!("Warning: Failed to initialize logger: {}", e);
     }
 
+    if cli.global.ignore_python_version {
+        std::env::set_var(config_manager::IGNORE_PYTHON_VERSION_ENV, "1");
+    }
+
     if let Err(e) = config_manager::Config::load().and_then(|mut cfg| {
         cfg.ensure_uv_path()?;
         cfg.ensure_cache_path()?;
+        cli.global.fallback_to_runtime |= cfg.fallback_to_runtime.unwrap_or(false);
         Ok(())
     }) {
         logger::warn(&format!("Failed to setup CLI: {}", e));
@@ -145,21 +234,67 @@ fn main() {
         Commands::Config { action } => {
             config::handle_config(action, cli.global);
         }
-        Commands::List { plugin, module } => {
-            if let Err(e) = plugins::list_plugins(&cli.global, plugin, module) {
+        Commands::List {
+            plugin,
+            module,
+            kind,
+            tag,
+            search,
+            outdated,
+            graph,
+            format,
+        } => {
+            let result = if graph {
+                plugins::show_dependency_graph(format)
+            } else if outdated {
+                plugins::list_outdated_plugins(plugin, module, kind, tag)
+            } else {
+                plugins::list_plugins(&cli.global, plugin, module, kind, tag, search)
+            };
+            if let Err(e) = result {
                 logger::error(&e);
             }
         }
         Commands::Install {
             plugin,
+            requirements,
             editable,
             no_cache,
+            dry_run,
             host,
             branch,
             tag,
             commit,
-        } => match plugin {
-            Some(pkg) => {
+        } => match (plugin, requirements) {
+            (_, Some(path)) => {
+                let git_opts = plugins::GitOptions {
+                    host,
+                    branch,
+                    tag,
+                    commit,
+                };
+                if let Err(e) = plugins::install_from_requirements(
+                    &path,
+                    editable,
+                    no_cache,
+                    git_opts,
+                    &cli.global,
+                ) {
+                    logger::error(&e);
+                }
+            }
+            (Some(pkg), None) if dry_run => {
+                let git_opts = plugins::GitOptions {
+                    host,
+                    branch,
+                    tag,
+                    commit,
+                };
+                if let Err(e) = plugins::dry_run_install(&pkg, &git_opts) {
+                    logger::error(&e);
+                }
+            }
+            (Some(pkg), None) => {
                 if let Err(e) = plugins::install_plugin(
                     &pkg,
                     editable,
@@ -175,7 +310,7 @@ fn main() {
                     logger::error(&e);
                 }
             }
-            None => {
+            (None, None) => {
                 if let Err(e) = plugins::show_install_help() {
                     logger::error(&e);
                 }
@@ -186,8 +321,21 @@ fn main() {
                 logger::error(&e);
             }
         }
-        Commands::Sync => {
-            if let Err(e) = plugins::sync_manifest(&cli.global) {
+        Commands::Update { package, all } => {
+            let result = match package {
+                Some(package) if !all => plugins::update_plugin(&package, &cli.global),
+                _ => plugins::update_all_plugins(&cli.global),
+            };
+            if let Err(e) = result {
+                logger::error(&e);
+            }
+        }
+        Commands::Sync {
+            metrics,
+            jobs,
+            no_cache,
+        } => {
+            if let Err(e) = plugins::sync_manifest(&cli.global, metrics, jobs, no_cache) {
                 logger::error(&e);
             }
         }
@@ -196,13 +344,51 @@ fn main() {
                 logger::error(&e);
             }
         }
+        Commands::Cache { action } => {
+            if let Err(e) = cache::handle_cache(action, cli.global) {
+                logger::error(&e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Manifest { action } => {
+            if let Err(e) = manifest::handle_manifest(action, cli.global) {
+                logger::error(&e);
+                std::process::exit(1);
+            }
+        }
+        Commands::Export { output, format } => {
+            if let Err(e) = export::handle_export(output.as_deref(), format, cli.global) {
+                logger::error(&e);
+                std::process::exit(1);
+            }
+        }
+        Commands::New { action } => {
+            if let Err(e) = new::handle_new(action, cli.global) {
+                logger::error(&e);
+                std::process::exit(1);
+            }
+        }
         Commands::Init { file } => {
             init::handle_init(file, cli.global);
         }
 
         Commands::Run(cmd) => {
+            let verbosity = cli.global.verbosity_level();
             if let Err(e) = run::handle_run(cmd, cli.global) {
-                logger::error(&format!("Run command failed: {}", e));
+                let message = if verbosity > 0 {
+                    e.to_string()
+                } else {
+                    e.user_friendly_message()
+                };
+                logger::error(&format!("Run command failed: {}", message));
+                if let Some(bridge_error) = e.bridge_error() {
+                    log_diagnostic(bridge_error);
+                    match bridge_error.traceback() {
+                        Some(traceback) if verbosity >= 2 => logger::traceback(traceback),
+                        Some(_) => logger::warn("Run with -vv to see the Python traceback"),
+                        None => {}
+                    }
+                }
                 std::process::exit(1);
             }
         }
@@ -213,5 +399,32 @@ fn main() {
                 std::process::exit(1);
             }
         }
+        Commands::Doctor => {
+            std::process::exit(doctor::handle_doctor(cli.global));
+        }
+        Commands::Status { json } => {
+            if let Err(e) = status::handle_status(json, cli.global) {
+                logger::error(&e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Log what a diagnostic carries beyond its message: its code, help, documentation
+/// link and related diagnostics
+fn log_diagnostic(diagnostic: &dyn Diagnostic) {
+    if let Some(code) = diagnostic.code() {
+        logger::debug(&format!("Error code: {}", code));
+    }
+    if let Some(help) = diagnostic.help() {
+        logger::help(&help.to_string());
+    }
+    if let Some(url) = diagnostic.url() {
+        logger::help(&format!("See {}", url));
+    }
+    for related in diagnostic.related().into_iter().flatten() {
+        logger::error(&related.to_string());
+        log_diagnostic(related);
     }
 }
@@ -2,11 +2,12 @@
 
 use crate::config_manager::Config;
 use crate::logger;
-use crate::r2x_manifest::Manifest;
+use crate::plugins::install::Installer;
+use crate::r2x_manifest::{DistInfo, Manifest, PluginSpec};
 use r2x_python::resolve_site_package_path;
 use std::collections::HashSet;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum VerificationResult {
@@ -61,14 +62,14 @@ pub fn verify_plugin_packages(
     logger::debug(&format!("Verifying packages for plugin: {}", plugin_key));
 
     // Find the plugin and its package from manifest
-    let package_name = manifest
+    let (package_name, plugin) = manifest
         .packages
         .iter()
         .find_map(|pkg| {
             pkg.plugins
                 .iter()
                 .find(|p| p.name == plugin_key)
-                .map(|_| pkg.name.clone())
+                .map(|plugin| (pkg.name.clone(), plugin))
         })
         .ok_or_else(|| {
             VerificationError::VerificationFailed(format!(
@@ -92,6 +93,7 @@ pub fn verify_plugin_packages(
 
     if missing_packages.is_empty() {
         logger::debug(&format!("Package '{}' verified successfully", package_name));
+        warn_on_r2x_core_mismatch(plugin, &venv_path);
         Ok(VerificationResult::Valid)
     } else {
         logger::debug(&format!("Missing packages: {:?}", missing_packages));
@@ -120,10 +122,9 @@ fn check_packages_installed(
 
         // Check if package directory exists
         let package_dir = site_packages.join(&package_dir_name);
-        let dist_info_pattern = format!("{}-*.dist-info", package_dir_name);
 
         let package_exists =
-            package_dir.exists() || dist_info_exists(&site_packages, &dist_info_pattern);
+            package_dir.exists() || DistInfo::find(&site_packages, package).is_some();
 
         if !package_exists {
             logger::debug(&format!("Package '{}' not found in site-packages", package));
@@ -136,6 +137,55 @@ fn check_packages_installed(
     Ok(missing)
 }
 
+/// Warn when the installed r2x-core does not satisfy the plugin's declared requirement
+///
+/// Informational only: the plugin still runs, since the requirement may be stale.
+fn warn_on_r2x_core_mismatch(plugin: &PluginSpec, venv_path: &PathBuf) {
+    let Some(requirement) = plugin.r2x_core_requirement.as_deref() else {
+        return;
+    };
+    let Some(installed) = get_site_packages_dir(venv_path)
+        .ok()
+        .and_then(|site_packages| DistInfo::find(&site_packages, "r2x-core")?.version)
+    else {
+        return;
+    };
+
+    if plugin.is_compatible_with_r2x_core(&installed) == Some(false) {
+        logger::warn(&format!(
+            "Plugin '{}' requires r2x-core{} but {} is installed",
+            plugin.name, requirement, installed
+        ));
+    }
+}
+
+/// Manifest packages that are no longer installed, or installed at another version
+/// than the one recorded when their plugins were discovered
+pub fn stale_packages(
+    manifest: &Manifest,
+    venv_path: &PathBuf,
+) -> Result<Vec<String>, VerificationError> {
+    let site_packages = get_site_packages_dir(venv_path)?;
+    Ok(stale_packages_in(manifest, &site_packages))
+}
+
+fn stale_packages_in(manifest: &Manifest, site_packages: &Path) -> Vec<String> {
+    manifest
+        .packages
+        .iter()
+        .filter(
+            |pkg| match DistInfo::find(site_packages, &pkg.name).and_then(|d| d.version) {
+                Some(installed) => pkg
+                    .version
+                    .as_deref()
+                    .is_some_and(|recorded| !recorded.eq_ignore_ascii_case(&installed)),
+                None => true,
+            },
+        )
+        .map(|pkg| pkg.name.clone())
+        .collect()
+}
+
 /// Get the site-packages directory from venv
 fn get_site_packages_dir(venv_path: &PathBuf) -> Result<PathBuf, VerificationError> {
     logger::debug(&format!(
@@ -149,24 +199,7 @@ fn get_site_packages_dir(venv_path: &PathBuf) -> Result<PathBuf, VerificationErr
     })
 }
 
-/// Check if a dist-info directory matching the pattern exists
-fn dist_info_exists(site_packages: &PathBuf, pattern: &str) -> bool {
-    let pattern_prefix = pattern.split('-').next().unwrap_or("");
-
-    if let Ok(entries) = std::fs::read_dir(site_packages) {
-        for entry in entries.flatten() {
-            let name = entry.file_name();
-            let name_str = name.to_string_lossy();
-            if name_str.starts_with(pattern_prefix) && name_str.ends_with(".dist-info") {
-                return true;
-            }
-        }
-    }
-
-    false
-}
-
-/// Reinstall missing packages using uv
+/// Reinstall missing packages with uv, or the venv pip when uv is unavailable
 ///
 /// # Arguments
 /// * `packages` - List of package names to install
@@ -185,21 +218,14 @@ pub fn ensure_packages(packages: Vec<String>, config: &Config) -> Result<(), Ver
         packages.join(", ")
     ));
 
-    let uv_path = config
-        .uv_path
-        .as_ref()
-        .ok_or_else(|| VerificationError::ReinstallFailed("uv not configured".to_string()))?;
+    let installer = Installer::find(config).ok_or_else(|| {
+        VerificationError::ReinstallFailed("Neither uv nor pip is available".to_string())
+    })?;
 
     let python_exe = config.get_venv_python_path();
 
-    // Build uv pip install command
-    let mut cmd = Command::new(uv_path);
-    cmd.arg("pip")
-        .arg("install")
-        .arg("--python")
-        .arg(&python_exe)
-        .arg("--prerelease=allow")
-        .arg("--no-progress");
+    let mut cmd = installer.install_command(&python_exe);
+    cmd.args(installer.no_progress_flags());
 
     // Add all packages
     for package in &packages {
@@ -214,11 +240,18 @@ pub fn ensure_packages(packages: Vec<String>, config: &Config) -> Result<(), Ver
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .status()
-        .map_err(|e| VerificationError::ReinstallFailed(format!("Failed to execute uv: {}", e)))?;
+        .map_err(|e| {
+            VerificationError::ReinstallFailed(format!(
+                "Failed to execute {}: {}",
+                installer.path(),
+                e
+            ))
+        })?;
 
     if !status.success() {
         return Err(VerificationError::ReinstallFailed(format!(
-            "uv pip install failed: exit code {}",
+            "{} install failed: exit code {}",
+            installer.name(),
             status.code().unwrap_or(-1)
         )));
     }
@@ -347,6 +380,24 @@ mod tests {
         assert_eq!(converted, "r2x_reeds");
     }
 
+    #[test]
+    fn test_stale_packages() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("r2x_reeds-1.2.0.dist-info")).unwrap();
+        std::fs::create_dir(dir.path().join("r2x_plexos-0.4.0.dist-info")).unwrap();
+        std::fs::create_dir(dir.path().join("r2x.sienna-0.1.0.dist-info")).unwrap();
+
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-reeds").version = Some("1.2.0".to_string());
+        manifest.get_or_create_package("r2x-plexos").version = Some("0.3.0".to_string());
+        manifest.get_or_create_package("r2x-sienna").version = Some("0.1.0".to_string());
+        manifest.get_or_create_package("r2x-nodal");
+        assert_eq!(
+            stale_packages_in(&manifest, dir.path()),
+            vec!["r2x-plexos", "r2x-nodal"]
+        );
+    }
+
     #[test]
     fn test_verification_error_display() {
         let err = VerificationError::VerificationFailed("test error".to_string());
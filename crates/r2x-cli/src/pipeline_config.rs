@@ -27,20 +27,24 @@ pub struct PipelineConfig {
 impl PipelineConfig {
     /// Load pipeline configuration from YAML file
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PipelineError> {
+        let content = Self::read_source(path)?;
+        let config: PipelineConfig = serde_yaml::from_str(&content)?;
+        Ok(config)
+    }
+
+    /// Read a pipeline file, trying `.yaml` and `.yml` when `path` has no extension
+    pub fn read_source<P: AsRef<Path>>(path: P) -> Result<String, PipelineError> {
         let path_ref = path.as_ref();
-        let content = match fs::read_to_string(path_ref) {
-            Ok(content) => content,
+        match fs::read_to_string(path_ref) {
+            Ok(content) => Ok(content),
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                if let Some(fallback) = Self::resolve_fallback_path(path_ref) {
-                    fs::read_to_string(fallback)?
-                } else {
-                    return Err(PipelineError::Io(err));
+                match Self::resolve_fallback_path(path_ref) {
+                    Some(fallback) => Ok(fs::read_to_string(fallback)?),
+                    None => Err(PipelineError::Io(err)),
                 }
             }
-            Err(err) => return Err(PipelineError::Io(err)),
-        };
-        let config: PipelineConfig = serde_yaml::from_str(&content)?;
-        Ok(config)
+            Err(err) => Err(PipelineError::Io(err)),
+        }
     }
 
     /// List all available pipeline names
@@ -56,36 +60,57 @@ impl PipelineConfig {
     }
 
     /// Substitute variables in a string (supports ${var} and $(var) syntax)
+    ///
+    /// Variables may refer to other variables; a variable that refers back to itself
+    /// is reported as [`PipelineError::CircularDependency`].
     pub fn substitute_string(&self, input: &str) -> Result<String, PipelineError> {
-        let mut result = input.to_string();
+        self.substitute_resolving(input, &mut Vec::new())
+    }
 
-        // Handle ${var} syntax
-        while let Some(start) = result.find("${") {
-            if let Some(end) = result[start..].find('}') {
-                let var_name = &result[start + 2..start + end];
-                let value = self.get_variable_string(var_name)?;
-                result.replace_range(start..start + end + 1, &value);
-            } else {
-                return Err(PipelineError::InvalidConfig(
-                    "Unclosed variable substitution ${".to_string(),
-                ));
+    /// Substitute variables in `input` while the variables in `resolving` are expanded
+    fn substitute_resolving(
+        &self,
+        input: &str,
+        resolving: &mut Vec<String>,
+    ) -> Result<String, PipelineError> {
+        let mut result = input.to_string();
+        for (open, close) in [("${", '}'), ("$(", ')')] {
+            let mut substituted = String::with_capacity(result.len());
+            let mut rest = result.as_str();
+            while let Some(start) = rest.find(open) {
+                let Some(end) = rest[start..].find(close) else {
+                    return Err(PipelineError::InvalidConfig(format!(
+                        "Unclosed variable substitution {}",
+                        open
+                    )));
+                };
+                let var_name = &rest[start + 2..start + end];
+                substituted.push_str(&rest[..start]);
+                substituted.push_str(&self.resolve_variable(var_name, resolving)?);
+                rest = &rest[start + end + 1..];
             }
+            substituted.push_str(rest);
+            result = substituted;
         }
+        Ok(result)
+    }
 
-        // Handle $(var) syntax
-        while let Some(start) = result.find("$(") {
-            if let Some(end) = result[start..].find(')') {
-                let var_name = &result[start + 2..start + end];
-                let value = self.get_variable_string(var_name)?;
-                result.replace_range(start..start + end + 1, &value);
-            } else {
-                return Err(PipelineError::InvalidConfig(
-                    "Unclosed variable substitution $(".to_string(),
-                ));
-            }
+    /// Value of a variable with the variables it refers to substituted
+    fn resolve_variable(
+        &self,
+        name: &str,
+        resolving: &mut Vec<String>,
+    ) -> Result<String, PipelineError> {
+        if let Some(pos) = resolving.iter().position(|var| var == name) {
+            let mut cycle = resolving[pos..].to_vec();
+            cycle.push(name.to_string());
+            return Err(PipelineError::CircularDependency { cycle });
         }
-
-        Ok(result)
+        let value = self.get_variable_string(name)?;
+        resolving.push(name.to_string());
+        let resolved = self.substitute_resolving(&value, resolving);
+        resolving.pop();
+        resolved
     }
 
     /// Get a variable value as a string
@@ -274,6 +299,55 @@ mod tests {
         assert!(matches!(result, Err(PipelineError::VariableNotFound(_))));
     }
 
+    #[test]
+    fn test_variable_references() {
+        let yaml = r#"
+variables:
+  root: /data
+  inputs: ${root}/inputs
+  case: $(inputs)/case_${year}
+  year: 2032
+"#;
+        let config: PipelineConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            config.substitute_string("${case}").unwrap(),
+            "/data/inputs/case_2032"
+        );
+    }
+
+    #[test]
+    fn test_variable_circular_dependency() {
+        let yaml = r#"
+variables:
+  folder: ${run}/outputs
+  run: $(scenario)
+  scenario: ${folder}/base
+  year: 2032
+pipelines:
+  reeds-to-plexos: [reeds-parser]
+config:
+  reeds-parser:
+    path: ${folder}
+    solve_year: ${year}
+"#;
+        let config: PipelineConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.get_plugin_config("reeds-parser").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Circular dependency: folder -> run -> scenario -> folder"
+        );
+        assert!(matches!(
+            config.substitute_string("${year}-${year}"),
+            Ok(ref s) if s == "2032-2032"
+        ));
+
+        let config: PipelineConfig = serde_yaml::from_str("variables: {a: 'x${a}'}").unwrap();
+        assert!(matches!(
+            config.substitute_string("${a}"),
+            Err(PipelineError::CircularDependency { cycle }) if cycle == ["a", "a"]
+        ));
+    }
+
     #[test]
     fn test_substitute_yaml_value() {
         let mut vars = HashMap::new();
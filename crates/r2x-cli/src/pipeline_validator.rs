@@ -0,0 +1,384 @@
+//! Structural checks of pipeline YAML files
+//!
+//! `serde_yaml` ignores unknown keys and reports shape errors without context, so a
+//! typo such as `pipeline:` for `pipelines:` either passes silently or fails with a
+//! raw parse error. These checks run on the raw YAML and report each problem as a
+//! [`ValidationIssue`] that names the pipeline step it concerns.
+
+use crate::pipeline_config::PipelineConfig;
+use crate::r2x_manifest::{IOSlot, Manifest, PluginSpec};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::Path;
+
+/// Top-level keys of a pipeline file
+const KNOWN_KEYS: [&str; 4] = ["variables", "pipelines", "output_folder", "config"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    /// Plugin name of the step the issue concerns, when it concerns one
+    pub step_name: Option<String>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(step_name: Option<&str>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, step_name, message)
+    }
+
+    fn warning(step_name: Option<&str>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, step_name, message)
+    }
+
+    fn new(severity: Severity, step_name: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            step_name: step_name.map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.step_name {
+            Some(step) => write!(f, "{}: {}", step, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+/// Check the pipeline file at `path` against the installed plugin manifest
+///
+/// Plugin names are not checked when the manifest cannot be loaded; that is reported
+/// as a warning instead.
+pub fn validate_pipeline_yaml(path: &Path) -> Vec<ValidationIssue> {
+    let content = match PipelineConfig::read_source(path) {
+        Ok(content) => content,
+        Err(e) => return vec![ValidationIssue::error(None, e.to_string())],
+    };
+    match Manifest::load() {
+        Ok(manifest) => validate_pipeline_str(&content, Some(&manifest)),
+        Err(e) => {
+            let mut issues = validate_pipeline_str(&content, None);
+            issues.push(ValidationIssue::warning(
+                None,
+                format!("Plugin names not checked: {}", e),
+            ));
+            issues
+        }
+    }
+}
+
+/// Check pipeline YAML text; plugin names are looked up in `manifest` when given
+pub fn validate_pipeline_str(content: &str, manifest: Option<&Manifest>) -> Vec<ValidationIssue> {
+    let root: serde_yaml::Value = match serde_yaml::from_str(content) {
+        Ok(root) => root,
+        Err(e) => {
+            let location = e
+                .location()
+                .map(|loc| format!(" at line {}, column {}", loc.line(), loc.column()))
+                .unwrap_or_default();
+            return vec![ValidationIssue::error(
+                None,
+                format!("Invalid YAML{}: {}", location, e),
+            )];
+        }
+    };
+    let Some(root) = root.as_mapping() else {
+        return vec![ValidationIssue::error(
+            None,
+            "A pipeline file must be a mapping with a `pipelines:` section",
+        )];
+    };
+
+    let mut issues = Vec::new();
+    for key in root.keys() {
+        let Some(key) = key.as_str() else {
+            issues.push(ValidationIssue::error(
+                None,
+                "Top-level keys must be strings",
+            ));
+            continue;
+        };
+        if !KNOWN_KEYS.contains(&key) {
+            issues.push(ValidationIssue::error(
+                None,
+                format!("Unknown key `{}`{}", key, suggestion(key, KNOWN_KEYS)),
+            ));
+        }
+    }
+
+    let plugins: HashMap<&str, &PluginSpec> = manifest
+        .map(|manifest| {
+            manifest
+                .list_plugins()
+                .into_iter()
+                .map(|(_, plugin)| (plugin.name.as_str(), plugin))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut used_plugins = BTreeSet::new();
+    match root.get("pipelines") {
+        None => issues.push(ValidationIssue::error(
+            None,
+            "Missing `pipelines:` section listing the plugins of each pipeline",
+        )),
+        Some(serde_yaml::Value::Mapping(pipelines)) if pipelines.is_empty() => issues.push(
+            ValidationIssue::error(None, "The `pipelines:` section defines no pipeline"),
+        ),
+        Some(serde_yaml::Value::Mapping(pipelines)) => {
+            for (name, steps) in pipelines {
+                let name = name.as_str().unwrap_or("<non-string name>");
+                let steps = check_steps(name, steps, &mut issues);
+                if manifest.is_some() {
+                    check_plugins(name, &steps, &plugins, &mut issues);
+                }
+                used_plugins.extend(steps);
+            }
+        }
+        Some(_) => issues.push(ValidationIssue::error(
+            None,
+            "`pipelines:` must map pipeline names to lists of plugin names",
+        )),
+    }
+
+    if let Some(config) = root.get("config") {
+        match config.as_mapping() {
+            Some(config) => {
+                for key in config.keys().filter_map(|key| key.as_str()) {
+                    if !used_plugins.contains(key) {
+                        issues.push(ValidationIssue::warning(
+                            Some(key),
+                            format!(
+                                "Configured but not used by any pipeline{}",
+                                suggestion(key, used_plugins.iter().copied())
+                            ),
+                        ));
+                    }
+                }
+            }
+            None => issues.push(ValidationIssue::error(
+                None,
+                "`config:` must map plugin names to their configuration",
+            )),
+        }
+    }
+
+    check_variables(content, &mut issues);
+    issues
+}
+
+/// Plugin names of a pipeline, reporting entries that are not names and repeats
+fn check_steps<'a>(
+    pipeline: &str,
+    steps: &'a serde_yaml::Value,
+    issues: &mut Vec<ValidationIssue>,
+) -> Vec<&'a str> {
+    let Some(steps) = steps.as_sequence() else {
+        issues.push(ValidationIssue::error(
+            None,
+            format!("Pipeline '{}' must be a list of plugin names", pipeline),
+        ));
+        return Vec::new();
+    };
+    let mut names: Vec<&str> = Vec::new();
+    for (index, step) in steps.iter().enumerate() {
+        match step.as_str() {
+            Some(name) if names.contains(&name) => issues.push(ValidationIssue::warning(
+                Some(name),
+                format!(
+                    "Appears more than once in '{}'; every occurrence gets the same config",
+                    pipeline
+                ),
+            )),
+            Some(name) => names.push(name),
+            None => issues.push(ValidationIssue::error(
+                None,
+                format!(
+                    "Step {} of '{}' must be a plugin name; plugin settings go under `config:`",
+                    index + 1,
+                    pipeline
+                ),
+            )),
+        }
+    }
+    names
+}
+
+/// Report plugins missing from the manifest and steps left without an input system
+fn check_plugins(
+    pipeline: &str,
+    steps: &[&str],
+    plugins: &HashMap<&str, &PluginSpec>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let mut system_produced = false;
+    for step in steps {
+        let Some(plugin) = plugins.get(step) else {
+            issues.push(ValidationIssue::error(
+                Some(step),
+                format!(
+                    "Plugin not found in the manifest{}",
+                    suggestion(step, plugins.keys().copied())
+                ),
+            ));
+            continue;
+        };
+        if plugin.io.consumes.contains(&IOSlot::System) && !system_produced {
+            issues.push(ValidationIssue::error(
+                Some(step),
+                format!(
+                    "Consumes a system but no earlier step of '{}' produces one",
+                    pipeline
+                ),
+            ));
+        }
+        system_produced |= plugin.io.produces.contains(&IOSlot::System);
+    }
+}
+
+/// Report undefined and circular variable references
+fn check_variables(content: &str, issues: &mut Vec<ValidationIssue>) {
+    let Ok(config) = serde_yaml::from_str::<PipelineConfig>(content) else {
+        return;
+    };
+    let mut values = Vec::new();
+    values.extend(config.variables.values().cloned());
+    values.extend(config.config.values().cloned());
+    values.extend(config.output_folder.clone().map(serde_yaml::Value::String));
+
+    let mut reported = BTreeSet::new();
+    for value in &values {
+        if let Err(e) = config.substitute_value(value) {
+            let message = e.to_string();
+            if reported.insert(message.clone()) {
+                issues.push(ValidationIssue::error(None, message));
+            }
+        }
+    }
+}
+
+/// ` (did you mean `x`?)` for the candidate closest to `name`, if any is close
+fn suggestion<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= 2.max(candidate.len() / 4))
+        .min()
+        .map(|(_, candidate)| format!(" (did you mean `{}`?)", candidate))
+        .unwrap_or_default()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(row[j]).min(current)
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(content: &str) -> Vec<String> {
+        validate_pipeline_str(content, None)
+            .into_iter()
+            .map(|issue| issue.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_pipeline() {
+        let content = r#"
+variables:
+  year: 2030
+  folder: "out/${year}"
+pipelines:
+  main: [r2x-reeds.parser, r2x-plexos.exporter]
+config:
+  r2x-reeds.parser:
+    solve_year: ${year}
+output_folder: ${folder}
+"#;
+        assert_eq!(messages(content), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_structure_issues() {
+        let content = r#"
+pipeline:
+  main: [a]
+"#;
+        assert_eq!(
+            messages(content),
+            vec![
+                "Unknown key `pipeline` (did you mean `pipelines`?)",
+                "Missing `pipelines:` section listing the plugins of each pipeline",
+            ]
+        );
+
+        let content = r#"
+pipelines:
+  main:
+    - plugni: a
+    - b
+    - b
+config:
+  c: {}
+"#;
+        assert_eq!(
+            messages(content),
+            vec![
+                "Step 1 of 'main' must be a plugin name; plugin settings go under `config:`",
+                "b: Appears more than once in 'main'; every occurrence gets the same config",
+                "c: Configured but not used by any pipeline (did you mean `b`?)",
+            ]
+        );
+
+        assert!(messages("pipelines: [a")[0].starts_with("Invalid YAML at line"));
+    }
+
+    #[test]
+    fn test_variable_issues() {
+        let content = r#"
+variables:
+  a: "${b}"
+  b: "${a}"
+pipelines:
+  main: [p]
+config:
+  p:
+    path: ${missing}
+"#;
+        let messages = messages(content);
+        assert!(messages.iter().any(|m| m.contains("Circular dependency")));
+        assert!(messages.iter().any(|m| m.contains("missing")));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("plugni", "plugin"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("config", "config"), 0);
+    }
+}
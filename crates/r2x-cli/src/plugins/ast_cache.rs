@@ -0,0 +1,307 @@
+//! Cache of AST discovery results
+//!
+//! Results are keyed on the package name and a fingerprint of its installed version
+//! and of every Python file discovery reads, so unchanged packages are not parsed
+//! again on `r2x sync` while any edit, including to an editable install, is picked
+//! up. Entries also expire once older than the `cache-ttl-secs` setting.
+//!
+//! The file is written atomically while holding `ast_cache.lock`, so concurrent
+//! syncs never leave it half-written.
+
+use crate::config_manager::Config;
+use crate::logger;
+use crate::plugins::discovery::DiscoveredPlugins;
+use crate::r2x_manifest::atomic::write_atomic;
+use crate::r2x_manifest::lock::{lock_timeout, ManifestLock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File name of the cache inside the r2x cache directory
+pub const AST_CACHE_FILE: &str = "ast_cache.json";
+
+/// One cached discovery as stored on disk
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    package: String,
+    fingerprint: String,
+    result: DiscoveredPlugins,
+    /// Seconds since the epoch when the result was stored
+    #[serde(default)]
+    cached_at: u64,
+}
+
+/// Discovery results keyed on `(package_name, fingerprint)`
+///
+/// Values are the [`DiscoveredPlugins`] and the time they were stored.
+/// Lookups and inserts take `&self` so the cache can be shared by the threads of a
+/// parallel sync.
+#[derive(Debug, Default)]
+pub struct PluginAstCache {
+    path: PathBuf,
+    entries: Mutex<HashMap<(String, String), (DiscoveredPlugins, u64)>>,
+    /// Age in seconds after which entries are ignored; 0 keeps them forever
+    ttl_secs: u64,
+}
+
+impl PluginAstCache {
+    /// Location of the cache in the configured r2x cache directory
+    pub fn default_path(config: &Config) -> PathBuf {
+        Path::new(&config.get_cache_path()).join(AST_CACHE_FILE)
+    }
+
+    /// Read the cache at `path`; a missing or unreadable file gives an empty cache
+    pub fn load(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(
+                |content| match serde_json::from_str::<Vec<CacheEntry>>(&content) {
+                    Ok(entries) => Some(entries),
+                    Err(e) => {
+                        logger::debug(&format!("Ignoring unreadable AST cache: {}", e));
+                        None
+                    }
+                },
+            )
+            .unwrap_or_default()
+            .into_iter()
+            .map(|entry| {
+                (
+                    (entry.package, entry.fingerprint),
+                    (entry.result, entry.cached_at),
+                )
+            })
+            .collect();
+        Self {
+            path,
+            entries: Mutex::new(entries),
+            ttl_secs: 0,
+        }
+    }
+
+    /// Read the cache at `path`, expiring entries after the configured TTL
+    pub fn load_configured(config: &Config) -> Self {
+        Self::load(Self::default_path(config)).with_ttl(config.get_cache_ttl_secs())
+    }
+
+    /// Ignore entries stored more than `ttl_secs` ago; 0 disables expiry
+    pub fn with_ttl(mut self, ttl_secs: u64) -> Self {
+        self.ttl_secs = ttl_secs;
+        self
+    }
+
+    /// Cached discovery of `package` when its sources still have `fingerprint` and the
+    /// entry has not expired
+    pub fn get(&self, package: &str, fingerprint: &str) -> Option<DiscoveredPlugins> {
+        let entries = self.entries.lock().ok()?;
+        let (found, cached_at) = entries.get(&(package.to_string(), fingerprint.to_string()))?;
+        if self.ttl_secs > 0 && now_secs().saturating_sub(*cached_at) >= self.ttl_secs {
+            logger::debug(&format!("Cached discovery of {} expired", package));
+            return None;
+        }
+        Some(found.clone())
+    }
+
+    /// Store the discovery of `package`, replacing results for older sources
+    pub fn insert(&self, package: &str, fingerprint: &str, discovered: &DiscoveredPlugins) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|(name, _), _| name != package);
+            entries.insert(
+                (package.to_string(), fingerprint.to_string()),
+                (discovered.clone(), now_secs()),
+            );
+        }
+    }
+
+    /// Drop every cached result of `package`
+    pub fn invalidate(&self, package: &str) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.retain(|(name, _), _| name != package);
+        }
+    }
+
+    /// Names of the packages with cached results, sorted
+    pub fn packages(&self) -> Vec<String> {
+        let mut packages: Vec<String> = self
+            .entries
+            .lock()
+            .map(|entries| entries.keys().map(|(name, _)| name.clone()).collect())
+            .unwrap_or_default();
+        packages.sort();
+        packages.dedup();
+        packages
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Write the cache back to its file
+    pub fn save(&self) -> Result<(), String> {
+        let _lock = lock_file(&self.path)?;
+        self.write()
+    }
+
+    /// Write the cache to its file; the caller holds the file lock
+    fn write(&self) -> Result<(), String> {
+        let entries = self
+            .entries
+            .lock()
+            .map_err(|_| "AST cache lock poisoned".to_string())?;
+        let mut stored: Vec<CacheEntry> = entries
+            .iter()
+            .map(|((package, fingerprint), (result, cached_at))| CacheEntry {
+                package: package.clone(),
+                fingerprint: fingerprint.clone(),
+                result: result.clone(),
+                cached_at: *cached_at,
+            })
+            .collect();
+        stored.sort_by(|a, b| a.package.cmp(&b.package));
+
+        let json = serde_json::to_string(&stored)
+            .map_err(|e| format!("Failed to serialize AST cache: {}", e))?;
+        write_atomic(&self.path, json)
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))
+    }
+}
+
+/// Exclusive lock on the cache at `path`, creating its directory
+fn lock_file(path: &Path) -> Result<ManifestLock, String> {
+    let lock_path = path.with_extension("lock");
+    ManifestLock::exclusive_file(&lock_path, lock_timeout())
+        .map_err(|e| format!("Failed to lock {}: {}", lock_path.display(), e))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Drop the cached discovery of a package that was just (re)installed
+///
+/// Best-effort: failures are only logged.
+pub fn invalidate_package(package: &str) {
+    let Ok(config) = Config::load() else {
+        return;
+    };
+    let path = PluginAstCache::default_path(&config);
+    let result = lock_file(&path).and_then(|_lock| {
+        let cache = PluginAstCache::load(path.clone());
+        cache.invalidate(package);
+        cache.write()
+    });
+    if let Err(e) = result {
+        logger::debug(&format!("Failed to update AST cache: {}", e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn discovered(decorators: usize) -> DiscoveredPlugins {
+        DiscoveredPlugins {
+            plugins: Vec::new(),
+            decorator_registrations: (0..decorators)
+                .map(|i| crate::r2x_manifest::DecoratorRegistration {
+                    decorator_class: "Registry".to_string(),
+                    decorator_method: "register".to_string(),
+                    function_name: format!("fn{}", i),
+                    function_module: "r2x_reeds.plugins".to_string(),
+                    source_file: None,
+                    line_number: None,
+                    decorator_args: toml::Table::new(),
+                    function_signature: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_cache_roundtrip_and_invalidate() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(AST_CACHE_FILE);
+
+        let cache = PluginAstCache::load(path.clone());
+        assert!(cache.is_empty());
+        cache.insert("r2x-reeds", "abc", &discovered(2));
+        cache.insert("r2x-plexos", "def", &discovered(1));
+        cache.save().unwrap();
+
+        let cache = PluginAstCache::load(path.clone());
+        assert_eq!(
+            cache
+                .get("r2x-reeds", "abc")
+                .unwrap()
+                .decorator_registrations
+                .len(),
+            2
+        );
+        assert!(cache.get("r2x-reeds", "changed").is_none());
+
+        // A new hash replaces the stale entry
+        cache.insert("r2x-reeds", "xyz", &discovered(0));
+        assert!(cache.get("r2x-reeds", "abc").is_none());
+        assert_eq!(cache.len(), 2);
+
+        cache.invalidate("r2x-reeds");
+        assert!(cache.get("r2x-reeds", "xyz").is_none());
+        assert!(cache.get("r2x-plexos", "def").is_some());
+    }
+
+    #[test]
+    fn test_cache_ttl() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(AST_CACHE_FILE);
+        let stale = serde_json::json!([{
+            "package": "r2x-reeds",
+            "fingerprint": "abc",
+            "result": discovered(1),
+            "cached_at": now_secs() - 7200,
+        }]);
+        fs::write(&path, stale.to_string()).unwrap();
+
+        assert!(PluginAstCache::load(path.clone())
+            .get("r2x-reeds", "abc")
+            .is_some());
+        let cache = PluginAstCache::load(path).with_ttl(3600);
+        assert!(cache.get("r2x-reeds", "abc").is_none());
+
+        cache.insert("r2x-reeds", "abc", &discovered(1));
+        assert!(cache.get("r2x-reeds", "abc").is_some());
+    }
+
+    #[test]
+    fn test_cache_stores_plain_json() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(AST_CACHE_FILE);
+        let cache = PluginAstCache::load(path.clone());
+        cache.insert("r2x-reeds", "abc", &discovered(1));
+        cache.save().unwrap();
+
+        let stored: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(stored[0]["result"]["decorator_registrations"].is_array());
+    }
+
+    #[test]
+    fn test_corrupt_cache_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(AST_CACHE_FILE);
+        fs::write(&path, "not json").unwrap();
+        assert!(PluginAstCache::load(path).is_empty());
+    }
+}
@@ -3,11 +3,27 @@
 //! Manages the discovery and registration of plugins from packages,
 //! handling caching, dependencies, and manifest updates.
 
+use crate::config_manager::{Config, DEFAULT_CONFIDENCE_THRESHOLD};
 use crate::logger;
-use crate::plugins::{find_package_path, utils, AstDiscovery};
-use crate::r2x_manifest::Manifest;
+use crate::plugins::ast_cache::PluginAstCache;
+use crate::plugins::install::Installer;
+use crate::plugins::package_resolver::same_package_name;
+use crate::plugins::utils::{self, load_manifest};
+use crate::plugins::{find_package_path, AstDiscovery};
+use crate::python_bridge::resolve_site_package_path;
+use crate::r2x_ast::{BatchDiscoveryMetrics, DiscoveryMetrics, DiscoveryMode};
+use crate::r2x_manifest::{
+    integrity, DecoratorRegistration, DistInfo, Manifest, Package, PluginSpec,
+};
+use crate::BridgeError;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 /// Options for plugin discovery and registration
+#[derive(Debug, Default)]
 pub struct DiscoveryOptions {
     pub package: String,
     pub package_name_full: String,
@@ -16,14 +32,301 @@ pub struct DiscoveryOptions {
     pub no_cache: bool,
     pub editable: bool,
     pub source_path: Option<String>,
+    pub homepage: Option<String>,
+    pub license: Option<String>,
+    pub r2x_core_requirement: Option<String>,
+    /// Git URL or local path the package is installed from
+    pub install_source: Option<String>,
+    /// Registry the package is installed from, for `registry:package` installs
+    pub registry: Option<String>,
+    /// Plugins already discovered for this package or its dependencies, used instead
+    /// of running AST discovery again (e.g. when sync discovers packages in parallel)
+    pub prediscovered: HashMap<String, DiscoveredPlugins>,
+    /// Whether packages AST discovery cannot handle are loaded in Python
+    pub mode: DiscoveryMode,
+}
+
+/// Plugins found by AST discovery of one package
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscoveredPlugins {
+    pub plugins: Vec<PluginSpec>,
+    pub decorator_registrations: Vec<DecoratorRegistration>,
+}
+
+impl DiscoveryOptions {
+    /// Build options for a package installed in the configured venv
+    ///
+    /// The version, homepage, license, r2x-core requirement and r2x plugin dependencies
+    /// are read from the package's dist-info `METADATA`, without starting Python.
+    pub fn from_installed_package(
+        package_name: &str,
+        config: &Config,
+    ) -> Result<Self, BridgeError> {
+        let venv_path = PathBuf::from(config.get_venv_path());
+        let site_packages = resolve_site_package_path(&venv_path)?;
+        Self::from_site_packages(package_name, &site_packages)
+    }
+
+    fn from_site_packages(package_name: &str, site_packages: &Path) -> Result<Self, BridgeError> {
+        let dist_info = DistInfo::find(site_packages, package_name).ok_or_else(|| {
+            BridgeError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!(
+                    "No dist-info found for '{}' in {}",
+                    package_name,
+                    site_packages.display()
+                ),
+            ))
+        })?;
+
+        let metadata = dist_info.metadata()?;
+        let mut package_version = None;
+        let mut homepage = None;
+        let mut project_homepage = None;
+        let mut license = None;
+        let mut license_expression = None;
+        let mut r2x_core_requirement = None;
+        let mut dependencies = Vec::new();
+        for line in metadata.lines() {
+            // Headers end at the first blank line; the long description follows
+            if line.is_empty() {
+                break;
+            }
+            if let Some(version) = line.strip_prefix("Version:") {
+                package_version = Some(version.trim().to_string());
+            } else if let Some(url) = line.strip_prefix("Home-page:") {
+                homepage = Some(url.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("Project-URL:") {
+                project_homepage = project_homepage.or_else(|| homepage_project_url(value));
+            } else if let Some(value) = line.strip_prefix("License-Expression:") {
+                license_expression = Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("License:") {
+                license = Some(value.trim().to_string());
+            } else if let Some(requirement) = line.strip_prefix("Requires-Dist:") {
+                let name = requirement
+                    .trim()
+                    .split(|c: char| {
+                        !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+                    })
+                    .next()
+                    .unwrap_or_default();
+                // `extra == ...` markers only apply to optional installs
+                if same_package_name(name, "r2x-core") && !has_extra_marker(requirement) {
+                    r2x_core_requirement =
+                        parse_version_specifier(&requirement.trim()[name.len()..]);
+                }
+                if utils::looks_like_r2x_plugin(name) && !dependencies.iter().any(|d| d == name) {
+                    dependencies.push(name.to_string());
+                }
+            }
+        }
+
+        Ok(DiscoveryOptions {
+            package: package_name.to_string(),
+            package_name_full: package_name.to_string(),
+            dependencies,
+            package_version,
+            homepage: homepage.or(project_homepage),
+            license: license_expression.or(license),
+            r2x_core_requirement,
+            ..Default::default()
+        })
+    }
+}
+
+/// URL of a `Project-URL: <label>, <url>` value labelled as the homepage
+///
+/// Labels are compared after removing punctuation and whitespace, so `Homepage`,
+/// `homepage` and `Home page` all match.
+fn homepage_project_url(value: &str) -> Option<String> {
+    let (label, url) = value.split_once(',')?;
+    let label: String = label
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect();
+    (label == "homepage").then(|| url.trim().to_string())
+}
+
+/// Whether a `Requires-Dist` value is gated on an extra, e.g. `r2x-core ; extra == "dev"`
+fn has_extra_marker(requirement: &str) -> bool {
+    requirement.split_once(';').is_some_and(|(_, marker)| {
+        marker
+            .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .any(|token| token == "extra")
+    })
+}
+
+/// Extract the version specifier from the rest of a `Requires-Dist` line
+///
+/// `(>=0.1.0rc1,<0.2.0) ; python_version >= "3.11"` becomes `>=0.1.0rc1,<0.2.0`.
+/// Returns `None` for an unconstrained requirement.
+fn parse_version_specifier(rest: &str) -> Option<String> {
+    let spec = rest.split(';').next().unwrap_or_default().trim();
+    // Skip extras such as `r2x-core[plexos]>=0.1`
+    let spec = match spec.strip_prefix('[') {
+        Some(after) => after.split_once(']').map_or("", |(_, rest)| rest),
+        None => spec,
+    };
+    let spec: String = spec
+        .trim()
+        .trim_start_matches('(')
+        .trim_end_matches(')')
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    (!spec.is_empty()).then_some(spec)
+}
+
+/// Locate an installed package and run AST discovery on it, without touching the manifest
+pub fn discover_package(
+    package_name_full: &str,
+    package_version: Option<&str>,
+    venv_path: Option<&str>,
+    mode: DiscoveryMode,
+) -> Result<(DiscoveredPlugins, DiscoveryMetrics), String> {
+    let package_path = locate_package(package_name_full)?;
+    discover_at(
+        &package_path,
+        package_name_full,
+        package_version,
+        venv_path,
+        mode,
+    )
+}
+
+/// Like [`discover_package`], reusing `cache` when the package version and every
+/// source file discovery reads are unchanged
+///
+/// Fresh results are added to `cache`; saving it is left to the caller. An empty cached
+/// result is not reused when `mode` may fall back to loading the package in Python.
+pub fn discover_package_cached(
+    package_name_full: &str,
+    package_version: Option<&str>,
+    venv_path: Option<&str>,
+    mode: DiscoveryMode,
+    cache: &PluginAstCache,
+) -> Result<(DiscoveredPlugins, DiscoveryMetrics), String> {
+    let package_path = locate_package(package_name_full)?;
+    let module = AstDiscovery::plugin_module_path(&package_path, package_name_full, venv_path)
+        .ok()
+        .and_then(|path| {
+            let fingerprint = source_fingerprint(&path, package_version)?;
+            Some((path, fingerprint))
+        });
+
+    if let Some((path, fingerprint)) = &module {
+        let cached = cache
+            .get(package_name_full, fingerprint)
+            .filter(|found| mode == DiscoveryMode::AstOnly || !found.plugins.is_empty());
+        if let Some(found) = cached {
+            logger::debug(&format!(
+                "Using cached discovery for '{}'",
+                package_name_full
+            ));
+            let metrics = DiscoveryMetrics {
+                package: package_name_full.to_string(),
+                plugin_count: found.plugins.len(),
+                file_path: path.clone(),
+                ..Default::default()
+            };
+            return Ok((found, metrics));
+        }
+    }
+
+    let (found, metrics) = discover_at(
+        &package_path,
+        package_name_full,
+        package_version,
+        venv_path,
+        mode,
+    )?;
+    if let Some((_, fingerprint)) = &module {
+        cache.insert(package_name_full, fingerprint, &found);
+    }
+    Ok((found, metrics))
+}
+
+/// Checksum of `package_version` and of every Python file next to or below the
+/// plugin module at `module_path`, which are all the files AST discovery reads
+fn source_fingerprint(module_path: &Path, package_version: Option<&str>) -> Option<String> {
+    let root = module_path.parent()?;
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .flatten()
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "py"))
+        .collect();
+    files.sort();
+
+    let mut input = package_version.unwrap_or_default().to_string();
+    for file in files {
+        let content = fs::read_to_string(&file).ok()?;
+        input.push('\0');
+        input.push_str(&file.to_string_lossy());
+        input.push('\0');
+        input.push_str(&content);
+    }
+    Some(integrity::compute_checksum(&input))
+}
+
+fn locate_package(package_name_full: &str) -> Result<PathBuf, String> {
+    let package_path = find_package_path(package_name_full)
+        .map_err(|e| format!("Failed to locate package '{}': {}", package_name_full, e))?;
+
+    logger::debug(&format!(
+        "Found package path for '{}': {:?}",
+        package_name_full, package_path
+    ));
+    Ok(package_path)
+}
+
+fn discover_at(
+    package_path: &Path,
+    package_name_full: &str,
+    package_version: Option<&str>,
+    venv_path: Option<&str>,
+    mode: DiscoveryMode,
+) -> Result<(DiscoveredPlugins, DiscoveryMetrics), String> {
+    let (plugins, decorator_registrations, metrics) = AstDiscovery::discover_plugins(
+        package_path,
+        package_name_full,
+        venv_path,
+        package_version,
+        mode,
+    )
+    .map_err(|e| {
+        format!(
+            "Failed to discover plugins for '{}': {}",
+            package_name_full, e
+        )
+    })?;
+    Ok((
+        DiscoveredPlugins {
+            plugins,
+            decorator_registrations,
+        },
+        metrics,
+    ))
 }
 
 /// Discover and register plugins from a package and its dependencies
 pub fn discover_and_register_entry_points_with_deps(
-    _uv_path: &str,
-    _python_path: &str,
+    installer: &Installer,
+    python_path: &str,
     opts: DiscoveryOptions,
 ) -> Result<usize, String> {
+    discover_and_register_with_metrics(installer, python_path, opts).map(|(count, _)| count)
+}
+
+/// Like [`discover_and_register_entry_points_with_deps`], also returning AST discovery
+/// timings for every package that was analyzed (cached packages are not listed)
+pub fn discover_and_register_with_metrics(
+    _installer: &Installer,
+    _python_path: &str,
+    opts: DiscoveryOptions,
+) -> Result<(usize, BatchDiscoveryMetrics), String> {
+    let mut metrics = BatchDiscoveryMetrics::default();
     let package = &opts.package;
     let package_name_full = &opts.package_name_full;
     let dependencies = &opts.dependencies;
@@ -31,12 +334,15 @@ pub fn discover_and_register_entry_points_with_deps(
     let package_version = opts.package_version.as_deref().unwrap_or("unknown");
 
     // Get venv path from config for entry_points.txt lookup
-    let venv_path = crate::config_manager::Config::load()
-        .ok()
-        .map(|c| c.get_venv_path());
+    let config = Config::load().ok();
+    let venv_path = config.as_ref().map(|c| c.get_venv_path());
+    let confidence_threshold = config.as_ref().map_or(
+        DEFAULT_CONFIDENCE_THRESHOLD,
+        Config::get_confidence_threshold,
+    );
 
     // Load manifest
-    let mut manifest = match Manifest::load() {
+    let mut manifest = match load_manifest() {
         Ok(m) => m,
         Err(e) => {
             logger::warn(&format!("Failed to load manifest: {}", e));
@@ -65,8 +371,10 @@ pub fn discover_and_register_entry_points_with_deps(
         .map(|pkg| !pkg.plugins.is_empty())
         .unwrap_or(false);
 
-    let (discovered_plugins, decorator_regs) =
-        if has_package_cached && !no_cache && cached_has_plugins {
+    let (mut discovered_plugins, decorator_regs) =
+        if let Some(found) = opts.prediscovered.get(package_name_full) {
+            (found.plugins.clone(), found.decorator_registrations.clone())
+        } else if has_package_cached && !no_cache && cached_has_plugins {
             if let Some(pkg) = manifest
                 .packages
                 .iter()
@@ -77,35 +385,38 @@ pub fn discover_and_register_entry_points_with_deps(
                 (Vec::new(), Vec::new())
             }
         } else {
-            let package_path = find_package_path(package_name_full)
-                .map_err(|e| format!("Failed to locate package '{}': {}", package_name_full, e))?;
-
-            logger::debug(&format!(
-                "Found package path for '{}': {:?}",
-                package_name_full, package_path
-            ));
-
-            AstDiscovery::discover_plugins(
-                &package_path,
+            let (found, package_metrics) = discover_package(
                 package_name_full,
-                venv_path.as_deref(),
                 Some(package_version),
-            )
-            .map_err(|e| format!("Failed to discover plugins for '{}': {}", package, e))?
+                venv_path.as_deref(),
+                opts.mode,
+            )?;
+            metrics.push(package_metrics);
+            (found.plugins, found.decorator_registrations)
         };
 
-    for plugin in &discovered_plugins {
+    for plugin in &mut discovered_plugins {
+        if plugin.r2x_core_requirement.is_none() {
+            plugin.r2x_core_requirement = opts.r2x_core_requirement.clone();
+        }
         logger::debug(&format!(
             "Discovered plugin '{}' of kind {:?}",
             plugin.name, plugin.kind
         ));
+        if let Some(confidence) = plugin.confidence.filter(|c| *c < confidence_threshold) {
+            logger::warn(&format!(
+                "Plugin '{}' was only partially understood (confidence {:.2}); \
+                 run `r2x list {} -v` for details",
+                plugin.name, confidence, package_name_full
+            ));
+        }
     }
 
     let mut total_plugins = discovered_plugins.len();
 
     if total_plugins == 0 {
         logger::warn(&format!("No plugins found in package '{}'", package));
-        return Ok(0);
+        return Ok((0, metrics));
     }
 
     logger::debug(&format!(
@@ -114,16 +425,27 @@ pub fn discover_and_register_entry_points_with_deps(
     ));
 
     {
+        // Only set editable fields if they're explicitly requested (e.g., during install).
+        // During sync, merging keeps them and other install metadata from the existing entry.
+        let fresh = Package {
+            name: package_name_full.to_string(),
+            version: opts.package_version.clone(),
+            homepage: opts.homepage.clone(),
+            license: opts.license.clone(),
+            entry_points_dist_info: String::new(),
+            editable_install: opts.editable,
+            pth_file: None,
+            resolved_source_path: opts.source_path.clone().filter(|_| opts.editable),
+            install_source: opts.install_source.clone(),
+            install_type: None,
+            installed_by: Vec::new(),
+            registry: opts.registry.clone(),
+            dependencies: Vec::new(),
+            plugins: discovered_plugins.clone(),
+            decorator_registrations: decorator_regs.clone(),
+        };
         let pkg = manifest.get_or_create_package(package_name_full);
-        pkg.entry_points_dist_info = String::new();
-        pkg.plugins = discovered_plugins.clone();
-        pkg.decorator_registrations = decorator_regs.clone();
-        // Only update editable fields if they're explicitly set (e.g., during install)
-        // During sync, these should be preserved from existing manifest
-        if opts.editable {
-            pkg.editable_install = true;
-            pkg.resolved_source_path = opts.source_path.clone();
-        }
+        *pkg = Package::merge_metadata(pkg, fresh);
     }
     manifest.mark_explicit(package_name_full);
 
@@ -142,7 +464,9 @@ pub fn discover_and_register_entry_points_with_deps(
         manifest.add_dependency(package_name_full, &dep);
 
         let has_dep_cached = manifest.packages.iter().any(|p| p.name == dep);
-        let (dep_plugins, dep_decorators) = if has_dep_cached && !no_cache {
+        let (dep_plugins, dep_decorators) = if let Some(found) = opts.prediscovered.get(&dep) {
+            (found.plugins.clone(), found.decorator_registrations.clone())
+        } else if has_dep_cached && !no_cache {
             if let Some(pkg) = manifest.packages.iter().find(|p| p.name == dep) {
                 (pkg.plugins.clone(), pkg.decorator_registrations.clone())
             } else {
@@ -155,8 +479,12 @@ pub fn discover_and_register_entry_points_with_deps(
                     &dep,
                     venv_path.as_deref(),
                     None,
+                    opts.mode,
                 ) {
-                    Ok(result) => result,
+                    Ok((plugins, decorators, dep_metrics)) => {
+                        metrics.push(dep_metrics);
+                        (plugins, decorators)
+                    }
                     Err(e) => {
                         logger::warn(&format!(
                             "Failed to discover plugins from dependency '{}': {}",
@@ -194,13 +522,124 @@ pub fn discover_and_register_entry_points_with_deps(
         .save()
         .map_err(|e| format!("Failed to save manifest: {}", e))?;
 
-    Ok(total_plugins)
+    Ok((total_plugins, metrics))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_source_fingerprint_tracks_every_module() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let package = dir.path().join("r2x_reeds");
+        fs::create_dir_all(package.join("config")).unwrap();
+        let plugins_py = package.join("plugins.py");
+        fs::write(&plugins_py, "def register_plugin(): ...").unwrap();
+        fs::write(package.join("config").join("models.py"), "class A: ...").unwrap();
+
+        let before = source_fingerprint(&plugins_py, Some("0.1.0")).unwrap();
+        assert_eq!(
+            before,
+            source_fingerprint(&plugins_py, Some("0.1.0")).unwrap()
+        );
+        assert_ne!(
+            before,
+            source_fingerprint(&plugins_py, Some("0.2.0")).unwrap()
+        );
+
+        fs::write(package.join("config").join("models.py"), "class B: ...").unwrap();
+        assert_ne!(
+            before,
+            source_fingerprint(&plugins_py, Some("0.1.0")).unwrap(),
+            "editing a referenced module invalidates the cache"
+        );
+    }
+
+    #[test]
+    fn test_discovery_options_from_dist_info() {
+        let site_packages = tempfile::TempDir::new().unwrap();
+        let dist_info = site_packages.path().join("r2x_plexos-0.3.0.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(
+            dist_info.join("METADATA"),
+            "Metadata-Version: 2.3\n\
+             Name: r2x-plexos\n\
+             Version: 0.3.0\n\
+             Home-page: https://github.com/NREL/r2x-plexos\n\
+             License: BSD-3-Clause\n\
+             Requires-Dist: R2X.Core (>=0.1.0rc1, <0.2.0)\n\
+             Requires-Dist: r2x-core>=0.3; extra == 'next'\n\
+             Requires-Dist: r2x-reeds>=0.1; extra == 'reeds'\n\
+             Requires-Dist: numpy\n\
+             \n\
+             Requires-Dist: r2x-ignored\n",
+        )
+        .unwrap();
+
+        let opts =
+            DiscoveryOptions::from_site_packages("r2x-plexos", site_packages.path()).unwrap();
+        assert_eq!(opts.package_name_full, "r2x-plexos");
+        assert_eq!(opts.package_version.as_deref(), Some("0.3.0"));
+        assert_eq!(opts.dependencies, vec!["r2x-reeds".to_string()]);
+        assert_eq!(
+            opts.homepage.as_deref(),
+            Some("https://github.com/NREL/r2x-plexos")
+        );
+        assert_eq!(opts.license.as_deref(), Some("BSD-3-Clause"));
+        assert_eq!(
+            opts.r2x_core_requirement.as_deref(),
+            Some(">=0.1.0rc1,<0.2.0")
+        );
+        assert!(!opts.no_cache);
+
+        // Core metadata 2.1+ moves the homepage to Project-URL and the SPDX license to
+        // License-Expression
+        fs::write(
+            dist_info.join("METADATA"),
+            "Metadata-Version: 2.4\n\
+             Name: r2x-plexos\n\
+             Version: 0.3.0\n\
+             Project-URL: Documentation, https://nrel.github.io/r2x-plexos\n\
+             Project-URL: Home page, https://github.com/NREL/r2x-plexos\n\
+             License: Full license text\n\
+             License-Expression: BSD-3-Clause\n",
+        )
+        .unwrap();
+        let opts =
+            DiscoveryOptions::from_site_packages("r2x-plexos", site_packages.path()).unwrap();
+        assert_eq!(
+            opts.homepage.as_deref(),
+            Some("https://github.com/NREL/r2x-plexos")
+        );
+        assert_eq!(opts.license.as_deref(), Some("BSD-3-Clause"));
+
+        assert!(DiscoveryOptions::from_site_packages("r2x-missing", site_packages.path()).is_err());
+    }
+
+    #[test]
+    fn test_parse_version_specifier() {
+        assert_eq!(
+            parse_version_specifier(">=0.1.0rc1,<0.2.0 ; python_version >= \"3.11\""),
+            Some(">=0.1.0rc1,<0.2.0".to_string())
+        );
+        assert_eq!(
+            parse_version_specifier("[plexos]>=0.1"),
+            Some(">=0.1".to_string())
+        );
+        assert_eq!(parse_version_specifier(""), None);
+        assert_eq!(parse_version_specifier("; extra == 'dev'"), None);
+
+        assert!(has_extra_marker("r2x-core>=0.3 ; extra == \"dev\""));
+        assert!(has_extra_marker(
+            "r2x-core; python_version>'3.10' and extra=='dev'"
+        ));
+        assert!(!has_extra_marker(
+            "r2x-core>=0.3 ; python_version >= \"3.11\""
+        ));
+        assert!(!has_extra_marker("r2x-core>=0.3"));
+    }
+
     #[test]
     fn test_looks_like_r2x_plugin() {
         assert!(utils::looks_like_r2x_plugin("r2x-reeds"));
@@ -0,0 +1,271 @@
+//! Install-time dependency graph of plugin packages
+//!
+//! Nodes are the packages of the manifest. An edge `a -> b` means `b` was installed
+//! as a dependency of `a`, as recorded in `b.installed_by` (or `a.dependencies`).
+//! A dependency-only package whose incoming edges all come from a removed package is
+//! what `r2x remove` cleans up as an orphan.
+
+use crate::r2x_manifest::Manifest;
+use colored::Colorize;
+use std::collections::{BTreeSet, HashSet};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphNode {
+    pub name: String,
+    pub version: Option<String>,
+    /// Installed by the user rather than as a dependency
+    pub explicit: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    /// Packages in manifest order
+    pub nodes: Vec<GraphNode>,
+    /// `(installer, dependency)` pairs, sorted
+    pub edges: BTreeSet<(String, String)>,
+}
+
+/// Graph of the `installed_by` relationships between the packages of `manifest`
+pub fn build_dependency_graph(manifest: &Manifest) -> DependencyGraph {
+    let nodes = manifest
+        .packages
+        .iter()
+        .map(|pkg| GraphNode {
+            name: pkg.name.clone(),
+            version: pkg.version.clone(),
+            explicit: pkg.install_type.as_deref() != Some("dependency"),
+        })
+        .collect();
+
+    let mut edges = BTreeSet::new();
+    for pkg in &manifest.packages {
+        for installer in &pkg.installed_by {
+            edges.insert((installer.clone(), pkg.name.clone()));
+        }
+        for dep in &pkg.dependencies {
+            edges.insert((pkg.name.clone(), dep.clone()));
+        }
+    }
+
+    DependencyGraph { nodes, edges }
+}
+
+impl DependencyGraph {
+    /// Packages installed as dependencies of `name`
+    pub fn dependencies_of(&self, name: &str) -> Vec<&str> {
+        self.edges
+            .iter()
+            .filter(|(from, _)| from == name)
+            .map(|(_, to)| to.as_str())
+            .collect()
+    }
+
+    /// Packages that `name` was installed as a dependency of
+    pub fn installers_of(&self, name: &str) -> Vec<&str> {
+        self.edges
+            .iter()
+            .filter(|(_, to)| to == name)
+            .map(|(from, _)| from.as_str())
+            .collect()
+    }
+
+    fn node(&self, name: &str) -> Option<&GraphNode> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+
+    /// Render as an indented tree below each package nothing else installed
+    ///
+    /// Explicitly installed packages are bold. A dependency shared by several
+    /// packages is listed under each of them and notes the other installers, since
+    /// removing only one of them leaves it in place.
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        let mut shown = HashSet::new();
+        let roots: Vec<&str> = self
+            .nodes
+            .iter()
+            .map(|node| node.name.as_str())
+            .filter(|name| self.installers_of(name).is_empty())
+            .collect();
+        for root in roots {
+            self.render_subtree(root, None, "", "", &mut Vec::new(), &mut shown, &mut out);
+        }
+        // Packages only reachable through a dependency cycle
+        for node in &self.nodes {
+            if !shown.contains(node.name.as_str()) {
+                self.render_subtree(
+                    &node.name,
+                    None,
+                    "",
+                    "",
+                    &mut Vec::new(),
+                    &mut shown,
+                    &mut out,
+                );
+            }
+        }
+        out
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_subtree<'a>(
+        &'a self,
+        name: &'a str,
+        parent: Option<&str>,
+        branch: &str,
+        indent: &str,
+        path: &mut Vec<&'a str>,
+        shown: &mut HashSet<&'a str>,
+        out: &mut String,
+    ) {
+        shown.insert(name);
+        let node = self.node(name);
+        let mut label = match node.and_then(|node| node.version.as_deref()) {
+            Some(version) => format!("{} {}", name, version.dimmed()),
+            None => name.to_string(),
+        };
+        if node.is_some_and(|node| node.explicit) {
+            label = format!("{}", label.bold());
+        }
+        if node.is_none() {
+            label.push_str(&format!(" {}", "(not installed)".dimmed()));
+        }
+        let others: Vec<&str> = self
+            .installers_of(name)
+            .into_iter()
+            .filter(|installer| Some(*installer) != parent)
+            .collect();
+        if parent.is_some() && !others.is_empty() {
+            label.push_str(&format!(
+                " {}",
+                format!("(also needed by {})", others.join(", ")).dimmed()
+            ));
+        }
+        if path.contains(&name) {
+            out.push_str(&format!(
+                "{}{}{} {}\n",
+                indent,
+                branch,
+                label,
+                "(cycle)".dimmed()
+            ));
+            return;
+        }
+        out.push_str(&format!("{}{}{}\n", indent, branch, label));
+
+        let child_indent = match branch {
+            "├── " => format!("{}│   ", indent),
+            "└── " => format!("{}    ", indent),
+            _ => indent.to_string(),
+        };
+        path.push(name);
+        let children = self.dependencies_of(name);
+        for (index, child) in children.iter().enumerate() {
+            let child_branch = if index + 1 == children.len() {
+                "└── "
+            } else {
+                "├── "
+            };
+            self.render_subtree(
+                child,
+                Some(name),
+                child_branch,
+                &child_indent,
+                path,
+                shown,
+                out,
+            );
+        }
+        path.pop();
+    }
+
+    /// Render in Graphviz DOT format, explicitly installed packages in bold
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph r2x_plugins {\n    rankdir=LR;\n");
+        for node in &self.nodes {
+            let label = match &node.version {
+                Some(version) => format!("{}\\n{}", node.name, version),
+                None => node.name.clone(),
+            };
+            let style = if node.explicit { ", style=bold" } else { "" };
+            dot.push_str(&format!(
+                "    \"{}\" [label=\"{}\"{}];\n",
+                node.name, label, style
+            ));
+        }
+        for (from, to) in &self.edges {
+            dot.push_str(&format!("    \"{}\" -> \"{}\";\n", from, to));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-plexos").version = Some("0.3.0".to_string());
+        manifest.mark_explicit("r2x-plexos");
+        manifest.get_or_create_package("r2x-reeds");
+        manifest.mark_explicit("r2x-reeds");
+        manifest.get_or_create_package("r2x-core");
+        for installer in ["r2x-plexos", "r2x-reeds"] {
+            manifest.mark_dependency("r2x-core", installer);
+            manifest.add_dependency(installer, "r2x-core");
+        }
+        manifest.get_or_create_package("plexosdb");
+        manifest.mark_dependency("plexosdb", "r2x-plexos");
+        manifest
+    }
+
+    #[test]
+    fn test_build_dependency_graph() {
+        let graph = build_dependency_graph(&manifest());
+        assert_eq!(graph.nodes.len(), 4);
+        assert!(graph.nodes[0].explicit);
+        assert!(!graph.nodes[2].explicit);
+        assert_eq!(
+            graph.dependencies_of("r2x-plexos"),
+            vec!["plexosdb", "r2x-core"]
+        );
+        assert_eq!(
+            graph.installers_of("r2x-core"),
+            vec!["r2x-plexos", "r2x-reeds"]
+        );
+    }
+
+    #[test]
+    fn test_render_tree_and_dot() {
+        colored::control::set_override(false);
+        let graph = build_dependency_graph(&manifest());
+        assert_eq!(
+            graph.render_tree(),
+            "r2x-plexos 0.3.0\n\
+             ├── plexosdb\n\
+             └── r2x-core (also needed by r2x-reeds)\n\
+             r2x-reeds\n\
+             └── r2x-core (also needed by r2x-plexos)\n"
+        );
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph r2x_plugins {"));
+        assert!(dot.contains("\"r2x-plexos\" [label=\"r2x-plexos\\n0.3.0\", style=bold];"));
+        assert!(dot.contains("\"plexosdb\" [label=\"plexosdb\"];"));
+        assert!(dot.contains("\"r2x-reeds\" -> \"r2x-core\";"));
+    }
+
+    #[test]
+    fn test_render_tree_with_cycle() {
+        colored::control::set_override(false);
+        let mut manifest = Manifest::default();
+        for (name, installer) in [("a", "b"), ("b", "a")] {
+            manifest.get_or_create_package(name);
+            manifest.mark_dependency(name, installer);
+        }
+        let tree = build_dependency_graph(&manifest).render_tree();
+        assert_eq!(tree, "a\n└── b\n    └── a (cycle)\n");
+    }
+}
@@ -1,16 +1,110 @@
+use crate::config_manager::Config;
 use crate::logger;
+use std::path::Path;
 use std::process::Command;
 
+/// Tool that installs packages into the venv
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Installer {
+    /// uv executable, run as `uv pip <subcommand> --python <venv python>`
+    Uv(String),
+    /// The venv's own pip, used when uv is unavailable
+    Pip(String),
+}
+
+impl Installer {
+    /// Installer available for the configured venv, without installing or configuring anything
+    ///
+    /// Prefers the configured uv, then uv on `PATH`, then the venv pip.
+    pub fn find(config: &Config) -> Option<Self> {
+        config
+            .uv_path
+            .clone()
+            .filter(|path| Path::new(path).exists())
+            .or_else(|| {
+                which::which("uv")
+                    .ok()
+                    .map(|path| path.to_string_lossy().into_owned())
+            })
+            .map(Installer::Uv)
+            .or_else(|| config.ensure_pip_path().ok().map(Installer::Pip))
+    }
+
+    /// Path of the executable
+    pub fn path(&self) -> &str {
+        match self {
+            Installer::Uv(path) | Installer::Pip(path) => path,
+        }
+    }
+
+    pub fn is_pip(&self) -> bool {
+        matches!(self, Installer::Pip(_))
+    }
+
+    /// Command prefix for messages, e.g. `uv pip`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Installer::Uv(_) => "uv pip",
+            Installer::Pip(_) => "pip",
+        }
+    }
+
+    /// A `pip <subcommand>` call acting on the venv of `python_path`
+    ///
+    /// pip always acts on its own venv; uv is pointed at it with `--python`.
+    pub fn pip_command(&self, subcommand: &str, python_path: &str) -> Command {
+        match self {
+            Installer::Uv(uv) => {
+                let mut command = Command::new(uv);
+                command.args(["pip", subcommand, "--python", python_path]);
+                command
+            }
+            Installer::Pip(pip) => {
+                let mut command = Command::new(pip);
+                command.arg(subcommand);
+                command
+            }
+        }
+    }
+
+    /// A `pip install` call acting on the venv of `python_path`, allowing pre-releases
+    pub fn install_command(&self, python_path: &str) -> Command {
+        let mut command = self.pip_command("install", python_path);
+        command.arg(match self {
+            Installer::Uv(_) => "--prerelease=allow",
+            Installer::Pip(_) => "--pre",
+        });
+        command
+    }
+
+    /// `pip install` flag that bypasses the download cache
+    pub fn no_cache_flag(&self) -> &'static str {
+        match self {
+            Installer::Uv(_) => "--no-cache",
+            Installer::Pip(_) => "--no-cache-dir",
+        }
+    }
+
+    /// `pip install` flags that turn off the progress bar
+    pub fn no_progress_flags(&self) -> &'static [&'static str] {
+        match self {
+            Installer::Uv(_) => &["--no-progress"],
+            Installer::Pip(_) => &["--progress-bar", "off"],
+        }
+    }
+}
+
 /// Query package info via a single pip show call.
 /// Returns (version, dependencies) tuple.
 /// Returns (None, empty_vec) on any error (best-effort, non-fatal).
 pub fn get_package_info(
-    uv_path: &str,
+    installer: &Installer,
     python_path: &str,
     package: &str,
 ) -> Result<(Option<String>, Vec<String>), String> {
-    let show_output = Command::new(uv_path)
-        .args(["pip", "show", "--python", python_path, package])
+    let show_output = installer
+        .pip_command("show", python_path)
+        .arg(package)
         .output()
         .map_err(|e| {
             logger::debug(&format!(
@@ -64,8 +158,27 @@ pub fn get_package_info(
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
     #[test]
     fn test_get_package_info() {
         // Test package info extraction
     }
+
+    #[test]
+    fn test_pip_command() {
+        let uv = Installer::Uv("/usr/bin/uv".to_string());
+        let command = uv.pip_command("show", "/venv/bin/python");
+        assert_eq!(command.get_program(), "/usr/bin/uv");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["pip", "show", "--python", "/venv/bin/python"]
+        );
+
+        let pip = Installer::Pip("/venv/bin/pip".to_string());
+        let command = pip.pip_command("show", "/venv/bin/python");
+        assert_eq!(command.get_program(), "/venv/bin/pip");
+        assert_eq!(command.get_args().collect::<Vec<_>>(), ["show"]);
+        assert!(pip.is_pip() && !uv.is_pip());
+    }
 }
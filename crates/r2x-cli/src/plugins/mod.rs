@@ -1,14 +1,20 @@
 // Core plugin infrastructure modules
+pub mod ast_cache;
 pub mod config;
 pub mod discovery;
+pub mod graph;
 pub mod install;
 pub mod package_resolver;
 pub mod package_spec;
+pub mod removal;
+pub mod result_cache;
+pub mod scaffold;
 pub mod utils;
 
 // Re-export public functions from core infrastructure
 pub use install::get_package_info;
 pub use package_resolver::find_package_path;
+pub use removal::{RemovalHooks, RemovalReport};
 // Re-export AstDiscovery from new location
 pub use crate::r2x_ast::AstDiscovery;
 
@@ -3,8 +3,100 @@
 //! Handles locating installed packages in virtual environments,
 //! including support for UV editable installs via .pth files.
 
-use r2x_python::resolve_site_package_path;
+use super::install::Installer;
+use super::utils::looks_like_r2x_plugin;
+use crate::config_manager::{IndexArgs, InstalledPackage};
+use crate::r2x_manifest::dist_info::normalize_package_name;
+use r2x_python::{resolve_site_package_path, BridgeError};
+use serde::Deserialize;
 use std::path::PathBuf;
+
+/// List the r2x plugin packages installed in a venv
+///
+/// Runs `pip list --format json` against the venv of `python_path` and keeps the
+/// packages that look like r2x plugins (see [`looks_like_r2x_plugin`]).
+pub fn list_installed_r2x_packages(
+    installer: &Installer,
+    python_path: &str,
+) -> Result<Vec<InstalledPackage>, BridgeError> {
+    let output = installer
+        .pip_command("list", python_path)
+        .args(["--format", "json"])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BridgeError::Python(format!(
+            "{} list failed: {}",
+            installer.name(),
+            stderr.trim()
+        )));
+    }
+
+    parse_installed_r2x_packages(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `pip list --format json` output, keeping only r2x plugin packages
+fn parse_installed_r2x_packages(json: &str) -> Result<Vec<InstalledPackage>, BridgeError> {
+    let packages: Vec<InstalledPackage> =
+        serde_json::from_str(json).map_err(|e| BridgeError::Serialization(e.to_string()))?;
+    Ok(packages
+        .into_iter()
+        .filter(|pkg| looks_like_r2x_plugin(&pkg.name))
+        .collect())
+}
+
+/// A package reported by `pip list --outdated`
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct OutdatedPackage {
+    pub name: String,
+    pub version: String,
+    pub latest_version: String,
+}
+
+/// List the r2x plugin packages in a venv that have a newer release on the index
+///
+/// Runs `pip list --outdated --format json` against the venv of `python_path`, with
+/// `index` selecting the package index.
+pub fn list_outdated_r2x_packages(
+    installer: &Installer,
+    python_path: &str,
+    index: &IndexArgs,
+) -> Result<Vec<OutdatedPackage>, BridgeError> {
+    let output = installer
+        .pip_command("list", python_path)
+        .args(["--outdated", "--format", "json"])
+        .args(&index.args)
+        .envs(index.env.iter().map(|(key, value)| (key, value)))
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(BridgeError::Python(format!(
+            "{} list --outdated failed: {}",
+            installer.name(),
+            stderr.trim()
+        )));
+    }
+
+    parse_outdated_r2x_packages(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parse `pip list --outdated --format json` output, keeping only r2x plugin packages
+fn parse_outdated_r2x_packages(json: &str) -> Result<Vec<OutdatedPackage>, BridgeError> {
+    let packages: Vec<OutdatedPackage> =
+        serde_json::from_str(json).map_err(|e| BridgeError::Serialization(e.to_string()))?;
+    Ok(packages
+        .into_iter()
+        .filter(|pkg| looks_like_r2x_plugin(&pkg.name))
+        .collect())
+}
+
+/// Whether two distribution names refer to the same package (PEP 503 normalization)
+pub fn same_package_name(a: &str, b: &str) -> bool {
+    normalize_package_name(a) == normalize_package_name(b)
+}
+
 /// Find the path to an installed package
 pub fn find_package_path(package_name_full: &str) -> Result<PathBuf, String> {
     let config = crate::config_manager::Config::load()
@@ -109,3 +201,60 @@ fn try_find_package_via_pth(normalized_package_name: &str) -> Result<PathBuf, St
         normalized_package_name
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_installed_r2x_packages() {
+        let stdout = r#"[
+            {"name": "numpy", "version": "2.1.3"},
+            {"name": "r2x-core", "version": "0.1.0"},
+            {"name": "r2x-reeds", "version": "0.2.0"},
+            {"name": "r2x-plexos", "version": "0.3.1", "editable_project_location": "/src/r2x-plexos"}
+        ]"#;
+
+        let packages = parse_installed_r2x_packages(stdout).unwrap();
+        assert_eq!(
+            packages,
+            vec![
+                InstalledPackage {
+                    name: "r2x-reeds".to_string(),
+                    version: "0.2.0".to_string()
+                },
+                InstalledPackage {
+                    name: "r2x-plexos".to_string(),
+                    version: "0.3.1".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_outdated_r2x_packages() {
+        let stdout = r#"[
+            {"name": "numpy", "version": "2.1.3", "latest_version": "2.2.0", "latest_filetype": "wheel"},
+            {"name": "r2x-reeds", "version": "0.2.0", "latest_version": "0.3.0", "latest_filetype": "wheel"}
+        ]"#;
+
+        let packages = parse_outdated_r2x_packages(stdout).unwrap();
+        assert_eq!(
+            packages,
+            vec![OutdatedPackage {
+                name: "r2x-reeds".to_string(),
+                version: "0.2.0".to_string(),
+                latest_version: "0.3.0".to_string(),
+            }]
+        );
+        assert!(same_package_name("R2X_Reeds", "r2x-reeds"));
+        assert!(same_package_name("r2x.core", "r2x__core"));
+        assert!(!same_package_name("r2x-reeds", "r2x-plexos"));
+    }
+
+    #[test]
+    fn test_parse_installed_r2x_packages_invalid_json() {
+        let result = parse_installed_r2x_packages("not json");
+        assert!(matches!(result, Err(BridgeError::Serialization(_))));
+    }
+}
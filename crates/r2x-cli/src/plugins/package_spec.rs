@@ -1,3 +1,145 @@
+use std::path::{Path, PathBuf};
+
+/// Where `r2x install` gets a package from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallSource {
+    /// A project directory on the local filesystem
+    LocalPath(PathBuf),
+    /// A `git+` URL, including any `@ref`
+    Git(String),
+    /// A package name or requirement resolved from the package index
+    PyPi(String),
+    /// A package from a registry named in the `[registries]` config section,
+    /// given as `registry:package`
+    Registry { registry: String, package: String },
+}
+
+impl InstallSource {
+    /// Classify a package argument, applying the git options to repository sources
+    ///
+    /// Local paths start with `.`, `/`, `~` or a Windows drive letter and reject git options.
+    pub fn resolve(
+        package: &str,
+        host: Option<String>,
+        branch: Option<String>,
+        tag: Option<String>,
+        commit: Option<String>,
+    ) -> Result<Self, String> {
+        let has_git_ref = branch.is_some() || tag.is_some() || commit.is_some();
+
+        if is_local_path(package) {
+            if has_git_ref || host.is_some() {
+                return Err("Cannot use git flags with local paths".to_string());
+            }
+            return Ok(InstallSource::LocalPath(PathBuf::from(expand_tilde(
+                package,
+            ))));
+        }
+
+        // Full URLs (http://, https://, git@, git+)
+        let is_full_url = package.starts_with("http://")
+            || package.starts_with("https://")
+            || package.starts_with("git@")
+            || package.starts_with("git+");
+
+        if is_full_url {
+            // Check if URL already has @ref
+            if package.contains('@') && !package.starts_with("git@") {
+                if has_git_ref {
+                    return Err(
+                        "Cannot use --branch/--tag/--commit with URL that already contains @ref"
+                            .to_string(),
+                    );
+                }
+                return Ok(InstallSource::Git(package.to_string()));
+            }
+
+            // Add git+ prefix if needed
+            let url = if package.starts_with("git+") || package.starts_with("git@") {
+                package.to_string()
+            } else {
+                format!("git+{}", package)
+            };
+            return Ok(InstallSource::Git(add_git_ref(&url, branch, tag, commit)));
+        }
+
+        // org/repo shorthand, only when git flags or a host are given
+        if package.contains('/') && !package.contains('\\') && (host.is_some() || has_git_ref) {
+            let git_host = host.as_deref().unwrap_or("github.com");
+            let url = format!("git+https://{}/{}", git_host, package);
+            return Ok(InstallSource::Git(add_git_ref(&url, branch, tag, commit)));
+        }
+
+        if let Some((registry, name)) = split_registry(package) {
+            if has_git_ref || host.is_some() {
+                return Err("Cannot use git flags with a registry package".to_string());
+            }
+            return Ok(InstallSource::Registry {
+                registry: registry.to_string(),
+                package: name.to_string(),
+            });
+        }
+
+        if has_git_ref || host.is_some() {
+            return Err("Cannot use git flags with PyPI package name".to_string());
+        }
+        Ok(InstallSource::PyPi(package.to_string()))
+    }
+
+    /// Specifier passed to `pip install`
+    pub fn spec(&self) -> String {
+        match self {
+            InstallSource::LocalPath(path) => path.to_string_lossy().into_owned(),
+            InstallSource::Git(spec) | InstallSource::PyPi(spec) => spec.clone(),
+            InstallSource::Registry { package, .. } => package.clone(),
+        }
+    }
+
+    /// Distribution name of the package, read from `pyproject.toml` for local paths
+    pub fn package_name(&self) -> Result<String, String> {
+        match self {
+            InstallSource::LocalPath(path) => extract_name_from_pyproject(path).ok_or_else(|| {
+                format!(
+                    "Failed to extract package name from {}/pyproject.toml",
+                    path.display()
+                )
+            }),
+            InstallSource::Git(url) => Ok(git_repository_name(url)),
+            InstallSource::PyPi(package) | InstallSource::Registry { package, .. } => {
+                Ok(package.clone())
+            }
+        }
+    }
+}
+
+/// Split `registry:package` into its parts
+///
+/// The registry name may only hold ASCII letters, digits, `-` and `_`, so URLs and
+/// Windows paths are not mistaken for registry packages.
+fn split_registry(package: &str) -> Option<(&str, &str)> {
+    let (registry, name) = package.split_once(':')?;
+    let valid_registry = !registry.is_empty()
+        && registry
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    (valid_registry && !name.is_empty() && !name.starts_with('/')).then_some((registry, name))
+}
+
+/// Whether an install argument names a local path rather than a package or URL
+///
+/// Matches `.`/`..` prefixes, absolute Unix paths, `~` and Windows drive letters such as `C:\`.
+pub fn is_local_path(package: &str) -> bool {
+    let bytes = package.as_bytes();
+    let has_drive_letter = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/');
+    package.starts_with('.')
+        || package.starts_with('/')
+        || package.starts_with('~')
+        || has_drive_letter
+}
+
 /// Expand tilde (~) to home directory path (cross-platform)
 /// Works on Windows, macOS, and Linux
 fn expand_tilde(path: &str) -> String {
@@ -27,11 +169,9 @@ fn expand_tilde(path: &str) -> String {
 }
 
 /// Extract package name from pyproject.toml
-fn extract_name_from_pyproject(path: &str) -> Option<String> {
+fn extract_name_from_pyproject(project_path: &Path) -> Option<String> {
     use std::fs;
-    use std::path::Path;
 
-    let project_path = Path::new(path);
     let pyproject_path = project_path.join("pyproject.toml");
 
     if !pyproject_path.exists() {
@@ -63,97 +203,19 @@ fn extract_name_from_pyproject(path: &str) -> Option<String> {
     None
 }
 
-/// Extract package name from package specifier
-pub fn extract_package_name(package: &str) -> Result<String, String> {
-    // Remove git+ prefix if present
-    let pkg = package.strip_prefix("git+").unwrap_or(package);
-
-    // Remove @ref if present
-    let pkg = pkg.split('@').next().unwrap_or(pkg);
-
-    // For URLs, extract the repository name
-    if pkg.contains("://") || pkg.starts_with("git@") {
-        // Extract last path component and remove .git suffix
-        Ok(pkg
-            .split('/')
-            .next_back()
-            .unwrap_or(pkg)
-            .trim_end_matches(".git")
-            .to_string())
-    } else if pkg.contains('/') || pkg.contains('\\') {
-        // For local paths, always read from pyproject.toml
-        extract_name_from_pyproject(pkg)
-            .ok_or_else(|| format!("Failed to extract package name from {}", package))
-    } else {
-        // For PyPI packages, use as-is
-        Ok(pkg.to_string())
-    }
-}
-
-/// Build package specifier for pip install
-/// Handles PyPI packages, local paths, git URLs, and org/repo shorthand
-pub fn build_package_spec(
-    package: &str,
-    host: Option<String>,
-    branch: Option<String>,
-    tag: Option<String>,
-    commit: Option<String>,
-) -> Result<String, String> {
-    // Expand tilde to home directory (cross-platform)
-    let expanded_package = expand_tilde(package);
-    let package = expanded_package.as_str();
-
-    // 1. If it's a local path (starts with ./ or ../ or / or contains file separators like ./packages/)
-    if package.starts_with("./") || package.starts_with("../") || package.starts_with('/') {
-        if branch.is_some() || tag.is_some() || commit.is_some() || host.is_some() {
-            return Err("Cannot use git flags with local paths".to_string());
-        }
-        return Ok(package.to_string());
-    }
-
-    // 2. If it's already a full URL (http://, https://, git@, git+)
-    let is_full_url = package.starts_with("http://")
-        || package.starts_with("https://")
-        || package.starts_with("git@")
-        || package.starts_with("git+");
-
-    if is_full_url {
-        // Check if URL already has @ref
-        if package.contains('@') && !package.starts_with("git@") {
-            if branch.is_some() || tag.is_some() || commit.is_some() {
-                return Err(
-                    "Cannot use --branch/--tag/--commit with URL that already contains @ref"
-                        .to_string(),
-                );
-            }
-            return Ok(package.to_string());
-        }
-
-        // Add git+ prefix if needed
-        let url = if package.starts_with("git+") || package.starts_with("git@") {
-            package.to_string()
-        } else {
-            format!("git+{}", package)
-        };
-
-        return Ok(add_git_ref(&url, branch, tag, commit));
-    }
-
-    // 3. If it contains '/' and looks like org/repo (GitHub shorthand) - only if git flags or host provided
-    if package.contains('/')
-        && !package.contains('\\')
-        && (host.is_some() || branch.is_some() || tag.is_some() || commit.is_some())
-    {
-        let git_host = host.as_deref().unwrap_or("github.com");
-        let url = format!("git+https://{}/{}", git_host, package);
-        return Ok(add_git_ref(&url, branch, tag, commit));
-    }
-
-    // 4. Otherwise, treat as PyPI package name
-    if branch.is_some() || tag.is_some() || commit.is_some() || host.is_some() {
-        return Err("Cannot use git flags with PyPI package name".to_string());
-    }
-    Ok(package.to_string())
+/// Repository name of a git URL, e.g. `r2x-reeds` for `git+https://github.com/nrel/r2x-reeds@main`
+fn git_repository_name(url: &str) -> String {
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    // Drop the @ref, but keep the user of `git@host:org/repo` URLs
+    let url = match url.rsplit_once('@') {
+        Some((base, _)) if base.contains('/') => base,
+        _ => url,
+    };
+    url.rsplit(['/', ':'])
+        .next()
+        .unwrap_or(url)
+        .trim_end_matches(".git")
+        .to_string()
 }
 
 /// Add git ref (@branch, @tag, or @commit) to a URL
@@ -178,56 +240,64 @@ fn add_git_ref(
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_extract_package_name_pypi() {
-        assert_eq!(extract_package_name("r2x-reeds").unwrap(), "r2x-reeds");
-    }
-
-    #[test]
-    fn test_extract_package_name_git_url() {
-        assert_eq!(
-            extract_package_name("git+https://github.com/nrel/r2x-reeds@main").unwrap(),
-            "r2x-reeds"
-        );
+    fn spec(package: &str, branch: Option<&str>) -> Result<String, String> {
+        InstallSource::resolve(package, None, branch.map(str::to_string), None, None)
+            .map(|source| source.spec())
     }
 
     #[test]
-    fn test_extract_package_name_local_path() {
-        // For local paths, always reads from pyproject.toml
-        // Will be None if the path doesn't have a valid pyproject.toml
-        let result = extract_package_name("./packages/r2x-reeds");
-        // Just verify it returns a Result - actual value depends on whether pyproject.toml exists
-        assert!(result.is_ok() || result.is_err());
+    fn test_git_repository_name() {
+        for url in [
+            "git+https://github.com/nrel/r2x-reeds@main",
+            "git+https://github.com/nrel/r2x-reeds.git",
+            "git@github.com:nrel/r2x-reeds.git",
+            "git@github.com:nrel/r2x-reeds.git@v1.0",
+        ] {
+            assert_eq!(git_repository_name(url), "r2x-reeds", "{}", url);
+        }
     }
 
     #[test]
-    fn test_build_package_spec_pypi() {
-        let result = build_package_spec("r2x-reeds", None, None, None, None);
-        assert_eq!(result.unwrap(), "r2x-reeds");
+    fn test_install_source_spec() {
+        assert_eq!(spec("r2x-reeds", None).unwrap(), "r2x-reeds");
+        assert_eq!(
+            spec("./packages/r2x-reeds", None).unwrap(),
+            "./packages/r2x-reeds"
+        );
+        assert!(spec("nrel/r2x-reeds", Some("develop"))
+            .unwrap()
+            .contains("@develop"));
+        assert!(spec("r2x-reeds", Some("main")).is_err());
+        // The spec should be an absolute path (tilde expanded)
+        assert!(!spec("~/some/local/path", None).unwrap().starts_with('~'));
     }
 
     #[test]
-    fn test_build_package_spec_local_path() {
-        let result = build_package_spec("./packages/r2x-reeds", None, None, None, None);
-        assert_eq!(result.unwrap(), "./packages/r2x-reeds");
-    }
+    fn test_install_source_registry() {
+        let source =
+            InstallSource::resolve("internal:r2x-proprietary", None, None, None, None).unwrap();
+        assert_eq!(
+            source,
+            InstallSource::Registry {
+                registry: "internal".to_string(),
+                package: "r2x-proprietary".to_string(),
+            }
+        );
+        assert_eq!(source.spec(), "r2x-proprietary");
+        assert_eq!(source.package_name().unwrap(), "r2x-proprietary");
 
-    #[test]
-    fn test_build_package_spec_with_branch() {
-        let result = build_package_spec(
-            "nrel/r2x-reeds",
+        let result = InstallSource::resolve(
+            "internal:r2x-proprietary",
             None,
-            Some("develop".to_string()),
+            Some("main".to_string()),
             None,
             None,
         );
-        assert!(result.unwrap().contains("@develop"));
-    }
-
-    #[test]
-    fn test_build_package_spec_rejects_git_flags_with_pypi() {
-        let result = build_package_spec("r2x-reeds", None, Some("main".to_string()), None, None);
         assert!(result.is_err());
+        assert!(matches!(
+            InstallSource::resolve("https://github.com/NREL/r2x-reeds", None, None, None, None),
+            Ok(InstallSource::Git(_))
+        ));
     }
 
     #[test]
@@ -251,10 +321,43 @@ mod tests {
     }
 
     #[test]
-    fn test_build_package_spec_with_tilde_path() {
-        let result = build_package_spec("~/some/local/path", None, None, None, None);
-        let spec = result.unwrap();
-        // The spec should be an absolute path (tilde expanded)
-        assert!(!spec.starts_with('~'));
+    fn test_install_source_local_paths() {
+        for path in [
+            ".",
+            "./my-plugin",
+            "../my-plugin",
+            "/abs/path/to/plugin",
+            "C:\\plugins\\r2x-reeds",
+            "D:/r2x-reeds",
+        ] {
+            assert_eq!(
+                InstallSource::resolve(path, None, None, None, None),
+                Ok(InstallSource::LocalPath(PathBuf::from(path))),
+                "{}",
+                path
+            );
+        }
+        assert!(!is_local_path("r2x-reeds"));
+        assert!(!is_local_path("nrel/r2x-reeds"));
+        assert!(InstallSource::resolve(".", None, Some("main".to_string()), None, None).is_err());
+    }
+
+    #[test]
+    fn test_install_source_package_name_from_pyproject() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"r2x-example\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let source = InstallSource::LocalPath(dir.path().to_path_buf());
+        assert_eq!(source.package_name().unwrap(), "r2x-example");
+        assert_eq!(
+            InstallSource::PyPi("r2x-reeds".to_string())
+                .package_name()
+                .unwrap(),
+            "r2x-reeds"
+        );
     }
 }
@@ -0,0 +1,181 @@
+//! Hooks around plugin package removal
+//!
+//! Embedders register Rust hooks globally; packages can also declare a Python
+//! cleanup callable under the `r2x_uninstall` entry point group, which is run
+//! in the venv before the package is uninstalled.
+
+use crate::logger;
+use crate::r2x_manifest::DistInfo;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+/// Hook run before a package is removed, with the package name
+pub type PreRemoveHook = Box<dyn Fn(&str) + Send>;
+
+/// Hook run after a package was removed and the manifest updated
+pub type PostRemoveHook = Box<dyn Fn(&str, &RemovalReport) + Send>;
+
+/// Outcome of removing a package
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RemovalReport {
+    /// Number of plugins removed from the manifest, including orphaned dependencies
+    pub removed_plugins: usize,
+    /// Dependency packages removed because nothing else needed them
+    pub orphaned_dependencies: Vec<String>,
+    /// Whether the package was uninstalled from the venv
+    pub uninstalled: bool,
+}
+
+/// Globally registered removal hooks
+#[derive(Default)]
+pub struct RemovalHooks {
+    pub pre_remove: Vec<PreRemoveHook>,
+    pub post_remove: Vec<PostRemoveHook>,
+}
+
+fn hooks() -> &'static Mutex<RemovalHooks> {
+    static HOOKS: OnceLock<Mutex<RemovalHooks>> = OnceLock::new();
+    HOOKS.get_or_init(|| Mutex::new(RemovalHooks::default()))
+}
+
+impl RemovalHooks {
+    /// Register a hook that runs before every package removal
+    pub fn register_pre(hook: PreRemoveHook) {
+        hooks()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pre_remove
+            .push(hook);
+    }
+
+    /// Register a hook that runs after every package removal
+    pub fn register_post(hook: PostRemoveHook) {
+        hooks()
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .post_remove
+            .push(hook);
+    }
+
+    pub(crate) fn run_pre(package: &str) {
+        let hooks = hooks().lock().unwrap_or_else(PoisonError::into_inner);
+        for hook in &hooks.pre_remove {
+            hook(package);
+        }
+    }
+
+    pub(crate) fn run_post(package: &str, report: &RemovalReport) {
+        let hooks = hooks().lock().unwrap_or_else(PoisonError::into_inner);
+        for hook in &hooks.post_remove {
+            hook(package, report);
+        }
+    }
+}
+
+/// Find the `module:attr` target of a package's `r2x_uninstall` entry point
+pub fn find_uninstall_entry_point(site_packages: &Path, package: &str) -> Option<String> {
+    DistInfo::find(site_packages, package)?
+        .entry_points("r2x_uninstall")
+        .into_iter()
+        .next()
+        .map(|(_, target)| target)
+}
+
+/// Run a package's `r2x_uninstall` entry point with the venv Python
+///
+/// Failures are logged and do not block the removal.
+pub fn run_uninstall_entry_point(python_path: &str, package: &str, target: &str) {
+    let Some((module, attr)) = target.split_once(':') else {
+        logger::warn(&format!(
+            "Ignoring malformed r2x_uninstall entry point for '{}': {}",
+            package, target
+        ));
+        return;
+    };
+
+    // The target is passed as arguments so it is never interpreted as Python source
+    const SCRIPT: &str = "import importlib, sys\n\
+                          obj = importlib.import_module(sys.argv[1])\n\
+                          for part in sys.argv[2].split('.'):\n    obj = getattr(obj, part)\n\
+                          obj()\n";
+    logger::debug(&format!(
+        "Running r2x_uninstall hook for '{}': {}",
+        package, target
+    ));
+
+    match Command::new(python_path)
+        .args(["-c", SCRIPT, module.trim(), attr.trim()])
+        .output()
+    {
+        Ok(output) => {
+            logger::capture_output(&format!("r2x_uninstall hook for {}", package), &output);
+            if !output.status.success() {
+                logger::warn(&format!("r2x_uninstall hook for '{}' failed", package));
+            }
+        }
+        Err(e) => logger::warn(&format!(
+            "Failed to run r2x_uninstall hook for '{}': {}",
+            package, e
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_removal_hooks_record_calls() {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let pre_calls = Arc::clone(&calls);
+        RemovalHooks::register_pre(Box::new(move |package| {
+            pre_calls.lock().unwrap().push(format!("pre:{}", package));
+        }));
+        let post_calls = Arc::clone(&calls);
+        RemovalHooks::register_post(Box::new(move |package, report| {
+            post_calls
+                .lock()
+                .unwrap()
+                .push(format!("post:{}:{}", package, report.removed_plugins));
+        }));
+
+        RemovalHooks::run_pre("r2x-hooktest");
+        let report = RemovalReport {
+            removed_plugins: 2,
+            ..Default::default()
+        };
+        RemovalHooks::run_post("r2x-hooktest", &report);
+
+        let calls = calls.lock().unwrap();
+        let recorded: Vec<&String> = calls
+            .iter()
+            .filter(|call| call.contains("r2x-hooktest"))
+            .collect();
+        assert_eq!(recorded, vec!["pre:r2x-hooktest", "post:r2x-hooktest:2"]);
+    }
+
+    #[test]
+    fn test_find_uninstall_entry_point() {
+        let site_packages = tempfile::TempDir::new().unwrap();
+        let dist_info = site_packages.path().join("r2x_reeds-0.1.2.dist-info");
+        fs::create_dir_all(&dist_info).unwrap();
+        fs::write(
+            dist_info.join("entry_points.txt"),
+            "[r2x_plugin]\nreeds = r2x_reeds.plugins:register\n\n[r2x_uninstall]\ncleanup = r2x_reeds.cleanup:remove_cache\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_uninstall_entry_point(site_packages.path(), "r2x-reeds"),
+            Some("r2x_reeds.cleanup:remove_cache".to_string())
+        );
+        assert_eq!(
+            find_uninstall_entry_point(site_packages.path(), "r2x-plexos"),
+            None
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Cache of plugin results
+//!
+//! Each result is stored as `<key>.json` in the `results` directory of the r2x cache.
+//! The key is the SHA256 of the plugin name, the installed version of its package,
+//! its configuration, its stdin and the modification times of its `store_path` and
+//! every file below it, so upgrading a plugin package or editing any file of its data
+//! store invalidates its results.
+//!
+//! The plugin that produced a result is recorded next to it in `<key>.plugin`, since
+//! the key cannot be traced back to it.
+
+use crate::config_manager::Config;
+use crate::r2x_manifest::atomic::write_atomic;
+use crate::r2x_manifest::integrity;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use walkdir::WalkDir;
+
+/// Directory of the result cache inside the r2x cache directory
+pub const RESULTS_DIR: &str = "results";
+
+/// One stored result, as listed by [`PluginResultCache::entries`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultEntry {
+    pub key: String,
+    /// Plugin that produced the result; `None` for results stored without a name
+    pub plugin: Option<String>,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Plugin outputs keyed on their inputs
+#[derive(Debug, Clone)]
+pub struct PluginResultCache {
+    dir: PathBuf,
+}
+
+impl PluginResultCache {
+    /// Location of the cache in the configured r2x cache directory
+    pub fn default_dir(config: &Config) -> PathBuf {
+        Path::new(&config.get_cache_path()).join(RESULTS_DIR)
+    }
+
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Cache key for running `plugin_name` from version `package_version` of its
+    /// package with `config_json` on `stdin_json`
+    pub fn key(
+        plugin_name: &str,
+        package_version: Option<&str>,
+        config_json: &str,
+        stdin_json: Option<&str>,
+    ) -> String {
+        let store = store_path_fingerprint(config_json).unwrap_or_default();
+        let input = [
+            plugin_name,
+            package_version.unwrap_or(""),
+            config_json,
+            stdin_json.unwrap_or(""),
+            &store,
+        ]
+        .join("\0");
+        integrity::compute_checksum(&input)
+    }
+
+    /// Cached output for `key`
+    pub fn get(&self, key: &str) -> Option<String> {
+        fs::read_to_string(self.entry_path(key)).ok()
+    }
+
+    /// Store the output of `plugin_name` under `key`
+    pub fn insert(&self, key: &str, plugin_name: &str, output: &str) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create {}: {}", self.dir.display(), e))?;
+        // The plugin name goes first so a visible result always has one
+        let path = self.entry_path(key);
+        let plugin_path = path.with_extension("plugin");
+        write_atomic(&plugin_path, plugin_name)
+            .map_err(|e| format!("Failed to write {}: {}", plugin_path.display(), e))?;
+        write_atomic(&path, output)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Every stored result, in no particular order
+    pub fn entries(&self) -> Result<Vec<ResultEntry>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read {}: {}", self.dir.display(), e)),
+        };
+        Ok(entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| {
+                let key = path.file_stem()?.to_string_lossy().to_string();
+                let metadata = fs::metadata(&path).ok()?;
+                Some(ResultEntry {
+                    plugin: fs::read_to_string(path.with_extension("plugin")).ok(),
+                    size: metadata.len(),
+                    modified: metadata.modified().ok(),
+                    key,
+                })
+            })
+            .collect())
+    }
+
+    /// Remove every cached result, returning how many were removed
+    pub fn clear(&self) -> Result<usize, String> {
+        self.clear_older_than(None)
+    }
+
+    /// Remove the results stored before `cutoff` (all of them for `None`), returning
+    /// how many were removed
+    pub fn clear_older_than(&self, cutoff: Option<SystemTime>) -> Result<usize, String> {
+        let mut removed = 0;
+        for entry in self.entries()? {
+            let expired = match (cutoff, entry.modified) {
+                (None, _) => true,
+                (Some(cutoff), Some(modified)) => modified < cutoff,
+                (Some(_), None) => false,
+            };
+            if !expired {
+                continue;
+            }
+            let path = self.entry_path(&entry.key);
+            fs::remove_file(&path)
+                .map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+            let _ = fs::remove_file(path.with_extension("plugin"));
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+}
+
+/// Checksum of the path, size and modification time of the configuration's
+/// `store_path` and of every entry below it
+fn store_path_fingerprint(config_json: &str) -> Option<String> {
+    let config: serde_json::Value = serde_json::from_str(config_json).ok()?;
+    let store_path = config.get("store_path")?.as_str()?;
+    let mut entries: Vec<String> = WalkDir::new(store_path)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+            Some(format!(
+                "{}\0{}\0{}",
+                entry.path().display(),
+                metadata.len(),
+                modified.as_nanos()
+            ))
+        })
+        .collect();
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort();
+    Some(integrity::compute_checksum(&entries.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_result_cache_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let cache = PluginResultCache::new(dir.path().join(RESULTS_DIR));
+        assert_eq!(cache.clear(), Ok(0));
+
+        let key = PluginResultCache::key(
+            "reeds-parser",
+            Some("0.4.0"),
+            r#"{"solve_year": 2030}"#,
+            None,
+        );
+        assert!(cache.get(&key).is_none());
+        cache
+            .insert(&key, "reeds-parser", r#"{"system": 1}"#)
+            .unwrap();
+        assert_eq!(cache.get(&key).as_deref(), Some(r#"{"system": 1}"#));
+        let entries = cache.entries().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].plugin.as_deref(), Some("reeds-parser"));
+        assert_eq!(entries[0].size, 13);
+        assert_eq!(
+            cache.clear_older_than(Some(UNIX_EPOCH)),
+            Ok(0),
+            "results newer than the cutoff are kept"
+        );
+
+        assert_ne!(
+            key,
+            PluginResultCache::key(
+                "reeds-parser",
+                Some("0.4.0"),
+                r#"{"solve_year": 2035}"#,
+                None
+            )
+        );
+        assert_ne!(
+            key,
+            PluginResultCache::key(
+                "reeds-parser",
+                Some("0.4.0"),
+                r#"{"solve_year": 2030}"#,
+                Some("{}")
+            )
+        );
+        assert_ne!(
+            key,
+            PluginResultCache::key(
+                "reeds-parser",
+                Some("0.5.0"),
+                r#"{"solve_year": 2030}"#,
+                None
+            ),
+            "upgrading the package invalidates its results"
+        );
+
+        assert_eq!(cache.clear(), Ok(1));
+        assert!(cache.get(&key).is_none());
+        assert!(!dir
+            .path()
+            .join(RESULTS_DIR)
+            .join(format!("{}.plugin", key))
+            .exists());
+    }
+
+    #[test]
+    fn test_result_cache_key_tracks_store_path() {
+        let dir = TempDir::new().unwrap();
+        let store = dir.path().join("store.h5");
+        let config = serde_json::json!({ "store_path": store }).to_string();
+
+        let missing = PluginResultCache::key("reeds-parser", None, &config, None);
+        fs::write(&store, "v1").unwrap();
+        let written = PluginResultCache::key("reeds-parser", None, &config, None);
+        assert_ne!(missing, written);
+        assert_eq!(
+            written,
+            PluginResultCache::key("reeds-parser", None, &config, None)
+        );
+    }
+
+    #[test]
+    fn test_result_cache_key_tracks_store_directory_contents() {
+        let dir = TempDir::new().unwrap();
+        let store = dir.path().join("store");
+        let nested = store.join("inputs");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("load.csv"), "v1").unwrap();
+        let config = serde_json::json!({ "store_path": store }).to_string();
+
+        let before = PluginResultCache::key("reeds-parser", None, &config, None);
+        fs::write(nested.join("load.csv"), "v2 with more data").unwrap();
+        assert_ne!(
+            before,
+            PluginResultCache::key("reeds-parser", None, &config, None),
+            "editing a nested file invalidates the result"
+        );
+    }
+}
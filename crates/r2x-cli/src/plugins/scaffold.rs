@@ -0,0 +1,267 @@
+//! Skeletons for new r2x plugin packages
+//!
+//! Generates a `src`-layout Python package with an `r2x_plugin` entry point pointing at
+//! `register_plugin()`, so the result can be installed and discovered right away.
+
+use crate::r2x_manifest::PluginKind;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Create the skeleton of plugin package `name` under `parent`
+///
+/// Returns the package directory. Fails when it already exists, so existing work is
+/// never overwritten.
+pub fn scaffold_plugin(parent: &Path, name: &str, kind: &PluginKind) -> Result<PathBuf, String> {
+    validate_name(name)?;
+    let plugins_py = plugins_module(name, kind)?;
+
+    let root = parent.join(name);
+    if root.exists() {
+        return Err(format!("{} already exists", root.display()));
+    }
+
+    let module = module_name(name);
+    let files = [
+        (PathBuf::from("pyproject.toml"), pyproject(name, kind)),
+        (
+            Path::new("src").join(&module).join("__init__.py"),
+            format!("\"\"\"{} r2x plugin.\"\"\"\n", name),
+        ),
+        (
+            Path::new("src").join(&module).join("plugins.py"),
+            plugins_py,
+        ),
+        (
+            Path::new("tests").join("test_plugins.py"),
+            test_module(name, &module),
+        ),
+    ];
+    for (relative, content) in files {
+        let path = root.join(relative);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        fs::write(&path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    Ok(root)
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid package name '{}': use letters, digits, '-' and '_', starting with a letter",
+            name
+        ))
+    }
+}
+
+/// Python import name of the package, e.g. `r2x_foo` for `r2x-foo`
+pub fn module_name(name: &str) -> String {
+    name.replace('-', "_").to_lowercase()
+}
+
+/// Class name stem without the `r2x` prefix, e.g. `MyModel` for `r2x-my-model`
+fn class_stem(name: &str) -> String {
+    let short = short_name(name);
+    short
+        .split(['-', '_'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Package name without the `r2x-` prefix, used for plugin names
+fn short_name(name: &str) -> &str {
+    name.strip_prefix("r2x-")
+        .or_else(|| name.strip_prefix("r2x_"))
+        .filter(|short| !short.is_empty())
+        .unwrap_or(name)
+}
+
+fn pyproject(name: &str, kind: &PluginKind) -> String {
+    let module = module_name(name);
+    format!(
+        r#"[build-system]
+requires = ["hatchling"]
+build-backend = "hatchling.build"
+
+[project]
+name = "{name}"
+version = "0.1.0"
+description = "r2x {kind} plugin"
+requires-python = ">=3.11"
+dependencies = ["r2x-core"]
+
+[project.entry-points."r2x_plugin"]
+{name} = "{module}.plugins:register_plugin"
+
+[tool.hatch.build.targets.wheel]
+packages = ["src/{module}"]
+"#,
+        kind = kind.as_str(),
+    )
+}
+
+/// `plugins.py` declaring one plugin of `kind` and the `register_plugin()` entry point
+///
+/// Plugin classes subclass the matching r2x-core base, which provides the constructor
+/// and the entry method r2x calls.
+fn plugins_module(name: &str, kind: &PluginKind) -> Result<String, String> {
+    let stem = class_stem(name);
+    let short = short_name(name);
+    let (base, definition, registration) = match kind {
+        PluginKind::Parser => (
+            Some("BaseParser"),
+            format!(
+                r#"class {stem}Parser(BaseParser):
+    """Build a System from {name} input data."""
+
+    def build_system_components(self):
+        raise NotImplementedError
+
+    def build_time_series(self):
+        raise NotImplementedError"#
+            ),
+            format!(
+                r#"ParserPlugin(
+                name="{short}-parser",
+                obj={stem}Parser,
+                call_method="build_system",
+            )"#
+            ),
+        ),
+        PluginKind::Exporter => (
+            Some("BaseExporter"),
+            format!(
+                r#"class {stem}Exporter(BaseExporter):
+    """Write a System in the {name} format."""
+
+    def export(self):
+        raise NotImplementedError"#
+            ),
+            format!(
+                r#"ExporterPlugin(
+                name="{short}-exporter",
+                obj={stem}Exporter,
+                call_method="export",
+            )"#
+            ),
+        ),
+        PluginKind::Modifier => (
+            None,
+            r#"def modify_system(system):
+    """Modify the System in place and return it."""
+    return system"#
+                .to_string(),
+            format!(
+                r#"ModifierPlugin(
+                name="{short}-modifier",
+                obj=modify_system,
+            )"#
+            ),
+        ),
+        PluginKind::Upgrader => (
+            Some("BaseUpgrader"),
+            format!(
+                r#"class {stem}Upgrader(BaseUpgrader):
+    """Upgrade {name} input data to the current format.""""#
+            ),
+            format!(
+                r#"UpgraderPlugin(
+                name="{short}-upgrader",
+                obj={stem}Upgrader,
+            )"#
+            ),
+        ),
+        other => {
+            return Err(format!(
+                "Cannot scaffold a {} plugin; use parser, exporter, modifier or upgrader",
+                other.as_str()
+            ))
+        }
+    };
+    let plugin_class = registration.split('(').next().unwrap_or_default();
+    let mut imports: Vec<&str> = base.into_iter().chain(["Package", plugin_class]).collect();
+    imports.sort_unstable();
+    let imports = imports.join(", ");
+
+    Ok(format!(
+        r#""""Plugins provided by {name}."""
+
+from r2x_core import {imports}
+
+
+{definition}
+
+
+def register_plugin() -> Package:
+    return Package(
+        name="{name}",
+        plugins=[
+            {registration},
+        ],
+    )
+"#
+    ))
+}
+
+fn test_module(name: &str, module: &str) -> String {
+    format!(
+        r#"from {module}.plugins import register_plugin
+
+
+def test_register_plugin():
+    package = register_plugin()
+    assert package.name == "{name}"
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scaffold_parser_package() {
+        let dir = TempDir::new().unwrap();
+        let root = scaffold_plugin(dir.path(), "r2x-my-model", &PluginKind::Parser).unwrap();
+
+        let pyproject = fs::read_to_string(root.join("pyproject.toml")).unwrap();
+        assert!(pyproject.contains("[project.entry-points.\"r2x_plugin\"]"));
+        assert!(pyproject.contains("r2x-my-model = \"r2x_my_model.plugins:register_plugin\""));
+
+        let plugins = fs::read_to_string(root.join("src/r2x_my_model/plugins.py")).unwrap();
+        assert!(plugins.contains("from r2x_core import BaseParser, Package, ParserPlugin"));
+        assert!(plugins.contains("class MyModelParser(BaseParser):"));
+        assert!(plugins.contains("name=\"my-model-parser\""));
+        assert!(root.join("src/r2x_my_model/__init__.py").is_file());
+        assert!(root.join("tests/test_plugins.py").is_file());
+    }
+
+    #[test]
+    fn test_scaffold_rejects_bad_input() {
+        let dir = TempDir::new().unwrap();
+        assert!(scaffold_plugin(dir.path(), "1bad name", &PluginKind::Parser).is_err());
+        assert!(scaffold_plugin(dir.path(), "r2x-util", &PluginKind::Utility).is_err());
+        assert!(!dir.path().join("r2x-util").exists());
+
+        scaffold_plugin(dir.path(), "r2x-twice", &PluginKind::Modifier).unwrap();
+        assert!(scaffold_plugin(dir.path(), "r2x-twice", &PluginKind::Exporter).is_err());
+    }
+}
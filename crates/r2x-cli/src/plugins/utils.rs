@@ -1,6 +1,23 @@
 //! Shared utility functions for plugin discovery
 
+use crate::errors::ManifestError;
+use crate::logger;
+use crate::r2x_manifest::Manifest;
+
 /// Check if a dependency looks like an r2x plugin (exclude the shared runtime)
 pub fn looks_like_r2x_plugin(dep: &str) -> bool {
     dep.starts_with("r2x-") && dep != "r2x-core"
 }
+
+/// Load the manifest, falling back to the backup from the previous save
+///
+/// Warns when the backup was used, since it may be missing recent changes.
+pub fn load_manifest() -> Result<Manifest, ManifestError> {
+    let (manifest, used_backup) = Manifest::load_with_fallback()?;
+    if used_backup {
+        logger::warn(
+            "Loaded backup manifest from previous session; run `r2x sync` to refresh plugin registrations",
+        );
+    }
+    Ok(manifest)
+}
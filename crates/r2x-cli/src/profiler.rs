@@ -0,0 +1,169 @@
+//! Wall-time profiling of plugin invocations
+//!
+//! A disabled [`Profiler`] holds no storage and [`Profiler::time`] only calls the
+//! closure, so runs without `--profile` pay nothing beyond a branch.
+
+use crate::logger::{self, LogFormat};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Wall time of one pipeline step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StepTiming {
+    /// 1-based position of the step in the pipeline
+    pub step: usize,
+    pub plugin: String,
+    pub duration_ms: u64,
+}
+
+/// Step timings of a profiled run, in execution order
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub steps: Vec<StepTiming>,
+}
+
+impl Profile {
+    pub fn total_ms(&self) -> u64 {
+        self.steps.iter().map(|step| step.duration_ms).sum()
+    }
+
+    /// Table rows `(step, plugin, duration, % of total)`, slowest step first
+    pub fn rows(&self) -> Vec<(usize, &str, Duration, f64)> {
+        let total = self.total_ms();
+        let mut rows: Vec<_> = self
+            .steps
+            .iter()
+            .map(|timing| {
+                let share = if total == 0 {
+                    0.0
+                } else {
+                    timing.duration_ms as f64 * 100.0 / total as f64
+                };
+                (
+                    timing.step,
+                    timing.plugin.as_str(),
+                    Duration::from_millis(timing.duration_ms),
+                    share,
+                )
+            })
+            .collect();
+        rows.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+        rows
+    }
+
+    /// Print the timing table on stderr, keeping stdout for plugin output
+    ///
+    /// With JSON console logs the profile is written as one `{"profile": ...}` line.
+    pub fn print_table(&self) {
+        if logger::get_log_format() == LogFormat::Json {
+            eprintln!("{}", serde_json::json!({ "profile": self }));
+            return;
+        }
+
+        let width = self
+            .steps
+            .iter()
+            .map(|timing| timing.plugin.len())
+            .max()
+            .unwrap_or(0)
+            .max("plugin".len());
+        eprintln!(
+            "\n{:>4} | {:<width$} | {:>10} | {:>10}",
+            "step",
+            "plugin",
+            "duration",
+            "% of total",
+            width = width
+        );
+        eprintln!("{}", "-".repeat(width + 35));
+        for (step, plugin, duration, share) in self.rows() {
+            eprintln!(
+                "{:>4} | {:<width$} | {:>10} | {:>9.1}%",
+                step,
+                plugin,
+                format!("{:.3}s", duration.as_secs_f64()),
+                share,
+                width = width
+            );
+        }
+    }
+}
+
+/// Collects step timings when enabled
+#[derive(Debug, Default)]
+pub struct Profiler {
+    profile: Option<Profile>,
+}
+
+impl Profiler {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            profile: enabled.then(Profile::default),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.profile.is_some()
+    }
+
+    /// Run `f`, recording its wall time as step `step` when profiling
+    pub fn time<T>(&mut self, step: usize, plugin: &str, f: impl FnOnce() -> T) -> T {
+        let Some(profile) = self.profile.as_mut() else {
+            return f();
+        };
+        let start = Instant::now();
+        let result = f();
+        profile.steps.push(StepTiming {
+            step,
+            plugin: plugin.to_string(),
+            duration_ms: start.elapsed().as_millis() as u64,
+        });
+        result
+    }
+
+    /// Collected timings; `None` when profiling is disabled
+    pub fn finish(self) -> Option<Profile> {
+        self.profile
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_profiler_records_nothing() {
+        let mut profiler = Profiler::new(false);
+        assert_eq!(profiler.time(1, "parser", || 42), 42);
+        assert!(!profiler.is_enabled());
+        assert_eq!(profiler.finish(), None);
+    }
+
+    #[test]
+    fn test_profile_rows() {
+        let mut profiler = Profiler::new(true);
+        profiler.time(1, "parser", || ());
+        let mut profile = profiler.finish().unwrap();
+        assert_eq!(profile.steps.len(), 1);
+        assert_eq!(profile.steps[0].plugin, "parser");
+
+        profile.steps = vec![
+            StepTiming {
+                step: 1,
+                plugin: "parser".to_string(),
+                duration_ms: 250,
+            },
+            StepTiming {
+                step: 2,
+                plugin: "exporter".to_string(),
+                duration_ms: 750,
+            },
+        ];
+        let rows = profile.rows();
+        assert_eq!(profile.total_ms(), 1000);
+        assert_eq!(rows[0].0, 2);
+        assert_eq!(rows[0].1, "exporter");
+        assert!((rows[0].3 - 75.0).abs() < f64::EPSILON);
+        assert_eq!(rows[1].1, "parser");
+    }
+}
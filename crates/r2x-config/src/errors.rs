@@ -0,0 +1,22 @@
+use std::fmt;
+use thiserror::Error;
+
+/// Config values that are set but unusable
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    #[error("Invalid {key}: {message}")]
+    InvalidValue { key: String, message: String },
+}
+
+/// A non-fatal finding from [`crate::Config::validate`], e.g. an optional key left unset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    pub key: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
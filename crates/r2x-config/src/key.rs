@@ -0,0 +1,187 @@
+//! Typed config values keyed by their `r2x config` key name
+
+use std::path::PathBuf;
+
+/// A config value together with the key it belongs to
+///
+/// Versions are checked when parsed, so a `ConfigKey` always holds a usable value.
+/// List keys collect values: setting `TrustedHosts` appends hosts not yet present.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigKey {
+    CachePath(PathBuf),
+    UvPath(PathBuf),
+    /// `major.minor` or `major.minor.patch`
+    PythonVersion(String),
+    VenvPath(PathBuf),
+    /// A bare version or PEP 440 specifier such as `>=0.1.0,<0.2.0`
+    R2xCoreVersion(String),
+    IndexUrl(String),
+    TrustedHosts(Vec<String>),
+    AutoRepair(bool),
+    FallbackToRuntime(bool),
+    /// Seconds a plugin may run when it declares no timeout of its own
+    PluginTimeoutSecs(u64),
+    /// Seconds cached plugin discovery results stay valid; 0 keeps them forever
+    CacheTtlSecs(u64),
+    /// Discovery confidence below which plugins are reported as partially understood
+    ConfidenceThreshold(f32),
+}
+
+impl ConfigKey {
+    /// Key names accepted by [`ConfigKey::parse`]
+    pub const NAMES: &'static [&'static str] = &[
+        "cache-path",
+        "uv-path",
+        "python-version",
+        "venv-path",
+        "r2x-core-version",
+        "index-url",
+        "trusted-host",
+        "auto-repair",
+        "fallback-to-runtime",
+        "plugin-timeout-secs",
+        "cache-ttl-secs",
+        "confidence-threshold",
+    ];
+
+    /// Key name as used by `r2x config set`, e.g. `venv-path`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigKey::CachePath(_) => "cache-path",
+            ConfigKey::UvPath(_) => "uv-path",
+            ConfigKey::PythonVersion(_) => "python-version",
+            ConfigKey::VenvPath(_) => "venv-path",
+            ConfigKey::R2xCoreVersion(_) => "r2x-core-version",
+            ConfigKey::IndexUrl(_) => "index-url",
+            ConfigKey::TrustedHosts(_) => "trusted-host",
+            ConfigKey::AutoRepair(_) => "auto-repair",
+            ConfigKey::FallbackToRuntime(_) => "fallback-to-runtime",
+            ConfigKey::PluginTimeoutSecs(_) => "plugin-timeout-secs",
+            ConfigKey::CacheTtlSecs(_) => "cache-ttl-secs",
+            ConfigKey::ConfidenceThreshold(_) => "confidence-threshold",
+        }
+    }
+
+    /// Parse a string value for the key `name`
+    ///
+    /// Returns `Ok(None)` for unknown keys and an error when the value is invalid
+    /// for the key.
+    pub fn parse(name: &str, value: &str) -> Result<Option<Self>, String> {
+        let key = match name {
+            "cache-path" => ConfigKey::CachePath(PathBuf::from(value)),
+            "uv-path" => ConfigKey::UvPath(PathBuf::from(value)),
+            "python-version" => {
+                if !crate::is_python_version(value) {
+                    return Err(format!(
+                        "Invalid python version '{}': expected a version like 3.12 or 3.12.1",
+                        value
+                    ));
+                }
+                ConfigKey::PythonVersion(value.to_string())
+            }
+            "venv-path" => ConfigKey::VenvPath(PathBuf::from(value)),
+            "r2x-core-version" => {
+                crate::Config::validate_r2x_core_version_spec(value)?;
+                ConfigKey::R2xCoreVersion(value.to_string())
+            }
+            "index-url" => ConfigKey::IndexUrl(value.to_string()),
+            "trusted-host" => ConfigKey::TrustedHosts(vec![value.to_string()]),
+            "auto-repair" => ConfigKey::AutoRepair(value.parse().map_err(|_| {
+                format!(
+                    "Invalid auto-repair value '{}': expected true or false",
+                    value
+                )
+            })?),
+            "fallback-to-runtime" => ConfigKey::FallbackToRuntime(value.parse().map_err(|_| {
+                format!(
+                    "Invalid fallback-to-runtime value '{}': expected true or false",
+                    value
+                )
+            })?),
+            "plugin-timeout-secs" => ConfigKey::PluginTimeoutSecs(
+                value
+                    .parse()
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid plugin-timeout-secs value '{}': expected a positive number of seconds",
+                            value
+                        )
+                    })?,
+            ),
+            "cache-ttl-secs" => ConfigKey::CacheTtlSecs(value.parse().map_err(|_| {
+                format!(
+                    "Invalid cache-ttl-secs value '{}': expected a number of seconds",
+                    value
+                )
+            })?),
+            "confidence-threshold" => ConfigKey::ConfidenceThreshold(
+                value
+                    .parse()
+                    .ok()
+                    .filter(|threshold| (0.0..=1.0).contains(threshold))
+                    .ok_or_else(|| {
+                        format!(
+                            "Invalid confidence-threshold value '{}': expected a number between 0 and 1",
+                            value
+                        )
+                    })?,
+            ),
+            _ => return Ok(None),
+        };
+        Ok(Some(key))
+    }
+
+    /// The value as stored in the config file; lists are comma-separated
+    pub fn value_string(&self) -> String {
+        match self {
+            ConfigKey::CachePath(path) | ConfigKey::UvPath(path) | ConfigKey::VenvPath(path) => {
+                path.to_string_lossy().into_owned()
+            }
+            ConfigKey::PythonVersion(value)
+            | ConfigKey::R2xCoreVersion(value)
+            | ConfigKey::IndexUrl(value) => value.clone(),
+            ConfigKey::TrustedHosts(values) => values.join(","),
+            ConfigKey::AutoRepair(value) | ConfigKey::FallbackToRuntime(value) => value.to_string(),
+            ConfigKey::PluginTimeoutSecs(secs) | ConfigKey::CacheTtlSecs(secs) => secs.to_string(),
+            ConfigKey::ConfidenceThreshold(threshold) => threshold.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trip() {
+        for name in ConfigKey::NAMES {
+            let value = match *name {
+                "python-version" => "3.12",
+                "r2x-core-version" => ">=0.1.0,<0.2.0",
+                "auto-repair" | "fallback-to-runtime" => "true",
+                "plugin-timeout-secs" | "cache-ttl-secs" => "600",
+                "confidence-threshold" => "0.5",
+                _ => "value",
+            };
+            let key = ConfigKey::parse(name, value).unwrap().unwrap();
+            assert_eq!(key.name(), *name);
+            assert_eq!(key.value_string(), value);
+        }
+
+        assert_eq!(ConfigKey::parse("verbosity", "1"), Ok(None));
+        assert!(ConfigKey::parse("python-version", "python3").is_err());
+        assert!(ConfigKey::parse("r2x-core-version", "0.1.0rc").is_err());
+        assert!(ConfigKey::parse("auto-repair", "sometimes").is_err());
+        assert!(ConfigKey::parse("plugin-timeout-secs", "0").is_err());
+        assert!(ConfigKey::parse("plugin-timeout-secs", "10m").is_err());
+        assert_eq!(
+            ConfigKey::parse("cache-ttl-secs", "0"),
+            Ok(Some(ConfigKey::CacheTtlSecs(0)))
+        );
+        assert!(ConfigKey::parse("cache-ttl-secs", "1h").is_err());
+        assert!(ConfigKey::parse("confidence-threshold", "1.5").is_err());
+        assert!(ConfigKey::parse("confidence-threshold", "high").is_err());
+    }
+}
@@ -2,9 +2,47 @@ use colored::Colorize;
 use indicatif::ProgressBar;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
+use std::panic::Location;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Mutex;
 
+/// Environment variable selecting the console log format (`human` or `json`)
+pub const LOG_FORMAT_ENV: &str = "R2X_LOG_FORMAT";
+
+/// How log messages are written to the console
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Colored text
+    #[default]
+    Human,
+    /// One JSON object per line: `{"level", "ts", "msg", "module"}`
+    Json,
+}
+
+impl LogFormat {
+    /// Format set through [`LOG_FORMAT_ENV`], if it holds a known value
+    pub fn from_env() -> Option<Self> {
+        std::env::var(LOG_FORMAT_ENV).ok()?.parse().ok()
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "human" => Ok(LogFormat::Human),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "Unknown log format '{}'; expected 'human' or 'json'",
+                other
+            )),
+        }
+    }
+}
+
+static LOG_FORMAT: Mutex<LogFormat> = Mutex::new(LogFormat::Human);
 static LOG_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
 static VERBOSITY: Mutex<u8> = Mutex::new(0);
 static LOG_PYTHON: Mutex<bool> = Mutex::new(false);
@@ -41,6 +79,18 @@ pub fn set_no_stdout(disabled: bool) {
     }
 }
 
+/// Get the console log format
+pub fn get_log_format() -> LogFormat {
+    LOG_FORMAT.lock().ok().map(|v| *v).unwrap_or_default()
+}
+
+/// Set the console log format
+pub fn set_log_format(format: LogFormat) {
+    if let Ok(mut v) = LOG_FORMAT.lock() {
+        *v = format;
+    }
+}
+
 /// Get the current plugin name being executed
 pub fn get_current_plugin() -> Option<String> {
     CURRENT_PLUGIN.lock().ok().and_then(|guard| guard.clone())
@@ -131,61 +181,135 @@ fn write_to_log_with_source(message: &str, source: &str) {
     }
 }
 
+/// JSON log line for the console
+fn json_line(level: &str, message: &str, module: &str) -> String {
+    serde_json::json!({
+        "level": level,
+        "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        "msg": message,
+        "module": module,
+    })
+    .to_string()
+}
+
+/// Write a message to the console as `human`, or as JSON in [`LogFormat::Json`]
+fn emit(level: &str, message: &str, location: &Location<'_>, human: impl FnOnce() -> String) {
+    match get_log_format() {
+        LogFormat::Human => eprintln!("{}", human()),
+        LogFormat::Json => eprintln!("{}", json_line(level, message, location.file())),
+    }
+}
+
 /// Log an informational message (to console if verbose >= 1, always to file)
+#[track_caller]
 pub fn info(message: &str) {
     write_to_log(&format!("INFO {}", message));
     if get_verbosity() >= 1 {
-        eprintln!("{}", message);
+        emit("INFO", message, Location::caller(), || message.to_string());
     }
 }
 
 /// Log a debug message (to console if verbose >= 1, always to file)
+#[track_caller]
 pub fn debug(message: &str) {
     write_to_log(&format!("DEBUG {}", message));
-    if get_verbosity() >= 1 {
-        eprintln!("{} {}", "DEBUG:".blue().bold(), message);
-    }
+    debug_console_only(message);
 }
 
 /// Log a debug message to console only (not to file)
+#[track_caller]
 pub fn debug_console_only(message: &str) {
     if get_verbosity() >= 1 {
-        eprintln!("{} {}", "DEBUG:".blue().bold(), message);
+        emit("DEBUG", message, Location::caller(), || {
+            format!("{} {}", "DEBUG:".blue().bold(), message)
+        });
     }
 }
 
 /// Log a warning message (to both file and console)
+#[track_caller]
 pub fn warn(message: &str) {
     write_to_log(&format!("WARN {}", message));
-    eprintln!("{} {}", "warning:".yellow().bold(), message);
+    emit("WARN", message, Location::caller(), || {
+        format!("{} {}", "warning:".yellow().bold(), message)
+    });
 }
 
 /// Log an error message (to both file and console)
+#[track_caller]
 pub fn error(message: &str) {
     write_to_log(&format!("ERROR {}", message));
-    eprintln!("{} {}", "Error:".red().bold(), message);
+    emit("ERROR", message, Location::caller(), || {
+        format!("{} {}", "Error:".red().bold(), message)
+    });
 }
 
 /// Log a success message (to console only for user feedback)
+#[track_caller]
 pub fn success(message: &str) {
     write_to_log(&format!("SUCCESS {}", message));
-    let check = "\u{2714}".green().bold(); // 🗸 HEAVY CHECK MARK
-    eprintln!("{} {}", check, message);
+    emit("SUCCESS", message, Location::caller(), || {
+        let check = "\u{2714}".green().bold(); // 🗸 HEAVY CHECK MARK
+        format!("{} {}", check, message)
+    });
+}
+
+/// Show a hint on how to fix the error just reported (to console only)
+#[track_caller]
+pub fn help(message: &str) {
+    emit("HELP", message, Location::caller(), || {
+        format!("{} {}", "help:".cyan().bold(), message)
+    });
+}
+
+/// Show a Python traceback, as one record in [`LogFormat::Json`] (to console only)
+#[track_caller]
+pub fn traceback(traceback: &str) {
+    let traceback = traceback.trim_end();
+    emit("TRACEBACK", traceback, Location::caller(), || {
+        format!("\n{}", traceback)
+    });
+}
+
+/// Write a record from Python's logger to the console as a JSON line
+///
+/// Python keeps its own text format in [`LogFormat::Human`], so nothing is written
+/// then. The record already went to the log file through Python's own sink.
+pub fn python_record(level: &str, message: &str, module: &str) {
+    if get_log_format() == LogFormat::Json {
+        eprintln!("{}", json_line(level, message, module));
+    }
 }
 
 /// Log a step message (important user-facing step)
+#[track_caller]
 pub fn step(message: &str) {
     if get_verbosity() >= 2 {
-        eprintln!("TRACE: {}", message);
+        emit("TRACE", message, Location::caller(), || {
+            format!("TRACE: {}", message)
+        });
     }
     write_to_log(&format!("STEP: {}", message));
 }
 
 /// Capture command output and log it
+///
+/// In [`LogFormat::Json`] the output is also written to the console as
+/// `{"cmd", "stdout", "stderr", "exit_code"}` when verbose >= 1.
 pub fn capture_output(command_name: &str, output: &std::process::Output) {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
 
+    if get_log_format() == LogFormat::Json && get_verbosity() >= 1 {
+        let record = serde_json::json!({
+            "cmd": command_name,
+            "stdout": stdout,
+            "stderr": stderr,
+            "exit_code": output.status.code(),
+        });
+        eprintln!("{}", record);
+    }
+
     write_to_log(&format!(
         "COMMAND: {} (exit code: {:?})",
         command_name,
@@ -230,8 +354,8 @@ pub fn show_log_path() {
 
 /// Start a spinner with the given message (only if not verbose)
 pub fn spinner_start(message: &str) {
-    // Don't show spinner in verbose mode
-    if get_verbosity() > 0 {
+    // Don't show spinner in verbose mode or between JSON log lines
+    if get_verbosity() > 0 || get_log_format() == LogFormat::Json {
         return;
     }
 
@@ -251,6 +375,7 @@ pub fn spinner_start(message: &str) {
 }
 
 /// Complete the spinner with a success message
+#[track_caller]
 pub fn spinner_success(message: &str) {
     if let Ok(mut spinner_guard) = SPINNER.lock() {
         if let Some(spinner) = spinner_guard.take() {
@@ -258,10 +383,13 @@ pub fn spinner_success(message: &str) {
         }
     }
     // Show success message with checkmark
-    eprintln!("{} {}", "✔".green().bold(), message);
+    emit("SUCCESS", message, Location::caller(), || {
+        format!("{} {}", "✔".green().bold(), message)
+    });
 }
 
 /// Stop the spinner with an error message
+#[track_caller]
 pub fn spinner_error(message: &str) {
     if let Ok(mut spinner_guard) = SPINNER.lock() {
         if let Some(spinner) = spinner_guard.take() {
@@ -269,7 +397,9 @@ pub fn spinner_error(message: &str) {
         }
     }
     // Show error message with cross
-    eprintln!("  {} {}", "✗".red().bold(), message);
+    emit("ERROR", message, Location::caller(), || {
+        format!("  {} {}", "✗".red().bold(), message)
+    });
 }
 
 /// Stop the spinner without any message
@@ -280,3 +410,25 @@ pub fn spinner_stop() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_from_str() {
+        assert_eq!("json".parse(), Ok(LogFormat::Json));
+        assert_eq!(" Human ".parse(), Ok(LogFormat::Human));
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_json_line() {
+        let line = json_line("WARN", "Plugin \"reeds\" is slow", "src/run.rs");
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["msg"], "Plugin \"reeds\" is slow");
+        assert_eq!(value["module"], "src/run.rs");
+        assert!(value["ts"].as_str().unwrap().ends_with('Z'));
+    }
+}
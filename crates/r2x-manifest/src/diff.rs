@@ -0,0 +1,149 @@
+//! Comparing two manifest snapshots
+
+use crate::types::{Manifest, PluginSpec};
+use colored::Colorize;
+use std::fmt;
+
+/// Plugins added, removed or changed between two manifests
+///
+/// Each entry carries the name of the package providing the plugin. Plugins are
+/// matched by package and plugin name, and sorted the same way.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestDiff {
+    pub added: Vec<(String, PluginSpec)>,
+    pub removed: Vec<(String, PluginSpec)>,
+    /// `(package, before, after)`
+    pub changed: Vec<(String, PluginSpec, PluginSpec)>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl Manifest {
+    /// Compare two manifest snapshots
+    pub fn diff(before: &Manifest, after: &Manifest) -> ManifestDiff {
+        let old = before.list_plugins();
+        let new = after.list_plugins();
+        let find = |plugins: &[(&str, &PluginSpec)], package: &str, name: &str| {
+            plugins
+                .iter()
+                .find(|(pkg, plugin)| *pkg == package && plugin.name == name)
+                .map(|(_, plugin)| (*plugin).clone())
+        };
+
+        let mut diff = ManifestDiff::default();
+        for (package, plugin) in &new {
+            match find(&old, package, &plugin.name) {
+                None => diff.added.push((package.to_string(), (*plugin).clone())),
+                Some(previous) if !same_spec(&previous, plugin) => {
+                    diff.changed
+                        .push((package.to_string(), previous, (*plugin).clone()));
+                }
+                Some(_) => {}
+            }
+        }
+        for (package, plugin) in &old {
+            if find(&new, package, &plugin.name).is_none() {
+                diff.removed.push((package.to_string(), (*plugin).clone()));
+            }
+        }
+        diff
+    }
+}
+
+/// Compare plugin specs through their serialized form
+fn same_spec(a: &PluginSpec, b: &PluginSpec) -> bool {
+    serde_json::to_value(a).ok() == serde_json::to_value(b).ok()
+}
+
+/// Top-level fields that differ between two plugin specs
+fn changed_fields(before: &PluginSpec, after: &PluginSpec) -> Vec<String> {
+    let (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) =
+        (serde_json::to_value(before), serde_json::to_value(after))
+    else {
+        return Vec::new();
+    };
+    let mut fields: Vec<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter(|key| old.get(*key) != new.get(*key))
+        .cloned()
+        .collect();
+    fields.sort();
+    fields.dedup();
+    fields
+}
+
+impl fmt::Display for ManifestDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No plugin changes");
+        }
+        for (package, plugin) in &self.added {
+            let line = format!("+ {} ({}) {}", plugin.name, package, plugin.entry);
+            writeln!(f, "{}", line.green())?;
+        }
+        for (package, plugin) in &self.removed {
+            let line = format!("- {} ({}) {}", plugin.name, package, plugin.entry);
+            writeln!(f, "{}", line.red())?;
+        }
+        for (package, before, after) in &self.changed {
+            let line = format!(
+                "~ {} ({}) changed: {}",
+                after.name,
+                package,
+                changed_fields(before, after).join(", ")
+            );
+            writeln!(f, "{}", line.yellow())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::plugin;
+
+    #[test]
+    fn test_diff_added_removed_changed() {
+        let mut before = Manifest::default();
+        let pkg = before.get_or_create_package("r2x-reeds");
+        pkg.plugins
+            .push(plugin("reeds-parser", "r2x_reeds:ReEDSParser"));
+        pkg.plugins.push(plugin("reeds-old", "r2x_reeds:Old"));
+        pkg.plugins.push(plugin("reeds-same", "r2x_reeds:Same"));
+
+        let mut after = Manifest::default();
+        let pkg = after.get_or_create_package("r2x-reeds");
+        pkg.plugins
+            .push(plugin("reeds-parser", "r2x_reeds.parser:ReEDSParser"));
+        pkg.plugins.push(plugin("reeds-same", "r2x_reeds:Same"));
+        after
+            .get_or_create_package("r2x-plexos")
+            .plugins
+            .push(plugin("plexos-exporter", "r2x_plexos:Exporter"));
+
+        let diff = Manifest::diff(&before, &after);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].0, "r2x-plexos");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].1.name, "reeds-old");
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            changed_fields(&diff.changed[0].1, &diff.changed[0].2),
+            ["entry"]
+        );
+
+        colored::control::set_override(false);
+        let rendered = diff.to_string();
+        assert!(rendered.contains("+ plexos-exporter (r2x-plexos)"));
+        assert!(rendered.contains("- reeds-old (r2x-reeds)"));
+        assert!(rendered.contains("~ reeds-parser (r2x-reeds) changed: entry"));
+
+        assert!(Manifest::diff(&after, &after).is_empty());
+    }
+}
@@ -0,0 +1,161 @@
+//! Metadata of installed Python distributions
+//!
+//! Installers write a `<name>-<version>.dist-info` directory into site-packages for
+//! every distribution; `setup.py develop` installs create `<name>.egg-info` instead.
+//! Distribution names are matched after PEP 503 normalization, so `r2x-reeds`,
+//! `r2x_reeds` and `R2X.Reeds` all find `r2x_reeds-0.3.0.dist-info`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Normalize a distribution name per PEP 503: lowercase, with runs of `-`, `_`
+/// and `.` collapsed to a single `-`
+pub fn normalize_package_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Metadata directory of one installed distribution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistInfo {
+    pub path: PathBuf,
+    /// Version from the directory name; unversioned egg-info directories have none
+    pub version: Option<String>,
+}
+
+impl DistInfo {
+    /// Find the metadata directory of `package` in `site_packages`
+    ///
+    /// A `.dist-info` directory is preferred over an `.egg-info` one.
+    pub fn find(site_packages: &Path, package: &str) -> Option<Self> {
+        let wanted = normalize_package_name(package);
+        let mut names: Vec<String> = fs::read_dir(site_packages)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+
+        [".dist-info", ".egg-info"].iter().find_map(|suffix| {
+            names.iter().find_map(|name| {
+                let stem = name.strip_suffix(suffix)?;
+                // `name-version`, or `name-version-py3.12` for eggs
+                let (dist_name, version) = match stem.split_once('-') {
+                    Some((dist_name, rest)) => (dist_name, rest.split('-').next()),
+                    None => (stem, None),
+                };
+                (normalize_package_name(dist_name) == wanted).then(|| DistInfo {
+                    path: site_packages.join(name),
+                    version: version.map(str::to_string),
+                })
+            })
+        })
+    }
+
+    /// Core metadata: `METADATA` for dist-info, `PKG-INFO` for egg-info
+    pub fn metadata(&self) -> io::Result<String> {
+        fs::read_to_string(self.path.join("METADATA"))
+            .or_else(|_| fs::read_to_string(self.path.join("PKG-INFO")))
+    }
+
+    /// `(name, value)` pairs of the entry points in `group`, in file order
+    ///
+    /// Returns an empty list when the distribution has no `entry_points.txt`.
+    pub fn entry_points(&self, group: &str) -> Vec<(String, String)> {
+        let Ok(content) = fs::read_to_string(self.path.join("entry_points.txt")) else {
+            return Vec::new();
+        };
+        parse_entry_points(&content, group)
+    }
+}
+
+/// `(name, value)` pairs of the `[group]` section of an `entry_points.txt`
+pub fn parse_entry_points(content: &str, group: &str) -> Vec<(String, String)> {
+    let mut in_group = false;
+    let mut entry_points = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if let Some(section) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            in_group = section.trim() == group;
+            continue;
+        }
+        if !in_group || line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some((name, value)) = line.split_once('=') {
+            entry_points.push((name.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    entry_points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_package_name() {
+        assert_eq!(normalize_package_name("R2X._Core"), "r2x-core");
+        assert_eq!(normalize_package_name("r2x.reeds"), "r2x-reeds");
+        assert_eq!(normalize_package_name("r2x-reeds"), "r2x-reeds");
+    }
+
+    #[test]
+    fn test_find_dist_info() {
+        let site_packages = tempfile::TempDir::new().unwrap();
+        let dir = site_packages.path();
+        // A similarly named package must not match
+        fs::create_dir_all(dir.join("r2x_reeds_to_sienna-0.1.0.dist-info")).unwrap();
+        assert_eq!(DistInfo::find(dir, "r2x-reeds"), None);
+
+        fs::create_dir_all(dir.join("r2x_reeds.egg-info")).unwrap();
+        let egg = DistInfo::find(dir, "r2x-reeds").unwrap();
+        assert_eq!(egg.path, dir.join("r2x_reeds.egg-info"));
+        assert_eq!(egg.version, None);
+
+        // dist-info wins over egg-info, and names are matched after normalization
+        fs::create_dir_all(dir.join("r2x_reeds-0.3.0.dist-info")).unwrap();
+        let dist = DistInfo::find(dir, "R2X.Reeds").unwrap();
+        assert_eq!(dist.path, dir.join("r2x_reeds-0.3.0.dist-info"));
+        assert_eq!(dist.version.as_deref(), Some("0.3.0"));
+
+        fs::create_dir_all(dir.join("r2x_plexos-1.0-py3.12.egg-info")).unwrap();
+        let versioned_egg = DistInfo::find(dir, "r2x-plexos").unwrap();
+        assert_eq!(versioned_egg.version.as_deref(), Some("1.0"));
+    }
+
+    #[test]
+    fn test_parse_entry_points() {
+        let content = "[console_scripts]\n\
+                       reeds = r2x_reeds.cli:main\n\
+                       \n\
+                       [r2x_plugin]\n\
+                       # comment\n\
+                       r2x-reeds = r2x_reeds.plugins:register_plugin\n\
+                       reeds-parser=r2x_reeds.parser:ReEDSParser\n";
+        assert_eq!(
+            parse_entry_points(content, "r2x_plugin"),
+            vec![
+                (
+                    "r2x-reeds".to_string(),
+                    "r2x_reeds.plugins:register_plugin".to_string()
+                ),
+                (
+                    "reeds-parser".to_string(),
+                    "r2x_reeds.parser:ReEDSParser".to_string()
+                ),
+            ]
+        );
+        assert!(parse_entry_points(content, "r2x_uninstall").is_empty());
+    }
+}
@@ -1,4 +1,6 @@
+use crate::merge::MergeConflict;
 use std::io;
+use std::path::PathBuf;
 use thiserror::Error;
 
 /// Errors that can occur during plugin manifest operations
@@ -15,4 +17,54 @@ pub enum ManifestError {
 
     #[error("Invalid plugin: {0}")]
     InvalidPlugin(String),
+
+    #[error(
+        "Manifest checksum mismatch, file may be corrupted: {} (expected {expected}, got {got})",
+        path.display()
+    )]
+    ChecksumMismatch {
+        path: PathBuf,
+        expected: String,
+        got: String,
+    },
+
+    #[error("Timed out waiting for manifest lock: {}", .0.display())]
+    LockTimeout(PathBuf),
+
+    #[error("{} plugin(s) conflict between manifests", .0.len())]
+    MergeConflicts(Vec<MergeConflict>),
+}
+
+impl ManifestError {
+    /// Whether the manifest file itself is damaged, as opposed to being unreachable
+    /// (locked, unreadable for the current user, ...)
+    pub fn is_corruption(&self) -> bool {
+        match self {
+            ManifestError::Parse(_)
+            | ManifestError::ChecksumMismatch { .. }
+            | ManifestError::InvalidPlugin(_) => true,
+            ManifestError::Io(e) => e.kind() == io::ErrorKind::InvalidData,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_corruption() {
+        let mismatch = ManifestError::ChecksumMismatch {
+            path: PathBuf::from("manifest.toml"),
+            expected: "a".to_string(),
+            got: "b".to_string(),
+        };
+        assert!(mismatch.is_corruption());
+        assert!(ManifestError::Io(io::Error::from(io::ErrorKind::InvalidData)).is_corruption());
+        assert!(
+            !ManifestError::Io(io::Error::from(io::ErrorKind::PermissionDenied)).is_corruption()
+        );
+        assert!(!ManifestError::LockTimeout(PathBuf::from("manifest.lock")).is_corruption());
+    }
 }
@@ -0,0 +1,221 @@
+//! Install script generation
+//!
+//! Renders the explicitly installed packages of a manifest as a shell script of
+//! `r2x install` commands, or as a requirements file for `r2x install --requirements`,
+//! so another machine can reproduce the same plugin setup.
+
+use crate::types::{Manifest, Package};
+
+/// Shell flavor of a generated install script
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellKind {
+    Bash,
+    Powershell,
+    Fish,
+}
+
+impl Manifest {
+    /// Generate a script that reinstalls every explicitly installed package
+    ///
+    /// Packages are emitted in dependency order: an explicit package that depends on
+    /// another explicit package is installed after it. Dependency-only packages are
+    /// left to the installer.
+    pub fn generate_install_script(&self, shell: ShellKind) -> String {
+        let mut script = match shell {
+            ShellKind::Bash => String::from("#!/bin/bash\nset -e\n\n"),
+            ShellKind::Powershell => String::from("$ErrorActionPreference = \"Stop\"\n\n"),
+            ShellKind::Fish => String::from("#!/usr/bin/env fish\n\n"),
+        };
+
+        for pkg in self.explicit_packages_in_install_order() {
+            let spec = install_spec(pkg);
+            let flag = if spec.editable { "-e " } else { "" };
+            let line = match shell {
+                ShellKind::Bash => format!("r2x install {}'{}'\n", flag, spec.source),
+                ShellKind::Powershell => format!(
+                    "r2x install {}\"{}\"\nif ($LASTEXITCODE -ne 0) {{ exit $LASTEXITCODE }}\n",
+                    flag, spec.source
+                ),
+                ShellKind::Fish => format!("r2x install {}'{}'; or exit 1\n", flag, spec.source),
+            };
+            script.push_str(&line);
+        }
+
+        script
+    }
+
+    /// Generate a requirements file with one package spec per line, in install order
+    pub fn generate_requirements(&self) -> String {
+        let mut requirements = String::from(
+            "# r2x plugin packages; install with `r2x install --requirements <file>`\n",
+        );
+        for pkg in self.explicit_packages_in_install_order() {
+            let spec = install_spec(pkg);
+            if spec.editable {
+                requirements.push_str("-e ");
+            }
+            requirements.push_str(&spec.source);
+            requirements.push('\n');
+        }
+        requirements
+    }
+
+    fn explicit_packages_in_install_order(&self) -> Vec<&Package> {
+        let mut pending: Vec<&Package> = self
+            .packages
+            .iter()
+            .filter(|pkg| pkg.install_type.as_deref() == Some("explicit"))
+            .collect();
+        let mut ordered: Vec<&Package> = Vec::with_capacity(pending.len());
+
+        while !pending.is_empty() {
+            let ready = pending.iter().position(|pkg| {
+                pkg.dependencies.iter().all(|dep| {
+                    ordered.iter().any(|done| done.name == *dep)
+                        || !pending.iter().any(|other| other.name == *dep)
+                })
+            });
+            // A dependency cycle cannot be ordered; keep manifest order for the rest
+            let idx = ready.unwrap_or(0);
+            ordered.push(pending.remove(idx));
+        }
+
+        ordered
+    }
+}
+
+/// What to pass to `r2x install` to reproduce one package
+struct InstallSpec {
+    editable: bool,
+    /// Package spec, git URL or local path
+    source: String,
+}
+
+/// Rebuild the install argument from the source recorded in the manifest
+///
+/// Editable installs point at their checkout, git and local path installs reuse the
+/// recorded source, and index installs pin the recorded version.
+fn install_spec(pkg: &Package) -> InstallSpec {
+    if let Some(path) = pkg
+        .resolved_source_path
+        .as_ref()
+        .filter(|_| pkg.editable_install)
+    {
+        return InstallSpec {
+            editable: true,
+            source: path.clone(),
+        };
+    }
+    if let Some(source) = &pkg.install_source {
+        return InstallSpec {
+            editable: false,
+            source: source.clone(),
+        };
+    }
+    let name = match &pkg.registry {
+        Some(registry) => format!("{}:{}", registry, pkg.name),
+        None => pkg.name.clone(),
+    };
+    let source = match &pkg.version {
+        Some(version) => format!("{}=={}", name, version),
+        None => name,
+    };
+    InstallSpec {
+        editable: false,
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> Manifest {
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-plexos").version = Some("0.3.0".to_string());
+        manifest.mark_explicit("r2x-plexos");
+        manifest.add_dependency("r2x-plexos", "r2x-reeds");
+        manifest.get_or_create_package("r2x-reeds");
+        manifest.mark_explicit("r2x-reeds");
+        manifest.get_or_create_package("r2x-core");
+        manifest.mark_dependency("r2x-core", "r2x-reeds");
+        manifest
+    }
+
+    fn manifest_with_sources() -> Manifest {
+        let mut manifest = Manifest::default();
+        let pkg = manifest.get_or_create_package("r2x-reeds");
+        pkg.editable_install = true;
+        pkg.resolved_source_path = Some("/home/dev/r2x-reeds".to_string());
+        pkg.install_source = Some("/home/dev/r2x-reeds".to_string());
+        let pkg = manifest.get_or_create_package("r2x-plexos");
+        pkg.version = Some("0.3.0".to_string());
+        pkg.install_source = Some("git+https://github.com/NREL/r2x-plexos@v0.3.0".to_string());
+        let pkg = manifest.get_or_create_package("r2x-sienna");
+        pkg.version = Some("0.2.0".to_string());
+        pkg.install_source = Some("/opt/plugins/r2x-sienna".to_string());
+        let pkg = manifest.get_or_create_package("r2x-internal");
+        pkg.version = Some("1.0.0".to_string());
+        pkg.registry = Some("internal".to_string());
+        for name in ["r2x-reeds", "r2x-plexos", "r2x-sienna", "r2x-internal"] {
+            manifest.mark_explicit(name);
+        }
+        manifest
+    }
+
+    #[test]
+    fn test_bash_script_in_dependency_order() {
+        let script = manifest().generate_install_script(ShellKind::Bash);
+        assert_eq!(
+            script,
+            "#!/bin/bash\nset -e\n\nr2x install 'r2x-reeds'\nr2x install 'r2x-plexos==0.3.0'\n"
+        );
+    }
+
+    #[test]
+    fn test_requirements_file() {
+        let requirements = manifest().generate_requirements();
+        let lines: Vec<&str> = requirements.lines().collect();
+        assert!(lines[0].starts_with('#'));
+        assert_eq!(&lines[1..], ["r2x-reeds", "r2x-plexos==0.3.0"]);
+    }
+
+    #[test]
+    fn test_requirements_keep_install_sources() {
+        let requirements = manifest_with_sources().generate_requirements();
+        let lines: Vec<&str> = requirements.lines().skip(1).collect();
+        assert_eq!(
+            lines,
+            [
+                "-e /home/dev/r2x-reeds",
+                "git+https://github.com/NREL/r2x-plexos@v0.3.0",
+                "/opt/plugins/r2x-sienna",
+                "internal:r2x-internal==1.0.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_script_passes_editable_flag_outside_quotes() {
+        let manifest = manifest_with_sources();
+        let bash = manifest.generate_install_script(ShellKind::Bash);
+        assert!(bash.contains("r2x install -e '/home/dev/r2x-reeds'\n"));
+        assert!(bash.contains("r2x install 'git+https://github.com/NREL/r2x-plexos@v0.3.0'\n"));
+
+        let powershell = manifest.generate_install_script(ShellKind::Powershell);
+        assert!(powershell.contains("r2x install -e \"/home/dev/r2x-reeds\""));
+    }
+
+    #[test]
+    fn test_powershell_and_fish_scripts() {
+        let manifest = manifest();
+
+        let powershell = manifest.generate_install_script(ShellKind::Powershell);
+        assert!(powershell.starts_with("$ErrorActionPreference = \"Stop\""));
+        assert!(powershell.contains("r2x install \"r2x-plexos==0.3.0\""));
+
+        let fish = manifest.generate_install_script(ShellKind::Fish);
+        assert!(fish.contains("r2x install 'r2x-reeds'; or exit 1"));
+        assert!(!fish.contains("r2x-core"));
+    }
+}
@@ -0,0 +1,84 @@
+//! Manifest integrity checks
+//!
+//! A SHA256 digest of the serialized manifest is stored next to it in a
+//! companion `manifest.toml.sha256` file. Manifests written before checksums
+//! were introduced have no companion file and are accepted as-is.
+
+use crate::atomic::write_atomic;
+use crate::errors::ManifestError;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Compute the hex-encoded SHA256 digest of manifest content
+pub fn compute_checksum(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Path of the companion checksum file for a manifest path
+pub fn checksum_path(manifest_path: &Path) -> PathBuf {
+    let mut path = manifest_path.as_os_str().to_owned();
+    path.push(".sha256");
+    PathBuf::from(path)
+}
+
+/// Write the checksum of `content` next to the manifest at `manifest_path`
+pub fn write_checksum(manifest_path: &Path, content: &str) -> Result<(), ManifestError> {
+    write_atomic(&checksum_path(manifest_path), compute_checksum(content))?;
+    Ok(())
+}
+
+/// Verify `content` against the stored checksum for `manifest_path`
+///
+/// Returns `Ok(true)` when a checksum exists and matches, `Ok(false)` when no
+/// checksum file exists, and `ManifestError::ChecksumMismatch` on mismatch.
+pub fn verify_checksum(manifest_path: &Path, content: &str) -> Result<bool, ManifestError> {
+    let sha_path = checksum_path(manifest_path);
+    if !sha_path.exists() {
+        return Ok(false);
+    }
+
+    let expected = fs::read_to_string(&sha_path)?.trim().to_string();
+    let got = compute_checksum(content);
+    if expected != got {
+        return Err(ManifestError::ChecksumMismatch {
+            path: manifest_path.to_path_buf(),
+            expected,
+            got,
+        });
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_checksum_path() {
+        let path = checksum_path(Path::new("/tmp/r2x/manifest.toml"));
+        assert_eq!(path, PathBuf::from("/tmp/r2x/manifest.toml.sha256"));
+    }
+
+    #[test]
+    fn test_verify_checksum_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("manifest.toml");
+        let content = "[metadata]\nversion = \"2.0\"\n";
+        fs::write(&manifest_path, content).unwrap();
+
+        // No checksum file yet: accepted for backward compatibility
+        assert!(!verify_checksum(&manifest_path, content).unwrap());
+
+        write_checksum(&manifest_path, content).unwrap();
+        assert!(verify_checksum(&manifest_path, content).unwrap());
+
+        let tampered = "[metadata]\nversion = \"9.9\"\n";
+        let result = verify_checksum(&manifest_path, tampered);
+        assert!(matches!(
+            result,
+            Err(ManifestError::ChecksumMismatch { ref got, .. }) if *got == compute_checksum(tampered)
+        ));
+    }
+}
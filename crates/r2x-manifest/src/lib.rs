@@ -6,21 +6,40 @@
 //! The manifest is stored in TOML format and contains comprehensive metadata about
 //! installed plugins, their configurations, and decorator registrations.
 
+pub mod atomic;
+pub mod diff;
+pub mod dist_info;
 pub mod errors;
+pub mod install_script;
+pub mod integrity;
+pub mod lock;
 pub mod manifest;
 pub mod manifest_writer;
+pub mod merge;
+pub mod migration;
 pub mod package_discovery;
+pub mod repair;
 pub mod runtime;
 pub mod types;
+pub mod version_spec;
 
-pub use runtime::{build_runtime_bindings, RuntimeBindings};
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+
+pub use runtime::{build_runtime_bindings, looks_like_abstract_class, RuntimeBindings};
 pub use types::{
     ArgumentSpec, ConfigField, ConfigSpec, DecoratorRegistration, FunctionParameter,
     FunctionSignature, IOContract, IOSlot, ImplementationType, InvocationSpec, Manifest, Metadata,
-    Package, PluginKind, PluginSpec, ResourceSpec, StoreMode, StoreSpec, UpgradeSpec, VarArgType,
+    Package, PluginKind, PluginSpec, ResourceSpec, StoreMode, StoreSpec, TypeKind, UpgradeSpec,
+    VarArgType,
 };
 
+pub use diff::ManifestDiff;
+pub use dist_info::DistInfo;
 pub use errors::ManifestError;
+pub use install_script::ShellKind;
+pub use merge::{MergeConflict, MergeStrategy, PluginOrigin};
+pub use repair::RepairReport;
 
 // Re-export manifest writer utilities for custom paths (testing)
 pub use manifest_writer::{read_from_path, write_to_path};
@@ -0,0 +1,164 @@
+//! Advisory file locking for the manifest
+//!
+//! Concurrent `r2x` processes coordinate through a `manifest.lock` file next to
+//! the manifest: readers take a shared lock and writers an exclusive one.
+//! Whole install cycles (load, install, save) are serialized separately through
+//! `install.lock`, see [`ManifestLock::try_acquire_within`]. Other files shared by
+//! r2x processes, such as caches, use their own lock file through
+//! [`ManifestLock::exclusive_file`].
+
+use crate::errors::ManifestError;
+use crate::types::Manifest;
+use fs2::FileExt;
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Environment variable overriding the lock timeout, in seconds
+pub const LOCK_TIMEOUT_ENV: &str = "R2X_MANIFEST_LOCK_TIMEOUT_SECS";
+
+/// Default time to wait for the manifest lock
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Lock timeout from `R2X_MANIFEST_LOCK_TIMEOUT_SECS`, or the default
+pub fn lock_timeout() -> Duration {
+    std::env::var(LOCK_TIMEOUT_ENV)
+        .ok()
+        .and_then(|secs| secs.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(DEFAULT_LOCK_TIMEOUT)
+}
+
+/// Path of the lock file guarding the manifest at `manifest_path`
+pub fn lock_path(manifest_path: &Path) -> PathBuf {
+    manifest_path.with_file_name("manifest.lock")
+}
+
+/// Path of the lock file serializing install cycles on the manifest at `manifest_path`
+pub fn install_lock_path(manifest_path: &Path) -> PathBuf {
+    manifest_path.with_file_name("install.lock")
+}
+
+/// Held manifest lock, released on drop
+#[derive(Debug)]
+pub struct ManifestLock {
+    file: File,
+}
+
+impl ManifestLock {
+    /// Acquire a shared lock for reading
+    pub fn shared(manifest_path: &Path, timeout: Duration) -> Result<Self, ManifestError> {
+        Self::acquire(lock_path(manifest_path), timeout, false)
+    }
+
+    /// Acquire an exclusive lock for writing
+    pub fn exclusive(manifest_path: &Path, timeout: Duration) -> Result<Self, ManifestError> {
+        Self::acquire(lock_path(manifest_path), timeout, true)
+    }
+
+    /// Acquire an exclusive lock on `lock_file` itself
+    pub fn exclusive_file(lock_file: &Path, timeout: Duration) -> Result<Self, ManifestError> {
+        Self::acquire(lock_file.to_path_buf(), timeout, true)
+    }
+
+    /// Take the install lock of the default manifest, waiting up to `timeout`
+    ///
+    /// Hold it across a whole install so concurrent `r2x` processes do not interleave
+    /// their load-modify-save cycles. It uses its own lock file, so the holder can
+    /// still load and save the manifest.
+    pub fn try_acquire_within(timeout: Duration) -> Result<Self, ManifestError> {
+        Self::install(&Manifest::path(), timeout)
+    }
+
+    fn install(manifest_path: &Path, timeout: Duration) -> Result<Self, ManifestError> {
+        Self::acquire(install_lock_path(manifest_path), timeout, true)
+    }
+
+    fn acquire(path: PathBuf, timeout: Duration, exclusive: bool) -> Result<Self, ManifestError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        let start = Instant::now();
+        loop {
+            let result = if exclusive {
+                FileExt::try_lock_exclusive(&file)
+            } else {
+                FileExt::try_lock_shared(&file)
+            };
+            match result {
+                Ok(()) => return Ok(ManifestLock { file }),
+                Err(e) if e.kind() == fs2::lock_contended_error().kind() => {
+                    if start.elapsed() >= timeout {
+                        return Err(ManifestError::LockTimeout(path));
+                    }
+                    thread::sleep(RETRY_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl Drop for ManifestLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_exclusive_lock_times_out_while_held() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("manifest.toml");
+        let timeout = Duration::from_millis(100);
+
+        let held = ManifestLock::exclusive(&manifest_path, timeout).unwrap();
+        let result = ManifestLock::shared(&manifest_path, timeout);
+        assert!(matches!(result, Err(ManifestError::LockTimeout(_))));
+
+        drop(held);
+        assert!(ManifestLock::exclusive(&manifest_path, timeout).is_ok());
+    }
+
+    #[test]
+    fn test_install_lock_is_independent_of_manifest_lock() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("manifest.toml");
+        let timeout = Duration::from_millis(100);
+
+        let install = ManifestLock::install(&manifest_path, timeout).unwrap();
+        assert!(ManifestLock::exclusive(&manifest_path, timeout).is_ok());
+        assert!(matches!(
+            ManifestLock::install(&manifest_path, timeout),
+            Err(ManifestError::LockTimeout(_))
+        ));
+
+        drop(install);
+        assert!(ManifestLock::install(&manifest_path, timeout).is_ok());
+    }
+
+    #[test]
+    fn test_shared_locks_coexist() {
+        let dir = TempDir::new().unwrap();
+        let manifest_path = dir.path().join("manifest.toml");
+        let timeout = Duration::from_millis(100);
+
+        let _first = ManifestLock::shared(&manifest_path, timeout).unwrap();
+        assert!(ManifestLock::shared(&manifest_path, timeout).is_ok());
+        assert_eq!(lock_path(&manifest_path), dir.path().join("manifest.lock"));
+    }
+}
@@ -3,9 +3,17 @@
 //! This module provides the core operations for managing the r2x plugin manifest,
 //! including CRUD operations, dependency tracking, and persistence.
 
-use super::types::{Manifest, Metadata, Package};
+use super::types::{Manifest, Metadata, Package, PluginKind, PluginSpec, TypeKind};
+use crate::atomic::write_atomic;
 use crate::errors::ManifestError;
-use std::path::PathBuf;
+use crate::integrity;
+use crate::lock::{self, ManifestLock};
+use crate::migration;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Fields accepted by the `field:value` syntax of [`Manifest::search`]
+pub const SEARCH_FIELDS: &[&str] = &["name", "description", "type", "package", "module"];
 
 impl Manifest {
     /// Get the default path to the manifest file
@@ -30,6 +38,20 @@ impl Manifest {
         }
     }
 
+    /// Path of the backup written before each save
+    pub fn backup_path() -> PathBuf {
+        let mut path = Self::path().into_os_string();
+        path.push(".bak");
+        PathBuf::from(path)
+    }
+
+    /// Path the original of a migrated legacy manifest is kept at
+    pub fn legacy_backup_path() -> PathBuf {
+        let mut path = Self::path().into_os_string();
+        path.push(".legacy");
+        PathBuf::from(path)
+    }
+
     /// Load manifest from default location, returning empty manifest if file doesn't exist
     pub fn load() -> Result<Self, ManifestError> {
         let path = Self::path();
@@ -44,12 +66,103 @@ impl Manifest {
             });
         }
 
-        let content = std::fs::read_to_string(&path)?;
-        let manifest: Manifest = toml::from_str(&content)?;
+        let (manifest, migrated) = Self::read_from(&path)?;
+        if migrated {
+            // Keep the first legacy original; `save` rotates the regular backup
+            let legacy = Self::legacy_backup_path();
+            if !legacy.exists() {
+                std::fs::copy(&path, &legacy)?;
+            }
+            manifest.save()?;
+        }
         Ok(manifest)
     }
 
+    /// Load the manifest, falling back to the backup when the file is corrupted
+    ///
+    /// Returns the manifest and whether it came from the backup. Errors that say
+    /// nothing about the file's content, such as a lock timeout or a permission
+    /// error, are returned as they are. The original error is also returned when the
+    /// backup is missing or unreadable too.
+    pub fn load_with_fallback() -> Result<(Self, bool), ManifestError> {
+        let err = match Self::load() {
+            Ok(manifest) => return Ok((manifest, false)),
+            Err(e) if !e.is_corruption() => return Err(e),
+            Err(e) => e,
+        };
+
+        let backup = Self::backup_path();
+        if !backup.exists() {
+            return Err(err);
+        }
+        match Self::read_from(&backup) {
+            Ok((manifest, _)) => Ok((manifest, true)),
+            Err(_) => Err(err),
+        }
+    }
+
+    /// Read and verify a manifest file, migrating the legacy format
+    ///
+    /// The flag is true when the file was migrated and should be saved again.
+    fn read_from(path: &Path) -> Result<(Self, bool), ManifestError> {
+        // The file and its checksum are only consistent with each other under the lock
+        let content = {
+            let _lock = ManifestLock::shared(path, lock::lock_timeout())?;
+            let content = std::fs::read_to_string(path)?;
+            integrity::verify_checksum(path, &content)?;
+            content
+        };
+        Self::parse(&content)
+    }
+
+    /// Parse manifest content, migrating the legacy format
+    ///
+    /// The flag is true when the content was migrated.
+    fn parse(content: &str) -> Result<(Self, bool), ManifestError> {
+        let raw: toml::Value = toml::from_str(content)?;
+        if migration::is_legacy_format(&raw) {
+            return Ok((migration::migrate_legacy_manifest(&raw)?, true));
+        }
+
+        let manifest: Manifest = raw.try_into()?;
+        Ok((manifest, false))
+    }
+
+    /// Verify the manifest file against its stored checksum without parsing it
+    ///
+    /// Returns `Ok(false)` when there is no manifest or no checksum to compare against.
+    pub fn verify_integrity() -> Result<bool, ManifestError> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let _lock = ManifestLock::shared(&path, lock::lock_timeout())?;
+        let content = std::fs::read_to_string(&path)?;
+        integrity::verify_checksum(&path, &content)
+    }
+
+    /// Recompute the stored checksum after a deliberate manual edit
+    ///
+    /// The file must still load and validate as a manifest. Returns `Ok(false)` when
+    /// there is no manifest.
+    pub fn rehash() -> Result<bool, ManifestError> {
+        let path = Self::path();
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let _lock = ManifestLock::exclusive(&path, lock::lock_timeout())?;
+        let content = std::fs::read_to_string(&path)?;
+        Self::parse(&content)?.0.validate()?;
+        integrity::write_checksum(&path, &content)?;
+        Ok(true)
+    }
+
     /// Save manifest to default location
+    ///
+    /// The manifest and its checksum are each replaced atomically under the exclusive
+    /// lock, so readers never see a partial file or a checksum of other content.
     pub fn save(&self) -> Result<(), ManifestError> {
         let path = Self::path();
         if let Some(parent) = path.parent() {
@@ -57,7 +170,17 @@ impl Manifest {
         }
 
         let content = toml::to_string_pretty(self)?;
-        std::fs::write(&path, content)?;
+        let _lock = ManifestLock::exclusive(&path, lock::lock_timeout())?;
+        if path.exists() {
+            let backup = Self::backup_path();
+            std::fs::copy(&path, &backup)?;
+            let checksum = integrity::checksum_path(&path);
+            if checksum.exists() {
+                std::fs::copy(&checksum, integrity::checksum_path(&backup))?;
+            }
+        }
+        write_atomic(&path, &content)?;
+        integrity::write_checksum(&path, &content)?;
         Ok(())
     }
 
@@ -72,12 +195,17 @@ impl Manifest {
         if !self.packages.iter().any(|p| p.name == name) {
             self.packages.push(Package {
                 name: name.to_string(),
+                version: None,
+                homepage: None,
+                license: None,
                 entry_points_dist_info: String::new(),
                 editable_install: false,
                 pth_file: None,
                 resolved_source_path: None,
+                install_source: None,
                 install_type: None,
                 installed_by: Vec::new(),
+                registry: None,
                 dependencies: Vec::new(),
                 plugins: Vec::new(),
                 decorator_registrations: Vec::new(),
@@ -95,14 +223,148 @@ impl Manifest {
 
     /// Remove all plugins belonging to a package from the manifest
     pub fn remove_plugins_by_package(&mut self, package_name: &str) -> usize {
-        let mut count = 0;
+        self.filter_plugins_mut(|pkg, _| pkg == package_name).len()
+    }
+
+    /// Plugins provided by a package, in manifest order
+    pub fn get_plugins_by_package(&self, package_name: &str) -> Vec<&PluginSpec> {
+        self.filter_plugins(|pkg, _| pkg == package_name)
+            .into_iter()
+            .map(|(_, plugin)| plugin)
+            .collect()
+    }
+
+    /// Plugins of a type such as `parser` (case-insensitive), sorted by plugin name
+    pub fn get_plugins_by_type(&self, plugin_type: &str) -> Vec<(&str, &PluginSpec)> {
+        let mut plugins =
+            self.filter_plugins(|_, plugin| plugin.kind.as_str().eq_ignore_ascii_case(plugin_type));
+        plugins.sort_by(|(pkg_a, a), (pkg_b, b)| a.name.cmp(&b.name).then(pkg_a.cmp(pkg_b)));
+        plugins
+    }
+
+    /// Distinct plugin types present in the manifest
+    pub fn plugin_types(&self) -> BTreeSet<&str> {
+        self.packages
+            .iter()
+            .flat_map(|pkg| pkg.plugins.iter())
+            .map(|plugin| plugin.kind.as_str())
+            .collect()
+    }
+
+    /// Plugins matching a predicate on `(package_name, plugin)`, in manifest order
+    pub fn filter_plugins<F>(&self, pred: F) -> Vec<(&str, &PluginSpec)>
+    where
+        F: Fn(&str, &PluginSpec) -> bool,
+    {
+        self.packages
+            .iter()
+            .flat_map(|pkg| {
+                pkg.plugins
+                    .iter()
+                    .map(move |plugin| (pkg.name.as_str(), plugin))
+            })
+            .filter(|(pkg, plugin)| pred(pkg, plugin))
+            .collect()
+    }
+
+    /// Remove plugins matching a predicate on `(package_name, plugin)`
+    ///
+    /// Returns the names of the removed plugins.
+    pub fn filter_plugins_mut<F>(&mut self, pred: F) -> Vec<String>
+    where
+        F: Fn(&str, &PluginSpec) -> bool,
+    {
+        let mut removed = Vec::new();
         for pkg in &mut self.packages {
-            if pkg.name == package_name {
-                count = pkg.plugins.len();
-                pkg.plugins.clear();
+            let package_name = pkg.name.as_str();
+            pkg.plugins.retain(|plugin| {
+                if pred(package_name, plugin) {
+                    removed.push(plugin.name.clone());
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        removed
+    }
+
+    /// Rename a plugin, keeping its package and position
+    ///
+    /// Returns `Ok(false)` when no plugin is named `old_name`, and an error when
+    /// `new_name` is already taken.
+    pub fn rename_plugin(&mut self, old_name: &str, new_name: &str) -> Result<bool, ManifestError> {
+        if old_name == new_name {
+            return Ok(!self.filter_plugins(|_, p| p.name == old_name).is_empty());
+        }
+        if !self.filter_plugins(|_, p| p.name == new_name).is_empty() {
+            return Err(ManifestError::InvalidPlugin(format!(
+                "Plugin '{}' already exists",
+                new_name
+            )));
+        }
+
+        let plugin = self
+            .packages
+            .iter_mut()
+            .flat_map(|pkg| pkg.plugins.iter_mut())
+            .find(|p| p.name == old_name);
+        match plugin {
+            Some(plugin) => {
+                plugin.name = new_name.to_string();
+                Ok(true)
             }
+            None => Ok(false),
         }
-        count
+    }
+
+    /// Check that packages and plugins are named, entries are set and plugin names
+    /// are unique, as required before a manifest from elsewhere is used
+    pub fn validate(&self) -> Result<(), ManifestError> {
+        let mut problems = Vec::new();
+        let mut names = HashSet::new();
+        for pkg in &self.packages {
+            if pkg.name.trim().is_empty() {
+                problems.push("package without a name".to_string());
+            }
+            for plugin in &pkg.plugins {
+                if plugin.name.trim().is_empty() {
+                    problems.push(format!("plugin without a name in '{}'", pkg.name));
+                } else if !names.insert(plugin.name.as_str()) {
+                    problems.push(format!("plugin '{}' is defined twice", plugin.name));
+                }
+                if plugin.entry.trim().is_empty() {
+                    problems.push(format!("plugin '{}' has no entry", plugin.name));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ManifestError::InvalidPlugin(problems.join("; ")))
+        }
+    }
+
+    /// Remove plugins that share a package and entry point with a later plugin
+    ///
+    /// The last occurrence is treated as the newest and kept. Returns the number
+    /// of plugins removed.
+    pub fn deduplicate(&mut self) -> usize {
+        let mut removed = 0;
+        for pkg in &mut self.packages {
+            let mut seen = HashSet::new();
+            let mut kept: Vec<PluginSpec> = Vec::with_capacity(pkg.plugins.len());
+            for plugin in pkg.plugins.drain(..).rev() {
+                if seen.insert(plugin.entry.clone()) {
+                    kept.push(plugin);
+                } else {
+                    removed += 1;
+                }
+            }
+            kept.reverse();
+            pkg.plugins = kept;
+        }
+        removed
     }
 
     /// Remove decorator registrations for a package
@@ -117,18 +379,81 @@ impl Manifest {
         false
     }
 
-    /// List all plugins (compatibility method) - returns (plugin_name, package_name) tuples
-    pub fn list_plugins(&self) -> Vec<(String, String)> {
-        self.packages
+    /// List all plugins as `(package_name, plugin)`, sorted by plugin name
+    pub fn list_plugins(&self) -> Vec<(&str, &PluginSpec)> {
+        let mut plugins = self.filter_plugins(|_, _| true);
+        plugins.sort_by(|(pkg_a, a), (pkg_b, b)| a.name.cmp(&b.name).then(pkg_a.cmp(pkg_b)));
+        plugins
+    }
+
+    /// List plugins grouped by package, with packages and plugins sorted by name
+    ///
+    /// Packages without plugins are included with an empty list.
+    pub fn list_plugins_by_package(&self) -> Vec<(&str, Vec<&PluginSpec>)> {
+        let mut packages: Vec<(&str, Vec<&PluginSpec>)> = self
+            .packages
             .iter()
-            .flat_map(|pkg| {
-                pkg.plugins
-                    .iter()
-                    .map(move |plugin| (plugin.name.clone(), pkg.name.clone()))
+            .map(|pkg| {
+                let mut plugins: Vec<&PluginSpec> = pkg.plugins.iter().collect();
+                plugins.sort_by(|a, b| a.name.cmp(&b.name));
+                (pkg.name.as_str(), plugins)
             })
+            .collect();
+        packages.sort_by_key(|(name, _)| *name);
+        packages
+    }
+
+    /// Case-insensitive substring search over plugin metadata, sorted by plugin name
+    ///
+    /// A plain query matches the plugin name, description, type, package name or
+    /// entry point. A `field:value` query restricts the match to one field, where
+    /// field is `name`, `description`, `type`, `package` or `module`.
+    pub fn search(&self, query: &str) -> Vec<(&str, &PluginSpec)> {
+        let (field, needle) = match query.split_once(':') {
+            Some((field, value)) if SEARCH_FIELDS.contains(&field.trim()) => {
+                (Some(field.trim()), value.trim().to_lowercase())
+            }
+            _ => (None, query.trim().to_lowercase()),
+        };
+
+        let mut plugins = self.filter_plugins(|package, plugin| {
+            let values = [
+                ("name", plugin.name.as_str()),
+                ("description", plugin.description.as_deref().unwrap_or("")),
+                ("type", plugin.kind.as_str()),
+                ("package", package),
+                ("module", plugin.entry.as_str()),
+            ];
+            values
+                .iter()
+                .filter(|(name, _)| field.map_or(true, |field| field == *name))
+                .any(|(_, value)| value.to_lowercase().contains(&needle))
+        });
+        plugins.sort_by(|(pkg_a, a), (pkg_b, b)| a.name.cmp(&b.name).then(pkg_a.cmp(pkg_b)));
+        plugins
+    }
+
+    /// Number of plugins contributed by each package that provides any
+    pub fn count_by_package(&self) -> HashMap<String, usize> {
+        self.packages
+            .iter()
+            .filter(|pkg| !pkg.plugins.is_empty())
+            .map(|pkg| (pkg.name.clone(), pkg.plugins.len()))
             .collect()
     }
 
+    /// Number of packages that provide at least one plugin
+    pub fn total_package_count(&self) -> usize {
+        self.package_names().len()
+    }
+
+    /// Sorted, unique names of packages that provide at least one plugin
+    pub fn package_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.count_by_package().into_keys().collect();
+        names.sort();
+        names
+    }
+
     /// Check if manifest has no packages
     pub fn is_empty(&self) -> bool {
         self.packages.is_empty()
@@ -247,9 +572,318 @@ impl Manifest {
     }
 }
 
+impl PluginKind {
+    /// Lowercase name of the kind, e.g. `parser`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluginKind::Parser => "parser",
+            PluginKind::Exporter => "exporter",
+            PluginKind::Modifier => "modifier",
+            PluginKind::Upgrader => "upgrader",
+            PluginKind::Utility => "utility",
+            PluginKind::Translation => "translation",
+        }
+    }
+}
+
+impl std::str::FromStr for PluginKind {
+    type Err = String;
+
+    /// Parse a kind name as written by [`PluginKind::as_str`], ignoring case
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        [
+            PluginKind::Parser,
+            PluginKind::Exporter,
+            PluginKind::Modifier,
+            PluginKind::Upgrader,
+            PluginKind::Utility,
+            PluginKind::Translation,
+        ]
+        .into_iter()
+        .find(|kind| kind.as_str().eq_ignore_ascii_case(value.trim()))
+        .ok_or_else(|| {
+            format!(
+                "Unknown plugin type '{}'. Expected one of: parser, exporter, modifier, upgrader, utility, translation",
+                value
+            )
+        })
+    }
+}
+
+impl std::fmt::Display for TypeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeKind::String => f.write_str("string"),
+            TypeKind::Number => f.write_str("number"),
+            TypeKind::Float => f.write_str("float"),
+            TypeKind::Boolean => f.write_str("boolean"),
+            TypeKind::None => f.write_str("none"),
+            TypeKind::ClassReference => f.write_str("class_reference"),
+            TypeKind::Complex => f.write_str("complex"),
+            TypeKind::Optional(inner) => write!(f, "optional_{}", inner),
+            TypeKind::List(inner) => write!(f, "list_{}", inner),
+            TypeKind::Union => f.write_str("union"),
+            TypeKind::Identifier => f.write_str("identifier"),
+        }
+    }
+}
+
+impl From<TypeKind> for String {
+    fn from(kind: TypeKind) -> Self {
+        kind.to_string()
+    }
+}
+
+/// Read the stored form written by `Display`; unrecognized names become `Identifier`
+impl From<String> for TypeKind {
+    fn from(value: String) -> Self {
+        fn parse(value: &str) -> TypeKind {
+            if let Some(inner) = value.strip_prefix("optional_") {
+                return TypeKind::Optional(Box::new(parse(inner)));
+            }
+            if let Some(inner) = value.strip_prefix("list_") {
+                return TypeKind::List(Box::new(parse(inner)));
+            }
+            match value {
+                "string" => TypeKind::String,
+                "number" => TypeKind::Number,
+                "float" => TypeKind::Float,
+                "boolean" => TypeKind::Boolean,
+                "none" => TypeKind::None,
+                "class_reference" => TypeKind::ClassReference,
+                "complex" => TypeKind::Complex,
+                "union" => TypeKind::Union,
+                _ => TypeKind::Identifier,
+            }
+        }
+        parse(&value)
+    }
+}
+
+impl PluginSpec {
+    /// Whether the plugin declares `tag` (case-insensitive)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Whether `installed_version` of r2x-core satisfies the plugin's requirement
+    ///
+    /// Returns `None` when the plugin declares no requirement or the requirement
+    /// cannot be parsed.
+    pub fn is_compatible_with_r2x_core(&self, installed_version: &str) -> Option<bool> {
+        let requirement = self.r2x_core_requirement.as_deref()?;
+        crate::version_spec::satisfies(installed_version, requirement)
+    }
+}
+
+impl Package {
+    /// Homepage, if it looks like an http(s) URL
+    pub fn homepage_url(&self) -> Option<&str> {
+        self.homepage
+            .as_deref()
+            .map(str::trim)
+            .filter(|url| url.starts_with("http"))
+    }
+
+    /// Combine freshly discovered package metadata with an existing manifest entry
+    ///
+    /// Plugins, decorator registrations, the version, homepage and license come from
+    /// `fresh`. Install bookkeeping (install type, dependents, install source and
+    /// editable source) is kept from `existing` unless `fresh` sets it explicitly.
+    pub fn merge_metadata(existing: &Package, fresh: Package) -> Package {
+        let editable = fresh.editable_install;
+        Package {
+            version: fresh.version.or_else(|| existing.version.clone()),
+            homepage: fresh.homepage.or_else(|| existing.homepage.clone()),
+            license: fresh.license.or_else(|| existing.license.clone()),
+            install_type: fresh.install_type.or_else(|| existing.install_type.clone()),
+            installed_by: if fresh.installed_by.is_empty() {
+                existing.installed_by.clone()
+            } else {
+                fresh.installed_by
+            },
+            registry: fresh.registry.or_else(|| existing.registry.clone()),
+            install_source: fresh
+                .install_source
+                .or_else(|| existing.install_source.clone()),
+            editable_install: editable || existing.editable_install,
+            pth_file: fresh.pth_file.or_else(|| existing.pth_file.clone()),
+            resolved_source_path: if editable {
+                fresh.resolved_source_path
+            } else {
+                existing.resolved_source_path.clone()
+            },
+            ..fresh
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_fixtures;
+    use crate::types::PluginKind;
+
+    fn plugin(name: &str, kind: PluginKind) -> PluginSpec {
+        PluginSpec {
+            kind,
+            ..test_fixtures::plugin(name, &format!("module.{}", name))
+        }
+    }
+
+    fn manifest_with_plugins() -> Manifest {
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-reeds").plugins = vec![
+            plugin("reeds-parser", PluginKind::Parser),
+            plugin("reeds-exporter", PluginKind::Exporter),
+        ];
+        manifest.get_or_create_package("r2x-plexos").plugins = vec![
+            plugin("plexos-parser", PluginKind::Parser),
+            plugin("plexos-exporter", PluginKind::Exporter),
+        ];
+        manifest
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut manifest = manifest_with_plugins();
+        assert!(manifest.validate().is_ok());
+
+        manifest.packages[1].plugins[0].name = "reeds-parser".to_string();
+        manifest.packages[1].plugins[1].entry = String::new();
+        match manifest.validate() {
+            Err(ManifestError::InvalidPlugin(msg)) => assert_eq!(
+                msg,
+                "plugin 'reeds-parser' is defined twice; plugin 'plexos-exporter' has no entry"
+            ),
+            other => panic!("expected InvalidPlugin, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_is_compatible_with_r2x_core() {
+        let mut spec = plugin("reeds-parser", PluginKind::Parser);
+        assert_eq!(spec.is_compatible_with_r2x_core("0.1.0"), None);
+
+        spec.r2x_core_requirement = Some(">=0.1.0rc1,<0.2.0".to_string());
+        assert_eq!(spec.is_compatible_with_r2x_core("0.1.2"), Some(true));
+        assert_eq!(spec.is_compatible_with_r2x_core("0.2.1"), Some(false));
+        assert_eq!(spec.is_compatible_with_r2x_core("unknown"), None);
+    }
+
+    #[test]
+    fn test_homepage_url() {
+        let mut manifest = Manifest::default();
+        let package = manifest.get_or_create_package("r2x-reeds");
+        assert!(package.homepage_url().is_none());
+
+        package.homepage = Some("UNKNOWN".to_string());
+        assert!(package.homepage_url().is_none());
+
+        package.homepage = Some("https://github.com/NREL/r2x-reeds".to_string());
+        assert_eq!(
+            package.homepage_url(),
+            Some("https://github.com/NREL/r2x-reeds")
+        );
+    }
+
+    #[test]
+    fn test_has_tag() {
+        let mut spec = plugin("reeds-parser", PluginKind::Parser);
+        assert!(!spec.has_tag("reeds"));
+
+        spec.tags = vec!["energy".to_string(), "Reeds".to_string()];
+        assert!(spec.has_tag("reeds"));
+        assert!(spec.has_tag("ENERGY"));
+        assert!(!spec.has_tag("missing"));
+    }
+
+    #[test]
+    fn test_plugins_by_type_and_package() {
+        let manifest = manifest_with_plugins();
+
+        let parsers: Vec<&str> = manifest
+            .get_plugins_by_type("Parser")
+            .into_iter()
+            .map(|(_, plugin)| plugin.name.as_str())
+            .collect();
+        assert_eq!(parsers, vec!["plexos-parser", "reeds-parser"]);
+        assert!(manifest.get_plugins_by_type("upgrader").is_empty());
+        assert_eq!(
+            manifest.plugin_types().into_iter().collect::<Vec<_>>(),
+            vec!["exporter", "parser"]
+        );
+
+        let reeds: Vec<&str> = manifest
+            .get_plugins_by_package("r2x-reeds")
+            .into_iter()
+            .map(|plugin| plugin.name.as_str())
+            .collect();
+        assert_eq!(reeds, vec!["reeds-parser", "reeds-exporter"]);
+        assert!(manifest.get_plugins_by_package("missing").is_empty());
+    }
+
+    #[test]
+    fn test_search() {
+        let mut manifest = manifest_with_plugins();
+        manifest.packages[0].plugins[1].description =
+            Some("Export ReEDS outputs to PLEXOS".to_string());
+
+        let names = |query: &str| -> Vec<String> {
+            manifest
+                .search(query)
+                .into_iter()
+                .map(|(pkg, plugin)| format!("{}/{}", pkg, plugin.name))
+                .collect()
+        };
+
+        // Matches name, description and package across plugins
+        assert_eq!(
+            names("PLEXOS"),
+            vec![
+                "r2x-plexos/plexos-exporter",
+                "r2x-plexos/plexos-parser",
+                "r2x-reeds/reeds-exporter"
+            ]
+        );
+        assert_eq!(
+            names("type:parser"),
+            vec!["r2x-plexos/plexos-parser", "r2x-reeds/reeds-parser"]
+        );
+        assert_eq!(names("package: r2x-reeds").len(), 2);
+        assert_eq!(
+            names("description:outputs"),
+            vec!["r2x-reeds/reeds-exporter"]
+        );
+        assert_eq!(
+            names("module:module.plexos-parser"),
+            vec!["r2x-plexos/plexos-parser"]
+        );
+        assert!(names("name:r2x").is_empty());
+        assert!(names("nothing-matches").is_empty());
+    }
+
+    #[test]
+    fn test_package_statistics() {
+        let mut manifest = Manifest::default();
+        for (package, count) in [("r2x-sienna", 5), ("r2x-core", 1), ("r2x-reeds", 3)] {
+            manifest.get_or_create_package(package).plugins = (0..count)
+                .map(|i| plugin(&format!("{}-{}", package, i), PluginKind::Parser))
+                .collect();
+        }
+        manifest.get_or_create_package("r2x-empty");
+
+        let counts = manifest.count_by_package();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["r2x-core"], 1);
+        assert_eq!(counts["r2x-reeds"], 3);
+        assert_eq!(counts["r2x-sienna"], 5);
+        assert_eq!(manifest.total_package_count(), 3);
+        assert_eq!(
+            manifest.package_names(),
+            vec!["r2x-core", "r2x-reeds", "r2x-sienna"]
+        );
+    }
 
     #[test]
     fn test_manifest_default() {
@@ -362,6 +996,183 @@ mod tests {
         assert_eq!(shared.installed_by, vec!["r2x-main2"]);
     }
 
+    #[test]
+    fn test_filter_plugins_multiple_criteria() {
+        let manifest = manifest_with_plugins();
+
+        let parsers: Vec<&str> = manifest
+            .filter_plugins(|_, p| p.kind == PluginKind::Parser)
+            .into_iter()
+            .map(|(_, p)| p.name.as_str())
+            .collect();
+        assert_eq!(parsers, vec!["reeds-parser", "plexos-parser"]);
+
+        let matches =
+            manifest.filter_plugins(|pkg, p| pkg == "r2x-plexos" && p.kind == PluginKind::Exporter);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0, "r2x-plexos");
+        assert_eq!(matches[0].1.name, "plexos-exporter");
+    }
+
+    #[test]
+    fn test_filter_plugins_mut_removes_matches() {
+        let mut manifest = manifest_with_plugins();
+
+        let removed = manifest
+            .filter_plugins_mut(|pkg, p| pkg == "r2x-reeds" && p.kind == PluginKind::Parser);
+        assert_eq!(removed, vec!["reeds-parser".to_string()]);
+        assert_eq!(manifest.total_plugin_count(), 3);
+
+        assert_eq!(manifest.remove_plugins_by_package("r2x-plexos"), 2);
+        assert_eq!(manifest.total_plugin_count(), 1);
+        assert_eq!(manifest.remove_plugins_by_package("r2x-missing"), 0);
+    }
+
+    #[test]
+    fn test_read_from_backup_copy() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("manifest.toml");
+        let backup = dir.path().join("manifest.toml.bak");
+
+        let content = toml::to_string_pretty(&manifest_with_plugins()).unwrap();
+        std::fs::write(&backup, &content).unwrap();
+        integrity::write_checksum(&backup, &content).unwrap();
+        std::fs::write(&path, "[metadata\nversion = ").unwrap();
+
+        assert!(Manifest::read_from(&path).is_err());
+        let (manifest, migrated) = Manifest::read_from(&backup).unwrap();
+        assert!(!migrated);
+        assert_eq!(manifest.total_plugin_count(), 4);
+    }
+
+    #[test]
+    fn test_parse_rejects_valid_toml_that_is_not_a_manifest() {
+        let content = toml::to_string_pretty(&manifest_with_plugins()).unwrap();
+        assert!(Manifest::parse(&content).is_ok());
+
+        // Still valid TOML, but the plugins lost a required field
+        let broken = content.replace("entry = ", "entry_point = ");
+        assert!(toml::from_str::<toml::Value>(&broken).is_ok());
+        assert!(Manifest::parse(&broken).is_err());
+    }
+
+    #[test]
+    fn test_rename_plugin() {
+        let mut manifest = manifest_with_plugins();
+
+        assert!(manifest.rename_plugin("reeds-parser", "reeds").unwrap());
+        let renamed = manifest.filter_plugins(|_, p| p.name == "reeds");
+        assert_eq!(renamed.len(), 1);
+        assert_eq!(renamed[0].0, "r2x-reeds");
+
+        assert!(!manifest.rename_plugin("reeds-parser", "other").unwrap());
+        assert!(matches!(
+            manifest.rename_plugin("reeds", "plexos-parser"),
+            Err(ManifestError::InvalidPlugin(_))
+        ));
+    }
+
+    #[test]
+    fn test_deduplicate_keeps_newest() {
+        let mut manifest = manifest_with_plugins();
+        let mut newer = plugin("reeds-parser-v2", PluginKind::Parser);
+        newer.entry = "module.reeds-parser".to_string();
+        manifest
+            .get_or_create_package("r2x-reeds")
+            .plugins
+            .push(newer);
+
+        assert_eq!(manifest.deduplicate(), 1);
+        let names: Vec<&str> = manifest
+            .packages
+            .iter()
+            .find(|pkg| pkg.name == "r2x-reeds")
+            .unwrap()
+            .plugins
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["reeds-exporter", "reeds-parser-v2"]);
+        assert_eq!(manifest.deduplicate(), 0);
+    }
+
+    #[test]
+    fn test_merge_metadata_preserves_install_fields() {
+        let mut manifest = manifest_with_plugins();
+        {
+            let existing = manifest.get_or_create_package("r2x-reeds");
+            existing.version = Some("0.1.0".to_string());
+            existing.install_type = Some("dependency".to_string());
+            existing.installed_by = vec!["r2x-plexos".to_string()];
+            existing.registry = Some("internal".to_string());
+            existing.editable_install = true;
+            existing.resolved_source_path = Some("/home/dev/r2x-reeds".to_string());
+        }
+
+        let mut fresh = Manifest::default();
+        let rediscovered = fresh.get_or_create_package("r2x-reeds");
+        rediscovered.version = Some("0.2.0".to_string());
+        rediscovered.plugins = vec![plugin("reeds-upgrader", PluginKind::Upgrader)];
+        let rediscovered = rediscovered.clone();
+
+        let existing = &manifest.packages[0];
+        let merged = Package::merge_metadata(existing, rediscovered);
+
+        assert_eq!(merged.name, "r2x-reeds");
+        assert_eq!(merged.version.as_deref(), Some("0.2.0"));
+        assert_eq!(merged.plugins.len(), 1);
+        assert_eq!(merged.plugins[0].name, "reeds-upgrader");
+        assert_eq!(merged.install_type.as_deref(), Some("dependency"));
+        assert_eq!(merged.installed_by, vec!["r2x-plexos".to_string()]);
+        assert_eq!(merged.registry.as_deref(), Some("internal"));
+        assert!(merged.editable_install);
+        assert_eq!(
+            merged.resolved_source_path.as_deref(),
+            Some("/home/dev/r2x-reeds")
+        );
+    }
+
+    #[test]
+    fn test_list_plugins_sorted() {
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-zeta").plugins = vec![
+            plugin("zeta-parser", PluginKind::Parser),
+            plugin("beta-exporter", PluginKind::Exporter),
+        ];
+        manifest.get_or_create_package("r2x-alpha").plugins = vec![
+            plugin("omega-modifier", PluginKind::Modifier),
+            plugin("alpha-parser", PluginKind::Parser),
+        ];
+
+        let names: Vec<&str> = manifest
+            .list_plugins()
+            .into_iter()
+            .map(|(_, p)| p.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "alpha-parser",
+                "beta-exporter",
+                "omega-modifier",
+                "zeta-parser"
+            ]
+        );
+
+        let grouped: Vec<(&str, Vec<&str>)> = manifest
+            .list_plugins_by_package()
+            .into_iter()
+            .map(|(pkg, plugins)| (pkg, plugins.iter().map(|p| p.name.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            grouped,
+            vec![
+                ("r2x-alpha", vec!["alpha-parser", "omega-modifier"]),
+                ("r2x-zeta", vec!["beta-exporter", "zeta-parser"]),
+            ]
+        );
+    }
+
     #[test]
     fn test_clear_manifest() {
         let mut manifest = Manifest::default();
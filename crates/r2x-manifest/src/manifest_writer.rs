@@ -46,7 +46,7 @@ mod tests {
     use super::*;
     use crate::types::{
         ArgumentSpec, IOContract, ImplementationType, InvocationSpec, Metadata, Package,
-        PluginKind, PluginSpec,
+        PluginKind, PluginSpec, TypeKind,
     };
     use tempfile::TempDir;
 
@@ -57,12 +57,17 @@ mod tests {
 
         let packages = vec![Package {
             name: "r2x-example".to_string(),
+            version: Some("0.1.0".to_string()),
+            homepage: None,
+            license: None,
             entry_points_dist_info: "/path/to/entry_points.txt".to_string(),
             editable_install: true,
             pth_file: Some("/path/to/easy-install.pth".to_string()),
             resolved_source_path: Some("/home/dev/r2x-example".to_string()),
+            install_source: None,
             install_type: Some("explicit".to_string()),
             installed_by: Vec::new(),
+            registry: None,
             dependencies: Vec::new(),
             plugins: vec![PluginSpec {
                 name: "example-plugin".to_string(),
@@ -76,8 +81,12 @@ mod tests {
                         annotation: Some("str".to_string()),
                         default: Some("example-plugin".to_string()),
                         required: false,
+                        type_kind: Some(TypeKind::String),
                     }],
                     call: vec![],
+                    timeout_secs: None,
+                    retry_count: None,
+                    cacheable: false,
                 },
                 io: IOContract {
                     consumes: vec![],
@@ -87,6 +96,8 @@ mod tests {
                 upgrade: None,
                 description: None,
                 tags: vec![],
+                r2x_core_requirement: None,
+                confidence: None,
             }],
             decorator_registrations: vec![],
         }];
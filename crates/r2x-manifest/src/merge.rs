@@ -0,0 +1,185 @@
+//! Merging plugins from another manifest, e.g. a team-wide list of approved plugins
+
+use crate::errors::ManifestError;
+use crate::types::{Manifest, Package, PluginSpec};
+
+/// How [`Manifest::merge`] resolves a plugin defined differently in both manifests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Replace the existing plugin with the one from the other manifest
+    OtherWins,
+    /// Keep the existing plugin
+    SelfWins,
+    /// Fail without changing the manifest
+    ErrorOnConflict,
+}
+
+/// Where one side of a [`MergeConflict`] defines the plugin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginOrigin {
+    pub package: String,
+    pub entry: String,
+}
+
+/// A plugin name present in both manifests with a different package or entry point
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    pub plugin: String,
+    /// Definition in the manifest being merged into
+    pub ours: PluginOrigin,
+    /// Definition in the other manifest
+    pub theirs: PluginOrigin,
+}
+
+impl Manifest {
+    /// Add the plugins of `other` to this manifest
+    ///
+    /// Plugins are matched by name. Plugins only in `other` are added, together with
+    /// their package when it is new. New packages keep the install metadata recorded
+    /// in `other`, so callers should first drop packages the venv does not have.
+    /// Returns the conflicts that were resolved by `strategy`; with
+    /// [`MergeStrategy::ErrorOnConflict`] any conflict is an error.
+    pub fn merge(
+        &mut self,
+        other: &Manifest,
+        strategy: MergeStrategy,
+    ) -> Result<Vec<MergeConflict>, ManifestError> {
+        let mut conflicts = Vec::new();
+        let mut additions: Vec<(&Package, &PluginSpec)> = Vec::new();
+        for pkg in &other.packages {
+            for plugin in &pkg.plugins {
+                let existing = self
+                    .packages
+                    .iter()
+                    .find_map(|p| Some((p, p.plugins.iter().find(|s| s.name == plugin.name)?)));
+                match existing {
+                    None => additions.push((pkg, plugin)),
+                    Some((ours, spec)) if ours.name != pkg.name || spec.entry != plugin.entry => {
+                        conflicts.push(MergeConflict {
+                            plugin: plugin.name.clone(),
+                            ours: PluginOrigin {
+                                package: ours.name.clone(),
+                                entry: spec.entry.clone(),
+                            },
+                            theirs: PluginOrigin {
+                                package: pkg.name.clone(),
+                                entry: plugin.entry.clone(),
+                            },
+                        });
+                        if strategy == MergeStrategy::OtherWins {
+                            additions.push((pkg, plugin));
+                        }
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        if strategy == MergeStrategy::ErrorOnConflict && !conflicts.is_empty() {
+            return Err(ManifestError::MergeConflicts(conflicts));
+        }
+
+        if strategy == MergeStrategy::OtherWins {
+            for conflict in &conflicts {
+                self.filter_plugins_mut(|pkg, plugin| {
+                    pkg == conflict.ours.package && plugin.name == conflict.plugin
+                });
+            }
+        }
+        for (pkg, plugin) in additions {
+            if !self.packages.iter().any(|p| p.name == pkg.name) {
+                self.packages.push(Package {
+                    plugins: Vec::new(),
+                    ..pkg.clone()
+                });
+            }
+            self.get_or_create_package(&pkg.name)
+                .plugins
+                .push(plugin.clone());
+        }
+        Ok(conflicts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::plugin;
+
+    fn manifests() -> (Manifest, Manifest) {
+        let mut ours = Manifest::default();
+        ours.get_or_create_package("r2x-reeds").plugins = vec![
+            plugin("reeds-parser", "r2x_reeds.ReEDSParser"),
+            plugin("shared", "r2x_reeds.Shared"),
+        ];
+
+        let mut theirs = Manifest::default();
+        let pkg = theirs.get_or_create_package("r2x-plexos");
+        pkg.version = Some("0.3.0".to_string());
+        pkg.plugins = vec![
+            plugin("plexos-parser", "r2x_plexos.PlexosParser"),
+            plugin("shared", "r2x_plexos.Shared"),
+        ];
+        theirs
+            .get_or_create_package("r2x-reeds")
+            .plugins
+            .push(plugin("reeds-parser", "r2x_reeds.ReEDSParser"));
+        (ours, theirs)
+    }
+
+    fn names(manifest: &Manifest) -> Vec<String> {
+        manifest
+            .list_plugins()
+            .into_iter()
+            .map(|(pkg, plugin)| format!("{}/{}", pkg, plugin.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_self_wins() {
+        let (mut ours, theirs) = manifests();
+        let conflicts = ours.merge(&theirs, MergeStrategy::SelfWins).unwrap();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].plugin, "shared");
+        assert_eq!(conflicts[0].theirs.package, "r2x-plexos");
+        assert_eq!(
+            names(&ours),
+            vec![
+                "r2x-plexos/plexos-parser",
+                "r2x-reeds/reeds-parser",
+                "r2x-reeds/shared"
+            ]
+        );
+        let plexos = ours.packages.iter().find(|p| p.name == "r2x-plexos");
+        assert_eq!(plexos.unwrap().version.as_deref(), Some("0.3.0"));
+    }
+
+    #[test]
+    fn test_merge_other_wins() {
+        let (mut ours, theirs) = manifests();
+        let conflicts = ours.merge(&theirs, MergeStrategy::OtherWins).unwrap();
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            names(&ours),
+            vec![
+                "r2x-plexos/plexos-parser",
+                "r2x-reeds/reeds-parser",
+                "r2x-plexos/shared"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_error_on_conflict() {
+        let (mut ours, theirs) = manifests();
+        let result = ours.merge(&theirs, MergeStrategy::ErrorOnConflict);
+
+        assert!(matches!(result, Err(ManifestError::MergeConflicts(ref c)) if c.len() == 1));
+        assert_eq!(
+            names(&ours),
+            vec!["r2x-reeds/reeds-parser", "r2x-reeds/shared"]
+        );
+    }
+}
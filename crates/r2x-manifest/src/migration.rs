@@ -0,0 +1,329 @@
+//! Migration of legacy manifests
+//!
+//! Manifests written before r2x 0.0.14 stored a `[packages]` table of package
+//! records next to a flat `[plugins]` table keyed by plugin name, where each
+//! plugin pointed back to its package through `package_name`. Such manifests are
+//! converted to the package-grouped layout when loaded. Plugin records that cannot
+//! be converted are skipped with a warning; the original file is kept next to the
+//! manifest, see [`Manifest::legacy_backup_path`].
+
+use crate::errors::ManifestError;
+use crate::types::{
+    IOContract, IOSlot, ImplementationType, InvocationSpec, Manifest, Metadata, PluginKind,
+    PluginSpec,
+};
+use toml::Value;
+use tracing::warn;
+
+/// Schema version written for migrated manifests
+pub const MIGRATED_MANIFEST_VERSION: &str = "2";
+
+/// `plugin_type` values understood by the migration
+const LEGACY_PLUGIN_TYPES: &[&str] = &[
+    "parser",
+    "exporter",
+    "modifier",
+    "system_modifier",
+    "upgrader",
+    "translation",
+    "utility",
+];
+
+/// Whether a raw manifest uses the legacy `[plugins]` table layout
+pub fn is_legacy_format(value: &Value) -> bool {
+    value.get("plugins").is_some_and(Value::is_table)
+}
+
+/// Convert a legacy manifest into the current layout
+///
+/// Package versions come from the legacy `[packages]` records; plugins are grouped
+/// under the package named by their `package_name` field. Plugin records failing
+/// [`validate_legacy_plugins`] are left out with a warning.
+pub fn migrate_legacy_manifest(value: &Value) -> Result<Manifest, ManifestError> {
+    let plugins = value
+        .get("plugins")
+        .and_then(Value::as_table)
+        .ok_or_else(|| ManifestError::InvalidPlugin("legacy manifest has no plugins".into()))?;
+
+    let mut manifest = Manifest {
+        metadata: Metadata {
+            version: MIGRATED_MANIFEST_VERSION.to_string(),
+            generated_at: chrono::Utc::now().to_rfc3339(),
+            uv_lock_path: None,
+        },
+        packages: Vec::new(),
+    };
+
+    if let Some(packages) = value.get("packages").and_then(Value::as_table) {
+        for (key, record) in packages {
+            let name = str_field(record, "name").unwrap_or(key);
+            let pkg = manifest.get_or_create_package(name);
+            pkg.version = str_field(record, "version").map(str::to_string);
+            if let Some(dist_info) = str_field(record, "entry_points_dist_info") {
+                pkg.entry_points_dist_info = dist_info.to_string();
+            }
+            pkg.install_type = Some("explicit".to_string());
+        }
+    }
+
+    for (name, record) in plugins {
+        let errors = legacy_plugin_errors(name, record);
+        if !errors.is_empty() {
+            warn!(
+                "Skipping legacy plugin '{}' during migration: {}",
+                name,
+                errors.join("; ")
+            );
+            continue;
+        }
+        let package_name = str_field(record, "package_name").ok_or_else(|| {
+            ManifestError::InvalidPlugin(format!("legacy plugin '{}' has no package_name", name))
+        })?;
+        let plugin = migrate_plugin(name, record)?;
+
+        let pkg = manifest.get_or_create_package(package_name);
+        if pkg.install_type.is_none() {
+            pkg.install_type = Some("explicit".to_string());
+        }
+        pkg.plugins.push(plugin);
+    }
+
+    Ok(manifest)
+}
+
+/// Check the structure of every legacy plugin record before migrating
+///
+/// Each record needs non-empty `package_name` and `module` strings, an `obj` that
+/// is a non-empty string when present, and a known `plugin_type`. All problems are
+/// collected rather than stopping at the first one.
+pub fn validate_legacy_plugins(value: &Value) -> Result<(), Vec<String>> {
+    let Some(plugins) = value.get("plugins").and_then(Value::as_table) else {
+        return Err(vec!["legacy manifest has no plugins table".to_string()]);
+    };
+
+    let errors: Vec<String> = plugins
+        .iter()
+        .flat_map(|(name, record)| legacy_plugin_errors(name, record))
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Problems with one legacy plugin record, see [`validate_legacy_plugins`]
+fn legacy_plugin_errors(name: &str, record: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    if name.trim().is_empty() {
+        errors.push("plugin with an empty name".to_string());
+    }
+    if !record.is_table() {
+        errors.push(format!("plugin '{}' is not a table", name));
+        return errors;
+    }
+    for key in ["package_name", "module"] {
+        if !is_non_empty_str(record.get(key)) {
+            errors.push(format!(
+                "plugin '{}': '{}' must be a non-empty string",
+                name, key
+            ));
+        }
+    }
+    if let Some(obj) = record.get("obj") {
+        if !is_non_empty_str(Some(obj)) {
+            errors.push(format!(
+                "plugin '{}': 'obj' must be a non-empty string",
+                name
+            ));
+        }
+    }
+    match record.get("plugin_type").map(Value::as_str) {
+        Some(Some(kind)) if LEGACY_PLUGIN_TYPES.contains(&kind) => {}
+        Some(Some(kind)) => {
+            errors.push(format!("plugin '{}': unknown plugin_type '{}'", name, kind))
+        }
+        Some(None) => errors.push(format!("plugin '{}': 'plugin_type' must be a string", name)),
+        None => errors.push(format!("plugin '{}': missing 'plugin_type'", name)),
+    }
+    errors
+}
+
+fn is_non_empty_str(value: Option<&Value>) -> bool {
+    value
+        .and_then(Value::as_str)
+        .is_some_and(|s| !s.trim().is_empty())
+}
+
+fn migrate_plugin(name: &str, record: &Value) -> Result<PluginSpec, ManifestError> {
+    let module = str_field(record, "module").ok_or_else(|| {
+        ManifestError::InvalidPlugin(format!("legacy plugin '{}' has no module", name))
+    })?;
+    let obj = str_field(record, "obj");
+    let method = str_field(record, "call_method").map(str::to_string);
+
+    let kind = match str_field(record, "plugin_type").unwrap_or_default() {
+        "system_modifier" => PluginKind::Modifier,
+        // Unknown types are rejected by `validate_legacy_plugins`
+        other => other.parse().unwrap_or(PluginKind::Utility),
+    };
+
+    let is_class = method.is_some()
+        || obj.is_some_and(|obj| obj.starts_with(|c: char| c.is_ascii_uppercase()));
+    let entry = match obj {
+        Some(obj) => format!("{}.{}", module, obj),
+        None => module.to_string(),
+    };
+
+    Ok(PluginSpec {
+        name: name.to_string(),
+        io: default_io_contract(&kind),
+        kind,
+        entry,
+        invocation: InvocationSpec {
+            implementation: if is_class {
+                ImplementationType::Class
+            } else {
+                ImplementationType::Function
+            },
+            method,
+            constructor: Vec::new(),
+            call: Vec::new(),
+            timeout_secs: None,
+            retry_count: None,
+            cacheable: false,
+        },
+        resources: None,
+        upgrade: None,
+        description: str_field(record, "description").map(str::to_string),
+        tags: Vec::new(),
+        r2x_core_requirement: None,
+        confidence: None,
+    })
+}
+
+fn default_io_contract(kind: &PluginKind) -> IOContract {
+    let (consumes, produces) = match kind {
+        PluginKind::Parser => (
+            vec![IOSlot::StoreFolder, IOSlot::ConfigFile],
+            vec![IOSlot::System],
+        ),
+        PluginKind::Exporter => (
+            vec![IOSlot::System, IOSlot::ConfigFile],
+            vec![IOSlot::Folder],
+        ),
+        PluginKind::Modifier => (vec![IOSlot::System], vec![IOSlot::System]),
+        _ => (Vec::new(), Vec::new()),
+    };
+    IOContract { consumes, produces }
+}
+
+fn str_field<'a>(record: &'a Value, key: &str) -> Option<&'a str> {
+    record.get(key).and_then(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEGACY_MANIFEST: &str = include_str!("../tests/fixtures/legacy_manifest.toml");
+
+    #[test]
+    fn test_migrate_legacy_manifest_fixture() {
+        let value: Value = toml::from_str(LEGACY_MANIFEST).unwrap();
+        assert!(is_legacy_format(&value));
+
+        let manifest = migrate_legacy_manifest(&value).unwrap();
+        assert_eq!(manifest.metadata.version, MIGRATED_MANIFEST_VERSION);
+        assert_eq!(manifest.packages.len(), 2);
+
+        let reeds = &manifest.packages[0];
+        assert_eq!(reeds.name, "r2x-reeds");
+        assert_eq!(reeds.version.as_deref(), Some("0.1.2"));
+        assert_eq!(reeds.install_type.as_deref(), Some("explicit"));
+        assert_eq!(reeds.plugins.len(), 2);
+        assert_eq!(reeds.plugins[0].name, "reeds-parser");
+        assert_eq!(reeds.plugins[0].kind, PluginKind::Parser);
+        assert_eq!(reeds.plugins[0].entry, "r2x_reeds.parser.ReEDSParser");
+        assert_eq!(
+            reeds.plugins[0].invocation.method.as_deref(),
+            Some("build_system")
+        );
+        assert_eq!(
+            reeds.plugins[0].description.as_deref(),
+            Some("Parse ReEDS model outputs")
+        );
+        assert_eq!(reeds.plugins[1].kind, PluginKind::Upgrader);
+
+        let plexos = &manifest.packages[1];
+        assert_eq!(plexos.name, "r2x-plexos");
+        assert_eq!(plexos.version.as_deref(), Some("0.3.0"));
+        assert_eq!(plexos.plugins.len(), 2);
+        assert_eq!(plexos.plugins[1].kind, PluginKind::Modifier);
+        assert_eq!(
+            plexos.plugins[1].invocation.implementation,
+            ImplementationType::Function
+        );
+
+        // The migrated manifest round-trips through the current format
+        let content = toml::to_string_pretty(&manifest).unwrap();
+        let value: Value = toml::from_str(&content).unwrap();
+        assert!(!is_legacy_format(&value));
+        let reloaded: Manifest = toml::from_str(&content).unwrap();
+        assert_eq!(reloaded.total_plugin_count(), 4);
+    }
+
+    #[test]
+    fn test_migrate_legacy_plugin_without_package() {
+        let value: Value = toml::from_str("[plugins.orphan]\nmodule = \"pkg.mod\"\n").unwrap();
+        let manifest = migrate_legacy_manifest(&value).unwrap();
+        assert_eq!(manifest.total_plugin_count(), 0);
+
+        let value: Value = toml::from_str("packages = 1").unwrap();
+        let result = migrate_legacy_manifest(&value);
+        assert!(matches!(result, Err(ManifestError::InvalidPlugin(_))));
+    }
+
+    #[test]
+    fn test_validate_legacy_plugins_collects_all_errors() {
+        let value: Value = toml::from_str(LEGACY_MANIFEST).unwrap();
+        assert_eq!(validate_legacy_plugins(&value), Ok(()));
+
+        let value: Value = toml::from_str(
+            "[plugins.broken]\n\
+             package_name = \"r2x-reeds\"\n\
+             module = \"\"\n\
+             obj = 3\n\
+             plugin_type = \"importer\"\n\
+             [plugins.untyped]\n\
+             package_name = \"r2x-reeds\"\n\
+             module = \"r2x_reeds.untyped\"\n",
+        )
+        .unwrap();
+        let errors = validate_legacy_plugins(&value).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "plugin 'broken': 'module' must be a non-empty string",
+                "plugin 'broken': 'obj' must be a non-empty string",
+                "plugin 'broken': unknown plugin_type 'importer'",
+                "plugin 'untyped': missing 'plugin_type'",
+            ]
+        );
+
+        let mut with_valid = value.clone();
+        with_valid["plugins"].as_table_mut().unwrap().insert(
+            "reeds-parser".to_string(),
+            toml::from_str::<Value>(
+                "package_name = \"r2x-reeds\"\n\
+                 module = \"r2x_reeds.parser\"\n\
+                 obj = \"ReEDSParser\"\n\
+                 plugin_type = \"parser\"\n",
+            )
+            .unwrap(),
+        );
+        let manifest = migrate_legacy_manifest(&with_valid).unwrap();
+        assert_eq!(manifest.total_plugin_count(), 1);
+        assert_eq!(manifest.packages[0].plugins[0].name, "reeds-parser");
+    }
+}
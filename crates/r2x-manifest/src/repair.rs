@@ -0,0 +1,182 @@
+//! Dropping manifest entries whose Python module can no longer be found
+
+/// Prints each module argument that `importlib` cannot locate
+const FIND_SPEC_SCRIPT: &str = "\
+import importlib.util, sys
+for name in sys.argv[1:]:
+    try:
+        found = importlib.util.find_spec(name) is not None
+    except Exception:
+        found = False
+    if not found:
+        print(name)
+";
+
+use crate::errors::ManifestError;
+use crate::types::Manifest;
+use std::process::{Command, Stdio};
+
+/// Outcome of [`Manifest::auto_repair`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Removed plugins as `package/plugin`
+    pub removed: Vec<String>,
+    /// Packages dropped because none of their plugins were left
+    pub removed_packages: Vec<String>,
+    /// Number of plugins left in the manifest
+    pub kept: usize,
+}
+
+impl Manifest {
+    /// Remove plugins whose entry module `python_path` cannot locate
+    ///
+    /// Uses the same lookup as [`Manifest::unresolved_plugins`], so a module that
+    /// exists but fails to import is kept. Packages left without plugins are dropped.
+    /// Fails without touching the manifest when the interpreter cannot be started.
+    pub fn auto_repair(&mut self, python_path: &str) -> Result<RepairReport, ManifestError> {
+        let removed = self.unresolved_plugins(python_path)?;
+
+        let mut report = RepairReport::default();
+        for pkg in &mut self.packages {
+            let package = pkg.name.clone();
+            pkg.plugins
+                .retain(|plugin| !removed.contains(&format!("{}/{}", package, plugin.name)));
+            report.kept += pkg.plugins.len();
+        }
+        self.packages.retain(|pkg| {
+            let keep = !pkg.plugins.is_empty();
+            if !keep {
+                report.removed_packages.push(pkg.name.clone());
+            }
+            keep
+        });
+        report.removed = removed;
+        Ok(report)
+    }
+
+    /// Plugins, as `package/plugin`, whose entry module `python_path` cannot locate
+    ///
+    /// Modules are looked up without importing them, all in one interpreter run.
+    pub fn unresolved_plugins(&self, python_path: &str) -> Result<Vec<String>, ManifestError> {
+        let mut modules: Vec<&str> = self
+            .packages
+            .iter()
+            .flat_map(|pkg| &pkg.plugins)
+            .map(|plugin| entry_module(&plugin.entry))
+            .collect();
+        modules.sort_unstable();
+        modules.dedup();
+
+        let output = Command::new(python_path)
+            .args(["-c", FIND_SPEC_SCRIPT])
+            .args(&modules)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()?;
+        if !output.status.success() {
+            return Err(ManifestError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("{} exited with {}", python_path, output.status),
+            )));
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let missing: Vec<&str> = stdout.lines().collect();
+
+        Ok(self
+            .packages
+            .iter()
+            .flat_map(|pkg| {
+                pkg.plugins
+                    .iter()
+                    .filter(|plugin| missing.contains(&entry_module(&plugin.entry)))
+                    .map(move |plugin| format!("{}/{}", pkg.name, plugin.name))
+            })
+            .collect())
+    }
+}
+
+/// Module part of an entry point such as `r2x_reeds.parser:ReEDSParser` or `r2x_reeds.ReEDSParser`
+fn entry_module(entry: &str) -> &str {
+    match entry.split_once(':') {
+        Some((module, _)) => module,
+        None => entry.rsplit_once('.').map_or(entry, |(module, _)| module),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::plugin;
+
+    #[test]
+    fn test_entry_module() {
+        assert_eq!(
+            entry_module("r2x_reeds.parser:ReEDSParser"),
+            "r2x_reeds.parser"
+        );
+        assert_eq!(entry_module("r2x_reeds.ReEDSParser"), "r2x_reeds");
+        assert_eq!(entry_module("ReEDSParser"), "ReEDSParser");
+    }
+
+    #[test]
+    fn test_auto_repair_removes_missing_modules() {
+        let python = "python3";
+        if Command::new(python).arg("--version").output().is_err() {
+            return;
+        }
+
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-example").plugins = vec![
+            plugin("json-decoder", "json.JSONDecoder"),
+            plugin("missing", "r2x_no_such_module_for_tests.Parser"),
+            plugin("injected", "os; print(1).Parser"),
+        ];
+
+        manifest.get_or_create_package("r2x-gone").plugins =
+            vec![plugin("gone", "r2x_no_such_module_for_tests.Exporter")];
+
+        let report = manifest.auto_repair(python).unwrap();
+        assert_eq!(report.kept, 1);
+        assert_eq!(
+            report.removed,
+            vec![
+                "r2x-example/missing",
+                "r2x-example/injected",
+                "r2x-gone/gone"
+            ]
+        );
+        assert_eq!(report.removed_packages, vec!["r2x-gone"]);
+        assert_eq!(manifest.packages.len(), 1);
+        assert_eq!(manifest.packages[0].plugins[0].name, "json-decoder");
+    }
+
+    #[test]
+    fn test_unresolved_plugins() {
+        let python = "python3";
+        if Command::new(python).arg("--version").output().is_err() {
+            return;
+        }
+
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-example").plugins = vec![
+            plugin("json-decoder", "json.JSONDecoder"),
+            plugin("missing", "r2x_no_such_module_for_tests.parser:Parser"),
+        ];
+
+        assert_eq!(
+            manifest.unresolved_plugins(python).unwrap(),
+            vec!["r2x-example/missing"]
+        );
+        assert!(manifest.unresolved_plugins("/nonexistent/python").is_err());
+    }
+
+    #[test]
+    fn test_auto_repair_missing_interpreter() {
+        let mut manifest = Manifest::default();
+        manifest.get_or_create_package("r2x-example").plugins =
+            vec![plugin("json-decoder", "json.JSONDecoder")];
+
+        assert!(manifest.auto_repair("/nonexistent/python").is_err());
+        assert_eq!(manifest.packages[0].plugins.len(), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::{ArgumentSpec, ConfigSpec, ImplementationType, PluginKind, PluginSpec};
+use crate::types::{ArgumentSpec, ConfigSpec, IOSlot, ImplementationType, PluginKind, PluginSpec};
 
 #[derive(Debug, Clone)]
 pub struct RuntimeBindings {
@@ -10,6 +10,8 @@ pub struct RuntimeBindings {
     pub call_method: Option<String>,
     pub requires_store: bool,
     pub entry_parameters: Vec<ArgumentSpec>,
+    /// Names of plugin-defined input slots, passed through from the config as-is
+    pub custom_inputs: Vec<String>,
 }
 
 pub fn build_runtime_bindings(plugin: &PluginSpec) -> RuntimeBindings {
@@ -41,9 +43,57 @@ pub fn build_runtime_bindings(plugin: &PluginSpec) -> RuntimeBindings {
         call_method,
         requires_store,
         entry_parameters,
+        custom_inputs: plugin
+            .io
+            .consumes
+            .iter()
+            .filter_map(|slot| match slot {
+                IOSlot::Custom(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect(),
     }
 }
 
+/// Whether a class name suggests a class that is not meant to be instantiated
+///
+/// The CamelCase words of the name are checked for `Mixin`, `Abstract`, `Base` or
+/// `Meta`, and names ending in `ABC` also match. Whole words are compared, so
+/// `MetadataParser` or `DatabaseExporter` do not match.
+pub fn looks_like_abstract_class(name: &str) -> bool {
+    if name.ends_with("ABC") {
+        return true;
+    }
+    camel_case_words(name)
+        .iter()
+        .any(|word| matches!(*word, "Mixin" | "Abstract" | "Base" | "Meta"))
+}
+
+/// Split `ABCMetaParser` into `["ABC", "Meta", "Parser"]`
+fn camel_case_words(name: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = name.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start = 0;
+    for (i, &(idx, ch)) in chars.iter().enumerate().skip(1) {
+        let prev = chars[i - 1].1;
+        let next_is_lower = chars.get(i + 1).is_some_and(|(_, c)| c.is_lowercase());
+        let boundary = ch == '_'
+            || prev == '_'
+            || (ch.is_uppercase() && (prev.is_lowercase() || prev.is_ascii_digit()))
+            || (ch.is_uppercase() && prev.is_uppercase() && next_is_lower);
+        if boundary {
+            words.push(&name[start..idx]);
+            start = idx;
+        }
+    }
+    words.push(&name[start..]);
+    words
+        .into_iter()
+        .map(|word| word.trim_matches('_'))
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
 fn parse_entry_point(entry: &str) -> (String, String) {
     if let Some(pos) = entry.rfind('.') {
         (entry[..pos].to_string(), entry[pos + 1..].to_string())
@@ -61,3 +111,39 @@ fn default_method_for_kind(kind: &crate::types::PluginKind) -> Option<String> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_abstract_class() {
+        for name in [
+            "ABCMeta",
+            "MixinBase",
+            "AbstractParser",
+            "ParserABC",
+            "Base",
+        ] {
+            assert!(looks_like_abstract_class(name), "{}", name);
+        }
+        for name in [
+            "ReEDSParser",
+            "MetadataParser",
+            "DatabaseExporter",
+            "HTTPClient",
+        ] {
+            assert!(!looks_like_abstract_class(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_camel_case_words() {
+        assert_eq!(
+            camel_case_words("ABCMetaParser"),
+            vec!["ABC", "Meta", "Parser"]
+        );
+        assert_eq!(camel_case_words("ReEDSParser"), vec!["Re", "EDS", "Parser"]);
+        assert_eq!(camel_case_words("plexos_Base"), vec!["plexos", "Base"]);
+    }
+}
@@ -0,0 +1,31 @@
+//! Fixtures shared by the unit tests of this crate
+
+use crate::types::{IOContract, ImplementationType, InvocationSpec, PluginKind, PluginSpec};
+
+/// Class-based parser plugin `name` with entry point `entry` and no other metadata
+pub(crate) fn plugin(name: &str, entry: &str) -> PluginSpec {
+    PluginSpec {
+        name: name.to_string(),
+        kind: PluginKind::Parser,
+        entry: entry.to_string(),
+        invocation: InvocationSpec {
+            implementation: ImplementationType::Class,
+            method: None,
+            constructor: vec![],
+            call: vec![],
+            timeout_secs: None,
+            retry_count: None,
+            cacheable: false,
+        },
+        io: IOContract {
+            consumes: vec![],
+            produces: vec![],
+        },
+        resources: None,
+        upgrade: None,
+        description: None,
+        tags: vec![],
+        r2x_core_requirement: None,
+        confidence: None,
+    }
+}
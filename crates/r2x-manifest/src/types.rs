@@ -36,16 +36,35 @@ impl Default for Manifest {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Package {
     pub name: String,
+    /// Installed package version recorded at discovery time
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Project homepage from the package `METADATA`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// License from the package `METADATA`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
     pub entry_points_dist_info: String,
     #[serde(default)]
     pub editable_install: bool,
     pub pth_file: Option<String>,
     pub resolved_source_path: Option<String>,
+    /// Git URL (with any `@ref`) or local path the package was installed from;
+    /// unset for packages installed from a package index
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub install_source: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub install_type: Option<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub installed_by: Vec<String>,
+    /// Name of the `[registries]` entry the package was installed from
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub dependencies: Vec<String>,
@@ -72,6 +91,12 @@ pub struct PluginSpec {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
+    /// r2x-core version specifier from the package `Requires-Dist`, e.g. `>=0.1.0rc1,<0.2.0`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub r2x_core_requirement: Option<String>,
+    /// How completely AST discovery understood the plugin, from 0.0 to 1.0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
 }
 
 /// Plugin kind/type enumeration
@@ -98,6 +123,15 @@ pub struct InvocationSpec {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub call: Vec<ArgumentSpec>,
+    /// Maximum run time declared by the plugin package, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Number of times a failed invocation is retried
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_count: Option<u32>,
+    /// Output depends only on the configuration and input data, so results may be reused
+    #[serde(default)]
+    pub cacheable: bool,
 }
 
 /// Implementation type for plugins
@@ -117,6 +151,37 @@ pub struct ArgumentSpec {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<String>,
     pub required: bool,
+    /// Kind inferred from the annotation, stored as e.g. `optional_number` or `list_string`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_kind: Option<TypeKind>,
+}
+
+/// Kind of an argument, classified from its type annotation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
+pub enum TypeKind {
+    /// `str`
+    String,
+    /// `int`
+    Number,
+    /// `float`
+    Float,
+    /// `bool`
+    Boolean,
+    /// `None`
+    None,
+    /// A class such as `Path`
+    ClassReference,
+    /// Any other subscripted annotation, e.g. `dict[str, int]`
+    Complex,
+    /// A union with `None`: `Optional[T]`, `Union[T, None]` or `T | None`
+    Optional(Box<TypeKind>),
+    /// `list[T]` or `List[T]`
+    List(Box<TypeKind>),
+    /// Any other union
+    Union,
+    /// A lowercase name that is none of the above
+    Identifier,
 }
 
 /// Input/output contract for a plugin
@@ -140,6 +205,28 @@ pub enum IOSlot {
     File,
     Folder,
     Data,
+    /// Plugin-defined slot, declared as `"Custom:<name>"`
+    Custom(String),
+}
+
+impl IOSlot {
+    /// Parse a slot declared in plugin source, e.g. `"SYSTEM"` or `"Custom:database"`
+    pub fn from_declaration(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(name) = value.strip_prefix("Custom:") {
+            let name = name.trim();
+            return (!name.is_empty()).then(|| IOSlot::Custom(name.to_string()));
+        }
+        match value.to_ascii_uppercase().as_str() {
+            "SYSTEM" => Some(IOSlot::System),
+            "CONFIG_FILE" => Some(IOSlot::ConfigFile),
+            "STORE_FOLDER" => Some(IOSlot::StoreFolder),
+            "FILE" => Some(IOSlot::File),
+            "FOLDER" => Some(IOSlot::Folder),
+            "DATA" => Some(IOSlot::Data),
+            _ => None,
+        }
+    }
 }
 
 /// Resource requirements (config and data store)
@@ -176,6 +263,9 @@ pub struct ConfigSpec {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub fields: Vec<ConfigField>,
+    /// Major Pydantic version the config class is written for (1 or 2), when detectable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pydantic_version: Option<u8>,
 }
 
 /// Configuration field specification
@@ -187,6 +277,9 @@ pub struct ConfigField {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<String>,
     pub required: bool,
+    /// Pydantic `Field(...)` validation bounds, e.g. `{"ge": 0, "le": 100}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraints: Option<serde_json::Value>,
 }
 
 /// Upgrade specification for upgrader plugins
@@ -240,3 +333,44 @@ pub enum VarArgType {
     Args,
     Kwargs,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_slot_from_declaration() {
+        assert_eq!(IOSlot::from_declaration("SYSTEM"), Some(IOSlot::System));
+        assert_eq!(
+            IOSlot::from_declaration("store_folder"),
+            Some(IOSlot::StoreFolder)
+        );
+        assert_eq!(
+            IOSlot::from_declaration("Custom:network-resource"),
+            Some(IOSlot::Custom("network-resource".to_string()))
+        );
+        assert_eq!(IOSlot::from_declaration("Custom:"), None);
+        assert_eq!(IOSlot::from_declaration("unknown"), None);
+    }
+
+    #[test]
+    fn test_io_contract_serde_roundtrip() {
+        let contract = IOContract {
+            consumes: vec![IOSlot::System, IOSlot::Custom("database".to_string())],
+            produces: vec![IOSlot::Folder],
+        };
+
+        let json = serde_json::to_string(&contract).unwrap();
+        assert_eq!(
+            json,
+            r#"{"consumes":["SYSTEM",{"CUSTOM":"database"}],"produces":["FOLDER"]}"#
+        );
+        let from_json: IOContract = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_json.consumes, contract.consumes);
+
+        let toml_text = toml::to_string(&contract).unwrap();
+        let from_toml: IOContract = toml::from_str(&toml_text).unwrap();
+        assert_eq!(from_toml.consumes, contract.consumes);
+        assert_eq!(from_toml.produces, contract.produces);
+    }
+}
@@ -0,0 +1,192 @@
+//! Minimal PEP 440 version specifier matching
+//!
+//! Covers what plugin packages use to pin r2x-core: release segments with an
+//! optional `a`/`b`/`rc` pre-release, and comma-separated clauses using `==`,
+//! `!=`, `>=`, `<=`, `>`, `<` and `~=`. Local versions, epochs and dev/post
+//! releases are not supported; such specs are reported as unparseable.
+
+use std::cmp::Ordering;
+
+/// Split `value` into its leading release segments and the rest, e.g. `0.1.0rc1`
+/// into `[0, 1, 0]` and `rc1`
+///
+/// The release ends at the first character that is neither a digit nor a dot; a dot
+/// right before that character is left to the rest (`1.0.rc1` gives `.rc1`).
+/// Returns `None` when there is no release or a segment is empty, as in `1..2`.
+pub fn parse_release(value: &str) -> Option<(Vec<u64>, &str)> {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let release = if end < value.len() {
+        value[..end].trim_end_matches('.')
+    } else {
+        value
+    };
+    let segments = release
+        .split('.')
+        .map(|segment| segment.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some((segments, &value[release.len()..]))
+}
+
+/// A parsed version: release segments plus an optional pre-release
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    release: Vec<u64>,
+    /// `(phase, number)` where `a` < `b` < `rc`
+    pre: Option<(u8, u64)>,
+}
+
+impl Version {
+    fn parse(value: &str) -> Option<Self> {
+        let value = value.trim().trim_start_matches('v').to_lowercase();
+        let (release, suffix) = parse_release(&value)?;
+
+        let suffix = suffix.trim_start_matches(['.', '-', '_']);
+        let pre = if suffix.is_empty() {
+            None
+        } else {
+            let (phase, number) = [("rc", 2), ("a", 0), ("b", 1)]
+                .iter()
+                .find_map(|(tag, phase)| suffix.strip_prefix(tag).map(|rest| (*phase, rest)))?;
+            let number = if number.is_empty() {
+                0
+            } else {
+                number.parse().ok()?
+            };
+            Some((phase, number))
+        };
+
+        Some(Version { release, pre })
+    }
+
+    fn same_release(&self, other: &Self) -> bool {
+        let len = self.release.len().max(other.release.len());
+        (0..len).all(|idx| self.segment(idx) == other.segment(idx))
+    }
+
+    fn segment(&self, idx: usize) -> u64 {
+        self.release.get(idx).copied().unwrap_or(0)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        (0..len)
+            .map(|idx| self.segment(idx).cmp(&other.segment(idx)))
+            .find(|ord| ord.is_ne())
+            .unwrap_or_else(|| match (self.pre, other.pre) {
+                (None, None) => Ordering::Equal,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => a.cmp(&b),
+            })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Check whether `version` satisfies every clause of `spec`
+///
+/// Returns `None` when the version or any clause cannot be parsed.
+pub fn satisfies(version: &str, spec: &str) -> Option<bool> {
+    let version = Version::parse(version)?;
+    let mut compatible = true;
+    for clause in spec.split(',').map(str::trim).filter(|c| !c.is_empty()) {
+        compatible &= clause_matches(&version, clause)?;
+    }
+    Some(compatible)
+}
+
+fn clause_matches(version: &Version, clause: &str) -> Option<bool> {
+    let op_len = clause
+        .find(|c: char| !matches!(c, '=' | '!' | '<' | '>' | '~'))
+        .unwrap_or(clause.len());
+    let (op, target) = clause.split_at(op_len);
+    let target = target.trim();
+
+    if let Some(prefix) = target.strip_suffix(".*") {
+        let prefix = Version::parse(prefix)?;
+        let matches = prefix
+            .release
+            .iter()
+            .enumerate()
+            .all(|(idx, segment)| version.segment(idx) == *segment);
+        return match op {
+            "==" => Some(matches),
+            "!=" => Some(!matches),
+            _ => None,
+        };
+    }
+
+    let target = Version::parse(target)?;
+    let ord = version.cmp(&target);
+    match op {
+        "==" | "===" => Some(ord.is_eq()),
+        "!=" => Some(ord.is_ne()),
+        ">=" => Some(ord.is_ge()),
+        "<=" => Some(ord.is_le()),
+        ">" => Some(ord.is_gt()),
+        // `<V` excludes pre-releases of V itself unless V is a pre-release
+        "<" => Some(ord.is_lt() && (target.pre.is_some() || !version.same_release(&target))),
+        "~=" => {
+            // ~=X.Y.Z means >=X.Y.Z and ==X.Y.*
+            if target.release.len() < 2 {
+                return None;
+            }
+            let prefix_len = target.release.len() - 1;
+            let same_prefix =
+                (0..prefix_len).all(|idx| version.segment(idx) == target.segment(idx));
+            Some(ord.is_ge() && same_prefix)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_release() {
+        assert_eq!(parse_release("0.1.0rc1"), Some((vec![0, 1, 0], "rc1")));
+        assert_eq!(parse_release("1.0.rc1"), Some((vec![1, 0], ".rc1")));
+        assert_eq!(parse_release("3.12"), Some((vec![3, 12], "")));
+        assert_eq!(parse_release("1..2"), None);
+        assert_eq!(parse_release("0.1."), None);
+        assert_eq!(parse_release("latest"), None);
+    }
+
+    #[test]
+    fn test_satisfies_range_with_prerelease() {
+        let spec = ">=0.1.0rc1,<0.2.0";
+        assert_eq!(satisfies("0.1.0rc1", spec), Some(true));
+        assert_eq!(satisfies("0.1.0", spec), Some(true));
+        assert_eq!(satisfies("0.1.5", spec), Some(true));
+        assert_eq!(satisfies("0.1.0b2", spec), Some(false));
+        assert_eq!(satisfies("0.2.0", spec), Some(false));
+        assert_eq!(satisfies("0.2.0rc1", spec), Some(false));
+        assert_eq!(satisfies("0.2.0rc1", "<0.2.0rc2"), Some(true));
+    }
+
+    #[test]
+    fn test_satisfies_other_operators() {
+        assert_eq!(satisfies("0.1.3", "~=0.1.2"), Some(true));
+        assert_eq!(satisfies("0.2.0", "~=0.1.2"), Some(false));
+        assert_eq!(satisfies("0.1.7", "==0.1.*"), Some(true));
+        assert_eq!(satisfies("0.2", "!=0.2.0"), Some(false));
+        assert_eq!(satisfies("1.0", ""), Some(true));
+    }
+
+    #[test]
+    fn test_satisfies_unparseable() {
+        assert_eq!(satisfies("not-a-version", ">=0.1"), None);
+        assert_eq!(satisfies("0.1.0", ">=0.1.0.dev1"), None);
+        assert_eq!(satisfies("0.1.0", "=>0.1.0"), None);
+    }
+}
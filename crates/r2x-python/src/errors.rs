@@ -1,13 +1,30 @@
+use miette::Diagnostic;
+use pyo3::types::{PyAnyMethods, PyModule};
 use std::io;
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
+/// Suggested fix for a plugin class that is called with fewer positional arguments
+/// than it takes, which usually means its cached signature is out of date
+const STALE_CACHE_HINT: &str =
+    "This may happen if the plugin metadata cache is stale. Try running:\n  r2x sync";
+
 /// Errors that can occur during Python bridge operations
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Diagnostic)]
 pub enum BridgeError {
     #[error("Python error: {0}")]
     Python(String),
 
+    /// Python exception with its formatted traceback, when Python provided one
+    #[error("Python error: {message}")]
+    PythonDetailed {
+        message: String,
+        traceback: Option<String>,
+        #[help]
+        hint: Option<String>,
+    },
+
     #[error("Failed to import module '{0}': {1}")]
     Import(String, String),
 
@@ -29,12 +46,242 @@ pub enum BridgeError {
     #[error("Invalid entry point format: {0}")]
     InvalidEntryPoint(String),
 
+    #[error("Plugin '{plugin_name}' timed out after {}s", elapsed.as_secs())]
+    Timeout {
+        /// Manifest name of the plugin, or its call target when it has no metadata
+        plugin_name: String,
+        /// Limit the call ran into
+        elapsed: Duration,
+    },
+
+    /// Access to `path` was refused, by the OS or inside a plugin
+    #[error("Permission denied: {path}")]
+    PermissionDenied {
+        path: String,
+        /// Python traceback, when a plugin raised the `PermissionError`
+        traceback: Option<String>,
+    },
+
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
 }
 
+impl BridgeError {
+    /// Detailed error for the Python exception `err`, with `context` saying what failed
+    pub fn python(py: pyo3::Python<'_>, err: pyo3::PyErr, context: &str) -> Self {
+        Self::python_detailed(py, &err, format!("{}: {}", context, err))
+    }
+
+    fn python_detailed(py: pyo3::Python<'_>, err: &pyo3::PyErr, message: String) -> Self {
+        let hint = (message.contains("missing")
+            && message.contains("required positional argument"))
+        .then(|| STALE_CACHE_HINT.to_string());
+        BridgeError::PythonDetailed {
+            message,
+            traceback: render_traceback(py, err),
+            hint,
+        }
+    }
+
+    /// Python traceback of the error, when it came from a Python exception that had one
+    pub fn traceback(&self) -> Option<&str> {
+        match self {
+            BridgeError::PythonDetailed { traceback, .. }
+            | BridgeError::PermissionDenied { traceback, .. } => traceback.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Short message for end users with a suggested fix where one is known
+    ///
+    /// The `Display` output keeps the technical details and is shown with `--verbose`.
+    pub fn user_friendly_message(&self) -> String {
+        match self {
+            BridgeError::VenvNotFound(_) => "No usable Python environment was found. \
+                Run 'r2x config venv create --yes' or 'r2x config set venv-path <path>'"
+                .to_string(),
+            BridgeError::R2XCoreNotInstalled => {
+                "r2x-core is not installed. Run 'r2x install <package>' for a plugin package, \
+                 which installs r2x-core with it"
+                    .to_string()
+            }
+            BridgeError::PluginNotFound(name) => format!(
+                "Plugin '{}' not found. Run 'r2x list' to see available plugins, \
+                 or 'r2x install' to add new ones",
+                name
+            ),
+            BridgeError::Timeout {
+                plugin_name,
+                elapsed,
+            } => format!(
+                "Plugin '{}' did not finish within {}s. Raise the limit with --timeout \
+                 or 'r2x config set plugin-timeout-secs <secs>'",
+                plugin_name,
+                elapsed.as_secs()
+            ),
+            BridgeError::PermissionDenied { path, .. } => format!(
+                "Permission denied: {}. Check that the current user can access this path",
+                path
+            ),
+            _ => self.to_string(),
+        }
+    }
+}
+
 impl From<pyo3::PyErr> for BridgeError {
     fn from(err: pyo3::PyErr) -> Self {
-        BridgeError::Python(format!("{}", err))
+        pyo3::Python::attach(|py| {
+            if err.is_instance_of::<pyo3::exceptions::PyPermissionError>(py) {
+                // Name the path the plugin was denied, e.g. its output folder
+                let filename = err
+                    .value(py)
+                    .getattr("filename")
+                    .ok()
+                    .filter(|filename| !filename.is_none())
+                    .map(|filename| filename.to_string());
+                BridgeError::PermissionDenied {
+                    path: filename.unwrap_or_else(|| err.to_string()),
+                    traceback: render_traceback(py, &err),
+                }
+            } else {
+                Self::python_detailed(py, &err, format!("{}", err))
+            }
+        })
+    }
+}
+
+/// `traceback.format_exception` output for `err`; `None` without a traceback
+fn render_traceback(py: pyo3::Python<'_>, err: &pyo3::PyErr) -> Option<String> {
+    let traceback = err.traceback(py)?;
+    let traceback_module = PyModule::import(py, "traceback").ok()?;
+    let formatter = traceback_module.getattr("format_exception").ok()?;
+    let formatted = formatter
+        .call1((err.get_type(py), err.value(py), traceback))
+        .ok()?;
+    let lines: Vec<String> = formatted.extract().ok()?;
+    Some(lines.join(""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_friendly_message() {
+        let message =
+            BridgeError::VenvNotFound(PathBuf::from("/missing/.venv")).user_friendly_message();
+        assert!(message.contains("r2x config set venv-path"));
+        assert!(!message.contains("/missing/.venv"));
+
+        let message =
+            BridgeError::PluginNotFound("reeds-parser".to_string()).user_friendly_message();
+        assert!(message.starts_with("Plugin 'reeds-parser' not found."));
+        assert!(message.contains("r2x list"));
+
+        let message = BridgeError::Timeout {
+            plugin_name: "reeds-parser".to_string(),
+            elapsed: Duration::from_secs(90),
+        }
+        .user_friendly_message();
+        assert!(message.contains("within 90s"));
+        assert!(message.contains("plugin-timeout-secs"));
+
+        let message = BridgeError::PermissionDenied {
+            path: "/opt/r2x/.venv".to_string(),
+            traceback: None,
+        }
+        .user_friendly_message();
+        assert!(message.starts_with("Permission denied: /opt/r2x/.venv."));
+        assert!(message.contains("current user"));
+
+        let error = BridgeError::Serialization("bad json".to_string());
+        assert_eq!(error.user_friendly_message(), error.to_string());
+    }
+
+    #[test]
+    fn test_display() {
+        let error = BridgeError::Timeout {
+            plugin_name: "reeds-parser".to_string(),
+            elapsed: Duration::from_millis(90_500),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Plugin 'reeds-parser' timed out after 90s"
+        );
+
+        let error = BridgeError::PermissionDenied {
+            path: "/opt/r2x/.venv".to_string(),
+            traceback: None,
+        };
+        assert_eq!(error.to_string(), "Permission denied: /opt/r2x/.venv");
+    }
+
+    #[test]
+    fn test_from_py_err() {
+        let error = BridgeError::from(pyo3::exceptions::PyPermissionError::new_err(
+            "[Errno 13] Permission denied: '/opt/r2x/.venv'",
+        ));
+        assert!(
+            matches!(error, BridgeError::PermissionDenied { ref path, .. } if path.contains("Errno 13"))
+        );
+
+        let error = BridgeError::from(pyo3::exceptions::PyPermissionError::new_err((
+            13,
+            "Permission denied",
+            "/data/outputs",
+        )));
+        assert!(
+            matches!(error, BridgeError::PermissionDenied { ref path, .. } if path == "/data/outputs")
+        );
+
+        let error = BridgeError::from(pyo3::exceptions::PyValueError::new_err("bad value"));
+        assert!(matches!(
+            error,
+            BridgeError::PythonDetailed { ref message, traceback: None, hint: None }
+                if message == "ValueError: bad value"
+        ));
+        assert_eq!(error.to_string(), "Python error: ValueError: bad value");
+    }
+
+    #[test]
+    fn test_python_detailed_hint() {
+        let error = pyo3::Python::attach(|py| {
+            let err = py
+                .run(
+                    pyo3::ffi::c_str!("def build(config, system):\n    pass\nbuild(1)"),
+                    None,
+                    None,
+                )
+                .unwrap_err();
+            BridgeError::python(py, err, "Failed to instantiate 'Parser'")
+        });
+
+        assert!(error
+            .to_string()
+            .starts_with("Python error: Failed to instantiate 'Parser': TypeError"));
+        assert!(error.traceback().unwrap().starts_with("Traceback"));
+        assert_eq!(
+            error.help().map(|help| help.to_string()).as_deref(),
+            Some(STALE_CACHE_HINT)
+        );
+    }
+
+    #[test]
+    fn test_permission_denied_keeps_traceback() {
+        let error = pyo3::Python::attach(|py| {
+            let err = py
+                .run(
+                    pyo3::ffi::c_str!(
+                        "def write():\n    raise PermissionError(13, 'Permission denied', '/data/outputs')\nwrite()"
+                    ),
+                    None,
+                    None,
+                )
+                .unwrap_err();
+            BridgeError::from(err)
+        });
+
+        assert_eq!(error.to_string(), "Permission denied: /data/outputs");
+        assert!(error.traceback().unwrap().contains("in write"));
     }
 }
@@ -5,15 +5,19 @@
 
 use super::utils::{resolve_python_path, resolve_site_package_path};
 use crate::errors::BridgeError;
+use crate::plugin_invoker::hooks::{PostHook, PreHook};
 use once_cell::sync::OnceCell;
 use pyo3::prelude::*;
 use pyo3::types::PyModule;
 use r2x_config::Config;
 use r2x_logger as logger;
+use r2x_logger::LogFormat;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex};
 
 #[cfg(windows)]
 const PYTHON_BIN_DIR_NAME: &str = "Scripts";
@@ -124,7 +128,21 @@ fn ensure_python_installed(config: &Config) -> Result<(), BridgeError> {
     Ok(())
 }
 
-pub struct Bridge {}
+pub struct Bridge {
+    pub(crate) pre_hooks: Arc<Mutex<Vec<PreHook>>>,
+    pub(crate) post_hooks: Arc<Mutex<Vec<PostHook>>>,
+    /// Modules imported by [`Bridge::warm_up`], kept so invocations skip the import
+    modules: OnceCell<HashMap<&'static str, Py<PyModule>>>,
+}
+
+/// Modules every invocation needs
+const WARM_MODULES: [&str; 5] = [
+    "json",
+    "pathlib",
+    "r2x_core.system",
+    "r2x_core.store",
+    "r2x_core.logger",
+];
 
 #[derive(Debug, Clone)]
 pub struct PythonEnvironment {
@@ -135,8 +153,68 @@ pub struct PythonEnvironment {
 static BRIDGE_INSTANCE: OnceCell<Result<Bridge, BridgeError>> = OnceCell::new();
 
 impl Bridge {
+    pub(crate) fn new() -> Self {
+        Bridge {
+            pre_hooks: Arc::new(Mutex::new(Vec::new())),
+            post_hooks: Arc::new(Mutex::new(Vec::new())),
+            modules: OnceCell::new(),
+        }
+    }
+
+    /// Import the modules every invocation needs so the first plugin does not pay for it
+    ///
+    /// Importing `r2x_core` dominates the start-up of a plugin run. Calling this is
+    /// optional and only the first call does any work. Modules that fail to import are
+    /// reported by that call and left to the invocations that need them.
+    pub fn warm_up(&self) -> Result<(), BridgeError> {
+        self.warm_up_modules(&WARM_MODULES)
+    }
+
+    fn warm_up_modules(&self, names: &[&'static str]) -> Result<(), BridgeError> {
+        if self.modules.get().is_some() {
+            return Ok(());
+        }
+        let start = std::time::Instant::now();
+        let mut failure = None;
+        let modules = pyo3::Python::attach(|py| {
+            let mut modules = HashMap::new();
+            for &name in names {
+                match PyModule::import(py, name) {
+                    Ok(module) => {
+                        modules.insert(name, module.unbind());
+                    }
+                    Err(e) => {
+                        logger::debug(&format!("Skipping warm-up of {}: {}", name, e));
+                        failure.get_or_insert_with(|| {
+                            BridgeError::Import(name.to_string(), format!("{}", e))
+                        });
+                    }
+                }
+            }
+            modules
+        });
+        let _ = self.modules.set(modules);
+        logger::debug(&format!("Bridge warm-up took: {:?}", start.elapsed()));
+        failure.map_or(Ok(()), Err)
+    }
+
+    /// Import `name`, using the module cached by [`Bridge::warm_up`] when there is one
+    pub(crate) fn import<'py>(
+        &self,
+        py: Python<'py>,
+        name: &str,
+    ) -> PyResult<Bound<'py, PyModule>> {
+        match self.modules.get().and_then(|modules| modules.get(name)) {
+            Some(module) => Ok(module.bind(py).clone()),
+            None => PyModule::import(py, name),
+        }
+    }
+
     /// Get or initialize the bridge singleton
     pub fn get() -> Result<&'static Bridge, BridgeError> {
+        if BRIDGE_INSTANCE.get().is_none() {
+            verify_existing_venv_python()?;
+        }
         match BRIDGE_INSTANCE.get_or_init(Bridge::initialize) {
             Ok(bridge) => Ok(bridge),
             Err(e) => Err(BridgeError::Initialization(format!("{}", e))),
@@ -237,7 +315,7 @@ impl Bridge {
             "Total bridge initialization took: {:?}",
             start_time.elapsed()
         ));
-        Ok(Bridge {})
+        Ok(Bridge::new())
     }
 
     /// Configure Python loguru logging to integrate with Rust logger
@@ -266,8 +344,10 @@ impl Bridge {
             "[{time:YYYY-MM-DD HH:mm:ss}] [PYTHON] {level: <8} {message}".to_string()
         };
 
-        // Check if Python logs should be shown on console
+        // Check if Python logs should be shown on console; with JSON console logs they
+        // go through `JsonLogSink` instead of loguru's text sink
         let enable_console = logger::get_log_python();
+        let json_console = enable_console && logger::get_log_format() == LogFormat::Json;
 
         // Check if stdout should be suppressed from logs
         let no_stdout = logger::get_no_stdout();
@@ -290,13 +370,19 @@ impl Bridge {
             kwargs.set_item("level", log_level)?;
             kwargs.set_item("log_file", &log_file)?;
             kwargs.set_item("fmt", &fmt)?;
-            kwargs.set_item("enable_console_log", enable_console)?;
+            kwargs.set_item("enable_console_log", enable_console && !json_console)?;
             kwargs.set_item("suppress_stdout", no_stdout)?;
             setup_logging.call((), Some(&kwargs))?;
 
             // Explicitly enable logging for r2x modules
             let loguru = PyModule::import(py, "loguru")?;
             let logger = loguru.getattr("logger")?;
+            if json_console {
+                let sink_kwargs = pyo3::types::PyDict::new(py);
+                sink_kwargs.set_item("level", log_level)?;
+                sink_kwargs.set_item("format", "{message}")?;
+                logger.call_method("add", (Py::new(py, JsonLogSink)?,), Some(&sink_kwargs))?;
+            }
             logger.call_method1("enable", ("r2x_core",))?;
             logger.call_method1("enable", ("r2x_reeds",))?;
             logger.call_method1("enable", ("r2x_plexos",))?;
@@ -312,6 +398,25 @@ impl Bridge {
     }
 }
 
+/// Loguru sink writing each Python record to the console as a JSON log line
+#[pyclass]
+struct JsonLogSink;
+
+#[pymethods]
+impl JsonLogSink {
+    fn __call__(&self, message: &Bound<'_, PyAny>) -> PyResult<()> {
+        let record = message.getattr("record")?;
+        let level = record
+            .get_item("level")?
+            .getattr("name")?
+            .extract::<String>()?;
+        let text = record.get_item("message")?.extract::<String>()?;
+        let module = record.get_item("name")?.extract::<Option<String>>()?;
+        logger::python_record(&level, &text, module.as_deref().unwrap_or_default());
+        Ok(())
+    }
+}
+
 /// Helper: get python3.X directory inside venv lib/
 /// Detect the Python version from the embedded interpreter and store it in config
 ///
@@ -432,6 +537,36 @@ def _r2x_cache_path_override():
     Ok(())
 }
 
+/// Fail early when the venv holds a Python binary that cannot be executed
+///
+/// A missing venv or interpreter is left to `initialize`, which creates the venv
+/// or falls back to a uv-managed Python.
+fn verify_existing_venv_python() -> Result<(), BridgeError> {
+    let Ok(config) = Config::load() else {
+        return Ok(());
+    };
+    if !Path::new(&config.get_venv_python_path()).exists() {
+        return Ok(());
+    }
+    config
+        .verify_venv_python()
+        .map(|_| ())
+        .map_err(BridgeError::Initialization)
+}
+
+/// Report an existing venv that cannot be read instead of failing later on a missing interpreter
+fn check_venv_readable(venv_path: &Path) -> Result<(), BridgeError> {
+    match fs::read_dir(venv_path) {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            Err(BridgeError::PermissionDenied {
+                path: format!("{}: {}", venv_path.display(), e),
+                traceback: None,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Configure the Python virtual environment before PyO3 initialization
 pub fn configure_python_venv() -> Result<PythonEnvironment, BridgeError> {
     let mut config = Config::load()
@@ -456,6 +591,7 @@ pub fn configure_python_venv() -> Result<PythonEnvironment, BridgeError> {
     ensure_python_installed(&config)?;
 
     let venv_path = PathBuf::from(config.get_venv_path());
+    check_venv_readable(&venv_path)?;
 
     let python_path_result = resolve_python_path(&venv_path);
 
@@ -655,3 +791,37 @@ fn find_uv_python(config: &Config) -> Result<Option<(PathBuf, PathBuf)>, BridgeE
 
     Ok(Some((executable, prefix)))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_before_warm_up() {
+        let bridge = Bridge::new();
+        let name = pyo3::Python::attach(|py| {
+            bridge
+                .import(py, "json")?
+                .getattr("__name__")?
+                .extract::<String>()
+        })
+        .unwrap();
+        assert_eq!(name, "json");
+    }
+
+    #[test]
+    fn test_warm_up_keeps_modules_that_import() {
+        let bridge = Bridge::new();
+        let result = bridge.warm_up_modules(&["json", "r2x_missing_module"]);
+        assert!(
+            matches!(result, Err(BridgeError::Import(name, _)) if name == "r2x_missing_module")
+        );
+        // The failure is reported once and the imported module stays cached
+        assert!(bridge
+            .warm_up_modules(&["json", "r2x_missing_module"])
+            .is_ok());
+        let modules = bridge.modules.get().unwrap();
+        assert!(modules.contains_key("json"));
+        assert!(!modules.contains_key("r2x_missing_module"));
+    }
+}
@@ -9,6 +9,7 @@
 
 pub mod errors;
 mod initialization;
+mod package_loader;
 pub mod plugin_invoker;
 mod utils;
 
@@ -4,10 +4,10 @@
 //! entry points, with both fast (direct import) and slow (importlib.metadata) paths.
 
 use crate::errors::BridgeError;
-use r2x_config::Config;
-use r2x_logger as logger;
 use pyo3::prelude::*;
 use pyo3::types::PyModule;
+use r2x_config::Config;
+use r2x_logger as logger;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
@@ -208,25 +208,16 @@ impl super::Bridge {
             "Looking for entry_points.txt for package: {}",
             full_package_name
         ));
-        logger::debug(&format!(
-            "Venv path: {}",
-            venv_path.display()
-        ));
+        logger::debug(&format!("Venv path: {}", venv_path.display()));
 
         // Find site-packages directory using centralized resolver
         let site_packages_path = match super::resolve_site_package_path(&venv_path) {
             Ok(path) => {
-                logger::debug(&format!(
-                    "Found site-packages at: {}",
-                    path.display()
-                ));
+                logger::debug(&format!("Found site-packages at: {}", path.display()));
                 path
             }
             Err(e) => {
-                logger::debug(&format!(
-                    "Failed to resolve site-packages path: {}",
-                    e
-                ));
+                logger::debug(&format!("Failed to resolve site-packages path: {}", e));
                 return None;
             }
         };
@@ -245,10 +236,7 @@ impl super::Bridge {
                 // Match package name followed by hyphen (for version) to ensure exact match
                 let expected_prefix = format!("{}-", full_package_name);
                 if file_name.starts_with(&expected_prefix) && file_name.ends_with(".dist-info") {
-                    logger::debug(&format!(
-                        "Found dist-info directory: {}",
-                        file_name
-                    ));
+                    logger::debug(&format!("Found dist-info directory: {}", file_name));
                     dist_info_dir = Some(entry.path());
                     break;
                 }
@@ -0,0 +1,309 @@
+//! Dry-run validation of plugin invocations
+//!
+//! A dry run imports the plugin module and resolves the callable, then checks the
+//! arguments r2x would pass against `inspect.signature()` without calling anything.
+
+use super::{logger, BridgeError, InvocationConfig, PluginInvocationResult, RuntimeBindings};
+use crate::Bridge;
+use pyo3::types::{PyAnyMethods, PyModule};
+use r2x_manifest::build_runtime_bindings;
+use std::collections::HashSet;
+
+/// Outcome of a dry-run invocation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DryRunResult {
+    /// Whether the module imported and the callable was found
+    pub callable_found: bool,
+    /// Problems that would make the call fail, e.g. missing required parameters
+    pub parameter_errors: Vec<String>,
+    /// Problems that would not stop the call, e.g. config keys the plugin ignores
+    pub warnings: Vec<String>,
+}
+
+impl DryRunResult {
+    /// Whether the invocation is expected to succeed
+    pub fn is_ok(&self) -> bool {
+        self.callable_found && self.parameter_errors.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "callable_found": self.callable_found,
+            "parameter_errors": self.parameter_errors,
+            "warnings": self.warnings,
+        })
+        .to_string()
+    }
+
+    /// Parse the output of a dry-run invocation
+    pub fn from_json(json: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        let strings = |key: &str| -> Option<Vec<String>> {
+            value[key]
+                .as_array()?
+                .iter()
+                .map(|item| item.as_str().map(str::to_string))
+                .collect()
+        };
+        Some(DryRunResult {
+            callable_found: value["callable_found"].as_bool()?,
+            parameter_errors: strings("parameter_errors")?,
+            warnings: strings("warnings")?,
+        })
+    }
+}
+
+/// A parameter from `inspect.signature()`
+#[derive(Debug, Clone)]
+struct SignatureParam {
+    name: String,
+    /// `inspect.Parameter.kind` name, e.g. `POSITIONAL_OR_KEYWORD`
+    kind: String,
+    has_default: bool,
+}
+
+impl Bridge {
+    /// Resolve the plugin callable and validate its arguments without calling it
+    pub(super) fn dry_run_plugin(
+        &self,
+        invocation: &InvocationConfig,
+    ) -> Result<PluginInvocationResult, BridgeError> {
+        let target = invocation.target;
+        let (module_path, callable_path) = target
+            .split_once(':')
+            .ok_or_else(|| BridgeError::InvalidEntryPoint(target.to_string()))?;
+        let bindings = invocation.plugin_metadata.map(build_runtime_bindings);
+
+        let config_keys: Vec<String> =
+            serde_json::from_str::<serde_json::Value>(invocation.config_json)
+                .map_err(|e| BridgeError::Serialization(format!("Invalid config JSON: {}", e)))?
+                .as_object()
+                .ok_or_else(|| BridgeError::Python("Config must be a JSON object".to_string()))?
+                .keys()
+                .cloned()
+                .collect();
+
+        let result = pyo3::Python::attach(|py| {
+            let mut result = DryRunResult::default();
+
+            logger::debug(&format!("Dry run: importing module {}", module_path));
+            let module = match PyModule::import(py, module_path) {
+                Ok(module) => module,
+                Err(e) => {
+                    result
+                        .parameter_errors
+                        .push(format!("Failed to import module '{}': {}", module_path, e));
+                    return Ok::<_, BridgeError>(result);
+                }
+            };
+
+            // Classes are checked against their constructor; the method is only looked up
+            let (attr, method) = match callable_path.split_once('.') {
+                Some((class_name, method)) => (class_name, Some(method)),
+                None => (callable_path, None),
+            };
+            let callable = match module.getattr(attr) {
+                Ok(callable) => callable,
+                Err(_) => {
+                    result
+                        .parameter_errors
+                        .push(format!("'{}' not found in module '{}'", attr, module_path));
+                    return Ok(result);
+                }
+            };
+            if let Some(method) = method {
+                if callable.getattr(method).is_err() {
+                    result
+                        .parameter_errors
+                        .push(format!("Method '{}' not found on '{}'", method, attr));
+                    return Ok(result);
+                }
+            }
+            result.callable_found = true;
+
+            let inspect = PyModule::import(py, "inspect")?;
+            let signature = match inspect.getattr("signature")?.call1((&callable,)) {
+                Ok(signature) => signature,
+                Err(e) => {
+                    result
+                        .warnings
+                        .push(format!("Could not inspect signature of '{}': {}", attr, e));
+                    return Ok(result);
+                }
+            };
+            let empty = inspect.getattr("Parameter")?.getattr("empty")?;
+            let mut params = Vec::new();
+            for param in signature
+                .getattr("parameters")?
+                .call_method0("values")?
+                .try_iter()?
+            {
+                let param = param?;
+                params.push(SignatureParam {
+                    name: param.getattr("name")?.extract()?,
+                    kind: param.getattr("kind")?.getattr("name")?.extract()?,
+                    has_default: !param.getattr("default")?.is(&empty),
+                });
+            }
+
+            let provided = provided_names(
+                &config_keys,
+                invocation.stdin_json.is_some(),
+                bindings.as_ref(),
+            );
+            let uses_config_class = bindings.as_ref().is_some_and(|b| b.config.is_some());
+            check_parameters(
+                &params,
+                &provided,
+                &config_keys,
+                uses_config_class,
+                &mut result,
+            );
+            Ok(result)
+        })?;
+
+        logger::debug(&format!("Dry run for {}: {:?}", target, result));
+        Ok(PluginInvocationResult {
+            output: result.to_json(),
+            timings: None,
+        })
+    }
+}
+
+/// Parameter names the bridge would fill from the config, stdin and plugin metadata
+fn provided_names(
+    config_keys: &[String],
+    has_stdin: bool,
+    bindings: Option<&RuntimeBindings>,
+) -> HashSet<String> {
+    let mut provided: HashSet<String> = config_keys.iter().cloned().collect();
+    if has_stdin {
+        provided.extend(["stdin".to_string(), "system".to_string()]);
+    }
+    let Some(bindings) = bindings else {
+        return provided;
+    };
+
+    if let Some(config) = &bindings.config {
+        provided.insert("config".to_string());
+        provided.extend(
+            bindings
+                .entry_parameters
+                .iter()
+                .filter(|p| {
+                    p.annotation
+                        .as_deref()
+                        .is_some_and(|a| a.contains(config.name.as_str()))
+                })
+                .map(|p| p.name.clone()),
+        );
+    }
+
+    // A store parameter is built from any of these config keys
+    let has_store_value = ["store", "store_path", "path"]
+        .iter()
+        .any(|key| config_keys.iter().any(|k| k == key));
+    if has_store_value {
+        provided.extend(["store".to_string(), "data_store".to_string()]);
+    }
+    provided
+}
+
+fn check_parameters(
+    params: &[SignatureParam],
+    provided: &HashSet<String>,
+    config_keys: &[String],
+    uses_config_class: bool,
+    result: &mut DryRunResult,
+) {
+    let accepts_var_kwargs = params.iter().any(|p| p.kind == "VAR_KEYWORD");
+
+    for param in params {
+        let variadic = param.kind == "VAR_POSITIONAL" || param.kind == "VAR_KEYWORD";
+        if param.name == "self" || variadic || param.has_default {
+            continue;
+        }
+        if !provided.contains(&param.name) {
+            result
+                .parameter_errors
+                .push(format!("Missing required parameter '{}'", param.name));
+        }
+    }
+
+    // With a config class, every key is consumed by the config object instead
+    if !accepts_var_kwargs && !uses_config_class {
+        for key in config_keys {
+            if !params.iter().any(|p| &p.name == key) {
+                result.warnings.push(format!(
+                    "Config key '{}' is not a parameter of the plugin",
+                    key
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn param(name: &str, kind: &str, has_default: bool) -> SignatureParam {
+        SignatureParam {
+            name: name.to_string(),
+            kind: kind.to_string(),
+            has_default,
+        }
+    }
+
+    #[test]
+    fn test_check_parameters_reports_missing_and_unknown() {
+        let params = vec![
+            param("path", "POSITIONAL_OR_KEYWORD", false),
+            param("year", "KEYWORD_ONLY", false),
+            param("scenario", "POSITIONAL_OR_KEYWORD", true),
+            param("args", "VAR_POSITIONAL", false),
+        ];
+        let config_keys = vec!["path".to_string(), "weather_year".to_string()];
+        let provided = provided_names(&config_keys, false, None);
+
+        let mut result = DryRunResult {
+            callable_found: true,
+            ..Default::default()
+        };
+        check_parameters(&params, &provided, &config_keys, false, &mut result);
+
+        assert_eq!(
+            result.parameter_errors,
+            vec!["Missing required parameter 'year'"]
+        );
+        assert_eq!(
+            result.warnings,
+            vec!["Config key 'weather_year' is not a parameter of the plugin"]
+        );
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn test_check_parameters_with_stdin_and_var_kwargs() {
+        let params = vec![
+            param("system", "POSITIONAL_OR_KEYWORD", false),
+            param("kwargs", "VAR_KEYWORD", false),
+        ];
+        let config_keys = vec!["anything".to_string()];
+        let provided = provided_names(&config_keys, true, None);
+
+        let mut result = DryRunResult {
+            callable_found: true,
+            ..Default::default()
+        };
+        check_parameters(&params, &provided, &config_keys, false, &mut result);
+
+        assert!(result.is_ok());
+        assert!(result.warnings.is_empty());
+        assert_eq!(
+            result.to_json(),
+            r#"{"callable_found":true,"parameter_errors":[],"warnings":[]}"#
+        );
+        assert_eq!(DryRunResult::from_json(&result.to_json()), Some(result));
+    }
+}
@@ -0,0 +1,112 @@
+//! Pre/post execution hooks for plugin invocation
+//!
+//! Hooks let embedders instrument every plugin call, e.g. for auditing or metrics.
+//! Pre-hooks run in registration order before the plugin and can abort the call by
+//! returning an error. Post-hooks run in registration order after the call,
+//! whether it succeeded or failed.
+
+use super::{logger, BridgeError};
+use crate::Bridge;
+use std::sync::PoisonError;
+
+/// Hook run before a plugin call with `(target, config_json)`
+pub type PreHook = Box<dyn Fn(&str, &str) -> Result<(), BridgeError> + Send + Sync>;
+
+/// Hook run after a plugin call with `(target, result)`
+pub type PostHook = Box<dyn Fn(&str, Result<&str, &BridgeError>) + Send + Sync>;
+
+impl Bridge {
+    /// Register a hook that runs before every plugin invocation
+    pub fn register_pre_hook(&self, hook: PreHook) {
+        self.pre_hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(hook);
+    }
+
+    /// Register a hook that runs after every plugin invocation
+    pub fn register_post_hook(&self, hook: PostHook) {
+        self.post_hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(hook);
+    }
+
+    pub(super) fn run_pre_hooks(&self, target: &str, config_json: &str) -> Result<(), BridgeError> {
+        let hooks = self
+            .pre_hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for hook in hooks.iter() {
+            if let Err(e) = hook(target, config_json) {
+                logger::debug(&format!("Pre-hook aborted invocation of {}: {}", target, e));
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn run_post_hooks(&self, target: &str, result: Result<&str, &BridgeError>) {
+        let hooks = self
+            .post_hooks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        for hook in hooks.iter() {
+            hook(target, result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_pre_hooks_run_in_order_and_short_circuit() {
+        let bridge = Bridge::new();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let first = Arc::clone(&calls);
+        bridge.register_pre_hook(Box::new(move |target, _config| {
+            first.lock().unwrap().push(format!("first:{}", target));
+            Ok(())
+        }));
+        bridge.register_pre_hook(Box::new(|_target, _config| {
+            Err(BridgeError::Python("denied".to_string()))
+        }));
+        let third = Arc::clone(&calls);
+        bridge.register_pre_hook(Box::new(move |target, _config| {
+            third.lock().unwrap().push(format!("third:{}", target));
+            Ok(())
+        }));
+
+        let result = bridge.run_pre_hooks("pkg.module:func", "{}");
+        assert!(matches!(result, Err(BridgeError::Python(_))));
+        assert_eq!(*calls.lock().unwrap(), vec!["first:pkg.module:func"]);
+    }
+
+    #[test]
+    fn test_post_hooks_receive_result() {
+        let bridge = Bridge::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+
+        let sink = Arc::clone(&seen);
+        bridge.register_post_hook(Box::new(move |_target, result| {
+            let entry = match result {
+                Ok(output) => format!("ok:{}", output),
+                Err(e) => format!("err:{}", e),
+            };
+            sink.lock().unwrap().push(entry);
+        }));
+
+        bridge.run_post_hooks("pkg.module:func", Ok("null"));
+        let error = BridgeError::PluginNotFound("demo".to_string());
+        bridge.run_post_hooks("pkg.module:func", Err(&error));
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["ok:null", "err:Plugin 'demo' not found"]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Structured arguments for a single plugin invocation
+
+use r2x_manifest::PluginSpec;
+use std::time::Duration;
+
+/// Everything the bridge needs to invoke a plugin
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InvocationConfig<'a> {
+    /// Call target in `module:callable` form
+    pub target: &'a str,
+    /// Plugin configuration as a JSON object
+    pub config_json: &'a str,
+    /// Optional upstream JSON passed to the plugin as stdin
+    pub stdin_json: Option<&'a str>,
+    /// Manifest metadata for the plugin, used to route and bind arguments
+    pub plugin_metadata: Option<&'a PluginSpec>,
+    /// Maximum time the plugin may run, in seconds
+    pub timeout_secs: Option<u64>,
+    /// Timeout used when neither `timeout_secs` nor the plugin sets one, in seconds
+    pub default_timeout_secs: Option<u64>,
+    /// Resolve the callable and validate its arguments without executing the plugin;
+    /// the result output is a [`DryRunResult`](super::DryRunResult) as JSON
+    pub dry_run: bool,
+    /// Record the invocation in the log for auditing
+    pub audit: bool,
+}
+
+impl<'a> InvocationConfig<'a> {
+    /// Start building an invocation config
+    pub fn builder() -> InvocationConfigBuilder<'a> {
+        InvocationConfigBuilder::default()
+    }
+
+    /// Timeout for the call: `timeout_secs` if set, otherwise the one declared by the
+    /// plugin, otherwise `default_timeout_secs`
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs
+            .or_else(|| {
+                self.plugin_metadata
+                    .and_then(|plugin| plugin.invocation.timeout_secs)
+            })
+            .or(self.default_timeout_secs)
+            .map(Duration::from_secs)
+    }
+
+    /// Number of retries declared by the plugin, zero when none
+    pub fn retry_count(&self) -> u32 {
+        self.plugin_metadata
+            .and_then(|plugin| plugin.invocation.retry_count)
+            .unwrap_or(0)
+    }
+}
+
+/// Builder for [`InvocationConfig`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InvocationConfigBuilder<'a> {
+    config: InvocationConfig<'a>,
+}
+
+impl<'a> InvocationConfigBuilder<'a> {
+    pub fn target(mut self, target: &'a str) -> Self {
+        self.config.target = target;
+        self
+    }
+
+    pub fn config_json(mut self, config_json: &'a str) -> Self {
+        self.config.config_json = config_json;
+        self
+    }
+
+    pub fn stdin_json(mut self, stdin_json: Option<&'a str>) -> Self {
+        self.config.stdin_json = stdin_json;
+        self
+    }
+
+    pub fn plugin_metadata(mut self, plugin_metadata: Option<&'a PluginSpec>) -> Self {
+        self.config.plugin_metadata = plugin_metadata;
+        self
+    }
+
+    pub fn timeout_secs(mut self, timeout_secs: Option<u64>) -> Self {
+        self.config.timeout_secs = timeout_secs;
+        self
+    }
+
+    pub fn default_timeout_secs(mut self, default_timeout_secs: Option<u64>) -> Self {
+        self.config.default_timeout_secs = default_timeout_secs;
+        self
+    }
+
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.config.dry_run = dry_run;
+        self
+    }
+
+    pub fn audit(mut self, audit: bool) -> Self {
+        self.config.audit = audit;
+        self
+    }
+
+    pub fn build(self) -> InvocationConfig<'a> {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invocation_config_default() {
+        let config = InvocationConfig::default();
+        assert_eq!(config.target, "");
+        assert!(config.stdin_json.is_none());
+        assert!(config.timeout_secs.is_none());
+        assert!(!config.dry_run);
+        assert!(!config.audit);
+    }
+
+    #[test]
+    fn test_runtime_policy_from_plugin_metadata() {
+        let plugin: PluginSpec = serde_json::from_value(serde_json::json!({
+            "name": "reeds-parser",
+            "kind": "PARSER",
+            "entry": "r2x_reeds.parser.ReEDSParser",
+            "invocation": {
+                "implementation": "CLASS",
+                "timeout_secs": 60,
+                "retry_count": 2
+            },
+            "io": {}
+        }))
+        .unwrap();
+
+        let config = InvocationConfig::builder()
+            .plugin_metadata(Some(&plugin))
+            .build();
+        assert_eq!(config.timeout(), Some(Duration::from_secs(60)));
+        assert_eq!(config.retry_count(), 2);
+
+        // An explicit timeout overrides the one declared by the plugin, which overrides
+        // the configured default
+        let config = InvocationConfig::builder()
+            .plugin_metadata(Some(&plugin))
+            .timeout_secs(Some(5))
+            .build();
+        assert_eq!(config.timeout(), Some(Duration::from_secs(5)));
+        let config = InvocationConfig::builder()
+            .plugin_metadata(Some(&plugin))
+            .default_timeout_secs(Some(600))
+            .build();
+        assert_eq!(config.timeout(), Some(Duration::from_secs(60)));
+        let config = InvocationConfig::builder()
+            .default_timeout_secs(Some(600))
+            .build();
+        assert_eq!(config.timeout(), Some(Duration::from_secs(600)));
+        assert_eq!(InvocationConfig::default().retry_count(), 0);
+    }
+
+    #[test]
+    fn test_invocation_config_builder() {
+        let config = InvocationConfig::builder()
+            .target("r2x_reeds.parser:ReEDSParser.build_system")
+            .config_json("{}")
+            .stdin_json(Some("null"))
+            .timeout_secs(Some(30))
+            .dry_run(true)
+            .build();
+
+        assert_eq!(config.target, "r2x_reeds.parser:ReEDSParser.build_system");
+        assert_eq!(config.config_json, "{}");
+        assert_eq!(config.stdin_json, Some("null"));
+        assert_eq!(config.timeout_secs, Some(30));
+        assert!(config.dry_run);
+        assert!(!config.audit);
+    }
+}
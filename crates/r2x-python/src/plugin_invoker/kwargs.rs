@@ -3,9 +3,148 @@ use crate::Bridge;
 use pyo3::exceptions::PyFileNotFoundError;
 use pyo3::types::{PyDict, PyList, PyModule};
 use r2x_logger as logger;
-use r2x_manifest::ConfigSpec;
+use r2x_manifest::{ArgumentSpec, ConfigSpec};
 use std::path::Path;
 
+/// Config keys handled by the bridge and withheld from the plugin's config class
+pub const STORE_CONFIG_KEYS: [&str; 3] = ["store", "data_store", "store_path"];
+
+/// Prefix of the environment variables read for required plugin parameters
+/// missing from the config, e.g. `R2X_PLUGIN_STORE_PATH` for `store_path`
+pub const PARAM_ENV_PREFIX: &str = "R2X_PLUGIN_";
+
+/// Environment variable consulted when parameter `name` is missing from the config
+pub fn param_env_var(name: &str) -> String {
+    format!("{}{}", PARAM_ENV_PREFIX, name.to_uppercase())
+}
+
+/// Value of a `key=value` plugin argument or `R2X_PLUGIN_<NAME>` variable
+///
+/// JSON is taken as is; otherwise `true`/`false` in any case, an integer or a float,
+/// falling back to the text itself.
+pub fn parse_param_value(value: &str) -> serde_json::Value {
+    if let Ok(json_val) = serde_json::from_str::<serde_json::Value>(value) {
+        return json_val;
+    }
+
+    match value.to_lowercase().as_str() {
+        "true" => return serde_json::json!(true),
+        "false" => return serde_json::json!(false),
+        _ => {}
+    }
+
+    if let Ok(num) = value.parse::<i64>() {
+        return serde_json::json!(num);
+    }
+
+    if let Ok(num) = value.parse::<f64>() {
+        return serde_json::json!(num);
+    }
+
+    serde_json::json!(value)
+}
+
+/// Whether `param` receives the `DataStore` built by the bridge
+pub fn is_store_param(param: &ArgumentSpec) -> bool {
+    param.name == "store"
+        || param.name == "data_store"
+        || param
+            .annotation
+            .as_deref()
+            .is_some_and(|annotation| annotation.contains("DataStore"))
+}
+
+/// Config keys the store of parameter `param` is read from, in order of preference
+pub fn store_config_keys(param: &str) -> [&str; 4] {
+    ["store", param, "store_path", "path"]
+}
+
+/// Parameters whose `R2X_PLUGIN_<NAME>` variable provides the store of parameter
+/// `param` when the config has none of [`store_config_keys`]
+pub fn store_env_params(param: &str) -> [&str; 2] {
+    [param, "store_path"]
+}
+
+/// Call parameter that receives the config class instance, if the plugin has a config class
+///
+/// The parameter annotated with the config class wins, then one named `config`.
+/// Without either, the instance is still passed as `config`.
+pub fn config_param_name(bindings: &RuntimeBindings) -> Option<&str> {
+    let class = bindings.config.as_ref()?.name.as_str();
+    let params = &bindings.entry_parameters;
+    let param = params
+        .iter()
+        .find(|param| {
+            !class.is_empty()
+                && param
+                    .annotation
+                    .as_deref()
+                    .is_some_and(|annotation| annotation.contains(class))
+        })
+        .or_else(|| params.iter().find(|param| param.name == "config"));
+    Some(param.map_or("config", |param| param.name.as_str()))
+}
+
+/// `R2X_PLUGIN_<NAME>` for parameter `name`, parsed with [`parse_param_value`]
+fn param_from_env<'py>(
+    py: pyo3::Python<'py>,
+    name: &str,
+) -> Result<Option<pyo3::Bound<'py, PyAny>>, BridgeError> {
+    let var = param_env_var(name);
+    let Ok(value) = std::env::var(&var) else {
+        return Ok(None);
+    };
+    logger::debug(&format!("Using {} for parameter '{}'", var, name));
+    let json = parse_param_value(&value).to_string();
+    let loads = PyModule::import(py, "json")?.getattr("loads")?;
+    Ok(Some(loads.call1((json,))?))
+}
+
+/// Arguments for the config class: the config without [`STORE_CONFIG_KEYS`], with
+/// required fields it lacks taken from `R2X_PLUGIN_<NAME>`
+pub(super) fn config_class_params<'py>(
+    py: pyo3::Python<'py>,
+    config_dict: &pyo3::Bound<'py, PyDict>,
+    config_spec: Option<&ConfigSpec>,
+) -> Result<pyo3::Bound<'py, PyDict>, BridgeError> {
+    let params = PyDict::new(py);
+    for (key, value) in config_dict.iter() {
+        let key_str = key.extract::<String>()?;
+        if !STORE_CONFIG_KEYS.contains(&key_str.as_str()) {
+            params.set_item(key, value)?;
+        }
+    }
+    let fields = config_spec.iter().flat_map(|spec| &spec.fields);
+    for field in fields.filter(|field| field.required) {
+        if params.contains(&field.name)? {
+            continue;
+        }
+        if let Some(value) = param_from_env(py, &field.name)? {
+            params.set_item(&field.name, value)?;
+        }
+    }
+    Ok(params)
+}
+
+/// Store location for parameter `param` from the config, or from `R2X_PLUGIN_<NAME>`
+pub(super) fn store_value<'py>(
+    py: pyo3::Python<'py>,
+    config_dict: &pyo3::Bound<'py, PyDict>,
+    param: &str,
+) -> Result<Option<pyo3::Bound<'py, PyAny>>, BridgeError> {
+    for key in store_config_keys(param) {
+        if let Some(value) = config_dict.get_item(key)? {
+            return Ok(Some(value));
+        }
+    }
+    for name in store_env_params(param) {
+        if let Some(value) = param_from_env(py, name)? {
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
 impl Bridge {
     pub(super) fn build_kwargs<'py>(
         &self,
@@ -29,74 +168,26 @@ impl Bridge {
             }
         };
 
-        let mut needs_config_class = false;
-        let mut config_param_name = String::new();
-
-        // Use ConfigSpec metadata as the authoritative source for config parameter detection.
-        // Match parameters by their annotation against the config class name from the manifest,
-        // allowing plugin authors to name their config parameter anything they want.
-        if let Some(config_spec) = &runtime.config {
-            let config_class_name = &config_spec.name;
-            logger::debug(&format!(
-                "Looking for config parameter with annotation matching '{}'",
-                config_class_name
-            ));
-
-            // Find the parameter whose annotation matches the config class name
-            for param in &runtime.entry_parameters {
-                let annotation = param.annotation.as_deref().unwrap_or("");
-                if annotation == config_class_name
-                    || annotation.contains(config_class_name.as_str())
-                {
-                    needs_config_class = true;
-                    config_param_name = param.name.clone();
-                    logger::debug(&format!(
-                        "Config parameter detected: '{}' (annotation '{}' matches config class '{}')",
-                        param.name, annotation, config_class_name
-                    ));
-                    break;
-                }
-            }
-
-            // Fallback: if no annotation match, look for a param explicitly named after the config
-            if !needs_config_class {
-                for param in &runtime.entry_parameters {
-                    if param.name == "config" {
-                        needs_config_class = true;
-                        config_param_name = "config".to_string();
-                        logger::debug(
-                            "Config parameter detected by fallback: param named 'config'",
-                        );
-                        break;
-                    }
-                }
-            }
-
-            // Last resort: we have config metadata but no matching param, use "config" as default
-            if !needs_config_class {
-                needs_config_class = true;
-                config_param_name = "config".to_string();
-                logger::warn("No matching config parameter found in function signature, defaulting to 'config'");
+        let config_param_name = config_param_name(runtime);
+        if let Some(name) = config_param_name {
+            if !runtime
+                .entry_parameters
+                .iter()
+                .any(|param| param.name == name)
+            {
+                logger::warn(
+                    "No matching config parameter found in function signature, defaulting to 'config'",
+                );
             }
         }
 
         let mut config_instance: Option<pyo3::Py<pyo3::PyAny>> = None;
-        if needs_config_class {
+        if let Some(config_param_name) = config_param_name {
             // Always pass the full config dict to the config class.
             // The config class (e.g., ZonalToNodal which extends PluginConfig) may have
             // its own nested "config" field, but it needs ALL top-level fields too
-            // (name, output_folder, etc.). We filter out store-related keys since those
-            // are handled separately.
-            let config_params = {
-                let params = PyDict::new(py);
-                for (key, value) in config_dict.iter() {
-                    let key_str = key.extract::<String>()?;
-                    if key_str != "store" && key_str != "data_store" && key_str != "store_path" {
-                        params.set_item(key, value)?;
-                    }
-                }
-                params
-            };
+            // (name, output_folder, etc.).
+            let config_params = config_class_params(py, config_dict, runtime.config.as_ref())?;
 
             logger::step(&format!(
                 "Instantiating config class with params: {:?}",
@@ -108,28 +199,19 @@ impl Bridge {
                 "Config class instantiated, setting as kwarg '{}'",
                 config_param_name
             ));
-            kwargs.set_item(&config_param_name, &config_obj)?;
+            kwargs.set_item(config_param_name, &config_obj)?;
             config_instance = Some(config_obj.unbind());
         }
 
         for param in &runtime.entry_parameters {
             // Skip the config parameter - it was already handled above
-            if needs_config_class && param.name == config_param_name {
+            if config_param_name == Some(param.name.as_str()) {
                 continue;
             }
 
-            let annotation = param.annotation.as_deref().unwrap_or("");
-            if param.name == "store"
-                || param.name == "data_store"
-                || annotation.contains("DataStore")
-            {
+            if is_store_param(param) {
                 logger::step(&format!("Processing store parameter: {}", param.name));
-                // Look for store value: prefer "store" key, then param name, then "path"
-                let value = config_dict
-                    .get_item("store")?
-                    .or_else(|| config_dict.get_item(&param.name).ok().flatten())
-                    .or_else(|| config_dict.get_item("store_path").ok().flatten())
-                    .or_else(|| config_dict.get_item("path").ok().flatten());
+                let value = store_value(py, config_dict, &param.name)?;
 
                 if let Some(value) = value {
                     let config_binding = config_instance.as_ref().map(|obj| obj.bind(py));
@@ -162,12 +244,34 @@ impl Bridge {
                         "Required parameter '{}' will be provided via stdin",
                         param.name
                     ));
+                } else if let Some(value) = param_from_env(py, &param.name)? {
+                    kwargs.set_item(&param.name, value)?;
                 } else {
                     logger::warn(&format!(
-                        "Required parameter '{}' missing in config",
-                        param.name
+                        "Required parameter '{}' missing in config (set {} to provide it)",
+                        param.name,
+                        param_env_var(&param.name)
+                    ));
+                }
+            }
+        }
+
+        for slot in &runtime.custom_inputs {
+            if kwargs.contains(slot)? {
+                continue;
+            }
+            match config_dict.get_item(slot)? {
+                Some(value) => {
+                    logger::warn(&format!(
+                        "Passing custom input '{}' from config without validation",
+                        slot
                     ));
+                    kwargs.set_item(slot, value)?;
                 }
+                None => logger::warn(&format!(
+                    "Custom input '{}' declared by the plugin is missing in config",
+                    slot
+                )),
             }
         }
 
@@ -206,7 +310,14 @@ impl Bridge {
             ))
         })?;
 
-        config_class.call((), Some(&config_params)).map_err(|e| {
+        // Validate through the model API when the Pydantic version is known, so
+        // aliases and validators behave as they do in Python
+        let instance = match config_meta.pydantic_version {
+            Some(2) => config_class.call_method1("model_validate", (config_params,)),
+            Some(1) => config_class.call_method1("parse_obj", (config_params,)),
+            _ => config_class.call((), Some(config_params)),
+        };
+        instance.map_err(|e| {
             BridgeError::Python(format!(
                 "Failed to instantiate config class '{}': {}",
                 config_meta.name, e
@@ -235,7 +346,7 @@ impl Bridge {
             ));
         };
 
-        let data_store_module = PyModule::import(py, "r2x_core.store")?;
+        let data_store_module = self.import(py, "r2x_core.store")?;
         let data_store_class = data_store_module.getattr("DataStore")?;
 
         if let Some(config) = config_instance {
@@ -346,6 +457,18 @@ fn detect_missing_data_file_from_mapping(
     class_obj: &pyo3::Bound<'_, PyAny>,
     folder_path: &str,
 ) -> Option<String> {
+    missing_data_files(class_obj, folder_path)?
+        .into_iter()
+        .next()
+}
+
+/// Required files of the config class's `load_file_mapping()` missing under `folder_path`
+///
+/// Returns `None` when the class has no usable file mapping.
+pub(super) fn missing_data_files(
+    class_obj: &pyo3::Bound<'_, PyAny>,
+    folder_path: &str,
+) -> Option<Vec<String>> {
     logger::debug(&format!(
         "Validating ReEDS data files under {}",
         folder_path
@@ -354,6 +477,7 @@ fn detect_missing_data_file_from_mapping(
     let records = loader.call0().ok()?;
     let records = records.cast::<PyList>().ok()?;
     let base = Path::new(folder_path);
+    let mut missing = Vec::new();
 
     for record in records {
         let record = record.cast::<PyDict>().ok()?;
@@ -379,11 +503,11 @@ fn detect_missing_data_file_from_mapping(
                 "Detected missing data file during ReEDS run: {}",
                 full_path.display()
             ));
-            return Some(full_path.to_string_lossy().to_string());
+            missing.push(full_path.to_string_lossy().to_string());
         }
     }
 
-    None
+    Some(missing)
 }
 
 fn detect_missing_data_file_from_metadata(
@@ -395,7 +519,7 @@ fn detect_missing_data_file_from_metadata(
     detect_missing_data_file_from_mapping(&class_obj, folder_path)
 }
 
-fn resolve_config_class<'py>(
+pub(super) fn resolve_config_class<'py>(
     py: pyo3::Python<'py>,
     config_instance: Option<&pyo3::Bound<'py, PyAny>>,
     metadata: Option<&ConfigSpec>,
@@ -408,3 +532,79 @@ fn resolve_config_class<'py>(
     let module = PyModule::import(py, &meta.module).ok()?;
     module.getattr(&meta.name).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_param_env_var() {
+        assert_eq!(param_env_var("store_path"), "R2X_PLUGIN_STORE_PATH");
+        assert_eq!(param_env_var("weather_year"), "R2X_PLUGIN_WEATHER_YEAR");
+    }
+
+    #[test]
+    fn test_config_param_name() {
+        let arg = |name: &str, annotation: &str| ArgumentSpec {
+            name: name.to_string(),
+            annotation: Some(annotation.to_string()),
+            default: None,
+            required: true,
+            type_kind: None,
+        };
+        let mut bindings = RuntimeBindings {
+            entry_module: "r2x_test.parser".to_string(),
+            entry_name: "TestParser".to_string(),
+            implementation_type: r2x_manifest::ImplementationType::Class,
+            plugin_kind: PluginKind::Parser,
+            config: None,
+            call_method: None,
+            requires_store: true,
+            entry_parameters: vec![arg("settings", "TestConfig"), arg("store", "DataStore")],
+            custom_inputs: Vec::new(),
+        };
+        assert_eq!(config_param_name(&bindings), None);
+
+        bindings.config = Some(ConfigSpec {
+            module: "r2x_test.config".to_string(),
+            name: "TestConfig".to_string(),
+            fields: Vec::new(),
+            pydantic_version: Some(2),
+        });
+        assert_eq!(config_param_name(&bindings), Some("settings"));
+        bindings.entry_parameters.remove(0);
+        assert_eq!(config_param_name(&bindings), Some("config"));
+
+        assert!(is_store_param(&bindings.entry_parameters[0]));
+        assert!(is_store_param(&arg("inputs", "r2x_core.DataStore")));
+        assert!(!is_store_param(&arg("path", "str")));
+    }
+
+    #[test]
+    fn test_store_sources() {
+        assert_eq!(
+            store_config_keys("data_store"),
+            ["store", "data_store", "store_path", "path"]
+        );
+        let vars: Vec<String> = store_env_params("data_store")
+            .into_iter()
+            .map(param_env_var)
+            .collect();
+        assert_eq!(vars, ["R2X_PLUGIN_DATA_STORE", "R2X_PLUGIN_STORE_PATH"]);
+    }
+
+    #[test]
+    fn test_parse_param_value() {
+        assert_eq!(parse_param_value("2030"), serde_json::json!(2030));
+        assert_eq!(parse_param_value("0.5"), serde_json::json!(0.5));
+        assert_eq!(parse_param_value("True"), serde_json::json!(true));
+        assert_eq!(
+            parse_param_value("[2030, 2035]"),
+            serde_json::json!([2030, 2035])
+        );
+        assert_eq!(
+            parse_param_value("/data/run"),
+            serde_json::json!("/data/run")
+        );
+    }
+}
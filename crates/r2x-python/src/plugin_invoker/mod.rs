@@ -7,13 +7,37 @@ use r2x_manifest::{
     runtime::{build_runtime_bindings, RuntimeBindings},
     PluginKind, PluginSpec,
 };
-use std::time::Duration;
+use std::os::raw::c_long;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::time::{Duration, Instant};
 
+pub use dry_run::DryRunResult;
+pub use invocation::{InvocationConfig, InvocationConfigBuilder};
+pub use kwargs::{
+    config_param_name, is_store_param, param_env_var, parse_param_value, store_config_keys,
+    store_env_params, PARAM_ENV_PREFIX, STORE_CONFIG_KEYS,
+};
+pub use pipeline::{execution_waves, PipelineStep};
+pub use streaming::PluginEvent;
+pub use validate::ValidationReport;
+
+mod dry_run;
+pub mod hooks;
+pub mod invocation;
 mod kwargs;
+mod pipeline;
 mod regular;
+mod streaming;
 mod upgrader;
+mod validate;
+
+use streaming::StdoutRoute;
+
+/// How long a timed out plugin waits for its worker to report the Python thread to interrupt
+const THREAD_IDENT_WAIT: Duration = Duration::from_secs(2);
 
 /// Timings for a plugin invocation phase
+#[derive(Debug)]
 pub struct PluginInvocationTimings {
     pub python_invocation: Duration,
     pub serialization: Duration,
@@ -28,13 +52,228 @@ pub struct PluginInvocationResult {
 }
 
 impl super::Bridge {
+    /// Run a plugin and return its output
+    ///
+    /// Collects the events of [`invoke_plugin_streaming`](Self::invoke_plugin_streaming);
+    /// lines the plugin prints are logged as they arrive.
     pub fn invoke_plugin(
+        &self,
+        invocation: &InvocationConfig,
+    ) -> Result<PluginInvocationResult, BridgeError> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::scope(|scope| {
+            let collector = scope.spawn(move || {
+                let mut collected = PluginInvocationResult {
+                    output: String::new(),
+                    timings: None,
+                };
+                for event in receiver {
+                    match event {
+                        PluginEvent::Progress(line) => logger::info(&line),
+                        PluginEvent::Output(output) => collected.output = output,
+                        PluginEvent::Done(timings) => {
+                            collected.timings = timings;
+                            break;
+                        }
+                    }
+                }
+                collected
+            });
+            let status = self.invoke_plugin_streaming(invocation, sender);
+            let collected = collector
+                .join()
+                .map_err(|_| BridgeError::Python("Plugin output collector panicked".to_string()))?;
+            status.map(|()| collected)
+        })
+    }
+
+    /// Run a plugin, sending what it prints and its output to `events` as they arrive
+    ///
+    /// On success the plugin's output is sent as [`PluginEvent::Output`].
+    /// [`PluginEvent::Done`] is sent last in every case, also before an error is
+    /// returned.
+    pub fn invoke_plugin_streaming(
+        &self,
+        invocation: &InvocationConfig,
+        events: Sender<PluginEvent>,
+    ) -> Result<(), BridgeError> {
+        let result = self.invoke_plugin_with_events(invocation, &events);
+        match result {
+            Ok(result) => {
+                let _ = events.send(PluginEvent::Output(result.output));
+                let _ = events.send(PluginEvent::Done(result.timings));
+                Ok(())
+            }
+            Err(e) => {
+                let _ = events.send(PluginEvent::Done(None));
+                Err(e)
+            }
+        }
+    }
+
+    fn invoke_plugin_with_events(
+        &self,
+        invocation: &InvocationConfig,
+        events: &Sender<PluginEvent>,
+    ) -> Result<PluginInvocationResult, BridgeError> {
+        let target = invocation.target;
+        let config_json = invocation.config_json;
+
+        if invocation.audit {
+            logger::info(&format!(
+                "Invoking plugin {} with config {}",
+                target, config_json
+            ));
+        }
+
+        if invocation.dry_run {
+            return self.dry_run_plugin(invocation);
+        }
+
+        let result = self
+            .run_pre_hooks(target, config_json)
+            .and_then(|_| self.dispatch_with_retries(invocation, events));
+        self.run_post_hooks(target, result.as_ref().map(|r| r.output.as_str()));
+        result
+    }
+
+    /// Dispatch the plugin, retrying failures up to the plugin's `retry_count`
+    ///
+    /// The timeout covers all attempts together; a call still running when it expires
+    /// fails with [`BridgeError::Timeout`] and is not retried.
+    fn dispatch_with_retries(
+        &self,
+        invocation: &InvocationConfig,
+        events: &Sender<PluginEvent>,
+    ) -> Result<PluginInvocationResult, BridgeError> {
+        let target = invocation.target;
+        let timeout = invocation.timeout();
+        let retries = invocation.retry_count();
+        let start = Instant::now();
+        let mut attempt = 0;
+
+        loop {
+            let result = match timeout {
+                Some(limit) => self.dispatch_until(invocation, start + limit, limit, events),
+                None => self.dispatch_plugin(
+                    target,
+                    invocation.config_json,
+                    invocation.stdin_json,
+                    invocation.plugin_metadata,
+                    events,
+                ),
+            };
+            let timed_out = timeout.is_some_and(|limit| start.elapsed() >= limit);
+
+            match result {
+                Err(e) if attempt < retries && !timed_out => {
+                    attempt += 1;
+                    logger::warn(&format!(
+                        "Plugin {} failed ({}), retrying ({}/{})",
+                        target, e, attempt, retries
+                    ));
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Dispatch the plugin on a worker thread and stop waiting for it at `deadline`
+    ///
+    /// On expiry the call is abandoned, not cancelled: a `TimeoutError` is raised in the
+    /// worker thread, which surfaces at the plugin's next bytecode boundary or once a
+    /// blocking native call returns. Until then, or for good if the plugin catches the
+    /// exception, the worker keeps running in the background and holds the GIL whenever
+    /// it executes Python code.
+    fn dispatch_until(
+        &self,
+        invocation: &InvocationConfig,
+        deadline: Instant,
+        limit: Duration,
+        events: &Sender<PluginEvent>,
+    ) -> Result<PluginInvocationResult, BridgeError> {
+        let events = events.clone();
+        let target = invocation.target.to_string();
+        let config_json = invocation.config_json.to_string();
+        let stdin_json = invocation.stdin_json.map(str::to_string);
+        let plugin = invocation.plugin_metadata.cloned();
+        let (thread_sender, thread_receiver) = mpsc::channel();
+        let (result_sender, result_receiver) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let result = super::Bridge::get().and_then(|bridge| {
+                // Stay attached from reporting the ident until the plugin returns, so an
+                // interrupt sent before dispatch starts is raised at its first bytecode
+                Python::attach(|py| {
+                    let ident = py
+                        .import("threading")?
+                        .call_method0("get_ident")?
+                        .extract::<u64>()?;
+                    let _ = thread_sender.send(ident);
+                    bridge.dispatch_plugin(
+                        &target,
+                        &config_json,
+                        stdin_json.as_deref(),
+                        plugin.as_ref(),
+                        &events,
+                    )
+                })
+            });
+            let _ = result_sender.send(result);
+        });
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match result_receiver.recv_timeout(remaining) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => {
+                match thread_receiver.recv_timeout(THREAD_IDENT_WAIT) {
+                    Ok(ident) => interrupt_python_thread(ident),
+                    Err(_) => logger::warn(&format!(
+                        "Could not interrupt plugin {}; it may keep running in the background",
+                        invocation.target
+                    )),
+                }
+                Err(BridgeError::Timeout {
+                    plugin_name: invocation
+                        .plugin_metadata
+                        .map_or(invocation.target, |plugin| plugin.name.as_str())
+                        .to_string(),
+                    elapsed: limit,
+                })
+            }
+            Err(RecvTimeoutError::Disconnected) => Err(BridgeError::Python(format!(
+                "Plugin {} stopped without a result",
+                invocation.target
+            ))),
+        }
+    }
+
+    #[deprecated(note = "use `invoke_plugin` with an `InvocationConfig` instead")]
+    pub fn invoke_plugin_with_args(
+        &self,
+        target: &str,
+        config_json: &str,
+        stdin_json: Option<&str>,
+        plugin_metadata: Option<&PluginSpec>,
+    ) -> Result<PluginInvocationResult, BridgeError> {
+        self.invoke_plugin(&InvocationConfig {
+            target,
+            config_json,
+            stdin_json,
+            plugin_metadata,
+            ..Default::default()
+        })
+    }
+
+    fn dispatch_plugin(
         &self,
         target: &str,
         config_json: &str,
         stdin_json: Option<&str>,
         plugin_metadata: Option<&PluginSpec>,
+        events: &Sender<PluginEvent>,
     ) -> Result<PluginInvocationResult, BridgeError> {
+        let _route = StdoutRoute::register(events.clone());
         let runtime_bindings = match plugin_metadata {
             Some(meta) => Some(build_runtime_bindings(meta)),
             None => None,
@@ -56,6 +295,35 @@ impl super::Bridge {
     }
 }
 
+/// Raise `TimeoutError` in the Python thread with identifier `ident`
+///
+/// Raising the exception needs the GIL. A plugin inside native code that holds the
+/// GIL (an extension call that does not release it) keeps it until that call returns,
+/// so the exception is raised from a helper thread instead of blocking the caller; such
+/// a plugin only stops once the native call returns.
+fn interrupt_python_thread(ident: u64) {
+    // Thread identifiers are 64-bit but `c_long` is 32-bit on Windows
+    let Ok(ident) = c_long::try_from(ident) else {
+        logger::warn(&format!(
+            "Cannot interrupt Python thread {}: identifier does not fit a C long",
+            ident
+        ));
+        return;
+    };
+    std::thread::spawn(move || {
+        Python::attach(|_py| {
+            // SAFETY: the GIL is held, and `PyExc_TimeoutError` is a static exception type
+            // that outlives the call. An unknown identifier is ignored by CPython.
+            let affected = unsafe {
+                pyo3::ffi::PyThreadState_SetAsyncExc(ident, pyo3::ffi::PyExc_TimeoutError)
+            };
+            if affected == 0 {
+                logger::debug("Timed out plugin thread had already finished");
+            }
+        })
+    });
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
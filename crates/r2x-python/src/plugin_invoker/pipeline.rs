@@ -0,0 +1,188 @@
+//! Concurrent execution of independent pipeline steps
+//!
+//! Steps are grouped into waves: a step without an input is in wave 0, and any other
+//! step is one wave after the step it reads from. The steps of a wave run on scoped
+//! threads, each attaching to the interpreter on its own, so Python releases the GIL
+//! between them whenever a plugin waits on I/O.
+//!
+//! A step's configuration can depend on what it reads from upstream, so the caller
+//! supplies it through a `configure` callback once the step's input is known.
+
+use super::{logger, BridgeError, InvocationConfig};
+use crate::Bridge;
+use r2x_manifest::PluginSpec;
+
+/// One step of a pipeline run by [`Bridge::invoke_pipeline_parallel`]
+#[derive(Debug, Clone, Default)]
+pub struct PipelineStep {
+    /// Call target in `module:callable` form
+    pub target: String,
+    /// Plugin configuration as a JSON object, before any upstream input is merged in
+    pub config_json: String,
+    /// Manifest metadata for the plugin, used to route and bind arguments
+    pub plugin: Option<PluginSpec>,
+    /// Index of the earlier step this step waits for and reads stdin from
+    ///
+    /// When that step produces no output, the stdin it received is passed on instead.
+    pub input: Option<usize>,
+    /// Timeout in seconds for a plugin that declares none
+    pub default_timeout_secs: Option<u64>,
+}
+
+/// Indices of `steps` grouped into waves that can run concurrently, in order
+///
+/// An `input` that does not refer to an earlier step is ignored here; such steps fail
+/// when the pipeline is invoked.
+pub fn execution_waves(steps: &[PipelineStep]) -> Vec<Vec<usize>> {
+    let mut wave_of: Vec<usize> = Vec::with_capacity(steps.len());
+    let mut waves: Vec<Vec<usize>> = Vec::new();
+    for (idx, step) in steps.iter().enumerate() {
+        let wave = step
+            .input
+            .filter(|&input| input < idx)
+            .map_or(0, |input| wave_of[input] + 1);
+        wave_of.push(wave);
+        if waves.len() <= wave {
+            waves.resize_with(wave + 1, Vec::new);
+        }
+        waves[wave].push(idx);
+    }
+    waves
+}
+
+impl Bridge {
+    /// Run pipeline steps, executing the steps of each wave concurrently
+    ///
+    /// `configure(index, stdin)` gives the configuration a step runs with when it reads
+    /// `stdin`. Returns the output of every step in input order. A step whose input step
+    /// failed is not run and reports an error.
+    pub fn invoke_pipeline_parallel<F>(
+        &self,
+        steps: Vec<PipelineStep>,
+        configure: F,
+    ) -> Vec<Result<String, BridgeError>>
+    where
+        F: Fn(usize, Option<&str>) -> Result<String, BridgeError> + Sync,
+    {
+        let configure = &configure;
+        let mut results: Vec<Option<Result<String, BridgeError>>> =
+            steps.iter().map(|_| None).collect();
+        // What each finished step passes to the steps reading from it
+        let mut forwarded: Vec<Option<String>> = vec![None; steps.len()];
+
+        for (wave_idx, wave) in execution_waves(&steps).into_iter().enumerate() {
+            logger::debug(&format!(
+                "Pipeline wave {}: running {} step(s)",
+                wave_idx,
+                wave.len()
+            ));
+            let inputs: Vec<Result<Option<String>, BridgeError>> = wave
+                .iter()
+                .map(|&idx| match steps[idx].input {
+                    None => Ok(None),
+                    Some(input) if input >= idx => Err(BridgeError::Python(format!(
+                        "Pipeline step {} reads from step {}, which does not run before it",
+                        idx, input
+                    ))),
+                    Some(input) => match &results[input] {
+                        Some(Ok(_)) => Ok(forwarded[input].clone()),
+                        _ => Err(BridgeError::Python(format!(
+                            "Skipped because pipeline step {} failed",
+                            input
+                        ))),
+                    },
+                })
+                .collect();
+
+            let outcomes: Vec<(Result<String, BridgeError>, Option<String>)> =
+                std::thread::scope(|scope| {
+                    let handles: Vec<_> = wave
+                        .iter()
+                        .zip(inputs)
+                        .map(|(&idx, input)| {
+                            let step = &steps[idx];
+                            scope.spawn(move || match input {
+                                Ok(stdin) => {
+                                    let output =
+                                        configure(idx, stdin.as_deref()).and_then(|config_json| {
+                                            self.invoke_step(step, &config_json, stdin.as_deref())
+                                        });
+                                    (output, stdin)
+                                }
+                                Err(e) => (Err(e), None),
+                            })
+                        })
+                        .collect();
+                    handles
+                        .into_iter()
+                        .map(|handle| {
+                            handle.join().unwrap_or_else(|_| {
+                                (
+                                    Err(BridgeError::Python(
+                                        "Pipeline step thread panicked".to_string(),
+                                    )),
+                                    None,
+                                )
+                            })
+                        })
+                        .collect()
+                });
+
+            for (&idx, (outcome, stdin)) in wave.iter().zip(outcomes) {
+                forwarded[idx] = match &outcome {
+                    Ok(output) if !output.is_empty() && output != "null" => Some(output.clone()),
+                    _ => stdin,
+                };
+                results[idx] = Some(outcome);
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(BridgeError::Python("Pipeline step was not run".to_string()))
+                })
+            })
+            .collect()
+    }
+
+    fn invoke_step(
+        &self,
+        step: &PipelineStep,
+        config_json: &str,
+        stdin: Option<&str>,
+    ) -> Result<String, BridgeError> {
+        let invocation = InvocationConfig::builder()
+            .target(&step.target)
+            .config_json(config_json)
+            .stdin_json(stdin)
+            .plugin_metadata(step.plugin.as_ref())
+            .default_timeout_secs(step.default_timeout_secs)
+            .build();
+        self.invoke_plugin(&invocation).map(|result| result.output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(input: Option<usize>) -> PipelineStep {
+        PipelineStep {
+            input,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_execution_waves() {
+        // parser -> modifier -> two exporters reading the modified system
+        let steps = vec![step(None), step(Some(0)), step(Some(1)), step(Some(1))];
+        assert_eq!(execution_waves(&steps), vec![vec![0], vec![1], vec![2, 3]]);
+
+        // Independent steps share wave 0; a forward reference is not a dependency
+        let steps = vec![step(None), step(None), step(Some(5))];
+        assert_eq!(execution_waves(&steps), vec![vec![0, 1, 2]]);
+    }
+}
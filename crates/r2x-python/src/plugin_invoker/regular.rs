@@ -27,7 +27,8 @@ impl Bridge {
             logger::debug(&format!("Importing module: {}", module_path));
             let module = PyModule::import(py, module_path)
                 .map_err(|e| BridgeError::Import(module_path.to_string(), format!("{}", e)))?;
-            let json_module = PyModule::import(py, "json")
+            let json_module = self
+                .import(py, "json")
                 .map_err(|e| BridgeError::Import("json".to_string(), format!("{}", e)))?;
             let loads = json_module.getattr("loads")?;
 
@@ -60,7 +61,7 @@ impl Bridge {
                     runtime_bindings,
                 )?
             } else {
-                Self::invoke_function_callable(
+                self.invoke_function_callable(
                     py,
                     &module,
                     callable_path,
@@ -131,13 +132,19 @@ impl Bridge {
             return Err(BridgeError::InvalidEntryPoint(callable_path.to_string()));
         }
         let (class_name, method_name) = (parts[0], parts[1]);
+        if r2x_manifest::looks_like_abstract_class(class_name) {
+            logger::warn(&format!(
+                "'{}' looks like an abstract or mixin class; instantiating it anyway",
+                class_name
+            ));
+        }
 
         let class = module.getattr(class_name).map_err(|e| {
-            BridgeError::Python(format_python_error(
+            BridgeError::python(
                 module.py(),
                 e,
                 &format!("Failed to get class '{}'", class_name),
-            ))
+            )
         })?;
 
         logger::step(&format!(
@@ -229,24 +236,19 @@ impl Bridge {
             remaining_kwargs.len()
         ));
         let instance = class.call(args, Some(&remaining_kwargs)).map_err(|err| {
-            let raw_msg = err.to_string();
-            let mut formatted = format_python_error(
+            BridgeError::python(
                 class.py(),
                 err,
                 &format!("Failed to instantiate '{}'", class_name),
-            );
-            if raw_msg.contains("missing") && raw_msg.contains("required positional argument") {
-                formatted.push_str("\n\nHint: This may happen if the plugin metadata cache is stale. Try running:\n  r2x sync");
-            }
-            BridgeError::Python(formatted)
+            )
         })?;
 
         let method = instance.getattr(method_name).map_err(|e| {
-            BridgeError::Python(format_python_error(
+            BridgeError::python(
                 instance.py(),
                 e,
                 &format!("Failed to get method '{}.{}'", class_name, method_name),
-            ))
+            )
         })?;
 
         let accepts_stdin = if stdin_obj.is_some() {
@@ -267,11 +269,11 @@ impl Bridge {
         if accepts_stdin {
             let stdin = stdin_obj.expect("checked Some above");
             method.call1((stdin,)).map_err(|e| {
-                BridgeError::Python(format_python_error(
+                BridgeError::python(
                     method.py(),
                     e,
                     &format!("Method '{}.{}' failed", class_name, method_name),
-                ))
+                )
             })
         } else {
             if stdin_obj.is_some() {
@@ -281,16 +283,17 @@ impl Bridge {
                 ));
             }
             method.call0().map_err(|e| {
-                BridgeError::Python(format_python_error(
+                BridgeError::python(
                     method.py(),
                     e,
                     &format!("Method '{}.{}' failed", class_name, method_name),
-                ))
+                )
             })
         }
     }
 
     fn invoke_function_callable<'py>(
+        &self,
         py: pyo3::Python<'py>,
         module: &pyo3::Bound<'py, PyModule>,
         callable_path: &str,
@@ -300,11 +303,11 @@ impl Bridge {
     ) -> Result<pyo3::Bound<'py, PyAny>, BridgeError> {
         logger::debug(&format!("Function pattern: {}", callable_path));
         let func = module.getattr(callable_path).map_err(|e| {
-            BridgeError::Python(format_python_error(
+            BridgeError::python(
                 module.py(),
                 e,
                 &format!("Failed to get function '{}'", callable_path),
-            ))
+            )
         })?;
 
         logger::step(&format!("Function kwargs before system: {:?}", kwargs));
@@ -314,7 +317,7 @@ impl Bridge {
             let json_str = dumps.call1((stdin,))?.extract::<String>()?;
             let json_bytes = json_str.as_bytes();
 
-            let system_module = PyModule::import(py, "r2x_core.system")?;
+            let system_module = self.import(py, "r2x_core.system")?;
             let system_class = system_module.getattr("System")?;
             let from_json = system_class.getattr("from_json")?;
             let system_obj = from_json.call1((json_bytes,))?;
@@ -323,11 +326,11 @@ impl Bridge {
 
         logger::step(&format!("Final function kwargs: {:?}", kwargs));
         func.call((), Some(kwargs)).map_err(|e| {
-            BridgeError::Python(format_python_error(
+            BridgeError::python(
                 func.py(),
                 e,
                 &format!("Function '{}' failed", callable_path),
-            ))
+            )
         })
     }
 }
@@ -341,25 +344,6 @@ fn format_duration(duration: Duration) -> String {
     }
 }
 
-fn format_python_error(py: pyo3::Python<'_>, err: pyo3::PyErr, context: &str) -> String {
-    if let Some(traceback_text) = render_traceback(py, &err) {
-        format!("{}:\n{}", context, traceback_text)
-    } else {
-        format!("{}: {}", context, err)
-    }
-}
-
-fn render_traceback(py: pyo3::Python<'_>, err: &pyo3::PyErr) -> Option<String> {
-    let traceback = err.traceback(py)?;
-    let traceback_module = PyModule::import(py, "traceback").ok()?;
-    let formatter = traceback_module.getattr("format_exception").ok()?;
-    let formatted = formatter
-        .call1((err.get_type(py), err.value(py), traceback))
-        .ok()?;
-    let lines: Vec<String> = formatted.extract().ok()?;
-    Some(lines.join(""))
-}
-
 fn method_accepts_stdin(method: &pyo3::Bound<'_, PyAny>) -> PyResult<bool> {
     let code = method.getattr("__code__")?;
     let argcount: usize = code.getattr("co_argcount")?.extract()?;
@@ -0,0 +1,200 @@
+//! Events streamed while a plugin runs
+//!
+//! Lines a plugin prints to `sys.stdout` are sent as [`PluginEvent::Progress`] instead
+//! of reaching the process stdout, which is reserved for JSON output. `sys.stdout` is
+//! replaced once by a router that sends each write to the channel registered for the
+//! calling thread and passes writes from any other thread to the original stream.
+
+use super::{logger, PluginInvocationTimings};
+use once_cell::sync::{Lazy, OnceCell};
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::sync::mpsc::Sender;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
+
+/// Event emitted by [`Bridge::invoke_plugin_streaming`](crate::Bridge::invoke_plugin_streaming)
+#[derive(Debug)]
+pub enum PluginEvent {
+    /// A line the plugin printed, without its line ending
+    Progress(String),
+    /// JSON output of a successful invocation (may be `"null"`)
+    Output(String),
+    /// The invocation finished, successfully or not; always the last event
+    Done(Option<PluginInvocationTimings>),
+}
+
+struct Route {
+    events: Sender<PluginEvent>,
+    pending: String,
+}
+
+static ROUTES: Lazy<Mutex<HashMap<ThreadId, Route>>> = Lazy::new(Default::default);
+static ROUTER_INSTALLED: OnceCell<bool> = OnceCell::new();
+
+/// Guard sending what the current thread prints to a channel until it is dropped
+pub(super) struct StdoutRoute {
+    thread: ThreadId,
+}
+
+impl StdoutRoute {
+    /// Route the current thread's stdout to `events`
+    ///
+    /// Returns `None` when `sys.stdout` could not be replaced; output then goes to the
+    /// process stdout as before.
+    pub(super) fn register(events: Sender<PluginEvent>) -> Option<Self> {
+        if !install_router() {
+            return None;
+        }
+        let thread = thread::current().id();
+        let route = Route {
+            events,
+            pending: String::new(),
+        };
+        ROUTES.lock().ok()?.insert(thread, route);
+        Some(Self { thread })
+    }
+}
+
+impl Drop for StdoutRoute {
+    fn drop(&mut self) {
+        let route = ROUTES
+            .lock()
+            .ok()
+            .and_then(|mut routes| routes.remove(&self.thread));
+        if let Some(route) = route {
+            if !route.pending.is_empty() {
+                let _ = route.events.send(PluginEvent::Progress(route.pending));
+            }
+        }
+    }
+}
+
+/// Replacement for `sys.stdout`
+#[pyclass]
+struct StdoutRouter {
+    fallback: Py<PyAny>,
+}
+
+#[pymethods]
+impl StdoutRouter {
+    fn write(&self, py: Python<'_>, text: &str) -> PyResult<usize> {
+        if route_write(text) || self.fallback.is_none(py) {
+            return Ok(text.chars().count());
+        }
+        self.fallback
+            .call_method1(py, "write", (text,))?
+            .extract(py)
+    }
+
+    fn flush(&self, py: Python<'_>) -> PyResult<()> {
+        if !route_flush() && !self.fallback.is_none(py) {
+            self.fallback.call_method0(py, "flush")?;
+        }
+        Ok(())
+    }
+
+    fn isatty(&self) -> bool {
+        false
+    }
+
+    fn writable(&self) -> bool {
+        true
+    }
+
+    #[getter]
+    fn encoding(&self) -> &'static str {
+        "utf-8"
+    }
+
+    /// Anything else (`fileno`, `buffer`, `reconfigure`, ...) comes from the original
+    /// stream, so code that expects a real `TextIOWrapper` keeps working
+    fn __getattr__(&self, py: Python<'_>, name: &str) -> PyResult<Py<PyAny>> {
+        if self.fallback.is_none(py) {
+            return Err(pyo3::exceptions::PyAttributeError::new_err(
+                name.to_string(),
+            ));
+        }
+        self.fallback.getattr(py, name)
+    }
+}
+
+fn install_router() -> bool {
+    *ROUTER_INSTALLED.get_or_init(|| {
+        let installed = Python::attach(|py| -> PyResult<()> {
+            let sys = py.import("sys")?;
+            let fallback = sys.getattr("stdout")?.unbind();
+            sys.setattr("stdout", Py::new(py, StdoutRouter { fallback })?)
+        });
+        match installed {
+            Ok(()) => true,
+            Err(e) => {
+                logger::debug(&format!("Plugin output is not streamed: {}", e));
+                false
+            }
+        }
+    })
+}
+
+/// Send `text` to the current thread's route; `false` when it has none
+fn route_write(text: &str) -> bool {
+    let Ok(mut routes) = ROUTES.lock() else {
+        return false;
+    };
+    let Some(route) = routes.get_mut(&thread::current().id()) else {
+        return false;
+    };
+    route.pending.push_str(text);
+    for line in take_complete_lines(&mut route.pending) {
+        let _ = route.events.send(PluginEvent::Progress(line));
+    }
+    true
+}
+
+/// Send the current thread's unfinished line; `false` when it has no route
+fn route_flush() -> bool {
+    let Ok(mut routes) = ROUTES.lock() else {
+        return false;
+    };
+    let Some(route) = routes.get_mut(&thread::current().id()) else {
+        return false;
+    };
+    if !route.pending.is_empty() {
+        let line = std::mem::take(&mut route.pending);
+        let _ = route.events.send(PluginEvent::Progress(line));
+    }
+    true
+}
+
+/// Remove the complete lines from `pending`, leaving any unfinished last line
+fn take_complete_lines(pending: &mut String) -> Vec<String> {
+    let Some(end) = pending.rfind('\n') else {
+        return Vec::new();
+    };
+    let rest = pending.split_off(end + 1);
+    let complete = std::mem::replace(pending, rest);
+    complete
+        .lines()
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_complete_lines() {
+        let mut pending = "Reading inputs\r\nBuilding sys".to_string();
+        assert_eq!(take_complete_lines(&mut pending), vec!["Reading inputs"]);
+        assert_eq!(pending, "Building sys");
+
+        pending.push_str("tem\n\n");
+        assert_eq!(
+            take_complete_lines(&mut pending),
+            vec!["Building system", ""]
+        );
+        assert!(pending.is_empty());
+        assert!(take_complete_lines(&mut pending).is_empty());
+    }
+}
@@ -1,7 +1,14 @@
+//! Upgrader plugin invocation
+//!
+//! Upgraders run with the GIL held, but reading and writing the system JSON
+//! does not touch Python objects. Those file operations run inside
+//! `Python::detach` so other threads can use the interpreter meanwhile.
+
 use super::{logger, BridgeError, PluginInvocationResult, RuntimeBindings};
 use crate::Bridge;
 use pyo3::types::{PyAny, PyAnyMethods, PyDict, PyDictMethods, PyModule, PyString};
 use r2x_manifest::PluginSpec;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 impl Bridge {
@@ -23,7 +30,8 @@ impl Bridge {
 
             let module = PyModule::import(py, module_path)
                 .map_err(|e| BridgeError::Import(module_path.to_string(), format!("{}", e)))?;
-            let json_module = PyModule::import(py, "json")
+            let json_module = self
+                .import(py, "json")
                 .map_err(|e| BridgeError::Import("json".to_string(), format!("{}", e)))?;
             let loads = json_module.getattr("loads")?;
             let config_dict = loads
@@ -75,7 +83,8 @@ impl Bridge {
                 })
             } else {
                 logger::debug("Upgrader missing run() method, invoking registered steps directly");
-                let output = Self::invoke_registered_steps(&instance)?;
+                let source_files = plugin_metadata.map(step_source_files).unwrap_or_default();
+                let output = self.invoke_registered_steps(&instance, &source_files)?;
                 Ok(PluginInvocationResult {
                     output,
                     timings: None,
@@ -85,6 +94,24 @@ impl Bridge {
     }
 }
 
+/// Files defining the upgrade steps recorded for `plugin` at discovery, by step name
+fn step_source_files(plugin: &PluginSpec) -> HashMap<String, String> {
+    let Some(steps) = find_arg_value(plugin, "upgrade_steps") else {
+        return HashMap::new();
+    };
+    let Ok(serde_json::Value::Array(steps)) = serde_json::from_str(steps) else {
+        return HashMap::new();
+    };
+    steps
+        .iter()
+        .filter_map(|step| {
+            let name = step.get("name")?.as_str()?;
+            let source_file = step.get("source_file")?.as_str()?;
+            Some((name.to_string(), source_file.to_string()))
+        })
+        .collect()
+}
+
 fn find_arg_value<'a>(plugin: &'a PluginSpec, name: &str) -> Option<&'a str> {
     let upgrade = plugin.upgrade.as_ref()?;
     match name {
@@ -97,7 +124,9 @@ fn find_arg_value<'a>(plugin: &'a PluginSpec, name: &str) -> Option<&'a str> {
 
 impl Bridge {
     fn invoke_registered_steps<'py>(
+        &self,
         instance: &pyo3::Bound<'py, pyo3::PyAny>,
+        source_files: &HashMap<String, String>,
     ) -> Result<String, BridgeError> {
         let steps = instance
             .getattr("steps")
@@ -125,7 +154,8 @@ impl Bridge {
             ))
         })?;
 
-        let json_module = PyModule::import(py, "json")
+        let json_module = self
+            .import(py, "json")
             .map_err(|e| BridgeError::Import("json".to_string(), format!("{}", e)))?;
         let loads = json_module.getattr("loads")?;
         let dumps = json_module.getattr("dumps")?;
@@ -154,19 +184,22 @@ impl Bridge {
             let upgrade_is_file =
                 upgrade_value.eq_ignore_ascii_case("FILE") || upgrade_value.ends_with(".FILE");
 
-            logger::debug(&format!(
-                "Executing upgrade step: {}",
-                step_obj
-                    .getattr("name")
-                    .and_then(|n| n.extract::<String>())
-                    .unwrap_or_else(|_| "<unknown>".to_string())
-            ));
+            let step_name = step_obj
+                .getattr("name")
+                .and_then(|n| n.extract::<String>())
+                .unwrap_or_else(|_| "<unknown>".to_string());
+            let step_location = match source_files.get(&step_name) {
+                Some(source_file) => format!("'{}' ({})", step_name, source_file),
+                None => format!("'{}'", step_name),
+            };
+            logger::debug(&format!("Executing upgrade step: {}", step_location));
 
             let data_arg = if upgrade_is_system {
                 saw_system_step = true;
                 if system_data.is_none() {
-                    let resolved =
-                        resolve_system_json_path(&path_buf).map_err(BridgeError::Python)?;
+                    let resolved = py
+                        .detach(|| resolve_system_json_path(&path_buf))
+                        .map_err(BridgeError::Python)?;
                     let data = load_system_data(py, &loads, &resolved)?;
                     system_data = Some(data);
                     system_json_path = Some(resolved);
@@ -187,7 +220,10 @@ impl Bridge {
             let result = run_upgrade_step
                 .call((step_obj.clone(), data_arg), Some(&kwargs))
                 .map_err(|e| {
-                    BridgeError::Python(format!("Upgrade step execution failed: {}", e))
+                    BridgeError::Python(format!(
+                        "Upgrade step {} execution failed: {}",
+                        step_location, e
+                    ))
                 })?;
 
             let is_err = result
@@ -205,9 +241,13 @@ impl Bridge {
                     .str()
                     .map(|s| s.to_string())
                     .unwrap_or_else(|_| "<unknown error>".to_string());
+                logger::error(&format!(
+                    "Upgrade step {} failed: {}",
+                    step_location, err_text
+                ));
                 return Err(BridgeError::Python(format!(
-                    "Upgrade step execution failed: {}",
-                    err_text
+                    "Upgrade step {} execution failed: {}",
+                    step_location, err_text
                 )));
             }
 
@@ -221,11 +261,7 @@ impl Bridge {
             } else if !upgrade_is_file {
                 logger::warn(&format!(
                     "Unknown upgrade type '{}' for step {}; defaulting to pass-through",
-                    upgrade_value,
-                    step_obj
-                        .getattr("name")
-                        .and_then(|n| n.extract::<String>())
-                        .unwrap_or_else(|_| "<unknown>".into())
+                    upgrade_value, step_location
                 ));
             }
         }
@@ -243,7 +279,8 @@ impl Bridge {
             }
             json_path
         } else {
-            resolve_system_json_path(&path_buf).unwrap_or(path_buf.clone())
+            py.detach(|| resolve_system_json_path(&path_buf))
+                .unwrap_or(path_buf.clone())
         };
 
         if let Some(data) = system_data {
@@ -258,18 +295,23 @@ impl Bridge {
                 })?;
             Ok(json_str)
         } else {
-            let contents = std::fs::read_to_string(&final_json_path).map_err(|e| {
-                BridgeError::Python(format!(
-                    "Failed to read upgraded system JSON {}: {}",
-                    final_json_path.display(),
-                    e
-                ))
-            })?;
+            let contents = py
+                .detach(|| std::fs::read_to_string(&final_json_path))
+                .map_err(|e| {
+                    BridgeError::Python(format!(
+                        "Failed to read upgraded system JSON {}: {}",
+                        final_json_path.display(),
+                        e
+                    ))
+                })?;
             Ok(contents)
         }
     }
 }
 
+/// Locate the system JSON for an upgrader path
+///
+/// Only touches the filesystem; call it with the GIL released.
 fn resolve_system_json_path(path: &Path) -> Result<PathBuf, String> {
     if path.is_file() {
         return Ok(path.to_path_buf());
@@ -301,18 +343,21 @@ fn resolve_system_json_path(path: &Path) -> Result<PathBuf, String> {
     ))
 }
 
+/// Read and parse the system JSON; needs the GIL, released for the file read
 fn load_system_data<'py>(
     py: pyo3::Python<'py>,
     loads: &pyo3::Bound<'py, pyo3::PyAny>,
     json_path: &Path,
 ) -> Result<pyo3::Py<PyAny>, BridgeError> {
-    let content = std::fs::read_to_string(json_path).map_err(|e| {
-        BridgeError::Python(format!(
-            "Failed to read system JSON {}: {}",
-            json_path.display(),
-            e
-        ))
-    })?;
+    let content = py
+        .detach(|| std::fs::read_to_string(json_path))
+        .map_err(|e| {
+            BridgeError::Python(format!(
+                "Failed to read system JSON {}: {}",
+                json_path.display(),
+                e
+            ))
+        })?;
     let py_str = PyString::new(py, &content);
     let data = loads.call1((py_str,)).map_err(|e| {
         BridgeError::Python(format!(
@@ -324,6 +369,7 @@ fn load_system_data<'py>(
     Ok(data.into())
 }
 
+/// Serialize and write the system JSON; needs the GIL, released for the file write
 fn write_system_data<'py>(
     py: pyo3::Python<'py>,
     dumps: &pyo3::Bound<'py, pyo3::PyAny>,
@@ -350,11 +396,73 @@ fn write_system_data<'py>(
                 e
             ))
         })?;
-    std::fs::write(json_path, json_str).map_err(|e| {
-        BridgeError::Python(format!(
-            "Failed to write upgraded system JSON {}: {}",
-            json_path.display(),
-            e
-        ))
-    })
+    py.detach(|| std::fs::write(json_path, json_str))
+        .map_err(|e| {
+            BridgeError::Python(format!(
+                "Failed to write upgraded system JSON {}: {}",
+                json_path.display(),
+                e
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn test_step_source_files() {
+        let mut plugin: PluginSpec = serde_json::from_value(serde_json::json!({
+            "name": "reeds-upgrader",
+            "kind": "UPGRADER",
+            "entry": "r2x_reeds.upgrader.ReEDSUpgrader",
+            "invocation": { "implementation": "CLASS" },
+            "io": {}
+        }))
+        .unwrap();
+        assert!(step_source_files(&plugin).is_empty());
+
+        plugin.upgrade = Some(r2x_manifest::UpgradeSpec {
+            version_strategy_json: None,
+            version_reader_json: None,
+            upgrade_steps_json: Some(
+                r#"[{"name": "rename_columns", "source_file": "/src/r2x_reeds/upgrader.py"},
+                    {"name": "drop_units"}]"#
+                    .to_string(),
+            ),
+        });
+        let source_files = step_source_files(&plugin);
+        assert_eq!(source_files.len(), 1);
+        assert_eq!(
+            source_files.get("rename_columns").map(String::as_str),
+            Some("/src/r2x_reeds/upgrader.py")
+        );
+    }
+
+    #[test]
+    fn test_load_system_data_from_concurrent_threads() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let handles: Vec<_> = (0..2)
+            .map(|idx| {
+                let path = dir.path().join(format!("system_{}.json", idx));
+                std::fs::write(&path, format!(r#"{{"name": "system-{}"}}"#, idx)).unwrap();
+                thread::spawn(move || {
+                    pyo3::Python::attach(|py| {
+                        let loads = PyModule::import(py, "json")?.getattr("loads")?;
+                        let data = load_system_data(py, &loads, &path)?;
+                        let name: String = data.bind(py).get_item("name")?.extract()?;
+                        Ok::<_, BridgeError>(name)
+                    })
+                })
+            })
+            .collect();
+
+        let mut names: Vec<String> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["system-0", "system-1"]);
+    }
 }
@@ -0,0 +1,168 @@
+//! Validation of a plugin's configuration and data store
+//!
+//! Builds the config object and the `DataStore` the plugin would receive and checks
+//! the files its config class requires, without calling the plugin itself.
+
+use super::kwargs::{
+    config_class_params, is_store_param, missing_data_files, param_env_var, resolve_config_class,
+    store_value,
+};
+use super::{logger, BridgeError, InvocationConfig};
+use crate::Bridge;
+use pyo3::types::{PyAnyMethods, PyDict, PyDictMethods};
+use r2x_manifest::build_runtime_bindings;
+use std::path::Path;
+
+/// Outcome of [`Bridge::validate_plugin`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Whether the config class accepted the configuration, or the plugin has none
+    pub config_valid: bool,
+    /// Whether the data store could be opened, or the plugin needs none
+    pub store_valid: bool,
+    /// Files the config class requires that do not exist under the store
+    pub missing_files: Vec<String>,
+    /// Why the config or store is invalid
+    pub errors: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Whether the plugin is expected to start with this configuration
+    pub fn is_ok(&self) -> bool {
+        self.config_valid && self.store_valid && self.missing_files.is_empty()
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::json!({
+            "config_valid": self.config_valid,
+            "store_valid": self.store_valid,
+            "missing_files": self.missing_files,
+            "errors": self.errors,
+        })
+        .to_string()
+    }
+}
+
+impl Bridge {
+    /// Check the configuration and data store of a plugin without running it
+    ///
+    /// The config and store are resolved as for a real call, including the
+    /// `R2X_PLUGIN_<NAME>` fallbacks. Errors are returned only when Python itself fails;
+    /// problems with the plugin's inputs are recorded in the report.
+    pub fn validate_plugin(
+        &self,
+        invocation: &InvocationConfig,
+    ) -> Result<ValidationReport, BridgeError> {
+        let bindings = invocation.plugin_metadata.map(build_runtime_bindings);
+        let config_spec = bindings.as_ref().and_then(|b| b.config.as_ref());
+        let store_param = bindings
+            .as_ref()
+            .and_then(|b| b.entry_parameters.iter().find(|p| is_store_param(p)));
+
+        let report = pyo3::Python::attach(|py| {
+            let mut report = ValidationReport::default();
+            let loads = self.import(py, "json")?.getattr("loads")?;
+            let config_dict = loads
+                .call1((invocation.config_json,))?
+                .cast::<PyDict>()
+                .map_err(|e| BridgeError::Python(format!("Config must be a JSON object: {}", e)))?
+                .clone();
+
+            let config_instance = match config_spec {
+                Some(spec) => {
+                    let params = config_class_params(py, &config_dict, Some(spec))?;
+                    match self.instantiate_config_class(py, &params, Some(spec)) {
+                        Ok(instance) => {
+                            report.config_valid = true;
+                            Some(instance)
+                        }
+                        Err(e) => {
+                            report.errors.push(e.to_string());
+                            None
+                        }
+                    }
+                }
+                None => {
+                    report.config_valid = true;
+                    None
+                }
+            };
+
+            let Some(store_param) = store_param else {
+                report.store_valid = true;
+                return Ok::<_, BridgeError>(report);
+            };
+            let Some(store_value) = store_value(py, &config_dict, &store_param.name)? else {
+                report.errors.push(format!(
+                    "No store path in config (set store_path or {})",
+                    param_env_var("store_path")
+                ));
+                return Ok(report);
+            };
+
+            let store_path = store_value
+                .cast::<PyDict>()
+                .ok()
+                .and_then(|store| store.get_item("path").ok().flatten())
+                .unwrap_or_else(|| store_value.clone())
+                .extract::<String>()
+                .ok();
+            if let Some(path) = &store_path {
+                if !Path::new(path).exists() {
+                    report
+                        .errors
+                        .push(format!("Store path '{}' does not exist", path));
+                    return Ok(report);
+                }
+                if let Some(class_obj) =
+                    resolve_config_class(py, config_instance.as_ref(), config_spec)
+                {
+                    report
+                        .missing_files
+                        .extend(missing_data_files(&class_obj, path).unwrap_or_default());
+                }
+            }
+
+            match self.instantiate_data_store(
+                py,
+                &store_value,
+                config_instance.as_ref(),
+                config_spec,
+            ) {
+                Ok(_) => report.store_valid = true,
+                Err(e) => report.errors.push(e.to_string()),
+            }
+            Ok(report)
+        })?;
+
+        logger::debug(&format!(
+            "Validation of {}: {:?}",
+            invocation.target, report
+        ));
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_report() {
+        let mut report = ValidationReport {
+            config_valid: true,
+            store_valid: true,
+            ..Default::default()
+        };
+        assert!(report.is_ok());
+        assert_eq!(
+            report.to_json(),
+            r#"{"config_valid":true,"errors":[],"missing_files":[],"store_valid":true}"#
+        );
+
+        report
+            .missing_files
+            .push("/data/inputs_case/hours.csv".to_string());
+        assert!(!report.is_ok());
+    }
+}
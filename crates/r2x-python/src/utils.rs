@@ -33,6 +33,8 @@ const PYTHON_EXE_CANDIDATES: &[&str] = &["python.exe", "python3.exe", "python3.1
 //
 // Windows
 // .venv/Lib/site-packages
+//
+// Both layouts are handled by `r2x_config::site_packages_path`.
 
 pub fn resolve_site_package_path(venv_path: &PathBuf) -> Result<PathBuf, BridgeError> {
     logger::debug(&format!(
@@ -49,91 +51,15 @@ pub fn resolve_site_package_path(venv_path: &PathBuf) -> Result<PathBuf, BridgeE
         return Err(BridgeError::VenvNotFound(venv_path.to_path_buf()));
     }
 
-    #[cfg(windows)]
-    {
-        let site_packages = venv_path.join(PYTHON_LIB_DIR).join("site-packages");
-        logger::debug(&format!(
-            "Windows: Looking for site-packages at: {}",
-            site_packages.display()
-        ));
-
-        // verify site_package_path exists
-        if !site_packages.is_dir() {
-            logger::debug(&format!(
-                "Windows: site-packages directory not found at: {}",
-                site_packages.display()
-            ));
-            return Err(BridgeError::Initialization(format!(
-                "unable to locate package directory: {}",
-                site_packages.display()
-            )));
-        }
-        logger::debug(&format!(
-            "Windows: Successfully resolved site-packages: {}",
-            site_packages.display()
-        ));
-        Ok(site_packages)
-    }
-
-    #[cfg(not(windows))]
-    {
-        let lib_dir = venv_path.join(PYTHON_LIB_DIR);
-        logger::debug(&format!(
-            "Unix: Looking for lib directory at: {}",
-            lib_dir.display()
-        ));
-
-        if !lib_dir.is_dir() {
-            logger::debug(&format!(
-                "Unix: lib directory not found at: {}",
-                lib_dir.display()
-            ));
-            return Err(BridgeError::Initialization(format!(
-                "unable to locate lib directory: {}",
-                lib_dir.display()
-            )));
-        }
-
-        let python_version_dir = fs::read_dir(&lib_dir)
-            .map_err(|e| {
-                logger::debug(&format!("Unix: Failed to read lib directory: {}", e));
-                BridgeError::Initialization(format!("Failed to read lib directory: {}", e))
-            })?
-            .filter_map(|e| e.ok())
-            .find(|e| e.file_name().to_string_lossy().starts_with("python"))
-            .ok_or_else(|| {
-                logger::debug("Unix: No python3.X directory found in venv/lib");
-                BridgeError::Initialization("No python3.X directory found in venv/lib".to_string())
-            })?;
-
-        logger::debug(&format!(
-            "Unix: Found python version directory: {}",
-            python_version_dir.path().display()
-        ));
-
-        let site_packages = python_version_dir.path().join("site-packages");
-        logger::debug(&format!(
-            "Unix: Looking for site-packages at: {}",
-            site_packages.display()
-        ));
-
-        if !site_packages.is_dir() {
-            logger::debug(&format!(
-                "Unix: site-packages directory not found at: {}",
-                site_packages.display()
-            ));
-            return Err(BridgeError::Initialization(format!(
-                "unable to locate package directory: {}",
-                site_packages.display()
-            )));
-        }
-
-        logger::debug(&format!(
-            "Unix: Successfully resolved site-packages: {}",
-            site_packages.display()
-        ));
-        Ok(site_packages)
-    }
+    let site_packages = r2x_config::site_packages_path(venv_path).map_err(|e| {
+        logger::debug(&format!("Failed to resolve site-packages: {}", e));
+        BridgeError::Initialization(e)
+    })?;
+    logger::debug(&format!(
+        "Resolved site-packages: {}",
+        site_packages.display()
+    ));
+    Ok(site_packages)
 }
 
 pub fn resolve_python_path(venv_path: &PathBuf) -> Result<PathBuf, BridgeError> {